@@ -26,7 +26,9 @@
 use client::APIClient;
 use common::{
     APIError,
-    parse_response
+    parse_response,
+    timestamp_to_param,
+    QueryBuilder
 };
 use api_v2::types::{
     APIKey,
@@ -39,6 +41,8 @@ use api_v2::types::{
     BankSlot,
     Cat,
     InventorySlot,
+    Subtoken,
+    Timestamp,
 };
 
 use reqwest::StatusCode;
@@ -51,9 +55,11 @@ macro_rules! get_endpoint {
     ("dungeons") => {"/v2/account/dungeons"};
     ("dyes") => {"/v2/account/dyes"};
     ("finishers") => {"/v2/account/finishers"};
+    ("dailycrafting") => {"/v2/account/dailycrafting"};
     ("cats") => {"/v2/account/home/cats"};
     ("nodes") => {"/v2/account/home/nodes"};
     ("inventory") => {"/v2/account/inventory"};
+    ("mapchests") => {"/v2/account/mapchests"};
     ("masteries") => {"/v2/account/masteries"};
     ("materials") => {"/v2/account/materials"};
     ("minis") => {"/v2/account/minis"};
@@ -63,7 +69,11 @@ macro_rules! get_endpoint {
     ("skins") => {"/v2/account/skins"};
     ("titles") => {"/v2/account/titles"};
     ("wallet") => {"/v2/account/wallet"};
+    ("worldbosses") => {"/v2/account/worldbosses"};
     ("tokeninfo") => {"/v2/tokeninfo"};
+    ("createsubtoken", $params: expr) => {
+        format!("/v2/createsubtoken?{}", $params)
+    };
 }
 
 
@@ -77,13 +87,12 @@ pub fn get_account(
     client: &APIClient
 ) -> Result<Account, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("account"))
-        .expect("failed to get account");
+        .make_authenticated_request(&get_endpoint!("account"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -97,13 +106,12 @@ pub fn get_account_achievements(
     client: &APIClient
 ) -> Result<Vec<AccountAchievement>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("achievements"))
-        .expect("failed to get account achievements");
+        .make_authenticated_request(&get_endpoint!("achievements"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -118,13 +126,31 @@ pub fn get_account_bank(
 ) -> Result<Vec<Option<BankSlot>>, APIError> {
     //TODO check behaviour for empty slots
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("bank"))
-        .expect("failed to get account bank");
+        .make_authenticated_request(&get_endpoint!("bank"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain time-gated items crafted since daily reset
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_account_dailycrafting(
+    client: &APIClient
+) -> Result<Vec<String>, APIError> {
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("dailycrafting"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -138,13 +164,12 @@ pub fn get_account_dungeons(
     client: &APIClient
 ) -> Result<Vec<String>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("dungeons"))
-        .expect("failed to get account dungeons");
+        .make_authenticated_request(&get_endpoint!("dungeons"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -158,13 +183,12 @@ pub fn get_account_dyes(
     client: &APIClient
 ) -> Result<Vec<i32>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("dyes"))
-        .expect("failed to get account dyes");
+        .make_authenticated_request(&get_endpoint!("dyes"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -178,13 +202,12 @@ pub fn get_account_finishers(
     client: &APIClient
 ) -> Result<Vec<AccountFinisher>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("finishers"))
-        .expect("failed to get account finishers");
+        .make_authenticated_request(&get_endpoint!("finishers"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -198,13 +221,12 @@ pub fn get_account_cats(
     client: &APIClient
 ) -> Result<Vec<Cat>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("cats"))
-        .expect("failed to get account cats");
+        .make_authenticated_request(&get_endpoint!("cats"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -218,13 +240,12 @@ pub fn get_account_nodes(
     client: &APIClient
 ) -> Result<Vec<String>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("nodes"))
-        .expect("failed to get account nodes");
+        .make_authenticated_request(&get_endpoint!("nodes"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -239,13 +260,31 @@ pub fn get_account_inventory(
 ) -> Result<Vec<Option<InventorySlot>>, APIError> {
     //TODO check behaviour with empty slots
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("inventory"))
-        .expect("failed to get shared account inventory slots");
+        .make_authenticated_request(&get_endpoint!("inventory"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain Hero's Choice chests opened since daily reset
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_account_mapchests(
+    client: &APIClient
+) -> Result<Vec<String>, APIError> {
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("mapchests"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -259,13 +298,12 @@ pub fn get_account_masteries(
     client: &APIClient
 ) -> Result<Vec<AccountMastery>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("masteries"))
-        .expect("failed to get account masteries");
+        .make_authenticated_request(&get_endpoint!("masteries"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -280,13 +318,12 @@ pub fn get_account_materials(
 ) -> Result<Vec<AccountMaterial>, APIError> {
 
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("materials"))
-        .expect("failed to get account materials");
+        .make_authenticated_request(&get_endpoint!("materials"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -300,13 +337,12 @@ pub fn get_account_minis(
     client: &APIClient
 ) -> Result<Vec<i32>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("minis"))
-        .expect("failed to get account minis");
+        .make_authenticated_request(&get_endpoint!("minis"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -320,13 +356,12 @@ pub fn get_account_outfits(
     client: &APIClient
 ) -> Result<Vec<i32>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("outfits"))
-        .expect("failed to get account outfits");
+        .make_authenticated_request(&get_endpoint!("outfits"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -340,13 +375,12 @@ pub fn get_account_raids(
     client: &APIClient
 ) -> Result<Vec<String>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("raids"))
-        .expect("failed to get account raids");
+        .make_authenticated_request(&get_endpoint!("raids"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -360,13 +394,12 @@ pub fn get_account_recipes(
     client: &APIClient
 ) -> Result<Vec<i32>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("recipes"))
-        .expect("failed to get account recipes");
+        .make_authenticated_request(&get_endpoint!("recipes"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -380,13 +413,12 @@ pub fn get_account_skins(
     client: &APIClient
 ) -> Result<Vec<i32>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("skins"))
-        .expect("failed to get account skins");
+        .make_authenticated_request(&get_endpoint!("skins"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -400,13 +432,12 @@ pub fn get_account_titles(
     client: &APIClient
 ) -> Result<Vec<i32>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("titles"))
-        .expect("failed to get account titles");
+        .make_authenticated_request(&get_endpoint!("titles"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -420,13 +451,31 @@ pub fn get_account_wallet(
     client: &APIClient
 ) -> Result<Vec<AccountCurrency>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("wallet"))
-        .expect("failed to get account wallet");
+        .make_authenticated_request(&get_endpoint!("wallet"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain world bosses defeated since daily reset
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_account_worldbosses(
+    client: &APIClient
+) -> Result<Vec<String>, APIError> {
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("worldbosses"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -440,20 +489,57 @@ pub fn get_token_info(
     client: &APIClient
 ) -> Result<APIKey, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("tokeninfo"))
-        .expect("failed to get API key details");
+        .make_authenticated_request(&get_endpoint!("tokeninfo"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
+    )
+}
+
+/// Create a subtoken with a subset of the permissions of the token
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `permissions` - Permissions of the subtoken (e.g. `account`, `wallet`)
+/// * `expire` - Time the subtoken expires at
+/// * `urls` - Endpoints the subtoken is restricted to (e.g.
+///     `/v2/account/bank`). The subtoken can access every endpoint allowed
+///     by its permissions if not provided
+pub fn create_subtoken(
+    client: &APIClient,
+    permissions: Vec<&str>,
+    expire: &Timestamp,
+    urls: Option<Vec<&str>>
+) -> Result<Subtoken, APIError> {
+    let mut query = QueryBuilder::new()
+        .strings("permissions", &permissions)
+        .param(&timestamp_to_param("expire", expire));
+
+    if let Some(urls) = urls {
+        query = query.strings("urls", &urls);
+    }
+
+    let params = query.build();
+
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("createsubtoken", params))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound, StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::BAD_REQUEST, StatusCode::FORBIDDEN]
     )
 }
 
 #[cfg(test)]
 mod tests {
-    use std::env;
+    use serde_json;
     use client::APIClient;
+    use fixtures::test_client;
     use api_v2::account::*;
 
     macro_rules! parse_test {
@@ -466,10 +552,51 @@ mod tests {
     }
 
     fn setup_client() -> APIClient {
-        match env::var("TOKEN") {
-            Ok(token) => APIClient::new("en", Some(token.to_string())),
-            Err(_) => panic!("Need a token to test endpoint"),
-        }
+        test_client()
+    }
+
+    #[test]
+    fn token_info() {
+        let client = setup_client();
+        let result = get_token_info(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn subtoken_info() {
+        let key: APIKey = serde_json::from_str(r#"{
+            "id": "ABCDE02B-8888-FEBA-1234-DE98765C7DEF",
+            "name": "My API Key",
+            "permissions": ["account", "inventories"],
+            "type": "Subtoken",
+            "expires_at": "2038-01-01T00:00:00.000Z",
+            "issued_at": "2017-06-01T12:00:00.000Z",
+            "urls": ["/v2/account/bank"]
+        }"#).unwrap();
+
+        assert!(key.is_subtoken());
+        assert!(key.expires_at.is_some());
+        assert!(key.allows_url("/v2/account/bank"));
+        assert!(!key.allows_url("/v2/account/wallet"));
+
+        let key: APIKey = serde_json::from_str(
+            r#"{"id": "X", "name": "Old", "permissions": ["account"]}"#
+        ).unwrap();
+        assert!(!key.is_subtoken());
+        assert!(key.allows_url("/v2/account/wallet"));
+    }
+
+    #[test]
+    fn subtoken() {
+        let client = setup_client();
+        let expire = "2038-01-01T00:00:00Z".parse().unwrap();
+        let result = create_subtoken(
+            &client,
+            vec!["account"],
+            &expire,
+            Some(vec!["/v2/account"])
+        );
+        parse_test!(result);
     }
 
     #[test]
@@ -563,6 +690,27 @@ mod tests {
         parse_test!(result);
     }
 
+    #[test]
+    fn account_dailycrafting() {
+        let client = setup_client();
+        let result = get_account_dailycrafting(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn account_mapchests() {
+        let client = setup_client();
+        let result = get_account_mapchests(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn account_worldbosses() {
+        let client = setup_client();
+        let result = get_account_worldbosses(&client);
+        parse_test!(result);
+    }
+
     #[test]
     fn account_raids() {
         let client = setup_client();
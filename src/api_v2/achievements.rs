@@ -23,37 +23,22 @@
 /// Achievement endpoints
 
 use client::APIClient;
-use common::{
-    APIError,
-    number_to_param,
-    numbers_to_param,
-    string_to_param,
-    strings_to_param,
-    parse_response
-};
+use common::{APIError, parse_response, Page};
+use api_v2::endpoint::{get_all, get_ids, get_many, get_one, get_page};
 use api_v2::types::{
     Achievement,
     AchievementCategory,
     AchievementGroup,
-    DailyAchievements
+    DailyAchievements,
+    IdList
 };
 
 use reqwest::StatusCode;
 
 /// Obtain the requested endpoint
 macro_rules! get_endpoint {
-    ("all_achievements") => {"/v2/achievements"};
-    ("achievements_id", $id: expr) => {format!("/v2/achievements?{}", $id)};
     ("daily_achievements") => {"/v2/achievements/daily"};
     ("daily_achievements_tomorrow") => {"/v2/achievements/daily/tomorrow"};
-    ("all_achievement_groups") => {"/v2/achievements/groups"};
-    ("achievement_groups_id", $id: expr) => {
-        format!("/v2/achievements/groups?{}", $id)
-    };
-    ("all_achievement_categories") => {"/v2/achievements/categories"};
-    ("achievement_categories_id", $id: expr) => {
-        format!("/v2/achievements/categories?{}", $id)
-    };
 }
 
 /// Obtain a list of all the achievement IDs
@@ -61,16 +46,10 @@ macro_rules! get_endpoint {
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_achievement_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_achievements"))
-        .expect("failed to get achievement IDs");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+pub fn get_achievement_ids(
+    client: &APIClient
+) -> Result<IdList<i32>, APIError> {
+    get_ids::<Achievement>(client)
 }
 
 /// Obtain details for the specified achievement
@@ -83,16 +62,7 @@ pub fn get_achievement(
     client: &APIClient,
     id: i32
 ) -> Result<Achievement, APIError> {
-    let param = number_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("achievements_id", param))
-        .expect("failed to get achievement");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<Achievement>(client, id)
 }
 
 /// Obtain details for the specified achievements
@@ -105,16 +75,24 @@ pub fn get_achievements(
     client: &APIClient,
     ids: Vec<i32>
 ) -> Result<Vec<Achievement>, APIError> {
-    let params = numbers_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("achievements_id", params))
-        .expect("failed to get achievements");
+    get_many::<Achievement>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain a page of achievements
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_achievements_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Achievement>, APIError> {
+    get_page::<Achievement>(client, page, page_size)
 }
 
 /// Obtain daily current achievements
@@ -126,13 +104,12 @@ pub fn get_daily_achievements(
     client: &APIClient
 ) -> Result<DailyAchievements, APIError> {
     let mut response = client
-        .make_request(&get_endpoint!("daily_achievements"))
-        .expect("failed to get achievements");
+        .make_request(&get_endpoint!("daily_achievements"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
     )
 }
 
@@ -145,13 +122,12 @@ pub fn get_daily_achievements_tomorrow(
     client: &APIClient
 ) -> Result<DailyAchievements, APIError> {
     let mut response = client
-        .make_request(&get_endpoint!("daily_achievements_tomorrow"))
-        .expect("failed to get achievements");
+        .make_request(&get_endpoint!("daily_achievements_tomorrow"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
     )
 }
 
@@ -162,16 +138,8 @@ pub fn get_daily_achievements_tomorrow(
 /// * `client` - The client to use when performing API requests
 pub fn get_achievement_group_ids(
     client: &APIClient
-) -> Result<Vec<String>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_achievement_groups"))
-        .expect("failed to get group IDs");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+) -> Result<IdList<String>, APIError> {
+    get_ids::<AchievementGroup>(client)
 }
 
 /// Obtain details for the specified achievement group
@@ -184,16 +152,7 @@ pub fn get_achievement_group(
     client: &APIClient,
     id: &str
 ) -> Result<AchievementGroup, APIError> {
-    let param = string_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("achievement_groups_id", param))
-        .expect("failed to get group");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<AchievementGroup>(client, id.to_string())
 }
 
 /// Obtain details for the specified achievement groups
@@ -206,16 +165,36 @@ pub fn get_achievement_groups(
     client: &APIClient,
     ids: Vec<&str>
 ) -> Result<Vec<AchievementGroup>, APIError> {
-    let param = strings_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("achievement_groups_id", param))
-        .expect("failed to get groups");
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    get_many::<AchievementGroup>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain details for all achievement groups
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_achievement_groups(
+    client: &APIClient
+) -> Result<Vec<AchievementGroup>, APIError> {
+    get_all::<AchievementGroup>(client)
+}
+
+/// Obtain a page of achievement groups
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_achievement_groups_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<AchievementGroup>, APIError> {
+    get_page::<AchievementGroup>(client, page, page_size)
 }
 
 /// Obtain a list of all the achievement category IDs
@@ -225,16 +204,8 @@ pub fn get_achievement_groups(
 /// * `client` - The client to use when performing API requests
 pub fn get_achievement_category_ids(
     client: &APIClient
-) -> Result<Vec<i32>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_achievement_categories"))
-        .expect("failed to get category IDs");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+) -> Result<IdList<i32>, APIError> {
+    get_ids::<AchievementCategory>(client)
 }
 
 /// Obtain details for the specified achievement category
@@ -247,16 +218,7 @@ pub fn get_achievement_category(
     client: &APIClient,
     id: i32
 ) -> Result<AchievementCategory, APIError> {
-    let param = number_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("achievement_categories_id", param))
-        .expect("failed to get category");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<AchievementCategory>(client, id)
 }
 
 /// Obtain details for the specified achievement categories
@@ -269,21 +231,40 @@ pub fn get_achievement_categories(
     client: &APIClient,
     ids: Vec<i32>
 ) -> Result<Vec<AchievementCategory>, APIError> {
-    let param = numbers_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("achievement_categories_id", param))
-        .expect("failed to get categories");
+    get_many::<AchievementCategory>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain details for all achievement categories
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_achievement_categories(
+    client: &APIClient
+) -> Result<Vec<AchievementCategory>, APIError> {
+    get_all::<AchievementCategory>(client)
+}
+
+/// Obtain a page of achievement categories
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_achievement_categories_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<AchievementCategory>, APIError> {
+    get_page::<AchievementCategory>(client, page, page_size)
 }
 
 #[cfg(test)]
 mod tests {
-    use client::APIClient;
+    use fixtures::test_client;
     use api_v2::achievements::*;
 
     macro_rules! parse_test {
@@ -297,49 +278,49 @@ mod tests {
 
     #[test]
     fn achievement_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_achievement_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn achievement() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_achievement(&client, 1);
         parse_test!(result);
     }
 
     #[test]
     fn achievements() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_achievements(&client, vec![1, 2, 3]);
         parse_test!(result);
     }
 
     #[test]
     fn daily_achievements() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_daily_achievements(&client);
         parse_test!(result);
     }
 
     #[test]
     fn daily_achievements_tomorrow() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_daily_achievements_tomorrow(&client);
         parse_test!(result);
     }
 
     #[test]
     fn achievement_group_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_achievement_group_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn achievement_group() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_achievement_group(
             &client,
             "65B4B678-607E-4D97-B458-076C3E96A810"
@@ -349,7 +330,7 @@ mod tests {
 
     #[test]
     fn achievement_groups() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_achievement_groups(
             &client,
             vec![
@@ -362,21 +343,21 @@ mod tests {
 
     #[test]
     fn achievement_category_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_achievement_category_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn achievement_category() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_achievement_category(&client, 1);
         parse_test!(result);
     }
 
     #[test]
     fn achievement_categories() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_achievement_categories(&client, vec![1, 2]);
         parse_test!(result);
     }
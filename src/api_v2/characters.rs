@@ -23,10 +23,18 @@
 /// Character endpoints
 /// These require an API key to view
 
+use bundle::try_fetch_bundle;
 use client::APIClient;
 use common::{
     APIError,
-    parse_response
+    APIErrorKind,
+    encode_param,
+    number_to_param,
+    page_to_param,
+    parse_page_response,
+    parse_response,
+    string_to_param,
+    Page
 };
 use api_v2::types::{
     Character,
@@ -39,6 +47,7 @@ use api_v2::types::{
     CharacterSkills,
     CharacterSpecializations,
     CharacterTraining,
+    EquipmentTab,
     SABProgress,
 };
 
@@ -47,23 +56,56 @@ use reqwest::StatusCode;
 /// Obtain the requested endpoint
 macro_rules! get_endpoint {
     ("names") => {"/v2/characters"};
-    ("character", $id: expr) => {format!("/v2/characters/{}", $id)};
-    ("backstory", $id: expr) => {format!("/v2/characters/{}/backstory", $id)};
-    ("core", $id: expr) => {format!("/v2/characters/{}/core", $id)};
-    ("crafting", $id: expr) => {format!("/v2/characters/{}/crafting", $id)};
-    ("equip", $id: expr) => {format!("/v2/characters/{}/equipment", $id)};
-    ("hp", $id: expr) => {format!("/v2/characters/{}/heropoints", $id)};
-    ("inv", $id: expr) => {format!("/v2/characters/{}/inventory", $id)};
-    ("recipes", $id: expr) => {format!("/v2/characters/{}/recipes", $id)};
-    ("sab", $id: expr) => {format!("/v2/characters/{}/sab", $id)};
-    ("skills", $id: expr) => {format!("/v2/characters/{}/skills", $id)};
-    ("specs", $id: expr) => {format!("/v2/characters/{}/specializations", $id)};
-    ("training", $id: expr) => {format!("/v2/characters/{}/training", $id)};
+    ("characters", $params: expr) => {format!("/v2/characters?{}", $params)};
+    ("character", $id: expr) => {
+        format!("/v2/characters/{}", encode_param($id))
+    };
+    ("backstory", $id: expr) => {
+        format!("/v2/characters/{}/backstory", encode_param($id))
+    };
+    ("core", $id: expr) => {
+        format!("/v2/characters/{}/core", encode_param($id))
+    };
+    ("crafting", $id: expr) => {
+        format!("/v2/characters/{}/crafting", encode_param($id))
+    };
+    ("equip", $id: expr) => {
+        format!("/v2/characters/{}/equipment", encode_param($id))
+    };
+    ("equiptabs", $id: expr) => {
+        format!("/v2/characters/{}/equipmenttabs", encode_param($id))
+    };
+    ("equiptabs", $id: expr, $tabs: expr) => {
+        format!(
+            "/v2/characters/{}/equipmenttabs?{}",
+            encode_param($id),
+            $tabs
+        )
+    };
+    ("hp", $id: expr) => {
+        format!("/v2/characters/{}/heropoints", encode_param($id))
+    };
+    ("inv", $id: expr) => {
+        format!("/v2/characters/{}/inventory", encode_param($id))
+    };
+    ("quests", $id: expr) => {
+        format!("/v2/characters/{}/quests", encode_param($id))
+    };
+    ("recipes", $id: expr) => {
+        format!("/v2/characters/{}/recipes", encode_param($id))
+    };
+    ("sab", $id: expr) => {format!("/v2/characters/{}/sab", encode_param($id))};
+    ("skills", $id: expr) => {
+        format!("/v2/characters/{}/skills", encode_param($id))
+    };
+    ("specs", $id: expr) => {
+        format!("/v2/characters/{}/specializations", encode_param($id))
+    };
+    ("training", $id: expr) => {
+        format!("/v2/characters/{}/training", encode_param($id))
+    };
 }
 
-//TODO percent-encode character names
-
-
 /// Obtain summary of details for the specified character
 ///
 /// # Arguments
@@ -76,13 +118,12 @@ pub fn get_character(
     name: &str
 ) -> Result<Character, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("character", name))
-        .expect("failed to get character");
+        .make_authenticated_request(&get_endpoint!("character", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound, StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
     )
 }
 
@@ -98,16 +139,15 @@ pub fn get_character_backstory(
     name: &str
 ) -> Result<CharacterBackstory, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("backstory", name))
-        .expect("failed to get character backstory");
+        .make_authenticated_request(&get_endpoint!("backstory", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
+        vec![StatusCode::OK],
         vec![
-            StatusCode::NotFound,
-            StatusCode::Forbidden,
-            StatusCode::BadRequest
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
         ]
     )
 }
@@ -125,16 +165,15 @@ pub fn get_character_core(
     name: &str
 ) -> Result<CharacterCore, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("core", name))
-        .expect("failed to get character information");
+        .make_authenticated_request(&get_endpoint!("core", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
+        vec![StatusCode::OK],
         vec![
-            StatusCode::NotFound,
-            StatusCode::Forbidden,
-            StatusCode::BadRequest
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
         ]
     )
 }
@@ -151,16 +190,15 @@ pub fn get_character_crafting(
     name: &str
 ) -> Result<CharacterCrafting, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("crafting", name))
-        .expect("failed to get crafting disciplines");
+        .make_authenticated_request(&get_endpoint!("crafting", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
+        vec![StatusCode::OK],
         vec![
-            StatusCode::NotFound,
-            StatusCode::Forbidden,
-            StatusCode::BadRequest
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
         ]
     )
 }
@@ -177,20 +215,90 @@ pub fn get_character_equipment(
     name: &str
 ) -> Result<CharacterEquipment, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("equip", name))
-        .expect("failed to get character equipment");
+        .make_authenticated_request(&get_endpoint!("equip", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
+        vec![StatusCode::OK],
         vec![
-            StatusCode::NotFound,
-            StatusCode::Forbidden,
-            StatusCode::BadRequest
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
         ]
     )
 }
 
+/// Obtain IDs of the equipment tabs of the specified character
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name` - Character to fetch
+pub fn get_character_equipmenttab_ids(
+    client: &APIClient,
+    name: &str
+) -> Result<Vec<i32>, APIError> {
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("equiptabs", name))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain a single equipment tab of the specified character
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name` - Character to fetch
+/// * `tab` - Index of the tab, starting at 1
+pub fn get_character_equipmenttab(
+    client: &APIClient,
+    name: &str,
+    tab: i32
+) -> Result<EquipmentTab, APIError> {
+    let params = number_to_param("tab", tab);
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("equiptabs", name, params))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
+        ]
+    )
+}
+
+/// Obtain every equipment tab of the specified character
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name` - Character to fetch
+pub fn get_character_equipmenttabs(
+    client: &APIClient,
+    name: &str
+) -> Result<Vec<EquipmentTab>, APIError> {
+    let params = string_to_param("tabs", "all");
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("equiptabs", name, params))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
+    )
+}
+
 /// Obtain hero points unlocked for the specified character
 ///
 /// # Arguments
@@ -203,16 +311,15 @@ pub fn get_character_heropoints(
     name: &str
 ) -> Result<Vec<String>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("hp", name))
-        .expect("failed to get hero points");
+        .make_authenticated_request(&get_endpoint!("hp", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
+        vec![StatusCode::OK],
         vec![
-            StatusCode::NotFound,
-            StatusCode::Forbidden,
-            StatusCode::BadRequest
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
         ]
     )
 }
@@ -229,20 +336,69 @@ pub fn get_character_inventory(
     name: &str
 ) -> Result<CharacterInventory, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("inv", name))
-        .expect("failed to get character inventory");
+        .make_authenticated_request(&get_endpoint!("inv", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
+        vec![StatusCode::OK],
         vec![
-            StatusCode::NotFound,
-            StatusCode::Forbidden,
-            StatusCode::BadRequest
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
         ]
     )
 }
 
+/// Obtain details of every character on the account in a single request
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_characters(
+    client: &APIClient
+) -> Result<Vec<Character>, APIError> {
+    let params = string_to_param("ids", "all");
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("characters", params))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain a page of the characters on the account
+///
+/// Useful for accounts with many characters, where requesting all of them
+/// at once results in a large response; use `Page::has_next()` to know
+/// whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of characters per page (max: 200)
+pub fn get_characters_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Character>, APIError> {
+    let params = page_to_param(page, page_size);
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("characters", params))?;
+
+    parse_page_response(
+        &mut response,
+        page,
+        page_size,
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN, StatusCode::BAD_REQUEST]
+    )
+}
+
 /// Obtain character names for an account
 ///
 /// # Arguments
@@ -253,13 +409,12 @@ pub fn get_character_names(
     client: &APIClient
 ) -> Result<Vec<String>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("names"))
-        .expect("failed to get character names");
+        .make_authenticated_request(&get_endpoint!("names"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -275,20 +430,40 @@ pub fn get_character_recipes(
     name: &str
 ) -> Result<CharacterRecipes, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("recipes", name))
-        .expect("failed to get unlocked recipes");
+        .make_authenticated_request(&get_endpoint!("recipes", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
+        vec![StatusCode::OK],
         vec![
-            StatusCode::NotFound,
-            StatusCode::Forbidden,
-            StatusCode::BadRequest
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
         ]
     )
 }
 
+/// Obtain the IDs of the story quests completed by a character
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name` - Character name to fetch
+pub fn get_character_quests(
+    client: &APIClient,
+    name: &str
+) -> Result<Vec<i32>, APIError> {
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("quests", name))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
+    )
+}
+
 /// Obtain SAB progress for the specified character
 ///
 /// # Arguments
@@ -301,13 +476,12 @@ pub fn get_character_sab(
     name: &str
 ) -> Result<SABProgress, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("sab", name))
-        .expect("failed to get SAB progress");
+        .make_authenticated_request(&get_endpoint!("sab", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound, StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
     )
 }
 
@@ -323,16 +497,15 @@ pub fn get_character_skills(
     name: &str
 ) -> Result<CharacterSkills, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("skills", name))
-        .expect("failed to get character skills");
+        .make_authenticated_request(&get_endpoint!("skills", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
+        vec![StatusCode::OK],
         vec![
-            StatusCode::NotFound,
-            StatusCode::Forbidden,
-            StatusCode::BadRequest
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
         ]
     )
 }
@@ -349,16 +522,15 @@ pub fn get_character_specializations(
     name: &str
 ) -> Result<CharacterSpecializations, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("specs", name))
-        .expect("failed to get character specializations");
+        .make_authenticated_request(&get_endpoint!("specs", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
+        vec![StatusCode::OK],
         vec![
-            StatusCode::NotFound,
-            StatusCode::Forbidden,
-            StatusCode::BadRequest
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
         ]
     )
 }
@@ -375,24 +547,111 @@ pub fn get_character_training(
     name: &str
 ) -> Result<CharacterTraining, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("training", name))
-        .expect("failed to get character training");
+        .make_authenticated_request(&get_endpoint!("training", name))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
+        vec![StatusCode::OK],
         vec![
-            StatusCode::NotFound,
-            StatusCode::Forbidden,
-            StatusCode::BadRequest
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST
         ]
     )
 }
 
+/// Obtain the value of a sub-request, or the default value if the token
+/// lacks the permission required by the endpoint
+fn unless_missing_permission<T: Default>(
+    result: Result<T, APIError>
+) -> Result<T, APIError> {
+    match result {
+        Err(ref e) if e.kind() == APIErrorKind::MissingPermission => {
+            Ok(T::default())
+        },
+        result => result
+    }
+}
+
+/// Obtain all the details of a character using the sub-endpoints
+///
+/// The core, backstory, crafting, equipment, inventory, recipes, skills,
+/// specializations and training endpoints are requested concurrently. This
+/// is useful when `get_character()` is slow or fails because the token lacks
+/// some permission: details requiring a missing permission are left empty.
+/// PvP equipment and WvW abilities are only available through
+/// `get_character()` and are always empty.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name` - Character to fetch
+pub fn get_character_full(
+    client: &APIClient,
+    name: &str
+) -> Result<Character, APIError> {
+    let (core, equipment, inventory, skills, specializations, training,
+         crafting, extra) = try_fetch_bundle(client, (
+        |c: &APIClient| get_character_core(c, name),
+        |c: &APIClient| get_character_equipment(c, name),
+        |c: &APIClient| get_character_inventory(c, name),
+        |c: &APIClient| get_character_skills(c, name),
+        |c: &APIClient| get_character_specializations(c, name),
+        |c: &APIClient| get_character_training(c, name),
+        |c: &APIClient| get_character_crafting(c, name),
+        |c: &APIClient| {
+            let backstory = unless_missing_permission(
+                get_character_backstory(c, name).map(|b| b.backstory)
+            )?;
+            let recipes = unless_missing_permission(
+                get_character_recipes(c, name).map(|r| r.recipes)
+            )?;
+
+            Ok((backstory, recipes))
+        }
+    ));
+
+    let core = core?;
+    let (backstory, recipes) = extra?;
+
+    Ok(Character {
+        backstory: backstory,
+        name: core.name,
+        race: core.race,
+        gender: core.gender,
+        profession: core.profession,
+        level: core.level,
+        guild: core.guild,
+        age: core.age,
+        created: core.created,
+        deaths: core.deaths,
+        title: core.title,
+        crafting: unless_missing_permission(crafting.map(|c| c.crafting))?,
+        equipment: unless_missing_permission(
+            equipment.map(|e| e.equipment)
+        )?,
+        equipment_pvp: Default::default(),
+        bags: unless_missing_permission(inventory.map(|i| i.bags))?,
+        recipes: recipes,
+        skills: unless_missing_permission(skills.map(|s| s.skills))?,
+        specializations: unless_missing_permission(
+            specializations.map(|s| s.specializations)
+        )?,
+        training: unless_missing_permission(training.map(|t| t.training))?,
+        wvw_abilities: Vec::new(),
+        active_equipment_tab: None,
+        equipment_tabs_unlocked: None,
+        equipment_tabs: Vec::new()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::env;
+    use serde_json;
     use client::APIClient;
+    use fixtures::test_client;
     use api_v2::characters::*;
 
     macro_rules! parse_test {
@@ -405,16 +664,13 @@ mod tests {
     }
 
     fn setup_client() -> APIClient {
-        match env::var("TOKEN") {
-            Ok(token) => APIClient::new("en", Some(token.to_string())),
-            Err(_) => panic!("Need a token to test endpoint"),
-        }
+        test_client()
     }
 
     fn set_name() -> String {
         match env::var("CHAR_NAME") {
             Ok(name) => name,
-            Err(_) => panic!("Need a character name to test endpoint"),
+            Err(_) => "Fixture Character".to_string(),
         }
     }
 
@@ -489,6 +745,14 @@ mod tests {
         parse_test!(result);
     }
 
+    #[test]
+    fn character_quests() {
+        let client = setup_client();
+        let name = set_name();
+        let result = get_character_quests(&client, &name.as_str());
+        parse_test!(result);
+    }
+
     #[test]
     fn character_sab() {
         let client = setup_client();
@@ -520,4 +784,66 @@ mod tests {
         let result = get_character_training(&client, &name.as_str());
         parse_test!(result);
     }
+
+    #[test]
+    fn characters() {
+        let client = setup_client();
+        let result = get_characters(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn characters_page() {
+        let client = setup_client();
+        let result = get_characters_page(&client, 0, 2);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn character_full() {
+        let client = setup_client();
+        let name = set_name();
+        let result = get_character_full(&client, &name.as_str());
+        parse_test!(result);
+    }
+
+    #[test]
+    fn character_equipmenttabs() {
+        let client = setup_client();
+        let name = set_name();
+        let result = get_character_equipmenttabs(&client, &name.as_str());
+        parse_test!(result);
+    }
+
+    #[test]
+    fn equipmenttab_parse() {
+        let tab: EquipmentTab = serde_json::from_str(r#"{
+            "tab": 2,
+            "name": "Raid",
+            "is_active": true,
+            "equipment": [
+                {"id": 48073, "slot": "Helm", "upgrades": [24836]}
+            ],
+            "equipment_pvp": {"amulet": 8, "rune": null, "sigils": [null]}
+        }"#).unwrap();
+
+        assert_eq!(tab.tab, 2);
+        assert!(tab.is_active);
+        assert_eq!(tab.equipment[0].slot, "Helm");
+        assert_eq!(tab.equipment_pvp.amulet, Some(8));
+    }
+
+    #[test]
+    fn missing_permission() {
+        let error = APIError::with_kind(
+            APIErrorKind::MissingPermission,
+            "requires scope inventories"
+        );
+        let result: Result<Vec<i32>, APIError> = Err(error);
+        assert!(unless_missing_permission(result).unwrap().is_empty());
+
+        let error = APIError::new("no such id");
+        let result: Result<Vec<i32>, APIError> = Err(error);
+        assert!(unless_missing_permission(result).is_err());
+    }
 }
@@ -26,11 +26,13 @@ use client::APIClient;
 use common::{
     APIError,
     number_to_param,
-    numbers_to_param,
-    parse_response
+    parse_response,
+    Page
 };
+use api_v2::endpoint::{get_ids, get_many, get_one, get_page};
 use api_v2::types::{
     ExchangeRate,
+    IdList,
     TPItem,
     TPItemInfo,
     TPTransaction
@@ -47,10 +49,6 @@ macro_rules! get_endpoint {
     ("exchange_gems", $amount: expr) => {
         format!("/v2/commerce/exchange/gems?{}", $amount)
     };
-    ("all_listings") => {"/v2/commerce/listings"};
-    ("listings_id", $id: expr) => {format!("/v2/commerce/listings?{}", $id)};
-    ("all_prices") => {"/v2/commerce/prices"};
-    ("prices_id", $id: expr) => {format!("/v2/commerce/prices?{}", $id)};
     ("current_buy") => {"/v2/commerce/transactions/current/buys"};
     ("current_sell") => {"/v2/commerce/transactions/current/sells"};
     ("history_buy") => {"/v2/commerce/transactions/history/buys"};
@@ -74,13 +72,12 @@ macro_rules! get_endpoint {
 /// let exchange_resources = get_exchange(&client);
 /// ```
 pub fn get_exchange(client: &APIClient) -> Result<Vec<String>, APIError> {
-    let mut response = client.make_request(get_endpoint!("exchange"))
-        .expect("failed to get gem exchange resources");
+    let mut response = client.make_request(get_endpoint!("exchange"))?;
 
     parse_response::<Vec<String>>(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
     )
 }
 
@@ -96,13 +93,12 @@ pub fn get_coin_exchange(
 ) -> Result<ExchangeRate, APIError> {
     let param = number_to_param("quantity", amount);
     let mut response = client
-        .make_request(&get_endpoint!("exchange_coins", param))
-        .expect("failed to get coin exchange rate");
+        .make_request(&get_endpoint!("exchange_coins", param))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound, StatusCode::BadRequest]
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::BAD_REQUEST]
     )
 }
 
@@ -118,13 +114,12 @@ pub fn get_gem_exchange(
 ) -> Result<ExchangeRate, APIError> {
     let param = number_to_param("quantity", amount);
     let mut response = client
-        .make_request(&get_endpoint!("exchange_gems", param))
-        .expect("failed to get gem exchange rate");
+        .make_request(&get_endpoint!("exchange_gems", param))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound, StatusCode::BadRequest]
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::BAD_REQUEST]
     )
 }
 
@@ -133,16 +128,8 @@ pub fn get_gem_exchange(
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_listing_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_listings"))
-        .expect("failed to get listings IDs");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+pub fn get_listing_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<TPItem>(client)
 }
 
 /// Obtain details for the specified item listing
@@ -155,16 +142,7 @@ pub fn get_listing(
     client: &APIClient,
     id: i32
 ) -> Result<TPItem, APIError> {
-    let param = number_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("listings_id", param))
-        .expect("failed to get item listing");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<TPItem>(client, id)
 }
 
 /// Obtain details for the specified item listings
@@ -177,16 +155,24 @@ pub fn get_listings(
     client: &APIClient,
     ids: Vec<i32>
 ) -> Result<Vec<TPItem>, APIError> {
-    let params = numbers_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("listings_id", params))
-        .expect("failed to get item listings");
+    get_many::<TPItem>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain a page of item listings
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_listings_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<TPItem>, APIError> {
+    get_page::<TPItem>(client, page, page_size)
 }
 
 /// Obtain a list of item IDs present in the trading post
@@ -194,16 +180,8 @@ pub fn get_listings(
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_pricing_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_prices"))
-        .expect("failed to get item IDs");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+pub fn get_pricing_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<TPItemInfo>(client)
 }
 
 /// Obtain details for the specified item in the trading post
@@ -216,16 +194,7 @@ pub fn get_pricing(
     client: &APIClient,
     id: i32
 ) -> Result<TPItemInfo, APIError> {
-    let param = number_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("prices_id", param))
-        .expect("failed to get item information");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<TPItemInfo>(client, id)
 }
 
 /// Obtain details for the specified item listings
@@ -238,16 +207,24 @@ pub fn get_pricings(
     client: &APIClient,
     ids: Vec<i32>
 ) -> Result<Vec<TPItemInfo>, APIError> {
-    let params = numbers_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("prices_id", params))
-        .expect("failed to get item information");
+    get_many::<TPItemInfo>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain a page of item prices
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_pricings_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<TPItemInfo>, APIError> {
+    get_page::<TPItemInfo>(client, page, page_size)
 }
 
 /// Obtain currently unfulfilled buy transactions for an account
@@ -260,13 +237,12 @@ pub fn get_current_buy_transactions(
     client: &APIClient
 ) -> Result<Vec<TPTransaction>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("current_buy"))
-        .expect("failed to get transactions");
+        .make_authenticated_request(&get_endpoint!("current_buy"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -280,13 +256,12 @@ pub fn get_current_sell_transactions(
     client: &APIClient
 ) -> Result<Vec<TPTransaction>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("current_sell"))
-        .expect("failed to get transactions");
+        .make_authenticated_request(&get_endpoint!("current_sell"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -300,13 +275,12 @@ pub fn get_history_buy_transactions(
     client: &APIClient
 ) -> Result<Vec<TPTransaction>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("history_buy"))
-        .expect("failed to get transactions");
+        .make_authenticated_request(&get_endpoint!("history_buy"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
@@ -320,20 +294,19 @@ pub fn get_history_sell_transactions(
     client: &APIClient
 ) -> Result<Vec<TPTransaction>, APIError> {
     let mut response = client
-        .make_authenticated_request(&get_endpoint!("history_sell"))
-        .expect("failed to get transactions");
+        .make_authenticated_request(&get_endpoint!("history_sell"))?;
 
     parse_response(
         &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::Forbidden]
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
     )
 }
 
 #[cfg(test)]
 mod tests {
-    use std::env;
     use client::APIClient;
+    use fixtures::test_client;
     use api_v2::commerce::*;
 
     macro_rules! parse_test {
@@ -346,10 +319,7 @@ mod tests {
     }
 
     fn setup_client() -> APIClient {
-        match env::var("TOKEN") {
-            Ok(token) => APIClient::new("en", Some(token.to_string())),
-            Err(_) => panic!("Need a token to test endpoint"),
-        }
+        test_client()
     }
 
     #[test]
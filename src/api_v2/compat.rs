@@ -0,0 +1,246 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Deserializers accepting both old and new shapes of fields
+/// Used in fields of the API types whose format has changed over time, and
+/// in the API types that flatten other types
+
+use std::fmt;
+use std::marker::PhantomData;
+use serde::{Deserialize, Deserializer};
+use serde::de::{
+    self,
+    DeserializeOwned,
+    DeserializeSeed,
+    IgnoredAny,
+    IntoDeserializer,
+    MapAccess,
+    Visitor
+};
+use serde::de::value::{self, MapAccessDeserializer};
+
+
+/// Either a single string or a list of strings
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrList {
+    String(String),
+    List(Vec<String>)
+}
+
+/// Either a number or a boolean
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrBool {
+    Number(i32),
+    Bool(bool)
+}
+
+/// Deserialize a list of strings that used to be a single string
+///
+/// Used for `Account.access`, which was a string before the API started
+/// listing every expansion the account has access to.
+pub fn string_or_list<'de, D>(
+    deserializer: D
+) -> Result<Vec<String>, D::Error> where D: Deserializer<'de> {
+    Ok(match Option::<StringOrList>::deserialize(deserializer)? {
+        Some(StringOrList::String(value)) => vec![value],
+        Some(StringOrList::List(values)) => values,
+        None => Vec::new()
+    })
+}
+
+/// Deserialize a number that may also be sent as a boolean
+///
+/// Used for the `value` of skill and trait facts, which is `true` in
+/// `Unblockable` facts. Booleans are converted to `1` or `0`.
+pub fn number_or_bool<'de, D>(
+    deserializer: D
+) -> Result<Option<i32>, D::Error> where D: Deserializer<'de> {
+    Ok(match Option::<NumberOrBool>::deserialize(deserializer)? {
+        Some(NumberOrBool::Number(value)) => Some(value),
+        Some(NumberOrBool::Bool(value)) => Some(value as i32),
+        None => None
+    })
+}
+
+/// Type whose fields include flattened types
+///
+/// `serde(flatten)` buffers the fields of the response before handing them
+/// to the flattened types, so fields unknown to every type are consumed
+/// without being reported as ignored (e.g. to the strict mode). These types
+/// derive `Deserialize` with `#[serde(remote = "Self")]` and implement it
+/// through `known_fields()`, which skips the unknown fields itself.
+pub trait Flattened: Sized {
+    /// Names of the fields of the response used by the type, including
+    /// the ones of the flattened types
+    fn fields() -> Vec<&'static str>;
+
+    /// Deserialize the type with its derived implementation
+    fn deserialize_fields<'de, D>(
+        deserializer: D
+    ) -> Result<Self, D::Error> where D: Deserializer<'de>;
+}
+
+/// Deserialize a type that flattens other types, skipping unknown fields
+///
+/// Skipped fields are reported as ignored by the deserializer, like in any
+/// other type.
+pub fn known_fields<'de, D, T>(
+    deserializer: D
+) -> Result<T, D::Error> where D: Deserializer<'de>, T: Flattened {
+    deserializer.deserialize_map(KnownFieldsVisitor(PhantomData))
+}
+
+/// Visitor of the fields of a type that flattens other types
+struct KnownFieldsVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for KnownFieldsVisitor<T> where T: Flattened {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<T, A::Error>
+        where A: MapAccess<'de> {
+
+        T::deserialize_fields(MapAccessDeserializer::new(KnownFields {
+            map: map,
+            fields: T::fields()
+        }))
+    }
+}
+
+/// Fields of a map, skipping the ones that are not known
+struct KnownFields<A> {
+    /// Fields being deserialized
+    map: A,
+    /// Names of the known fields
+    fields: Vec<&'static str>
+}
+
+impl<'de, A> MapAccess<'de> for KnownFields<A> where A: MapAccess<'de> {
+    type Error = A::Error;
+
+    fn next_key_seed<K>(
+        &mut self,
+        seed: K
+    ) -> Result<Option<K::Value>, A::Error> where K: DeserializeSeed<'de> {
+        while let Some(key) = self.map.next_key::<String>()? {
+            if self.fields.contains(&key.as_str()) {
+                return seed.deserialize(key.into_deserializer()).map(Some);
+            }
+
+            self.map.next_value::<IgnoredAny>()?;
+        }
+
+        Ok(None)
+    }
+
+    fn next_value_seed<V>(
+        &mut self,
+        seed: V
+    ) -> Result<V::Value, A::Error> where V: DeserializeSeed<'de> {
+        self.map.next_value_seed(seed)
+    }
+}
+
+/// Obtain the names of the fields of a struct deriving `Deserialize`
+///
+/// Returns an empty list for types that are not structs, or that flatten
+/// other types.
+pub fn struct_fields<T>() -> &'static [&'static str] where T: DeserializeOwned {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// Deserializer that only records the names of the fields of a struct
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de, 'a> Deserializer<'de> for FieldNames<'a> {
+    type Error = value::Error;
+
+    fn deserialize_any<V>(self, _: V) -> Result<V::Value, value::Error>
+        where V: Visitor<'de> {
+
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        _: V
+    ) -> Result<V::Value, value::Error> where V: Visitor<'de> {
+        *self.0 = fields;
+        Err(de::Error::custom("only the names of the fields are obtained"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(all(test, feature = "account", feature = "static"))]
+mod tests {
+    use serde_json;
+    use api_v2::types::{Account, SkillFact};
+
+    fn account(access: &str) -> Account {
+        serde_json::from_str(&format!(r#"{{
+            "id": "00000000-0000-0000-0000-000000000000",
+            "age": 3600,
+            "name": "Account.1234",
+            "world": 2001,
+            "created": "2012-08-28T00:00:00Z",
+            "access": {},
+            "commander": false
+        }}"#, access)).unwrap()
+    }
+
+    #[test]
+    fn access_shapes() {
+        assert_eq!(account(r#""GuildWars2""#).access, vec!["GuildWars2"]);
+        assert_eq!(
+            account(r#"["GuildWars2", "HeartOfThorns"]"#).access,
+            vec!["GuildWars2", "HeartOfThorns"]
+        );
+        assert!(account("null").access.is_empty());
+    }
+
+    #[test]
+    fn fact_value_shapes() {
+        let fact: SkillFact = serde_json::from_str(
+            r#"{"text": "Unblockable", "type": "Unblockable", "value": true}"#
+        ).unwrap();
+        assert_eq!(fact.value, Some(1));
+
+        let fact: SkillFact = serde_json::from_str(
+            r#"{"text": "Range", "type": "Range", "value": 1200}"#
+        ).unwrap();
+        assert_eq!(fact.value, Some(1200));
+    }
+}
@@ -0,0 +1,443 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Continent endpoints
+///
+/// The continent data is nested (continent, floor, region, map and then
+/// sectors, points of interest and tasks), so the endpoints are accessed
+/// through a path builder rather than individual functions:
+///
+/// ```no_run
+/// use tyria::client::APIClient;
+/// use tyria::api_v2::continents::continents;
+///
+/// let client = APIClient::new("en", None);
+/// let map = continents().continent(1).floor(1).region(3).map(28);
+/// let sectors = map.sector_ids(&client);
+/// ```
+
+use client::APIClient;
+use common::{
+    APIError,
+    numbers_to_param,
+    parse_response
+};
+use api_v2::types::{
+    Continent,
+    ContinentFloor,
+    ContinentMap,
+    ContinentPointOfInterest,
+    ContinentRegion,
+    ContinentSector,
+    ContinentTask,
+    IdList
+};
+
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+
+
+/// Perform a request to a continent endpoint
+fn fetch<T>(client: &APIClient, url: &str) -> Result<T, APIError>
+    where T: DeserializeOwned {
+
+    let mut response = client.make_request(url)?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK, StatusCode::PARTIAL_CONTENT],
+        vec![StatusCode::NOT_FOUND, StatusCode::BAD_REQUEST]
+    )
+}
+
+/// Perform a bulk request to a continent endpoint
+fn fetch_many<T>(
+    client: &APIClient,
+    url: &str,
+    ids: Vec<i32>
+) -> Result<Vec<T>, APIError> where T: DeserializeOwned {
+    fetch(client, &format!("{}?{}", url, numbers_to_param("ids", &ids)))
+}
+
+/// Start a path to the continent endpoints
+pub fn continents() -> Continents {
+    Continents
+}
+
+/// Path to `/v2/continents`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Continents;
+
+impl Continents {
+    /// Endpoint of the path
+    pub fn path(&self) -> String {
+        "/v2/continents".to_string()
+    }
+
+    /// Obtain the IDs of all the continents
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn ids(&self, client: &APIClient) -> Result<IdList<i32>, APIError> {
+        fetch(client, &self.path())
+    }
+
+    /// Obtain details for the specified continents
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    /// * `ids` - IDs to fetch from the server
+    pub fn get_many(
+        &self,
+        client: &APIClient,
+        ids: Vec<i32>
+    ) -> Result<Vec<Continent>, APIError> {
+        fetch_many(client, &self.path(), ids)
+    }
+
+    /// Select a continent
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID of the continent
+    pub fn continent(&self, id: i32) -> ContinentPath {
+        ContinentPath {
+            continent: id
+        }
+    }
+}
+
+/// Path to `/v2/continents/:continent`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContinentPath {
+    /// Continent ID
+    continent: i32
+}
+
+impl ContinentPath {
+    /// Endpoint of the path
+    pub fn path(&self) -> String {
+        format!("/v2/continents/{}", self.continent)
+    }
+
+    /// Obtain details for the continent
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn get(&self, client: &APIClient) -> Result<Continent, APIError> {
+        fetch(client, &self.path())
+    }
+
+    /// Obtain the IDs of the floors in the continent
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn floor_ids(
+        &self,
+        client: &APIClient
+    ) -> Result<IdList<i32>, APIError> {
+        fetch(client, &format!("{}/floors", self.path()))
+    }
+
+    /// Obtain details for the specified floors, including all their
+    /// regions and maps
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    /// * `ids` - IDs to fetch from the server
+    pub fn floors(
+        &self,
+        client: &APIClient,
+        ids: Vec<i32>
+    ) -> Result<Vec<ContinentFloor>, APIError> {
+        fetch_many(client, &format!("{}/floors", self.path()), ids)
+    }
+
+    /// Select a floor of the continent
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID of the floor
+    pub fn floor(&self, id: i32) -> FloorPath {
+        FloorPath {
+            continent: *self,
+            floor: id
+        }
+    }
+}
+
+/// Path to `/v2/continents/:continent/floors/:floor`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloorPath {
+    /// Parent continent
+    continent: ContinentPath,
+    /// Floor ID
+    floor: i32
+}
+
+impl FloorPath {
+    /// Endpoint of the path
+    pub fn path(&self) -> String {
+        format!("{}/floors/{}", self.continent.path(), self.floor)
+    }
+
+    /// Obtain details for the floor, including all its regions and maps
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn get(
+        &self,
+        client: &APIClient
+    ) -> Result<ContinentFloor, APIError> {
+        fetch(client, &self.path())
+    }
+
+    /// Obtain the IDs of the regions in the floor
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn region_ids(
+        &self,
+        client: &APIClient
+    ) -> Result<IdList<i32>, APIError> {
+        fetch(client, &format!("{}/regions", self.path()))
+    }
+
+    /// Select a region of the floor
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID of the region
+    pub fn region(&self, id: i32) -> RegionPath {
+        RegionPath {
+            floor: *self,
+            region: id
+        }
+    }
+}
+
+/// Path to `/v2/continents/:continent/floors/:floor/regions/:region`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegionPath {
+    /// Parent floor
+    floor: FloorPath,
+    /// Region ID
+    region: i32
+}
+
+impl RegionPath {
+    /// Endpoint of the path
+    pub fn path(&self) -> String {
+        format!("{}/regions/{}", self.floor.path(), self.region)
+    }
+
+    /// Obtain details for the region, including all its maps
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn get(
+        &self,
+        client: &APIClient
+    ) -> Result<ContinentRegion, APIError> {
+        fetch(client, &self.path())
+    }
+
+    /// Obtain the IDs of the maps in the region
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn map_ids(&self, client: &APIClient) -> Result<IdList<i32>, APIError> {
+        fetch(client, &format!("{}/maps", self.path()))
+    }
+
+    /// Select a map of the region
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID of the map
+    pub fn map(&self, id: i32) -> MapPath {
+        MapPath {
+            region: *self,
+            map: id
+        }
+    }
+}
+
+/// Path to `/v2/continents/:continent/floors/:floor/regions/:region/maps/:map`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MapPath {
+    /// Parent region
+    region: RegionPath,
+    /// Map ID
+    map: i32
+}
+
+impl MapPath {
+    /// Endpoint of the path
+    pub fn path(&self) -> String {
+        format!("{}/maps/{}", self.region.path(), self.map)
+    }
+
+    /// Obtain details for the map, including its sectors, points of
+    /// interest and tasks
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn get(&self, client: &APIClient) -> Result<ContinentMap, APIError> {
+        fetch(client, &self.path())
+    }
+
+    /// Obtain the IDs of the sectors in the map
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn sector_ids(
+        &self,
+        client: &APIClient
+    ) -> Result<IdList<i32>, APIError> {
+        fetch(client, &format!("{}/sectors", self.path()))
+    }
+
+    /// Obtain details for the specified sectors of the map
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    /// * `ids` - IDs to fetch from the server
+    pub fn sectors(
+        &self,
+        client: &APIClient,
+        ids: Vec<i32>
+    ) -> Result<Vec<ContinentSector>, APIError> {
+        fetch_many(client, &format!("{}/sectors", self.path()), ids)
+    }
+
+    /// Obtain the IDs of the points of interest in the map
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn poi_ids(&self, client: &APIClient) -> Result<IdList<i32>, APIError> {
+        fetch(client, &format!("{}/pois", self.path()))
+    }
+
+    /// Obtain details for the specified points of interest of the map
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    /// * `ids` - IDs to fetch from the server
+    pub fn pois(
+        &self,
+        client: &APIClient,
+        ids: Vec<i32>
+    ) -> Result<Vec<ContinentPointOfInterest>, APIError> {
+        fetch_many(client, &format!("{}/pois", self.path()), ids)
+    }
+
+    /// Obtain the IDs of the tasks (renown hearts) in the map
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn task_ids(
+        &self,
+        client: &APIClient
+    ) -> Result<IdList<i32>, APIError> {
+        fetch(client, &format!("{}/tasks", self.path()))
+    }
+
+    /// Obtain details for the specified tasks (renown hearts) of the map
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    /// * `ids` - IDs to fetch from the server
+    pub fn tasks(
+        &self,
+        client: &APIClient,
+        ids: Vec<i32>
+    ) -> Result<Vec<ContinentTask>, APIError> {
+        fetch_many(client, &format!("{}/tasks", self.path()), ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fixtures::test_client;
+    use api_v2::continents::*;
+
+    macro_rules! parse_test {
+        ($result:expr) => {
+            match $result {
+                Ok(_) => assert!(true),
+                Err(e) => panic!(e.description().to_string()),
+            };
+        }
+    }
+
+    #[test]
+    fn paths() {
+        let map = continents().continent(1).floor(1).region(3).map(28);
+
+        assert_eq!(continents().path(), "/v2/continents");
+        assert_eq!(
+            map.path(),
+            "/v2/continents/1/floors/1/regions/3/maps/28"
+        );
+    }
+
+    #[test]
+    fn continent() {
+        let client = test_client();
+        let result = continents().continent(1).get(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn region() {
+        let client = test_client();
+        let result = continents().continent(1).floor(1).region(3)
+            .get(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn map_sectors() {
+        let client = test_client();
+        let map = continents().continent(1).floor(1).region(3).map(28);
+        let result = map.sector_ids(&client)
+            .and_then(|ids| map.sectors(&client, ids.into_ids()));
+        parse_test!(result);
+    }
+}
@@ -0,0 +1,129 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Daily reset endpoints
+/// These list the rewards that can be obtained once per day, to be compared
+/// against the account progress
+
+use client::APIClient;
+use common::{
+    APIError,
+    parse_response
+};
+use api_v2::types::IdList;
+
+use reqwest::StatusCode;
+
+/// Obtain the requested endpoint
+macro_rules! get_endpoint {
+    ("dailycrafting") => {"/v2/dailycrafting"};
+    ("mapchests") => {"/v2/mapchests"};
+    ("worldbosses") => {"/v2/worldbosses"};
+}
+
+/// Obtain a list of the items that can be crafted once per day
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_dailycrafting_ids(
+    client: &APIClient
+) -> Result<IdList<String>, APIError> {
+    let mut response = client.make_request(get_endpoint!("dailycrafting"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain a list of the Hero's Choice chests that can be opened once per
+/// day
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_mapchest_ids(
+    client: &APIClient
+) -> Result<IdList<String>, APIError> {
+    let mut response = client.make_request(get_endpoint!("mapchests"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain a list of the world bosses that reward a chest once per day
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_worldboss_ids(
+    client: &APIClient
+) -> Result<IdList<String>, APIError> {
+    let mut response = client.make_request(get_endpoint!("worldbosses"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use fixtures::test_client;
+    use api_v2::daily::*;
+
+    macro_rules! parse_test {
+        ($result:expr) => {
+            match $result {
+                Ok(_) => assert!(true),
+                Err(e) => panic!(e.description().to_string()),
+            };
+        }
+    }
+
+    #[test]
+    fn dailycrafting_ids() {
+        let client = test_client();
+        let result = get_dailycrafting_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn mapchest_ids() {
+        let client = test_client();
+        let result = get_mapchest_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn worldboss_ids() {
+        let client = test_client();
+        let result = get_worldboss_ids(&client);
+        parse_test!(result);
+    }
+}
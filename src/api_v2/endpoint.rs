@@ -0,0 +1,420 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Generic access to endpoints
+/// Response types implement the `Endpoint` traits, which describe where and
+/// how they are requested, so that they can be fetched with the generic
+/// functions of this module (e.g. `get_one::<Skill>(&client, 5491)`)
+///
+/// The traits are also available without the `blocking` feature, so that
+/// other HTTP clients (e.g. on `wasm32`) can reuse the endpoint definitions.
+
+use std::fmt::Display;
+
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "blocking")]
+use client::APIClient;
+#[cfg(feature = "blocking")]
+use common::{
+    APIError,
+    APIResponse,
+    page_to_param,
+    string_to_param,
+    strings_to_param,
+    Page
+};
+use api_v2::types::APIKey;
+#[cfg(feature = "account")]
+use api_v2::types::Account;
+#[cfg(feature = "commerce")]
+use api_v2::types::{
+    TPItem,
+    TPItemInfo
+};
+#[cfg(feature = "guild")]
+use api_v2::types::GuildUpgrade;
+#[cfg(feature = "static")]
+use api_v2::types::{
+    Achievement,
+    AchievementCategory,
+    AchievementGroup,
+    BackstoryAnswer,
+    BackstoryQuestion,
+    Color,
+    Item,
+    ItemStat,
+    Legend,
+    Mastery,
+    Outfit,
+    Pet,
+    Profession,
+    Quest,
+    Race,
+    Skill,
+    Specialization,
+    Story,
+    StorySeason,
+    Trait
+};
+#[cfg(feature = "wvw")]
+use api_v2::types::{
+    WvWAbility,
+    WvWMatch,
+    WvWRank,
+    WvWUpgrade
+};
+#[cfg(any(feature = "account", feature = "static"))]
+use api_v2::types::Cat;
+#[cfg(feature = "blocking")]
+use api_v2::types::{IdList, MAX_BULK_IDS};
+
+
+/// Type returned by an endpoint of the API
+pub trait Endpoint: DeserializeOwned {
+    /// Path of the endpoint, such as `/v2/skills`
+    const PATH: &'static str;
+
+    /// Whether the endpoint requires an API token
+    const AUTHENTICATED: bool;
+}
+
+/// Type returned by an endpoint requiring an API token
+pub trait AuthenticatedEndpoint: Endpoint {}
+
+/// Type returned by an endpoint supporting bulk requests
+///
+/// These endpoints list the available IDs and accept the `id`, `ids` and
+/// `page` parameters.
+pub trait BulkEndpoint: Endpoint {
+    /// Type of the IDs of the entries
+    type Id: Clone + Display + DeserializeOwned;
+}
+
+/// Implement the endpoint traits for a type
+macro_rules! impl_endpoint {
+    ($type: ty, $path: expr) => {
+        impl Endpoint for $type {
+            const PATH: &'static str = $path;
+            const AUTHENTICATED: bool = false;
+        }
+    };
+    ($type: ty, $path: expr, authenticated) => {
+        impl Endpoint for $type {
+            const PATH: &'static str = $path;
+            const AUTHENTICATED: bool = true;
+        }
+
+        impl AuthenticatedEndpoint for $type {}
+    };
+    ($type: ty, $path: expr, bulk $id: ty) => {
+        impl_endpoint!($type, $path);
+
+        impl BulkEndpoint for $type {
+            type Id = $id;
+        }
+    };
+}
+
+#[cfg(feature = "account")]
+impl_endpoint!(Account, "/v2/account", authenticated);
+impl_endpoint!(APIKey, "/v2/tokeninfo", authenticated);
+#[cfg(feature = "static")]
+impl_endpoint!(Achievement, "/v2/achievements", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(AchievementCategory, "/v2/achievements/categories", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(AchievementGroup, "/v2/achievements/groups", bulk String);
+#[cfg(feature = "static")]
+impl_endpoint!(BackstoryAnswer, "/v2/backstory/answers", bulk String);
+#[cfg(feature = "static")]
+impl_endpoint!(BackstoryQuestion, "/v2/backstory/questions", bulk i32);
+#[cfg(any(feature = "account", feature = "static"))]
+impl_endpoint!(Cat, "/v2/home/cats", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(Color, "/v2/colors", bulk i32);
+#[cfg(feature = "guild")]
+impl_endpoint!(GuildUpgrade, "/v2/guild/upgrades", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(Item, "/v2/items", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(ItemStat, "/v2/itemstats", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(Legend, "/v2/legends", bulk String);
+#[cfg(feature = "static")]
+impl_endpoint!(Mastery, "/v2/masteries", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(Outfit, "/v2/outfits", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(Pet, "/v2/pets", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(Profession, "/v2/professions", bulk String);
+#[cfg(feature = "static")]
+impl_endpoint!(Quest, "/v2/quests", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(Race, "/v2/races", bulk String);
+#[cfg(feature = "static")]
+impl_endpoint!(Skill, "/v2/skills", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(Specialization, "/v2/specializations", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(Story, "/v2/stories", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(StorySeason, "/v2/stories/seasons", bulk String);
+#[cfg(feature = "commerce")]
+impl_endpoint!(TPItem, "/v2/commerce/listings", bulk i32);
+#[cfg(feature = "commerce")]
+impl_endpoint!(TPItemInfo, "/v2/commerce/prices", bulk i32);
+#[cfg(feature = "static")]
+impl_endpoint!(Trait, "/v2/traits", bulk i32);
+#[cfg(feature = "wvw")]
+impl_endpoint!(WvWAbility, "/v2/wvw/abilities", bulk i32);
+#[cfg(feature = "wvw")]
+impl_endpoint!(WvWMatch, "/v2/wvw/matches", bulk String);
+#[cfg(feature = "wvw")]
+impl_endpoint!(WvWRank, "/v2/wvw/ranks", bulk i32);
+#[cfg(feature = "wvw")]
+impl_endpoint!(WvWUpgrade, "/v2/wvw/upgrades", bulk i32);
+
+/// Perform a request to the endpoint of a type and parse its response
+///
+/// Requests go through `APIClient::fetch()`, so they use the cache, the
+/// strict mode and every other setting of the client.
+#[cfg(feature = "blocking")]
+fn request<T: Endpoint, R: DeserializeOwned>(
+    client: &APIClient,
+    url: &str
+) -> Result<APIResponse<R>, APIError> {
+    client.fetch(url, T::AUTHENTICATED, true)
+}
+
+/// Obtain the data of an endpoint that does not take parameters
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+///
+/// # Example
+///
+/// ```no_run
+/// use tyria::client::APIClient;
+/// use tyria::api_v2::endpoint::get_single;
+/// use tyria::api_v2::types::Account;
+///
+/// let client = APIClient::new("en", Some("token".to_string()));
+/// let account = get_single::<Account>(&client);
+/// ```
+#[cfg(feature = "blocking")]
+pub fn get_single<T: Endpoint>(client: &APIClient) -> Result<T, APIError> {
+    request::<T, T>(client, T::PATH).map(|response| response.data)
+}
+
+/// Obtain a list of all the IDs of a bulk endpoint
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+#[cfg(feature = "blocking")]
+pub fn get_ids<T: BulkEndpoint>(
+    client: &APIClient
+) -> Result<IdList<T::Id>, APIError> {
+    request::<T, _>(client, T::PATH).map(|response| response.data)
+}
+
+/// Obtain the entry of a bulk endpoint with the specified ID
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+///
+/// # Example
+///
+/// ```no_run
+/// use tyria::client::APIClient;
+/// use tyria::api_v2::endpoint::get_one;
+/// use tyria::api_v2::types::Skill;
+///
+/// let client = APIClient::new("en", None);
+/// let skill = get_one::<Skill>(&client, 5491);
+/// ```
+#[cfg(feature = "blocking")]
+pub fn get_one<T: BulkEndpoint>(
+    client: &APIClient,
+    id: T::Id
+) -> Result<T, APIError> {
+    let param = string_to_param("id", &id.to_string());
+    let url = format!("{}?{}", T::PATH, param);
+    request::<T, _>(client, &url).map(|response| response.data)
+}
+
+/// Obtain the entries of a bulk endpoint with the specified IDs
+///
+/// IDs that do not exist are ignored by the API. IDs are requested in
+/// batches of `MAX_BULK_IDS`, and no request is made when `ids` is empty.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+#[cfg(feature = "blocking")]
+pub fn get_many<T: BulkEndpoint>(
+    client: &APIClient,
+    ids: &[T::Id]
+) -> Result<Vec<T>, APIError> {
+    let mut entries = Vec::new();
+
+    for batch in IdList::new(ids.to_vec()).batches(MAX_BULK_IDS) {
+        let ids: Vec<String> = batch.iter().map(|id| id.to_string()).collect();
+        let params = strings_to_param(
+            "ids",
+            &ids.iter().map(|id| id.as_str()).collect()
+        );
+        let url = format!("{}?{}", T::PATH, params);
+        entries.extend(request::<T, Vec<T>>(client, &url)?.data);
+    }
+
+    Ok(entries)
+}
+
+/// Obtain all the entries of a bulk endpoint
+///
+/// Some endpoints (e.g. items or skills) are too large to be requested at
+/// once and reject the request; use `get_page()` for those.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+#[cfg(feature = "blocking")]
+pub fn get_all<T: BulkEndpoint>(
+    client: &APIClient
+) -> Result<Vec<T>, APIError> {
+    let params = string_to_param("ids", "all");
+    let url = format!("{}?{}", T::PATH, params);
+    request::<T, _>(client, &url).map(|response| response.data)
+}
+
+/// Obtain a page of entries of a bulk endpoint
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+#[cfg(feature = "blocking")]
+pub fn get_page<T: BulkEndpoint>(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<T>, APIError> {
+    let params = page_to_param(page, page_size);
+    let url = format!("{}?{}", T::PATH, params);
+    let response = request::<T, _>(client, &url)?;
+
+    Ok(Page {
+        items: response.data,
+        page: page,
+        page_size: response.meta.page_size.unwrap_or(page_size),
+        page_total: response.meta.page_total,
+        result_total: response.meta.result_total
+    })
+}
+
+#[cfg(all(test, feature = "account", feature = "static"))]
+mod tests {
+    use std::sync::Arc;
+    use client::APIClient;
+    use transport::{MockTransport, Response};
+    use api_v2::endpoint::*;
+    use api_v2::types::{Account, Profession, Skill};
+
+    #[test]
+    fn paths() {
+        assert_eq!(Skill::PATH, "/v2/skills");
+        assert!(!Skill::AUTHENTICATED);
+        assert!(Account::AUTHENTICATED);
+    }
+
+    #[test]
+    fn bulk_requests() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/skills", Response::json("[1, 2, 3]"));
+        transport.respond("/v2/professions?ids=Guardian", Response::json("[]"));
+
+        let mut client = APIClient::new("en", None);
+        client.set_transport(transport.clone());
+
+        let ids = get_ids::<Skill>(&client).unwrap();
+        assert_eq!(ids.ids(), &[1, 2, 3]);
+
+        let professions = get_many::<Profession>(
+            &client,
+            &["Guardian".to_string()]
+        ).unwrap();
+        assert!(professions.is_empty());
+
+        assert!(get_one::<Skill>(&client, 5491).is_err());
+        assert!(get_page::<Skill>(&client, 1, 50).is_err());
+
+        assert_eq!(transport.requests(), vec![
+            "/v2/skills",
+            "/v2/professions?ids=Guardian",
+            "/v2/skills?id=5491",
+            "/v2/skills?page=1&page_size=50"
+        ]);
+    }
+
+    #[test]
+    fn bulk_batches() {
+        let transport = Arc::new(MockTransport::new());
+        let first: Vec<String> = (1..201).map(|id| id.to_string()).collect();
+        transport.respond(
+            &format!("/v2/skills?ids={}", first.join(",")),
+            Response::json("[]")
+        );
+        transport.respond("/v2/skills?ids=201", Response::json("[]"));
+
+        let mut client = APIClient::new("en", None);
+        client.set_transport(transport.clone());
+
+        assert!(get_many::<Skill>(&client, &[]).unwrap().is_empty());
+        assert!(transport.requests().is_empty());
+
+        let ids: Vec<i32> = (1..202).collect();
+        assert!(get_many::<Skill>(&client, &ids).unwrap().is_empty());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1], "/v2/skills?ids=201");
+    }
+
+    #[test]
+    fn authenticated() {
+        let client = APIClient::new("en", None);
+        assert_eq!(
+            get_single::<Account>(&client).unwrap_err().description(),
+            "token is not configured"
+        );
+    }
+}
@@ -0,0 +1,745 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Guild endpoints
+/// Some of these require an API key of the guild leader
+
+use client::APIClient;
+use common::{
+    APIError,
+    number_to_param,
+    numbers_to_param,
+    parse_response,
+    page_to_param,
+    parse_page_response,
+    Page
+};
+use api_v2::endpoint::{get_all, get_ids, get_many, get_one, get_page};
+use api_v2::types::{
+    EmblemLayers,
+    Guild,
+    GuildLog,
+    GuildStashSection,
+    GuildStorageItem,
+    GuildTeam,
+    GuildTreasuryItem,
+    GuildUpgrade,
+    IdList
+};
+
+use reqwest::StatusCode;
+
+/// Obtain the requested endpoint
+macro_rules! get_endpoint {
+    ("guild", $id: expr) => {format!("/v2/guild/{}", $id)};
+    ("log", $id: expr) => {format!("/v2/guild/{}/log", $id)};
+    ("log_since", $id: expr, $since: expr) => {
+        format!("/v2/guild/{}/log?{}", $id, $since)
+    };
+    ("stash", $id: expr) => {format!("/v2/guild/{}/stash", $id)};
+    ("storage", $id: expr) => {format!("/v2/guild/{}/storage", $id)};
+    ("teams", $id: expr) => {format!("/v2/guild/{}/teams", $id)};
+    ("treasury", $id: expr) => {format!("/v2/guild/{}/treasury", $id)};
+    ("upgrades", $id: expr) => {format!("/v2/guild/{}/upgrades", $id)};
+    ("all_emblems", $kind: expr) => {format!("/v2/emblem/{}", $kind)};
+    ("emblems_id", $kind: expr, $id: expr) => {
+        format!("/v2/emblem/{}?{}", $kind, $id)
+    };
+}
+
+/// Obtain details for the specified guild
+///
+/// If the client has a token configured, the request is authenticated, so
+/// that the guild leader obtains the additional details (level, favor,
+/// member counts, etc.). Otherwise only the public details are returned.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID of the guild
+pub fn get_guild(client: &APIClient, id: &str) -> Result<Guild, APIError> {
+    let url = get_endpoint!("guild", id);
+    let mut response = if client.has_token() {
+        client.make_authenticated_request(&url)
+    } else {
+        client.make_request(&url)
+    }?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::BAD_REQUEST]
+    )
+}
+
+/// Obtain the most recent events in the log of a guild
+///
+/// Entries are sorted from newest to oldest. Use `since` with the ID of the
+/// last entry already known to only obtain newer entries.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token of the guild leader
+/// * `id` - ID of the guild
+/// * `since` - Only obtain entries with an ID greater than this one
+pub fn get_guild_log(
+    client: &APIClient,
+    id: &str,
+    since: Option<i32>
+) -> Result<Vec<GuildLog>, APIError> {
+    let url = match since {
+        Some(since) => {
+            get_endpoint!("log_since", id, number_to_param("since", since))
+        },
+        None => get_endpoint!("log", id)
+    };
+
+    let mut response = client.make_authenticated_request(&url)?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain the contents of each section of a guild's stash
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token of the guild leader
+/// * `id` - ID of the guild
+pub fn get_guild_stash(
+    client: &APIClient,
+    id: &str
+) -> Result<Vec<GuildStashSection>, APIError> {
+    let url = get_endpoint!("stash", id);
+    let mut response = client.make_authenticated_request(&url)?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain the items in a guild's treasury and the upgrades requiring them
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token of the guild leader
+/// * `id` - ID of the guild
+pub fn get_guild_treasury(
+    client: &APIClient,
+    id: &str
+) -> Result<Vec<GuildTreasuryItem>, APIError> {
+    let url = get_endpoint!("treasury", id);
+    let mut response = client.make_authenticated_request(&url)?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain the items in a guild's storage
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token of the guild leader
+/// * `id` - ID of the guild
+pub fn get_guild_storage(
+    client: &APIClient,
+    id: &str
+) -> Result<Vec<GuildStorageItem>, APIError> {
+    let url = get_endpoint!("storage", id);
+    let mut response = client.make_authenticated_request(&url)?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain the PvP teams of a guild, with their members and recent matches
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token of the guild leader
+/// * `id` - ID of the guild
+pub fn get_guild_teams(
+    client: &APIClient,
+    id: &str
+) -> Result<Vec<GuildTeam>, APIError> {
+    let url = get_endpoint!("teams", id);
+    let mut response = client.make_authenticated_request(&url)?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain the IDs of the upgrades unlocked by a guild
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token of the guild leader
+/// * `id` - ID of the guild
+pub fn get_guild_unlocked_upgrades(
+    client: &APIClient,
+    id: &str
+) -> Result<Vec<i32>, APIError> {
+    let url = get_endpoint!("upgrades", id);
+    let mut response = client.make_authenticated_request(&url)?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain a list of all the guild upgrade IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_guild_upgrade_ids(
+    client: &APIClient
+) -> Result<IdList<i32>, APIError> {
+    get_ids::<GuildUpgrade>(client)
+}
+
+/// Obtain the definition of the specified guild upgrade
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_guild_upgrade(
+    client: &APIClient,
+    id: i32
+) -> Result<GuildUpgrade, APIError> {
+    get_one::<GuildUpgrade>(client, id)
+}
+
+/// Obtain the definitions of the specified guild upgrades
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_guild_upgrades(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<GuildUpgrade>, APIError> {
+    get_many::<GuildUpgrade>(client, &ids)
+}
+
+/// Obtain the definitions of all guild upgrades
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_guild_upgrades(
+    client: &APIClient
+) -> Result<Vec<GuildUpgrade>, APIError> {
+    get_all::<GuildUpgrade>(client)
+}
+
+/// Obtain the definitions of a page of guild upgrades
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_guild_upgrades_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<GuildUpgrade>, APIError> {
+    get_page::<GuildUpgrade>(client, page, page_size)
+}
+
+/// Obtain a list of all the emblem foreground IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_emblem_foreground_ids(
+    client: &APIClient
+) -> Result<IdList<i32>, APIError> {
+    let mut response = client
+        .make_request(&get_endpoint!("all_emblems", "foregrounds"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain the image layers of the specified emblem foreground
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_emblem_foreground(
+    client: &APIClient,
+    id: i32
+) -> Result<EmblemLayers, APIError> {
+    let param = number_to_param("id", id);
+    let mut response = client
+        .make_request(&get_endpoint!("emblems_id", "foregrounds", param))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain the image layers of the specified emblem foregrounds
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_emblem_foregrounds(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<EmblemLayers>, APIError> {
+    let params = numbers_to_param("ids", &ids);
+    let mut response = client
+        .make_request(&get_endpoint!("emblems_id", "foregrounds", params))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK, StatusCode::PARTIAL_CONTENT],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain the image layers of a page of emblem foregrounds
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_emblem_foregrounds_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<EmblemLayers>, APIError> {
+    let params = page_to_param(page, page_size);
+    let mut response = client
+        .make_request(&get_endpoint!("emblems_id", "foregrounds", params))?;
+
+    parse_page_response(
+        &mut response,
+        page,
+        page_size,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::BAD_REQUEST]
+    )
+}
+
+/// Obtain a list of all the emblem background IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_emblem_background_ids(
+    client: &APIClient
+) -> Result<IdList<i32>, APIError> {
+    let mut response = client
+        .make_request(&get_endpoint!("all_emblems", "backgrounds"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain the image layers of the specified emblem background
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_emblem_background(
+    client: &APIClient,
+    id: i32
+) -> Result<EmblemLayers, APIError> {
+    let param = number_to_param("id", id);
+    let mut response = client
+        .make_request(&get_endpoint!("emblems_id", "backgrounds", param))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain the image layers of the specified emblem backgrounds
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_emblem_backgrounds(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<EmblemLayers>, APIError> {
+    let params = numbers_to_param("ids", &ids);
+    let mut response = client
+        .make_request(&get_endpoint!("emblems_id", "backgrounds", params))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK, StatusCode::PARTIAL_CONTENT],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain the image layers of a page of emblem backgrounds
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_emblem_backgrounds_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<EmblemLayers>, APIError> {
+    let params = page_to_param(page, page_size);
+    let mut response = client
+        .make_request(&get_endpoint!("emblems_id", "backgrounds", params))?;
+
+    parse_page_response(
+        &mut response,
+        page,
+        page_size,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::BAD_REQUEST]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use serde_json;
+    use client::APIClient;
+    use fixtures::test_client;
+    use api_v2::types::{
+        Guild,
+        GuildLog,
+        GuildLogEntry,
+        GuildStashSection,
+        GuildTeam,
+        GuildUpgrade,
+        GuildUpgradeKind
+    };
+    use api_v2::guild::*;
+
+    macro_rules! parse_test {
+        ($result:expr) => {
+            match $result {
+                Ok(_) => assert!(true),
+                Err(e) => panic!(e.description().to_string()),
+            };
+        }
+    }
+
+    fn setup_client() -> APIClient {
+        test_client()
+    }
+
+    fn set_guild() -> String {
+        match env::var("GUILD_ID") {
+            Ok(id) => id,
+            Err(_) => "116E0C0E-0035-44A9-BB22-4AE3E23127E5".to_string(),
+        }
+    }
+
+    #[test]
+    fn response_shapes() {
+        let public: Guild = serde_json::from_str(r#"{
+            "id": "116E0C0E-0035-44A9-BB22-4AE3E23127E5",
+            "name": "Guild Name",
+            "tag": "TAG",
+            "emblem": {
+                "background": {"id": 2, "colors": [473]},
+                "foreground": {"id": 40, "colors": [673, 71]},
+                "flags": ["FlipBackgroundHorizontal"]
+            }
+        }"#).unwrap();
+        assert!(public.member_count.is_none());
+
+        let leader: Guild = serde_json::from_str(r#"{
+            "level": 42,
+            "motd": "Welcome",
+            "influence": 1000,
+            "aetherium": 500,
+            "favor": 20,
+            "resonance": 0,
+            "member_count": 100,
+            "member_capacity": 500,
+            "id": "116E0C0E-0035-44A9-BB22-4AE3E23127E5",
+            "name": "Guild Name",
+            "tag": "TAG"
+        }"#).unwrap();
+        assert_eq!(leader.member_count, Some(100));
+        assert_eq!(leader.favor, Some(20));
+    }
+
+    #[test]
+    fn guild() {
+        let client = test_client();
+        let result = get_guild(&client, &set_guild());
+        parse_test!(result);
+    }
+
+    #[test]
+    fn log_entries() {
+        let log: Vec<GuildLog> = serde_json::from_str(r#"[
+            {"id": 5, "time": "2017-07-01T10:00:00.000Z", "user": "A.1234",
+             "type": "stash", "operation": "deposit", "item_id": 19721,
+             "count": 10, "coins": 0},
+            {"id": 4, "time": "2017-07-01T09:00:00.000Z",
+             "user": "B.1234", "type": "rank_change",
+             "changed_by": "A.1234", "old_rank": "Member",
+             "new_rank": "Officer"},
+            {"id": 3, "time": "2017-07-01T08:00:00.000Z",
+             "type": "upgrade", "action": "queued", "upgrade_id": 38},
+            {"id": 2, "time": "2017-07-01T07:00:00.000Z",
+             "type": "influence", "activity": "daily_login"},
+            {"id": 1, "time": "2017-07-01T06:00:00.000Z", "user": "B.1234",
+             "type": "joined"}
+        ]"#).unwrap();
+
+        assert_eq!(log.len(), 5);
+
+        match log[0].entry {
+            GuildLogEntry::Stash { item_id, count, .. } => {
+                assert_eq!((item_id, count), (19721, 10));
+            },
+            ref other => panic!("unexpected entry: {:?}", other),
+        }
+
+        match log[1].entry {
+            GuildLogEntry::RankChange { ref new_rank, .. } => {
+                assert_eq!(new_rank, "Officer");
+            },
+            ref other => panic!("unexpected entry: {:?}", other),
+        }
+
+        assert!(log[2].user.is_none());
+        match log[3].entry {
+            GuildLogEntry::Unknown => {},
+            ref other => panic!("unexpected entry: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn guild_log() {
+        let client = setup_client();
+        let result = get_guild_log(&client, &set_guild(), None);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn stash_sections() {
+        let stash: Vec<GuildStashSection> = serde_json::from_str(r#"[
+            {"upgrade_id": 58, "size": 50, "coins": 1000,
+             "note": "Deposits only", "inventory": [
+                null, {"id": 19721, "count": 250}
+            ]}
+        ]"#).unwrap();
+
+        assert_eq!(stash[0].inventory.len(), 2);
+        assert!(stash[0].inventory[0].is_none());
+    }
+
+    #[test]
+    fn guild_stash() {
+        let client = setup_client();
+        let result = get_guild_stash(&client, &set_guild());
+        parse_test!(result);
+    }
+
+    #[test]
+    fn guild_treasury() {
+        let client = setup_client();
+        let result = get_guild_treasury(&client, &set_guild());
+        parse_test!(result);
+    }
+
+    #[test]
+    fn teams() {
+        let teams: Vec<GuildTeam> = serde_json::from_str(r#"[{
+            "id": 1,
+            "members": [{"name": "A.1234", "role": "Captain"}],
+            "name": "Team",
+            "aggregate": {"wins": 3, "losses": 1, "desertions": 0,
+                          "byes": 0, "forfeits": 0},
+            "ladders": {"ranked": {"wins": 3, "losses": 1}},
+            "games": [{
+                "id": "ABCD", "map_id": 894,
+                "started": "2017-07-01T10:00:00.000Z",
+                "ended": "2017-07-01T10:12:00.000Z",
+                "result": "Victory", "team": "Red",
+                "scores": {"red": 500, "blue": 320},
+                "rating_type": "Ranked", "rating_change": 12,
+                "season": "44B85826-B5ED-4890-8C77-82DDF9F2CF2B"
+            }],
+            "seasons": [{"id": "44B85826-B5ED-4890-8C77-82DDF9F2CF2B",
+                         "wins": 3, "losses": 1, "rating": 1200}]
+        }]"#).unwrap();
+
+        assert_eq!(teams.len(), 1);
+    }
+
+    #[test]
+    fn guild_storage() {
+        let client = setup_client();
+        let result = get_guild_storage(&client, &set_guild());
+        parse_test!(result);
+    }
+
+    #[test]
+    fn guild_teams() {
+        let client = setup_client();
+        let result = get_guild_teams(&client, &set_guild());
+        parse_test!(result);
+    }
+
+    #[test]
+    fn guild_unlocked_upgrades() {
+        let client = setup_client();
+        let result = get_guild_unlocked_upgrades(&client, &set_guild());
+        parse_test!(result);
+    }
+
+    #[test]
+    fn upgrade_kinds() {
+        let upgrades: Vec<GuildUpgrade> = serde_json::from_str(r#"[
+            {"id": 55, "name": "Guild Vault", "description": "",
+             "type": "BankBag", "bag_max_items": 50, "bag_max_coins": 1000,
+             "icon": "", "build_time": 0, "required_level": 3,
+             "experience": 5, "prerequisites": [],
+             "costs": [{"type": "Currency", "name": "Aetherium",
+                        "count": 100}]},
+            {"id": 1, "name": "Statue", "type": "Decoration",
+             "costs": [{"type": "Item", "name": "Marble", "count": 5,
+                        "item_id": 12345}]},
+            {"id": 2, "name": "New", "type": "SomethingElse"}
+        ]"#).unwrap();
+
+        match upgrades[0].kind {
+            GuildUpgradeKind::BankBag { bag_max_items, .. } => {
+                assert_eq!(bag_max_items, 50);
+            },
+            ref other => panic!("unexpected kind: {:?}", other),
+        }
+        assert!(match upgrades[1].kind {
+            GuildUpgradeKind::Decoration => true,
+            _ => false
+        });
+        assert!(match upgrades[2].kind {
+            GuildUpgradeKind::Unknown => true,
+            _ => false
+        });
+    }
+
+    #[test]
+    fn upgrade_ids() {
+        let client = test_client();
+        let result = get_guild_upgrade_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn upgrade() {
+        let client = test_client();
+        let result = get_guild_upgrade(&client, 55);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn upgrades() {
+        let client = test_client();
+        let result = get_guild_upgrades(&client, vec![55, 56]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn emblem_foreground_ids() {
+        let client = test_client();
+        let result = get_emblem_foreground_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn emblem_foregrounds() {
+        let client = test_client();
+        let result = get_emblem_foregrounds(&client, vec![1, 2]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn emblem_background_ids() {
+        let client = test_client();
+        let result = get_emblem_background_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn emblem_backgrounds() {
+        let client = test_client();
+        let result = get_emblem_backgrounds(&client, vec![1, 2]);
+        parse_test!(result);
+    }
+}
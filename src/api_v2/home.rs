@@ -0,0 +1,146 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Home instance endpoints
+/// These list everything that can be unlocked in the home instance, to be
+/// compared against the account unlocks
+
+use client::APIClient;
+use common::{APIError, parse_response, Page};
+use api_v2::endpoint::{get_all, get_ids, get_many, get_one, get_page};
+use api_v2::types::{Cat, IdList};
+
+use reqwest::StatusCode;
+
+/// Obtain the requested endpoint
+macro_rules! get_endpoint {
+    ("all_nodes") => {"/v2/home/nodes"};
+}
+
+/// Obtain a list of all available home instance cat IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_cat_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<Cat>(client)
+}
+
+/// Obtain details for the specified home instance cat
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_cat(client: &APIClient, id: i32) -> Result<Cat, APIError> {
+    get_one::<Cat>(client, id)
+}
+
+/// Obtain details for the specified home instance cats
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_cats(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<Cat>, APIError> {
+    get_many::<Cat>(client, &ids)
+}
+
+/// Obtain details for all home instance cats
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_cats(client: &APIClient) -> Result<Vec<Cat>, APIError> {
+    get_all::<Cat>(client)
+}
+
+/// Obtain a page of home instance cats
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_cats_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Cat>, APIError> {
+    get_page::<Cat>(client, page, page_size)
+}
+
+/// Obtain a list of all available home instance gathering node IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_node_ids(client: &APIClient) -> Result<IdList<String>, APIError> {
+    let mut response = client.make_request(get_endpoint!("all_nodes"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use fixtures::test_client;
+    use api_v2::home::*;
+
+    macro_rules! parse_test {
+        ($result:expr) => {
+            match $result {
+                Ok(_) => assert!(true),
+                Err(e) => panic!(e.description().to_string()),
+            };
+        }
+    }
+
+    #[test]
+    fn cat_ids() {
+        let client = test_client();
+        let result = get_cat_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn cats() {
+        let client = test_client();
+        let result = get_cats(&client, vec![1, 2]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn node_ids() {
+        let client = test_client();
+        let result = get_node_ids(&client);
+        parse_test!(result);
+    }
+}
@@ -0,0 +1,313 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Item, itemstat and dye color endpoints
+
+use client::APIClient;
+use common::{APIError, Page};
+use api_v2::endpoint::{get_all, get_ids, get_many, get_one, get_page};
+use api_v2::types::{Color, IdList, Item, ItemStat};
+
+/// Obtain a list of all the item IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_item_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<Item>(client)
+}
+
+/// Obtain details for the specified item
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_item(client: &APIClient, id: i32) -> Result<Item, APIError> {
+    get_one::<Item>(client, id)
+}
+
+/// Obtain details for the specified items
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_items(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<Item>, APIError> {
+    get_many::<Item>(client, &ids)
+}
+
+/// Obtain a page of items
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_items_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Item>, APIError> {
+    get_page::<Item>(client, page, page_size)
+}
+
+/// Obtain a list of all the itemstat IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_itemstat_ids(
+    client: &APIClient
+) -> Result<IdList<i32>, APIError> {
+    get_ids::<ItemStat>(client)
+}
+
+/// Obtain details for the specified itemstat
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_itemstat(
+    client: &APIClient,
+    id: i32
+) -> Result<ItemStat, APIError> {
+    get_one::<ItemStat>(client, id)
+}
+
+/// Obtain details for the specified itemstats
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_itemstats(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<ItemStat>, APIError> {
+    get_many::<ItemStat>(client, &ids)
+}
+
+/// Obtain details for all itemstats
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_itemstats(
+    client: &APIClient
+) -> Result<Vec<ItemStat>, APIError> {
+    get_all::<ItemStat>(client)
+}
+
+/// Obtain a page of itemstats
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_itemstats_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<ItemStat>, APIError> {
+    get_page::<ItemStat>(client, page, page_size)
+}
+
+/// Obtain a list of all the dye color IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_color_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<Color>(client)
+}
+
+/// Obtain details for the specified dye color
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_color(client: &APIClient, id: i32) -> Result<Color, APIError> {
+    get_one::<Color>(client, id)
+}
+
+/// Obtain details for the specified dye colors
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_colors(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<Color>, APIError> {
+    get_many::<Color>(client, &ids)
+}
+
+/// Obtain details for all dye colors
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_colors(client: &APIClient) -> Result<Vec<Color>, APIError> {
+    get_all::<Color>(client)
+}
+
+/// Obtain a page of dye colors
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_colors_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Color>, APIError> {
+    get_page::<Color>(client, page, page_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use reqwest::header::HeaderMap;
+    use client::APIClient;
+    use transport::{MockTransport, Response, Transport};
+    use fixtures::test_client;
+    use api_v2::items::*;
+
+    /// Transport failing every request, as when the network is down
+    struct Offline;
+
+    impl Transport for Offline {
+        fn get(&self, _: &str, _: HeaderMap) -> Result<Response, APIError> {
+            Err(APIError::new("connection refused"))
+        }
+    }
+
+    macro_rules! parse_test {
+        ($result:expr) => {
+            match $result {
+                Ok(_) => assert!(true),
+                Err(e) => panic!(e.description().to_string()),
+            };
+        }
+    }
+
+    #[test]
+    fn item_ids() {
+        let client = test_client();
+        let result = get_item_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn item_ids_mocked() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/items", Response::json("[24, 68, 69]"));
+
+        let mut client = APIClient::new("en", None);
+        client.set_transport(transport);
+
+        let ids = get_item_ids(&client).unwrap();
+        assert_eq!(ids.ids(), &[24, 68, 69]);
+    }
+
+    #[test]
+    fn item_offline() {
+        let mut client = APIClient::new("en", None);
+        client.set_transport(Arc::new(Offline));
+        client.set_retry_policy(None);
+
+        let result = get_item(&client, 19721);
+        assert_eq!(result.unwrap_err().description(), "connection refused");
+    }
+
+    #[test]
+    fn item() {
+        let client = test_client();
+        let result = get_item(&client, 19721);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn items() {
+        let client = test_client();
+        let result = get_items(&client, vec![19721, 24554]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn all_colors() {
+        let client = test_client();
+        let result = get_all_colors(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn items_page() {
+        let client = test_client();
+        let result = get_items_page(&client, 0, 10);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn itemstats() {
+        let client = test_client();
+        let result = get_itemstats(&client, vec![161, 1067]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn color_ids() {
+        let client = test_client();
+        let result = get_color_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn color() {
+        let client = test_client();
+        let result = get_color(&client, 10);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn colors() {
+        let client = test_client();
+        let result = get_colors(&client, vec![10, 11]);
+        parse_test!(result);
+    }
+}
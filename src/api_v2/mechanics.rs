@@ -25,13 +25,13 @@
 use client::APIClient;
 use common::{
     APIError,
-    number_to_param,
-    numbers_to_param,
-    string_to_param,
     strings_to_param,
-    parse_response
+    parse_response,
+    Page
 };
+use api_v2::endpoint::{get_all, get_ids, get_many, get_one, get_page};
 use api_v2::types::{
+    IdList,
     Legend,
     Mastery,
     Outfit,
@@ -47,24 +47,7 @@ use reqwest::StatusCode;
 
 /// Obtain the requested endpoint
 macro_rules! get_endpoint {
-    ("all_masteries") => {"/v2/masteries"};
-    ("masteries_id", $id: expr) => {format!("/v2/masteries?{}", $id)};
-    ("all_outfits") => {"/v2/outfits"};
-    ("outfits_id", $id: expr) => {format!("/v2/outfits?{}", $id)};
-    ("all_pets") => {"/v2/pets"};
-    ("pets_id", $id: expr) => {format!("/v2/pets?{}", $id)};
-    ("all_professions") => {"/v2/professions"};
     ("professions_id", $id: expr) => {format!("/v2/professions?{}", $id)};
-    ("all_races") => {"/v2/races"};
-    ("races_id", $id: expr) => {format!("/v2/races?{}", $id)};
-    ("all_specs") => {"/v2/specializations"};
-    ("specs_id", $id: expr) => {format!("/v2/specializations?{}", $id)};
-    ("all_skills") => {"/v2/skills"};
-    ("skills_id", $id: expr) => {format!("/v2/skills?{}", $id)};
-    ("all_traits") => {"/v2/traits"};
-    ("traits_id", $id: expr) => {format!("/v2/traits?{}", $id)};
-    ("all_legends") => {"/v2/legends"};
-    ("legends_id", $id: expr) => {format!("/v2/legends?{}", $id)};
 }
 
 /// Obtain a list of all available mastery IDs
@@ -72,16 +55,8 @@ macro_rules! get_endpoint {
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_mastery_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_masteries"))
-        .expect("failed to get mastery IDs");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+pub fn get_mastery_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<Mastery>(client)
 }
 
 /// Obtain details for the specified mastery
@@ -91,16 +66,7 @@ pub fn get_mastery_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
 /// * `client` - The client to use when performing API requests
 /// * `id` - ID to fetch from the server
 pub fn get_mastery(client: &APIClient, id: i32) -> Result<Mastery, APIError> {
-    let param = number_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("masteries_id", param))
-        .expect("failed to get mastery");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<Mastery>(client, id)
 }
 
 /// Obtain details for the specified masteries
@@ -113,33 +79,42 @@ pub fn get_masteries(
     client: &APIClient,
     ids: Vec<i32>
 ) -> Result<Vec<Mastery>, APIError> {
-    let param = numbers_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("masteries_id", param))
-        .expect("failed to get masteries");
+    get_many::<Mastery>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain details for all masteries
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_masteries(client: &APIClient) -> Result<Vec<Mastery>, APIError> {
+    get_all::<Mastery>(client)
 }
 
-/// Obtain a list of all available outfit IDs
+/// Obtain a page of masteries
+///
+/// Use `Page::has_next()` to know whether more pages are available.
 ///
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_outfit_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_outfits"))
-        .expect("failed to get outfit IDs");
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_masteries_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Mastery>, APIError> {
+    get_page::<Mastery>(client, page, page_size)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain a list of all available outfit IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_outfit_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<Outfit>(client)
 }
 
 /// Obtain details for the specified outfit
@@ -149,16 +124,7 @@ pub fn get_outfit_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
 /// * `client` - The client to use when performing API requests
 /// * `id` - ID to fetch from the server
 pub fn get_outfit(client: &APIClient, id: i32) -> Result<Outfit, APIError> {
-    let param = number_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("outfits_id", param))
-        .expect("failed to get outfit");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<Outfit>(client, id)
 }
 
 /// Obtain details for the specified outfits
@@ -171,33 +137,42 @@ pub fn get_outfits(
     client: &APIClient,
     ids: Vec<i32>
 ) -> Result<Vec<Outfit>, APIError> {
-    let param = numbers_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("outfits_id", param))
-        .expect("failed to get outfits");
+    get_many::<Outfit>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain details for all outfits
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_outfits(client: &APIClient) -> Result<Vec<Outfit>, APIError> {
+    get_all::<Outfit>(client)
 }
 
-/// Obtain a list of all available pet IDs
+/// Obtain a page of outfits
+///
+/// Use `Page::has_next()` to know whether more pages are available.
 ///
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_pet_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_pets"))
-        .expect("failed to get pet IDs");
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_outfits_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Outfit>, APIError> {
+    get_page::<Outfit>(client, page, page_size)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain a list of all available pet IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_pet_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<Pet>(client)
 }
 
 /// Obtain details for the specified pet
@@ -207,16 +182,7 @@ pub fn get_pet_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
 /// * `client` - The client to use when performing API requests
 /// * `id` - ID to fetch from the server
 pub fn get_pet(client: &APIClient, id: i32) -> Result<Pet, APIError> {
-    let param = number_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("pets_id", param))
-        .expect("failed to get pet");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<Pet>(client, id)
 }
 
 /// Obtain details for the specified pets
@@ -229,33 +195,44 @@ pub fn get_pets(
     client: &APIClient,
     ids: Vec<i32>
 ) -> Result<Vec<Pet>, APIError> {
-    let param = numbers_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("pets_id", param))
-        .expect("failed to get pets");
+    get_many::<Pet>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain details for all pets
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_pets(client: &APIClient) -> Result<Vec<Pet>, APIError> {
+    get_all::<Pet>(client)
 }
 
-/// Obtain a list of all available profession IDs
+/// Obtain a page of pets
+///
+/// Use `Page::has_next()` to know whether more pages are available.
 ///
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_profession_ids(client: &APIClient) -> Result<Vec<String>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_professions"))
-        .expect("failed to get profession IDs");
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_pets_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Pet>, APIError> {
+    get_page::<Pet>(client, page, page_size)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain a list of all available profession IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_profession_ids(
+    client: &APIClient
+) -> Result<IdList<String>, APIError> {
+    get_ids::<Profession>(client)
 }
 
 /// Obtain details for the specified profession
@@ -268,16 +245,7 @@ pub fn get_profession(
     client: &APIClient,
     id: &str
 ) -> Result<Profession, APIError> {
-    let param = string_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("professions_id", param))
-        .expect("failed to get profession");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<Profession>(client, id.to_string())
 }
 
 /// Obtain details for the specified professions
@@ -292,31 +260,50 @@ pub fn get_professions(
 ) -> Result<Vec<Profession>, APIError> {
     let param = strings_to_param("ids", &ids);
     let mut response = client
-        .make_request(&get_endpoint!("professions_id", param))
-        .expect("failed to get professions");
+        .make_request(&get_endpoint!("professions_id", param))?;
 
     parse_response::<Vec<Profession>>(
         &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
+        vec![StatusCode::OK, StatusCode::PARTIAL_CONTENT],
+        vec![StatusCode::NOT_FOUND]
     )
 }
 
-/// Obtain a list of all available race IDs
+/// Obtain details for all professions
 ///
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_race_ids(client: &APIClient) -> Result<Vec<String>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_races"))
-        .expect("failed to get race IDs");
+pub fn get_all_professions(
+    client: &APIClient
+) -> Result<Vec<Profession>, APIError> {
+    get_all::<Profession>(client)
+}
 
-    parse_response::<Vec<String>>(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain a page of professions
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_professions_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Profession>, APIError> {
+    get_page::<Profession>(client, page, page_size)
+}
+
+/// Obtain a list of all available race IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_race_ids(client: &APIClient) -> Result<IdList<String>, APIError> {
+    get_ids::<Race>(client)
 }
 
 /// Obtain details for the specified race
@@ -326,16 +313,7 @@ pub fn get_race_ids(client: &APIClient) -> Result<Vec<String>, APIError> {
 /// * `client` - The client to use when performing API requests
 /// * `id` - ID to fetch from the server
 pub fn get_race(client: &APIClient, id: &str) -> Result<Race, APIError> {
-    let param = string_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("races_id", param))
-        .expect("failed to get race");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<Race>(client, id.to_string())
 }
 
 /// Obtain details for the specified races
@@ -348,16 +326,34 @@ pub fn get_races(
     client: &APIClient,
     ids: Vec<&str>
 ) -> Result<Vec<Race>, APIError> {
-    let param = strings_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("races_id", param))
-        .expect("failed to get races");
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    get_many::<Race>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain details for all races
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_races(client: &APIClient) -> Result<Vec<Race>, APIError> {
+    get_all::<Race>(client)
+}
+
+/// Obtain a page of races
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_races_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Race>, APIError> {
+    get_page::<Race>(client, page, page_size)
 }
 
 /// Obtain a list of all available specialization IDs
@@ -367,16 +363,8 @@ pub fn get_races(
 /// * `client` - The client to use when performing API requests
 pub fn get_specialization_ids(
     client: &APIClient
-) -> Result<Vec<i32>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_specs"))
-        .expect("failed to get specialization IDs");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+) -> Result<IdList<i32>, APIError> {
+    get_ids::<Specialization>(client)
 }
 
 /// Obtain details for the specified specialization
@@ -389,16 +377,7 @@ pub fn get_specialization(
     client: &APIClient,
     id: i32
 ) -> Result<Specialization, APIError> {
-    let param = number_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("specs_id", param))
-        .expect("failed to get specialization");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<Specialization>(client, id)
 }
 
 /// Obtain details for the specified specializations
@@ -411,33 +390,44 @@ pub fn get_specializations(
     client: &APIClient,
     ids: Vec<i32>
 ) -> Result<Vec<Specialization>, APIError> {
-    let param = numbers_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("specs_id", param))
-        .expect("failed to get specializations");
+    get_many::<Specialization>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain details for all specializations
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_specializations(
+    client: &APIClient
+) -> Result<Vec<Specialization>, APIError> {
+    get_all::<Specialization>(client)
 }
 
-/// Obtain a list of all available skill IDs
+/// Obtain a page of specializations
+///
+/// Use `Page::has_next()` to know whether more pages are available.
 ///
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_skill_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_skills"))
-        .expect("failed to get skill IDs");
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_specializations_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Specialization>, APIError> {
+    get_page::<Specialization>(client, page, page_size)
+}
 
-    parse_response::<Vec<i32>>(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain a list of all available skill IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_skill_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<Skill>(client)
 }
 
 /// Obtain details for the specified skill
@@ -447,16 +437,7 @@ pub fn get_skill_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
 /// * `client` - The client to use when performing API requests
 /// * `id` - ID to fetch from the server
 pub fn get_skill(client: &APIClient, id: i32) -> Result<Skill, APIError> {
-    let param = number_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("skills_id", param))
-        .expect("failed to get skill");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<Skill>(client, id)
 }
 
 /// Obtain details for the specified skills
@@ -469,16 +450,24 @@ pub fn get_skills(
     client: &APIClient,
     ids: Vec<i32>
 ) -> Result<Vec<Skill>, APIError> {
-    let param = numbers_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("skills_id", param))
-        .expect("failed to get skill");
+    get_many::<Skill>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain a page of skills
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_skills_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Skill>, APIError> {
+    get_page::<Skill>(client, page, page_size)
 }
 
 /// Obtain a list of all available  IDs
@@ -486,16 +475,8 @@ pub fn get_skills(
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_trait_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_traits"))
-        .expect("failed to get trait IDs");
-
-    parse_response::<Vec<i32>>(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+pub fn get_trait_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<Trait>(client)
 }
 
 /// Obtain details for the specified trait
@@ -505,16 +486,7 @@ pub fn get_trait_ids(client: &APIClient) -> Result<Vec<i32>, APIError> {
 /// * `client` - The client to use when performing API requests
 /// * `id` - ID to fetch from the server
 pub fn get_trait(client: &APIClient, id: i32) -> Result<Trait, APIError> {
-    let param = number_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("traits_id", param))
-        .expect("failed to get trait");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<Trait>(client, id)
 }
 
 /// Obtain details for the specified traits
@@ -527,16 +499,24 @@ pub fn get_traits(
     client: &APIClient,
     ids: Vec<i32>
 ) -> Result<Vec<Trait>, APIError> {
-    let param = numbers_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("traits_id", param))
-        .expect("failed to gettraits");
+    get_many::<Trait>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain a page of traits
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_traits_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Trait>, APIError> {
+    get_page::<Trait>(client, page, page_size)
 }
 
 /// Obtain a list of all available Revenant legend IDs
@@ -544,16 +524,8 @@ pub fn get_traits(
 /// # Arguments
 ///
 /// * `client` - The client to use when performing API requests
-pub fn get_legend_ids(client: &APIClient) -> Result<Vec<String>, APIError> {
-    let mut response = client
-        .make_request(get_endpoint!("all_legends"))
-        .expect("failed to get legend IDs");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+pub fn get_legend_ids(client: &APIClient) -> Result<IdList<String>, APIError> {
+    get_ids::<Legend>(client)
 }
 
 /// Obtain details for the specified Revenant legend
@@ -563,16 +535,7 @@ pub fn get_legend_ids(client: &APIClient) -> Result<Vec<String>, APIError> {
 /// * `client` - The client to use when performing API requests
 /// * `id` - ID to fetch from the server
 pub fn get_legend(client: &APIClient, id: &str) -> Result<Legend, APIError> {
-    let param = string_to_param("id", id);
-    let mut response = client
-        .make_request(&get_endpoint!("legends_id", param))
-        .expect("failed to get legend");
-
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok],
-        vec![StatusCode::NotFound]
-    )
+    get_one::<Legend>(client, id.to_string())
 }
 
 /// Obtain details for the specified Revenant legend
@@ -585,22 +548,40 @@ pub fn get_legends(
     client: &APIClient,
     ids: Vec<&str>
 ) -> Result<Vec<Legend>, APIError> {
-    let param = strings_to_param("ids", &ids);
-    let mut response = client
-        .make_request(&get_endpoint!("legends_id", param))
-        .expect("failed to get legends");
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    get_many::<Legend>(client, &ids)
+}
 
-    parse_response(
-        &mut response,
-        vec![StatusCode::Ok, StatusCode::PartialContent],
-        vec![StatusCode::NotFound]
-    )
+/// Obtain details for all Revenant legends
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_legends(client: &APIClient) -> Result<Vec<Legend>, APIError> {
+    get_all::<Legend>(client)
+}
+
+/// Obtain a page of Revenant legends
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_legends_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Legend>, APIError> {
+    get_page::<Legend>(client, page, page_size)
 }
 
 
 #[cfg(test)]
 mod tests {
-    use client::APIClient;
+    use fixtures::test_client;
     use api_v2::mechanics::*;
 
     macro_rules! parse_test {
@@ -614,189 +595,189 @@ mod tests {
 
     #[test]
     fn mastery_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_mastery_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn mastery() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_mastery(&client, 1);
         parse_test!(result);
     }
 
     #[test]
     fn masteries() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_masteries(&client, vec![1, 2]);
         parse_test!(result);
     }
 
     #[test]
     fn outfit_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_outfit_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn outfit() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_outfit(&client, 30);
         parse_test!(result);
     }
 
     #[test]
     fn outfits() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_outfits(&client, vec![30, 45]);
         parse_test!(result);
     }
 
     #[test]
     fn pet_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_pet_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn pet() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_pet(&client, 33);
         parse_test!(result);
     }
 
     #[test]
     fn pets() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_pets(&client, vec![33, 42]);
         parse_test!(result);
     }
 
     #[test]
     fn profession_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_profession_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn profession() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_profession(&client, "Thief");
         parse_test!(result);
     }
 
     #[test]
     fn professions() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_professions(&client, vec!["Thief", "Necromancer"]);
         parse_test!(result);
     }
 
     #[test]
     fn race_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_race_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn race() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_race(&client, "Human");
         parse_test!(result);
     }
 
     #[test]
     fn races() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_races(&client, vec!["Human", "Sylvari", "Norn"]);
         parse_test!(result);
     }
 
     #[test]
     fn specialization_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_specialization_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn specialization() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_specialization(&client, 1);
         parse_test!(result);
     }
 
     #[test]
     fn specializations() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_specializations(&client, vec![2, 3]);
         parse_test!(result);
     }
 
     #[test]
     fn skill_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_skill_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn skill() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_skill(&client, 14375);
         parse_test!(result);
     }
 
     #[test]
     fn skills() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_skills(&client, vec![5516, 5517]);
         parse_test!(result);
     }
 
     #[test]
     fn trait_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_trait_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn single_trait() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_trait(&client, 214);
         parse_test!(result);
     }
 
     #[test]
     fn traits() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_traits(&client, vec![277, 334]);
         parse_test!(result);
     }
 
     #[test]
     fn legend_ids() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_legend_ids(&client);
         parse_test!(result);
     }
 
     #[test]
     fn legend() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_legend(&client, "Legend1");
         parse_test!(result);
     }
 
     #[test]
     fn legends() {
-        let client = APIClient::new("en", None);
+        let client = test_client();
         let result = get_legends(&client, vec!["Legend2", "Legend5"]);
         parse_test!(result);
     }
@@ -21,9 +21,35 @@
 // SOFTWARE.
 
 pub mod types;
+// Only the helpers of the enabled endpoint groups are used
+#[cfg_attr(not(feature = "endpoints"), allow(dead_code, unused_imports))]
+pub(crate) mod compat;
+pub mod registry;
+pub mod endpoint;
 
+#[cfg(feature = "static")]
 pub mod achievements;
+#[cfg(feature = "account")]
 pub mod account;
+#[cfg(feature = "characters")]
 pub mod characters;
+#[cfg(feature = "commerce")]
 pub mod commerce;
+#[cfg(feature = "static")]
+pub mod continents;
+#[cfg(feature = "static")]
+pub mod daily;
+#[cfg(feature = "guild")]
+pub mod guild;
+#[cfg(feature = "static")]
+pub mod home;
+#[cfg(feature = "static")]
+pub mod items;
+#[cfg(feature = "static")]
 pub mod mechanics;
+#[cfg(feature = "pvp")]
+pub mod pvp;
+#[cfg(feature = "static")]
+pub mod story;
+#[cfg(feature = "wvw")]
+pub mod wvw;
@@ -0,0 +1,215 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// PvP endpoints
+
+use client::APIClient;
+use common::{
+    APIError,
+    Page,
+    page_to_param,
+    parse_page_response,
+    parse_response
+};
+use api_v2::types::{IdList, PvPLeaderboardEntry, PvPStats};
+
+use reqwest::StatusCode;
+
+/// Obtain the requested endpoint
+macro_rules! get_endpoint {
+    ("stats") => {"/v2/pvp/stats"};
+    ("all_seasons") => {"/v2/pvp/seasons"};
+    ("leaderboards", $season: expr) => {
+        format!("/v2/pvp/seasons/{}/leaderboards", $season)
+    };
+    ("leaderboard_regions", $season: expr, $board: expr) => {
+        format!("/v2/pvp/seasons/{}/leaderboards/{}", $season, $board)
+    };
+    ("leaderboard",
+     $season: expr, $board: expr, $region: expr, $page: expr) => {
+        format!(
+            "/v2/pvp/seasons/{}/leaderboards/{}/{}?{}",
+            $season, $board, $region, $page
+        )
+    };
+}
+
+
+/// Obtain PvP statistics for the account
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_pvp_stats(
+    client: &APIClient
+) -> Result<PvPStats, APIError> {
+    let mut response = client
+        .make_authenticated_request(&get_endpoint!("stats"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::FORBIDDEN]
+    )
+}
+
+/// Obtain a list of all PvP season IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_pvp_season_ids(
+    client: &APIClient
+) -> Result<IdList<String>, APIError> {
+    let mut response = client.make_request(get_endpoint!("all_seasons"))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain the leaderboards available for a PvP season
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `season` - ID of the season
+pub fn get_pvp_leaderboards(
+    client: &APIClient,
+    season: &str
+) -> Result<Vec<String>, APIError> {
+    let mut response = client
+        .make_request(&get_endpoint!("leaderboards", season))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain the regions available for a PvP season leaderboard
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `season` - ID of the season
+/// * `board` - Leaderboard name (e.g. `ladder`, `legendary`, `guild`)
+pub fn get_pvp_leaderboard_regions(
+    client: &APIClient,
+    season: &str,
+    board: &str
+) -> Result<Vec<String>, APIError> {
+    let mut response = client
+        .make_request(&get_endpoint!("leaderboard_regions", season, board))?;
+
+    parse_response(
+        &mut response,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND]
+    )
+}
+
+/// Obtain a page of a PvP season leaderboard
+///
+/// Leaderboards are paginated rather than ID-based; use `Page::has_next()`
+/// to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `season` - ID of the season
+/// * `board` - Leaderboard name (e.g. `ladder`, `legendary`, `guild`)
+/// * `region` - Region of the leaderboard (`na` or `eu`)
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_pvp_leaderboard(
+    client: &APIClient,
+    season: &str,
+    board: &str,
+    region: &str,
+    page: i32,
+    page_size: i32
+) -> Result<Page<PvPLeaderboardEntry>, APIError> {
+    let params = page_to_param(page, page_size);
+    let mut response = client
+        .make_request(
+            &get_endpoint!("leaderboard", season, board, region, params)
+        )?;
+
+    parse_page_response(
+        &mut response,
+        page,
+        page_size,
+        vec![StatusCode::OK],
+        vec![StatusCode::NOT_FOUND, StatusCode::BAD_REQUEST]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use client::APIClient;
+    use fixtures::test_client;
+    use api_v2::pvp::*;
+
+    macro_rules! parse_test {
+        ($result:expr) => {
+            match $result {
+                Ok(_) => assert!(true),
+                Err(e) => panic!(e.description().to_string()),
+            };
+        }
+    }
+
+    fn setup_client() -> APIClient {
+        test_client()
+    }
+
+    #[test]
+    fn pvp_stats() {
+        let client = setup_client();
+        let result = get_pvp_stats(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn pvp_season_ids() {
+        let client = test_client();
+        let result = get_pvp_season_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn pvp_leaderboard() {
+        let client = test_client();
+        let seasons = get_pvp_season_ids(&client).unwrap().into_ids();
+        let season = seasons.last().expect("no seasons available");
+
+        let result = get_pvp_leaderboard(
+            &client, season, "ladder", "eu", 0, 10
+        );
+        parse_test!(result);
+    }
+}
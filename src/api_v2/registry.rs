@@ -0,0 +1,289 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Registry of the endpoints supported by the crate
+/// Useful to list capabilities at runtime, e.g. when generating CLI help
+
+
+/// Details of a supported endpoint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EndpointInfo {
+    /// Module of `api_v2` containing the endpoint functions
+    pub module: &'static str,
+    /// Path of the endpoint. Path parameters are written as `:name`
+    pub path: &'static str,
+    /// Whether the endpoint requires an authentication token
+    pub authenticated: bool,
+    /// Whether several entries can be fetched at once by ID
+    pub bulk: bool,
+    /// Whether the response depends on the language of the client
+    pub localized: bool
+}
+
+impl EndpointInfo {
+    /// Check whether a concrete path (without query string) matches the
+    /// path of the endpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to check, e.g. `/v2/guild/ABC/log`
+    pub fn matches(&self, path: &str) -> bool {
+        let expected: Vec<&str> = self.path.split('/').collect();
+        let actual: Vec<&str> = path.split('/').collect();
+
+        expected.len() == actual.len()
+            && expected.iter().zip(actual.iter()).all(|(e, a)| {
+                e == a || (e.starts_with(':') && !a.is_empty())
+            })
+    }
+}
+
+/// Build the details of an endpoint
+///
+/// Flags are, in order, whether the endpoint requires authentication,
+/// supports bulk requests and is localized.
+macro_rules! endpoint {
+    ($module:expr, $path:expr, $auth:expr, $bulk:expr, $lang:expr) => {
+        EndpointInfo {
+            module: $module,
+            path: $path,
+            authenticated: $auth,
+            bulk: $bulk,
+            localized: $lang
+        }
+    };
+}
+
+/// Every endpoint supported by the crate, sorted by path
+pub static ENDPOINTS: &'static [EndpointInfo] = &[
+    endpoint!("account", "/v2/account", true, false, false),
+    endpoint!("account", "/v2/account/achievements", true, false, false),
+    endpoint!("account", "/v2/account/bank", true, false, false),
+    endpoint!("account", "/v2/account/dailycrafting", true, false, false),
+    endpoint!("account", "/v2/account/dungeons", true, false, false),
+    endpoint!("account", "/v2/account/dyes", true, false, false),
+    endpoint!("account", "/v2/account/finishers", true, false, false),
+    endpoint!("account", "/v2/account/home/cats", true, false, false),
+    endpoint!("account", "/v2/account/home/nodes", true, false, false),
+    endpoint!("account", "/v2/account/inventory", true, false, false),
+    endpoint!("account", "/v2/account/mapchests", true, false, false),
+    endpoint!("account", "/v2/account/masteries", true, false, false),
+    endpoint!("account", "/v2/account/materials", true, false, false),
+    endpoint!("account", "/v2/account/minis", true, false, false),
+    endpoint!("account", "/v2/account/outfits", true, false, false),
+    endpoint!("account", "/v2/account/raids", true, false, false),
+    endpoint!("account", "/v2/account/recipes", true, false, false),
+    endpoint!("account", "/v2/account/skins", true, false, false),
+    endpoint!("account", "/v2/account/titles", true, false, false),
+    endpoint!("account", "/v2/account/wallet", true, false, false),
+    endpoint!("account", "/v2/account/worldbosses", true, false, false),
+    endpoint!("achievements", "/v2/achievements", false, true, true),
+    endpoint!("achievements", "/v2/achievements/categories", false, true, true),
+    endpoint!("achievements", "/v2/achievements/daily", false, false, false),
+    endpoint!(
+        "achievements",
+        "/v2/achievements/daily/tomorrow",
+        false, false, false
+    ),
+    endpoint!("achievements", "/v2/achievements/groups", false, true, true),
+    endpoint!("story", "/v2/backstory/answers", false, true, true),
+    endpoint!("story", "/v2/backstory/questions", false, true, true),
+    endpoint!("characters", "/v2/characters", true, true, false),
+    endpoint!("characters", "/v2/characters/:id", true, false, false),
+    endpoint!("characters", "/v2/characters/:id/backstory", true, false, false),
+    endpoint!("characters", "/v2/characters/:id/core", true, false, false),
+    endpoint!("characters", "/v2/characters/:id/crafting", true, false, false),
+    endpoint!("characters", "/v2/characters/:id/equipment", true, false, false),
+    endpoint!(
+        "characters",
+        "/v2/characters/:id/equipmenttabs",
+        true, false, false
+    ),
+    endpoint!(
+        "characters",
+        "/v2/characters/:id/heropoints",
+        true, false, false
+    ),
+    endpoint!("characters", "/v2/characters/:id/inventory", true, false, false),
+    endpoint!("characters", "/v2/characters/:id/quests", true, false, false),
+    endpoint!("characters", "/v2/characters/:id/recipes", true, false, false),
+    endpoint!("characters", "/v2/characters/:id/sab", true, false, false),
+    endpoint!("characters", "/v2/characters/:id/skills", true, false, false),
+    endpoint!(
+        "characters",
+        "/v2/characters/:id/specializations",
+        true, false, false
+    ),
+    endpoint!("characters", "/v2/characters/:id/training", true, false, false),
+    endpoint!("items", "/v2/colors", false, true, true),
+    endpoint!("commerce", "/v2/commerce/exchange", false, false, false),
+    endpoint!("commerce", "/v2/commerce/exchange/coins", false, false, false),
+    endpoint!("commerce", "/v2/commerce/exchange/gems", false, false, false),
+    endpoint!("commerce", "/v2/commerce/listings", false, true, false),
+    endpoint!("commerce", "/v2/commerce/prices", false, true, false),
+    endpoint!(
+        "commerce",
+        "/v2/commerce/transactions/current/buys",
+        true, false, false
+    ),
+    endpoint!(
+        "commerce",
+        "/v2/commerce/transactions/current/sells",
+        true, false, false
+    ),
+    endpoint!(
+        "commerce",
+        "/v2/commerce/transactions/history/buys",
+        true, false, false
+    ),
+    endpoint!(
+        "commerce",
+        "/v2/commerce/transactions/history/sells",
+        true, false, false
+    ),
+    endpoint!("continents", "/v2/continents", false, true, true),
+    endpoint!("continents", "/v2/continents/:id/floors", false, true, true),
+    endpoint!(
+        "continents",
+        "/v2/continents/:id/floors/:floor/regions",
+        false, false, true
+    ),
+    endpoint!(
+        "continents",
+        "/v2/continents/:id/floors/:floor/regions/:region/maps",
+        false, false, true
+    ),
+    endpoint!(
+        "continents",
+        "/v2/continents/:id/floors/:floor/regions/:region/maps/:map/pois",
+        false, true, true
+    ),
+    endpoint!(
+        "continents",
+        "/v2/continents/:id/floors/:floor/regions/:region/maps/:map/sectors",
+        false, true, true
+    ),
+    endpoint!(
+        "continents",
+        "/v2/continents/:id/floors/:floor/regions/:region/maps/:map/tasks",
+        false, true, true
+    ),
+    endpoint!("account", "/v2/createsubtoken", true, false, false),
+    endpoint!("daily", "/v2/dailycrafting", false, false, false),
+    endpoint!("guild", "/v2/emblem/backgrounds", false, true, false),
+    endpoint!("guild", "/v2/emblem/foregrounds", false, true, false),
+    endpoint!("guild", "/v2/guild/:id", false, false, false),
+    endpoint!("guild", "/v2/guild/:id/log", true, false, false),
+    endpoint!("guild", "/v2/guild/:id/stash", true, false, false),
+    endpoint!("guild", "/v2/guild/:id/storage", true, false, false),
+    endpoint!("guild", "/v2/guild/:id/teams", true, false, false),
+    endpoint!("guild", "/v2/guild/:id/treasury", true, false, false),
+    endpoint!("guild", "/v2/guild/:id/upgrades", true, false, false),
+    endpoint!("guild", "/v2/guild/upgrades", false, true, true),
+    endpoint!("home", "/v2/home/cats", false, true, false),
+    endpoint!("home", "/v2/home/nodes", false, false, false),
+    endpoint!("items", "/v2/items", false, true, true),
+    endpoint!("items", "/v2/itemstats", false, true, true),
+    endpoint!("mechanics", "/v2/legends", false, true, false),
+    endpoint!("daily", "/v2/mapchests", false, false, false),
+    endpoint!("mechanics", "/v2/masteries", false, true, true),
+    endpoint!("mechanics", "/v2/outfits", false, true, true),
+    endpoint!("mechanics", "/v2/pets", false, true, true),
+    endpoint!("mechanics", "/v2/professions", false, true, true),
+    endpoint!("pvp", "/v2/pvp/seasons", false, false, true),
+    endpoint!("pvp", "/v2/pvp/seasons/:id/leaderboards", false, false, false),
+    endpoint!(
+        "pvp",
+        "/v2/pvp/seasons/:id/leaderboards/:board",
+        false, false, false
+    ),
+    endpoint!(
+        "pvp",
+        "/v2/pvp/seasons/:id/leaderboards/:board/:region",
+        false, false, false
+    ),
+    endpoint!("pvp", "/v2/pvp/stats", true, false, false),
+    endpoint!("story", "/v2/quests", false, true, true),
+    endpoint!("mechanics", "/v2/races", false, true, true),
+    endpoint!("mechanics", "/v2/skills", false, true, true),
+    endpoint!("mechanics", "/v2/specializations", false, true, true),
+    endpoint!("story", "/v2/stories", false, true, true),
+    endpoint!("story", "/v2/stories/seasons", false, true, true),
+    endpoint!("account", "/v2/tokeninfo", true, false, false),
+    endpoint!("mechanics", "/v2/traits", false, true, true),
+    endpoint!("daily", "/v2/worldbosses", false, false, false),
+    endpoint!("wvw", "/v2/wvw/abilities", false, true, true),
+    endpoint!("wvw", "/v2/wvw/matches", false, true, false),
+    endpoint!("wvw", "/v2/wvw/ranks", false, true, true),
+    endpoint!("wvw", "/v2/wvw/upgrades", false, true, true)
+];
+
+/// Obtain every endpoint supported by the crate, sorted by path
+pub fn endpoints() -> &'static [EndpointInfo] {
+    ENDPOINTS
+}
+
+/// Obtain the details of the endpoint a path belongs to
+///
+/// Paths without parameters take precedence, so `/v2/guild/upgrades` is not
+/// taken for the details of a guild.
+///
+/// # Arguments
+///
+/// * `path` - Path of the request. The query string is ignored
+pub fn find_endpoint(path: &str) -> Option<&'static EndpointInfo> {
+    let path = path.split('?').next().unwrap_or(path);
+
+    ENDPOINTS.iter()
+        .find(|endpoint| endpoint.path == path)
+        .or_else(|| ENDPOINTS.iter().find(|endpoint| endpoint.matches(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use api_v2::registry::*;
+
+    #[test]
+    fn sorted_paths() {
+        let paths: Vec<&str> = endpoints().iter().map(|e| e.path).collect();
+        let mut sorted = paths.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(paths, sorted);
+    }
+
+    #[test]
+    fn flags() {
+        let items = find_endpoint("/v2/items?ids=1,2").unwrap();
+        assert!(items.bulk && items.localized && !items.authenticated);
+
+        let log = find_endpoint("/v2/guild/ABC/log").unwrap();
+        assert_eq!(log.path, "/v2/guild/:id/log");
+        assert!(log.authenticated);
+
+        assert_eq!(
+            find_endpoint("/v2/guild/upgrades").unwrap().path,
+            "/v2/guild/upgrades"
+        );
+        assert!(find_endpoint("/v2/unknown").is_none());
+    }
+}
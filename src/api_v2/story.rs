@@ -0,0 +1,509 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Story and backstory endpoints
+
+use client::APIClient;
+use common::{APIError, Page};
+use api_v2::endpoint::{get_all, get_ids, get_many, get_one, get_page};
+use api_v2::types::{
+    BackstoryAnswer,
+    BackstoryQuestion,
+    IdList,
+    Quest,
+    Story,
+    StorySeason
+};
+
+/// Obtain a list of all the story quest IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_quest_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<Quest>(client)
+}
+
+/// Obtain details for the specified story quest
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_quest(client: &APIClient, id: i32) -> Result<Quest, APIError> {
+    get_one::<Quest>(client, id)
+}
+
+/// Obtain details for the specified story quests
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_quests(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<Quest>, APIError> {
+    get_many::<Quest>(client, &ids)
+}
+
+/// Obtain details for all story quests
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_quests(client: &APIClient) -> Result<Vec<Quest>, APIError> {
+    get_all::<Quest>(client)
+}
+
+/// Obtain a page of story quests
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_quests_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Quest>, APIError> {
+    get_page::<Quest>(client, page, page_size)
+}
+
+/// Obtain a list of all the story IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_story_ids(client: &APIClient) -> Result<IdList<i32>, APIError> {
+    get_ids::<Story>(client)
+}
+
+/// Obtain details for the specified story
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_story(client: &APIClient, id: i32) -> Result<Story, APIError> {
+    get_one::<Story>(client, id)
+}
+
+/// Obtain details for the specified stories
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_stories(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<Story>, APIError> {
+    get_many::<Story>(client, &ids)
+}
+
+/// Obtain details for all stories
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_stories(client: &APIClient) -> Result<Vec<Story>, APIError> {
+    get_all::<Story>(client)
+}
+
+/// Obtain a page of stories
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_stories_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<Story>, APIError> {
+    get_page::<Story>(client, page, page_size)
+}
+
+/// Obtain a list of all the story season IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_story_season_ids(
+    client: &APIClient
+) -> Result<IdList<String>, APIError> {
+    get_ids::<StorySeason>(client)
+}
+
+/// Obtain details for the specified story season
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_story_season(
+    client: &APIClient,
+    id: &str
+) -> Result<StorySeason, APIError> {
+    get_one::<StorySeason>(client, id.to_string())
+}
+
+/// Obtain details for the specified story seasons
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_story_seasons(
+    client: &APIClient,
+    ids: Vec<&str>
+) -> Result<Vec<StorySeason>, APIError> {
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    get_many::<StorySeason>(client, &ids)
+}
+
+/// Obtain details for all story seasons
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_story_seasons(
+    client: &APIClient
+) -> Result<Vec<StorySeason>, APIError> {
+    get_all::<StorySeason>(client)
+}
+
+/// Obtain a page of story seasons
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_story_seasons_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<StorySeason>, APIError> {
+    get_page::<StorySeason>(client, page, page_size)
+}
+
+/// Obtain a list of all the backstory question IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_backstory_question_ids(
+    client: &APIClient
+) -> Result<IdList<i32>, APIError> {
+    get_ids::<BackstoryQuestion>(client)
+}
+
+/// Obtain details for the specified backstory question
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_backstory_question(
+    client: &APIClient,
+    id: i32
+) -> Result<BackstoryQuestion, APIError> {
+    get_one::<BackstoryQuestion>(client, id)
+}
+
+/// Obtain details for the specified backstory questions
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_backstory_questions(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<BackstoryQuestion>, APIError> {
+    get_many::<BackstoryQuestion>(client, &ids)
+}
+
+/// Obtain details for all backstory questions
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_backstory_questions(
+    client: &APIClient
+) -> Result<Vec<BackstoryQuestion>, APIError> {
+    get_all::<BackstoryQuestion>(client)
+}
+
+/// Obtain a page of backstory questions
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_backstory_questions_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<BackstoryQuestion>, APIError> {
+    get_page::<BackstoryQuestion>(client, page, page_size)
+}
+
+/// Obtain a list of all the backstory answer IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_backstory_answer_ids(
+    client: &APIClient
+) -> Result<IdList<String>, APIError> {
+    get_ids::<BackstoryAnswer>(client)
+}
+
+/// Obtain details for the specified backstory answer
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_backstory_answer(
+    client: &APIClient,
+    id: &str
+) -> Result<BackstoryAnswer, APIError> {
+    get_one::<BackstoryAnswer>(client, id.to_string())
+}
+
+/// Obtain details for the specified backstory answers
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_backstory_answers(
+    client: &APIClient,
+    ids: Vec<&str>
+) -> Result<Vec<BackstoryAnswer>, APIError> {
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    get_many::<BackstoryAnswer>(client, &ids)
+}
+
+/// Obtain details for all backstory answers
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_backstory_answers(
+    client: &APIClient
+) -> Result<Vec<BackstoryAnswer>, APIError> {
+    get_all::<BackstoryAnswer>(client)
+}
+
+/// Obtain a page of backstory answers
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_backstory_answers_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<BackstoryAnswer>, APIError> {
+    get_page::<BackstoryAnswer>(client, page, page_size)
+}
+
+/// Sort stories in story journal order
+///
+/// Stories are sorted by the order of their season and then by their
+/// position within the season. Stories of unknown seasons go last.
+///
+/// # Arguments
+///
+/// * `seasons` - Seasons as obtained from `get_story_seasons()`
+/// * `stories` - Stories to sort
+pub fn sort_stories(seasons: &[StorySeason], stories: &mut Vec<Story>) {
+    stories.sort_by_key(|story| {
+        let season = seasons.iter()
+            .find(|season| season.id == story.season)
+            .map(|season| season.order);
+
+        (season.is_none(), season, story.order)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use fixtures::test_client;
+    use api_v2::types::{Quest, Story, StorySeason};
+    use api_v2::story::*;
+
+    macro_rules! parse_test {
+        ($result:expr) => {
+            match $result {
+                Ok(_) => assert!(true),
+                Err(e) => panic!(e.description().to_string()),
+            };
+        }
+    }
+
+    #[test]
+    fn quest_ids() {
+        let client = test_client();
+        let result = get_quest_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn quest() {
+        let client = test_client();
+        let result = get_quest(&client, 1);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn quests() {
+        let client = test_client();
+        let result = get_quests(&client, vec![1, 2]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn quest_goals() {
+        let quest: Quest = serde_json::from_str(r#"{
+            "id": 15,
+            "name": "Forging the Pact",
+            "level": 80,
+            "story": 8,
+            "goals": [
+                {"active": "Meet with Trahearne.",
+                 "complete": "Met with Trahearne."}
+            ]
+        }"#).unwrap();
+
+        assert_eq!(quest.story, 8);
+        assert_eq!(quest.goals.len(), 1);
+    }
+
+    #[test]
+    fn backstory_question_ids() {
+        let client = test_client();
+        let result = get_backstory_question_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn backstory_questions() {
+        let client = test_client();
+        let result = get_backstory_questions(&client, vec![7, 12]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn backstory_answers() {
+        let client = test_client();
+        let result = get_backstory_answers(&client, vec!["7-54", "12-91"]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn story_ids() {
+        let client = test_client();
+        let result = get_story_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn story() {
+        let client = test_client();
+        let result = get_story(&client, 1);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn stories() {
+        let client = test_client();
+        let result = get_stories(&client, vec![1, 2]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn ordering_and_requirements() {
+        let seasons: Vec<StorySeason> = serde_json::from_str(r#"[
+            {"id": "B", "name": "Second", "order": 20, "stories": [3]},
+            {"id": "A", "name": "First", "order": 10, "stories": [1, 2]}
+        ]"#).unwrap();
+        let mut stories: Vec<Story> = serde_json::from_str(r#"[
+            {"id": 4, "season": "X", "name": "Unknown", "level": 1,
+             "order": 0},
+            {"id": 3, "season": "B", "name": "Third", "level": 80,
+             "order": 0},
+            {"id": 2, "season": "A", "name": "Second", "level": 10,
+             "order": 1, "races": ["Asura"],
+             "chapters": [{"name": "One"}, {"name": "Two"}]},
+            {"id": 1, "season": "A", "name": "First", "level": 1,
+             "order": 0}
+        ]"#).unwrap();
+
+        sort_stories(&seasons, &mut stories);
+        let ids: Vec<i32> = stories.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+
+        assert!(stories[1].is_available_to("Asura", 10));
+        assert!(!stories[1].is_available_to("Asura", 9));
+        assert!(!stories[1].is_available_to("Human", 80));
+        assert!(stories[2].is_available_to_race("Human"));
+        assert_eq!(stories[1].chapter_names(), vec!["One", "Two"]);
+    }
+
+    #[test]
+    fn story_season_ids() {
+        let client = test_client();
+        let result = get_story_season_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn story_seasons() {
+        let client = test_client();
+        let result = get_story_season_ids(&client).and_then(|ids| {
+            get_story_seasons(&client, ids.str_batches(0).remove(0))
+        });
+        parse_test!(result);
+    }
+}
@@ -21,206 +21,415 @@
 // SOFTWARE.
 
 /// Type definitions for the deserialization of API results
+///
+/// Each type is only available with the feature of the endpoint group that
+/// returns it, except for the ones shared by every group.
 
+#[cfg(any(feature = "guild", feature = "pvp", feature = "static"))]
 use std::collections::HashMap;
-use chrono::prelude::*;
-use chrono::DateTime;
+use std::fmt;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+#[cfg(all(
+    feature = "chrono",
+    any(feature = "account", feature = "characters", feature = "static")
+))]
+use chrono::Duration;
+use serde::{Deserialize, Deserializer};
+
+#[cfg(any(feature = "account", feature = "guild", feature = "static"))]
+use api_v2::compat;
+
+
+/// Implement `Deserialize` through `compat::known_fields()` for a type that
+/// flattens other types and derives it with `#[serde(remote = "Self")]`
+///
+/// `$fields` lists the fields of the response used by the type.
+#[cfg(any(feature = "guild", feature = "static"))]
+macro_rules! known_fields_deserialize {
+    ($ty:ident, $fields:expr) => {
+        impl compat::Flattened for $ty {
+            fn fields() -> Vec<&'static str> {
+                $fields
+            }
+
+            fn deserialize_fields<'de, D>(
+                deserializer: D
+            ) -> Result<$ty, D::Error> where D: Deserializer<'de> {
+                $ty::deserialize(deserializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D>(
+                deserializer: D
+            ) -> Result<$ty, D::Error> where D: Deserializer<'de> {
+                compat::known_fields(deserializer)
+            }
+        }
+    }
+}
+
+
+/// Timestamp returned by the API
+///
+/// With the default `chrono` feature, timestamps are parsed as
+/// `DateTime<Utc>`. Without it, they are kept as the ISO-8601 strings
+/// returned by the API.
+#[cfg(feature = "chrono")]
+pub type Timestamp = DateTime<Utc>;
 
+/// Timestamp returned by the API
+///
+/// With the default `chrono` feature, timestamps are parsed as
+/// `DateTime<Utc>`. Without it, they are kept as the ISO-8601 strings
+/// returned by the API.
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = String;
+
+
+/// Permissions that can be granted to an API key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// Account display name, ID, home world and guild list
+    Account,
+    /// Builds stored in templates and the build storage
+    Builds,
+    /// Basic information about characters
+    Characters,
+    /// Guild rosters, history and MOTDs
+    Guilds,
+    /// Account and character bank and inventories
+    Inventories,
+    /// Achievements, dungeon and raid progress, masteries and crafting
+    Progression,
+    /// PvP stats, match history, reward track progression and custom arenas
+    PvP,
+    /// Trading post transactions
+    TradingPost,
+    /// Wardrobe unlocks: skins, dyes, minis, finishers, etc.
+    Unlocks,
+    /// Wallet and currencies
+    Wallet,
+    /// WvW guild assignment and team
+    WvW
+}
+
+impl Scope {
+    /// Every scope supported by the API
+    pub const ALL: [Scope; 11] = [
+        Scope::Account,
+        Scope::Builds,
+        Scope::Characters,
+        Scope::Guilds,
+        Scope::Inventories,
+        Scope::Progression,
+        Scope::PvP,
+        Scope::TradingPost,
+        Scope::Unlocks,
+        Scope::Wallet,
+        Scope::WvW
+    ];
+
+    /// Obtain the name used by the API for the scope
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Scope::Account => "account",
+            Scope::Builds => "builds",
+            Scope::Characters => "characters",
+            Scope::Guilds => "guilds",
+            Scope::Inventories => "inventories",
+            Scope::Progression => "progression",
+            Scope::PvP => "pvp",
+            Scope::TradingPost => "tradingpost",
+            Scope::Unlocks => "unlocks",
+            Scope::Wallet => "wallet",
+            Scope::WvW => "wvw"
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
 
 /// API key details
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct APIKey {
     /// Requested API key
-    id: String,
+    pub id: String,
     /// Name given to the API key by the account owner (not escaped!)
-    name: String,
+    pub name: String,
     /// Which permissions the API key has
-    permissions: Vec<String>
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    /// Type of token: `APIKey` or `Subtoken`
+    #[serde(default)]
+    #[serde(rename = "type")]
+    pub key_type: Option<String>,
+    /// Time the subtoken expires at. Only present for subtokens
+    #[serde(default)]
+    pub expires_at: Option<Timestamp>,
+    /// Time the subtoken was created at. Only present for subtokens
+    #[serde(default)]
+    pub issued_at: Option<Timestamp>,
+    /// Endpoints the subtoken is restricted to. Only present for subtokens
+    /// created with a URL whitelist
+    #[serde(default)]
+    pub urls: Option<Vec<String>>
+}
+
+impl APIKey {
+    /// Check whether the token has been granted a permission
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - Permission to check
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.permissions.iter().any(|permission| permission == scope.name())
+    }
+
+    /// Obtain the permissions from a list that the token lacks
+    ///
+    /// # Arguments
+    ///
+    /// * `scopes` - Permissions to check
+    pub fn missing_scopes(&self, scopes: &[Scope]) -> Vec<Scope> {
+        scopes.iter()
+            .filter(|&&scope| !self.has_scope(scope))
+            .cloned()
+            .collect()
+    }
+
+    /// Check whether the token is a subtoken
+    pub fn is_subtoken(&self) -> bool {
+        self.key_type.as_ref().map_or(false, |t| t == "Subtoken")
+    }
+
+    /// Check whether the token can access an endpoint, according to its URL
+    /// whitelist
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Path of the endpoint, without query string
+    pub fn allows_url(&self, url: &str) -> bool {
+        match self.urls {
+            Some(ref urls) => urls.iter().any(|allowed| allowed == url),
+            None => true
+        }
+    }
 }
 
 /// User account
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "account")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Account {
     /// Unique persisten account GUID
-    id: String,
+    pub id: String,
     /// Age of the account in seconds
-    age: i32,
+    pub age: i32,
     /// Unique account name with numerical suffix
-    name: String,
+    pub name: String,
     /// ID of the home world the account is assigned to
-    world: i32,
+    pub world: i32,
     /// List of guilds assigned to the given account
     #[serde(default)]
-    guilds: Vec<String>,
+    pub guilds: Vec<String>,
     /// List of guilds the account is leader of
     #[serde(default)]
-    guild_leader: Vec<String>,
+    pub guild_leader: Vec<String>,
     /// Timestamp of when the account was created
-    created: DateTime<Utc>,
+    pub created: Timestamp,
     /// Type of game the account has access to (F2P, base game, HoT, PoF etc.)
-    access: Vec<String>,
+    #[serde(default, deserialize_with = "compat::string_or_list")]
+    pub access: Vec<String>,
     /// True if the player has bought a commander tag
-    commander: bool,
+    pub commander: bool,
     /// Account's personal fractal reward level (requires `progression` scope)
     #[serde(default)]
-    fractal_level: i32,
+    pub fractal_level: i32,
     /// Account's daily AP (requires `progression` scope)
     #[serde(default)]
-    daily_ap: i32,
+    pub daily_ap: i32,
     /// Account's monthly AP (requires `progression` scope)
     #[serde(default)]
-    monthly_ap: i32,
+    pub monthly_ap: i32,
     /// Account's personal WvW rank (requires `progression` scope)
     #[serde(default)]
-    wvw_rank: i32
+    pub wvw_rank: i32
+}
+
+#[cfg(feature = "account")]
+impl Account {
+    /// Time the account has been played for
+    #[cfg(feature = "chrono")]
+    pub fn age(&self) -> Duration {
+        Duration::seconds(self.age as i64)
+    }
+
+    /// Raw age in seconds, as returned by the API
+    pub fn age_seconds(&self) -> i32 {
+        self.age
+    }
 }
 
 /// Achievements that the account has progress on
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "account")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AccountAchievement {
     /// Achievement ID
-    id: i32,
+    pub id: i32,
     /// Player's current progress towards the achievement (if any)
     #[serde(default)]
-    current: i32,
+    pub current: i32,
     /// Amount needed to complete the achievements (if any).
     /// Most WvW achievements have this set to `-1`
     #[serde(default)]
-    max: i32,
+    pub max: i32,
     /// Whether or not the achievement is done
-    done: bool,
+    pub done: bool,
     /// Number of times the achievement has been completed (if repeatable)
     #[serde(default)]
-    repeated: i32,
+    pub repeated: i32,
     /// Bits giving more information on the progress for the achievement
     #[serde(default)]
-    bits: Vec<i32>
+    pub bits: Vec<i32>
 }
 
 /// Currencies in an account's wallet
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "account")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AccountCurrency {
     /// ID of the currency
-    id: i32,
+    pub id: i32,
     /// Amount of this currency
-    value: i32
+    pub value: i32
 }
 
 /// Finishers unlocked for the account
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "account")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AccountFinisher {
     /// ID of the finisher
-    id: i32,
+    pub id: i32,
     /// Indicates if the finisher is permanent or temporary
-    permanent: bool,
+    pub permanent: bool,
     /// If not permanent, indicates the remaining uses
     #[serde(default)]
-    quantity: i32,
+    pub quantity: i32,
 }
 
 /// Unlocked masteries for the account
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "account")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AccountMastery {
     /// ID of the mastery
-    id: i32,
+    pub id: i32,
     /// Level at which the mastery is on the account
-    level: i32
+    pub level: i32
 }
 
 /// Materials stored in the account's vault
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "account")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AccountMaterial {
     /// Item ID of the material
-    id: i32,
+    pub id: i32,
     /// Material category the item belongs to
-    category: i32,
+    pub category: i32,
     /// Number of the material that is stored in the account vault
-    count: i32
+    pub count: i32
 }
 
 /// Player achievements
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Achievement {
     /// Achievement ID
-    id: i32,
+    pub id: i32,
     /// Achievement icon (if any)
     #[serde(default)]
-    icon: String,
+    pub icon: String,
     /// Achievement name
-    name: String,
+    pub name: String,
     /// Achievement description
-    description: String,
+    pub description: String,
     /// Achievement requirement as listed in-game
-    requirement: String,
+    pub requirement: String,
     /// Achievement description prior to unlocking it
-    locked_text: String,
+    pub locked_text: String,
     /// Achievement type
     #[serde(rename = "type")]
-    achievement_type: String,
+    pub achievement_type: String,
     /// Achievement categories
-    flags: Vec<String>,
+    pub flags: Vec<String>,
     /// Describes the achievement's tiers
-    tiers: Vec<AchievementTier>,
+    pub tiers: Vec<AchievementTier>,
     /// Achievement IDs required to progress the given achievement
     #[serde(default)]
-    prerequisites: Vec<i32>,
+    pub prerequisites: Vec<i32>,
     /// Describes the rewards given for the achievement
     #[serde(default)]
-    rewards: Vec<AchievementReward>,
+    pub rewards: Vec<AchievementReward>,
     /// Bitmask value that can give futher information on achievement progress
     #[serde(default)]
-    bits: Vec<AchievementBit>,
+    pub bits: Vec<AchievementBit>,
     /// Maximum number of AP that can be rewarded by a repeatable achievement
     #[serde(default)]
-    point_cap: i32
+    pub point_cap: i32
 }
 
 /// Achievement bits
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AchievementBit {
     /// Type of bit (`Text`, `Item`, `Minipet`, `Skin`)
     #[serde(rename = "type")]
-    bit_type: String,
+    pub bit_type: String,
     /// ID of the item, mini, or skin, if applicable
     #[serde(default)]
-    id: i32,
+    pub id: i32,
     /// Text for the bit if type is `Text`
     #[serde(default)]
-    text: String
+    pub text: String
 }
 
 /// Achievement categories
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AchievementCategory {
     /// Category's ID
-    id: i32,
+    pub id: i32,
     /// Category name
-    name: String,
+    pub name: String,
     /// Category description
-    description: String,
+    pub description: String,
     /// Describes where to sort this category among the other categories in
     /// its group. Lowest numbers go first, highest numbers go last
-    order: i32,
+    pub order: i32,
     /// URL to an image for the icon of the category
-    icon: String,
+    pub icon: String,
     /// Achievement IDs that this category contains
-    achievements: Vec<i32>
+    pub achievements: Vec<i32>
 }
 
 /// Achievement groups
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AchievementGroup {
     /// Group's ID
-    id: String,
+    pub id: String,
     /// Group name
-    name: String,
+    pub name: String,
     /// Group description
-    description: String,
+    pub description: String,
     /// Describes where to sort this group among other groups.
     /// Lowest numbers go first, highest numbers go last
-    order: i32,
+    pub order: i32,
     /// Category IDs that this group contains
-    categories: Vec<i32>
+    pub categories: Vec<i32>
 }
 
 /// Achievement awards
@@ -231,1212 +440,2601 @@ pub struct AchievementGroup {
 /// - "Item": uses attributes `id` and `count`
 /// - "Mastery": uses attributes `id` and `region`
 /// - "Title": uses attribute `id`
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AchievementReward {
     /// Type of reward (`Coins`, `Item`, `Mastery`, `Title`)
     #[serde(rename = "type")]
-    reward_type: String,
+    pub reward_type: String,
     /// ID of reward (when type is `Item`, `Mastery`, or `Title`)
     #[serde(default)]
-    id: i32,
+    pub id: i32,
     /// Number of items awarded (when type is `Item`)
     #[serde(default)]
-    count: i32,
+    pub count: i32,
     /// Region in which the Mastery Point applies to (when type is `Mastery`)
     #[serde(default)]
-    region: String
+    pub region: String
 }
 
 /// Achievement tiers
 ///
 /// This is used for achievements that can be repeated, showing the item count
 /// necessary to unlock the next tier and the points awarded.
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AchievementTier {
     /// Number of "things" that must be completed to achieve this tier
-    count: i32,
+    pub count: i32,
     /// Amount of AP awarded for completing this tier
-    points: i32
+    pub points: i32
+}
+
+/// Possible answer to a backstory question
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BackstoryAnswer {
+    /// Answer ID
+    pub id: String,
+    /// Localized title of the answer
+    pub title: String,
+    /// Localized description of the answer
+    pub description: String,
+    /// Localized text shown in the character's biography
+    pub journal: String,
+    /// ID of the question the answer belongs to
+    pub question: i32,
+    /// Races the answer is available to. All races if not present
+    #[serde(default)]
+    pub races: Option<Vec<String>>,
+    /// Professions the answer is available to. All professions if not
+    /// present
+    #[serde(default)]
+    pub professions: Option<Vec<String>>
+}
+
+/// Backstory question asked during character creation
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BackstoryQuestion {
+    /// Question ID
+    pub id: i32,
+    /// Localized title of the question
+    pub title: String,
+    /// Localized description of the question
+    pub description: String,
+    /// IDs of the possible answers
+    pub answers: Vec<String>,
+    /// Position of the question during character creation
+    pub order: i32,
+    /// Races the question is asked to. All races if not present
+    #[serde(default)]
+    pub races: Option<Vec<String>>,
+    /// Professions the question is asked to. All professions if not present
+    #[serde(default)]
+    pub professions: Option<Vec<String>>
 }
 
 /// Equiped bags in a character
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Bag {
     /// Item ID of the bag
-    id: i32,
+    pub id: i32,
     /// Amount of slogs available in this bag
-    size: i32,
+    pub size: i32,
     /// Describes item slots. If no item is in the specific slot, its value
     /// will be `None`
     #[serde(default)]
-    inventory: Vec<Option<BagSlot>>
+    pub inventory: Vec<Option<BagSlot>>
 }
 
 /// Bag slot
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BagSlot {
     /// Item ID
-    id: i32,
+    pub id: i32,
     /// Amount of item in the stack (min: 1, max: 250)
-    count: i32,
+    pub count: i32,
     /// List of infusion item IDs (if any)
     #[serde(default)]
-    infusions: Vec<i32>,
+    pub infusions: Vec<i32>,
     /// List of upgrade component item IDs (if any)
     #[serde(default)]
-    upgrades: Vec<i32>,
+    pub upgrades: Vec<i32>,
     /// Skin ID for the given equipment piece (if any)
     #[serde(default)]
-    skin: i32,
+    pub skin: i32,
     /// Contains information on the stats chosen if the item offers an option
     /// for stats/prefix
     #[serde(default)]
-    stats: Option<EquipmentStats>,
+    pub stats: Option<EquipmentStats>,
     /// Describes which type of binding the item has
     #[serde(default)]
-    binding: String,
+    pub binding: String,
     /// If character bound, name of the character the item is bound to
     #[serde(default)]
-    bound_to: String
+    pub bound_to: String
 }
 
 /// Item slot in the bank
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "account")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BankSlot {
     /// Item's ID
-    id: i32,
+    pub id: i32,
     /// Amount of items in the item stack
-    count: i32,
+    pub count: i32,
     /// The skin applied to the item, if it is different from its original
     #[serde(default)]
-    skin: i32,
+    pub skin: i32,
     /// Item IDs for each rune or signet applied to the item
     #[serde(default)]
-    upgrades: Vec<i32>,
+    pub upgrades: Vec<i32>,
     /// Item IDs for each infusion applied to the item
     #[serde(default)]
-    infusions: Vec<i32>,
+    pub infusions: Vec<i32>,
     /// Current binding of the item
     #[serde(default)]
-    binding: String,
+    pub binding: String,
     /// Amount of charges remaining on the item
     #[serde(default)]
-    charges: i32,
+    pub charges: i32,
     /// If `binding` is `Character`, which character the item is bound to
     #[serde(default)]
-    bound_to: String
+    pub bound_to: String
 }
 
 /// Home instance cats
-#[derive(Deserialize, Debug)]
+#[cfg(any(feature = "account", feature = "static"))]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Cat {
     /// ID for the cat
-    id: i32,
+    pub id: i32,
     /// Hint to identify what is needed for each cat
     #[serde(default)]
-    hint: String
+    pub hint: String
 }
 
 /// Character information
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Character {
     /// Backstory answer IDs pertaining to the questions answered during
     /// character creation
     #[serde(default)]
-    backstory: Vec<String>,
+    pub backstory: Vec<String>,
 
     /// Character's name
-    name: String,
+    pub name: String,
     /// Character's race
-    race: String,
+    pub race: String,
     /// Character's gender
-    gender: String,
+    pub gender: String,
     /// Character's profession
-    profession: String,
+    pub profession: String,
     /// Character's level
-    level: i32,
+    pub level: i32,
     /// Guild ID of the character's currently represented guild (if any)
     #[serde(default)]
-    guild: String,
+    pub guild: String,
     /// Amount of seconds this character was played
-    age: i32,
+    pub age: i32,
     /// Timestamp of the character's creation time
-    created: DateTime<Utc>,
+    pub created: Timestamp,
     /// Amount of times this character has been defeated
-    deaths: i32,
+    pub deaths: i32,
     /// Currently selected title ID for the character
     #[serde(default)]
-    title: i32,
+    pub title: i32,
 
     /// List of crafting disciplines the character has unlocked
-    crafting: Vec<CraftingDiscipline>,
+    #[serde(default)]
+    pub crafting: Vec<CraftingDiscipline>,
 
     /// List of pieces of equipment currently on the character
-    equipment: Vec<Equipment>,
+    #[serde(default)]
+    pub equipment: Vec<Equipment>,
     /// Contains information on character's PvP equipment setup
-    equipment_pvp: CharacterPvPEquipment,
+    #[serde(default)]
+    pub equipment_pvp: CharacterPvPEquipment,
 
     /// Describes bags in the character's inventory
-    bags: Vec<Bag>,
+    #[serde(default)]
+    pub bags: Vec<Bag>,
 
     /// List of recipe IDs unlocked by the character
-    recipes: Vec<i32>,
+    #[serde(default)]
+    pub recipes: Vec<i32>,
 
     /// Describes the utility skills equipped in PvE, PvP, and WvW
-    skills: CharacterSkillSets,
+    #[serde(default)]
+    pub skills: CharacterSkillSets,
 
     /// Describes the specializations and traits equipped in PvE, PvP, and WvW
-    specializations: CharacterSpecializationSet,
+    #[serde(default)]
+    pub specializations: CharacterSpecializationSet,
 
     /// Skill trees trained
-    training: Vec<CharacterSkillTree>,
+    #[serde(default)]
+    pub training: Vec<CharacterSkillTree>,
 
     /// WvW abilities trained by the character
-    wvw_abilities: Vec<CharacterWvWAbility>,
+    #[serde(default)]
+    pub wvw_abilities: Vec<CharacterWvWAbility>,
+
+    /// Index of the equipment tab in use. Requires schema version
+    /// `2019-12-19T00:00:00Z` or later
+    #[serde(default)]
+    pub active_equipment_tab: Option<i32>,
+    /// Number of equipment tabs unlocked. Requires schema version
+    /// `2019-12-19T00:00:00Z` or later
+    #[serde(default)]
+    pub equipment_tabs_unlocked: Option<i32>,
+    /// Equipment tabs of the character. Requires schema version
+    /// `2019-12-19T00:00:00Z` or later
+    #[serde(default)]
+    pub equipment_tabs: Vec<EquipmentTab>,
+}
+
+#[cfg(feature = "characters")]
+impl Character {
+    /// Time the character has been played for
+    #[cfg(feature = "chrono")]
+    pub fn age(&self) -> Duration {
+        Duration::seconds(self.age as i64)
+    }
+
+    /// Raw age in seconds, as returned by the API
+    pub fn age_seconds(&self) -> i32 {
+        self.age
+    }
 }
 
 /// Character backstory
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterBackstory {
     /// Backstory answer IDs pertaining to character creation questions
-    backstory: Vec<String>
+    #[serde(default)]
+    pub backstory: Vec<String>
 }
 
 /// Core information of a character
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterCore {
     /// Character's name
-    name: String,
+    pub name: String,
     /// Character's race
-    race: String,
+    pub race: String,
     /// Character's gender
-    gender: String,
+    pub gender: String,
     /// Character's profession
-    profession: String,
+    pub profession: String,
     /// Character's level
-    level: i32,
+    pub level: i32,
     /// Guild ID of the character's currently represented guild (if any)
     #[serde(default)]
-    guild: String,
+    pub guild: String,
     /// Amount of seconds this character was played
-    age: i32,
+    pub age: i32,
     /// Timestamp of the character's creation time
-    created: DateTime<Utc>,
+    pub created: Timestamp,
     /// Amount of times this character has been defeated
-    deaths: i32,
+    pub deaths: i32,
     /// Currently selected title ID for the character
     #[serde(default)]
-    title: i32,
+    pub title: i32,
+}
+
+#[cfg(feature = "characters")]
+impl CharacterCore {
+    /// Time the character has been played for
+    #[cfg(feature = "chrono")]
+    pub fn age(&self) -> Duration {
+        Duration::seconds(self.age as i64)
+    }
+
+    /// Raw age in seconds, as returned by the API
+    pub fn age_seconds(&self) -> i32 {
+        self.age
+    }
 }
 
 /// Unlocked crafting disciplines
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterCrafting {
     /// All crafting disciplines unlocked by the character
     #[serde(default)]
-    crafting: Vec<CraftingDiscipline>
+    pub crafting: Vec<CraftingDiscipline>
 }
 
 /// Current character equipment
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterEquipment {
     /// Each piece of equipment currently on the character
     #[serde(default)]
-    equipment: Vec<Equipment>
+    pub equipment: Vec<Equipment>
 }
 
 /// Character inventory
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterInventory {
     /// List of bags in the inventory of the character
     #[serde(default)]
-    bags: Vec<Bag>
+    pub bags: Vec<Bag>
 }
 
 /// PVP equipment setup
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterPvPEquipment {
-    /// ID for the equipped PvP amulet
-    amulet: i32,
-    /// Id for the equipped PvP rune
-    rune: i32,
+    /// ID for the equipped PvP amulet (if any)
+    #[serde(default)]
+    pub amulet: Option<i32>,
+    /// Id for the equipped PvP rune (if any)
+    #[serde(default)]
+    pub rune: Option<i32>,
     /// ID for all equipped PvP sigils
-    sigils: Vec<Option<i32>>
+    #[serde(default)]
+    pub sigils: Vec<Option<i32>>
 }
 
 /// Recipes unlocked by the character
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterRecipes {
     #[serde(default)]
-    recipes: Vec<i32>
+    pub recipes: Vec<i32>
 }
 
 /// Current character skills
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterSkills {
-    skills: CharacterSkillSets
+    #[serde(default)]
+    pub skills: CharacterSkillSets
 }
 
 /// Slotted character skills per game mode
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterSkillSets {
     /// PvE character skill set
-    pve: CharacterSkillSet,
+    #[serde(default)]
+    pub pve: CharacterSkillSet,
     /// PvP character skill set
-    pvp: CharacterSkillSet,
+    #[serde(default)]
+    pub pvp: CharacterSkillSet,
     /// WvW character skill set
-    wvw: CharacterSkillSet
+    #[serde(default)]
+    pub wvw: CharacterSkillSet
 }
 
 /// Set of skills slotted
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterSkillSet {
-    /// Skill ID for the heal skill
-    heal: i32,
-    /// List of skill IDs for the equipped utilities
-    utilities: Vec<i32>,
-    /// Skill ID for the elite skill
-    elite: i32
+    /// Skill ID for the heal skill (if any)
+    #[serde(default)]
+    pub heal: Option<i32>,
+    /// List of skill IDs for the equipped utilities. Empty slots are `None`
+    #[serde(default)]
+    pub utilities: Vec<Option<i32>>,
+    /// Skill ID for the elite skill (if any)
+    #[serde(default)]
+    pub elite: Option<i32>
 }
 
 /// Current specializations and traits in a character
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterSpecializations {
-    specializations: CharacterSpecializationSet
+    #[serde(default)]
+    pub specializations: CharacterSpecializationSet
 }
 
 /// Current specializations and traits in a character
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterSpecializationSet {
     /// PvE character specializations
-    pve: Vec<CharacterSpecialization>,
+    #[serde(default)]
+    pub pve: Vec<CharacterSpecialization>,
     /// PvP character specializations
-    pvp: Vec<CharacterSpecialization>,
+    #[serde(default)]
+    pub pvp: Vec<CharacterSpecialization>,
     /// WvW character specializations
-    wvw: Vec<CharacterSpecialization>
+    #[serde(default)]
+    pub wvw: Vec<CharacterSpecialization>
 }
 
 /// Current specializations and traits in a character
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterSpecialization {
-    /// Specialization ID
-    id: i32,
-    /// List of IDs for each selected trait
-    traits: Vec<i32>
+    /// Specialization ID, `None` if the slot is empty
+    #[serde(default)]
+    pub id: Option<i32>,
+    /// List of IDs for each selected trait. Unselected traits are `None`
+    #[serde(default)]
+    pub traits: Vec<Option<i32>>
 }
 
 /// Skill trees trained by the character
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterTraining {
     #[serde(default)]
-    training: Vec<CharacterSkillTree>
+    pub training: Vec<CharacterSkillTree>
 }
 
 /// Skill tree item
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterSkillTree {
     /// Skill tree ID
-    id: i32,
+    pub id: i32,
     /// Shows how many hero points have been spent in this tree
-    spent: i32,
+    pub spent: i32,
     /// States whether or not the tree is fully trained
-    done: bool
+    pub done: bool
 }
 
 /// Character WvW abilities
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharacterWvWAbility {
     /// AbilityID
-    id: i32,
+    pub id: i32,
     /// Current rank for the given ability
-    rank: i32
+    pub rank: i32
+}
+
+/// Dye color
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Color {
+    /// Color ID
+    pub id: i32,
+    /// Localized name of the color
+    pub name: String,
+    /// Base RGB values of the color
+    pub base_rgb: Vec<i32>,
+    /// Appearance of the color on cloth armor
+    pub cloth: ColorDetails,
+    /// Appearance of the color on leather armor
+    pub leather: ColorDetails,
+    /// Appearance of the color on metal armor
+    pub metal: ColorDetails,
+    /// Appearance of the color on fur (only for some colors)
+    #[serde(default)]
+    pub fur: Option<ColorDetails>,
+    /// ID of the dye item that unlocks the color. Not present for colors
+    /// that are unlocked by default or cannot be obtained as an item
+    #[serde(default)]
+    pub item: Option<i32>,
+    /// Categories of the color: hue, material and rarity
+    #[serde(default)]
+    pub categories: Vec<String>
+}
+
+/// Appearance of a dye color on a given material
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ColorDetails {
+    /// Brightness
+    pub brightness: i32,
+    /// Contrast
+    pub contrast: f64,
+    /// Hue in HSL colorspace
+    pub hue: i32,
+    /// Saturation in HSL colorspace
+    pub saturation: f64,
+    /// Lightness in HSL colorspace
+    pub lightness: f64,
+    /// Precalculated RGB values
+    pub rgb: Vec<i32>
+}
+
+/// Continent of the world map
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Continent {
+    /// Continent ID
+    pub id: i32,
+    /// Localized name of the continent
+    pub name: String,
+    /// Width and height of the continent
+    pub continent_dims: Vec<i32>,
+    /// Minimum zoom level of the map tiles
+    pub min_zoom: i32,
+    /// Maximum zoom level of the map tiles
+    pub max_zoom: i32,
+    /// IDs of the floors available in the continent
+    pub floors: Vec<i32>
+}
+
+/// Floor of a continent
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ContinentFloor {
+    /// Floor ID
+    pub id: i32,
+    /// Width and height of the floor texture
+    pub texture_dims: Vec<i32>,
+    /// Rectangle of the floor that contains maps, if restricted
+    #[serde(default)]
+    pub clamped_view: Option<Vec<Vec<i32>>>,
+    /// Regions in the floor, indexed by region ID
+    #[serde(default)]
+    pub regions: HashMap<i32, ContinentRegion>
+}
+
+/// Map in a continent region
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ContinentMap {
+    /// Map ID
+    pub id: i32,
+    /// Localized name of the map
+    pub name: String,
+    /// Minimum level of the map
+    pub min_level: i32,
+    /// Maximum level of the map
+    pub max_level: i32,
+    /// Default floor of the map
+    pub default_floor: i32,
+    /// Coordinates of the map label
+    #[serde(default)]
+    pub label_coord: Option<Vec<f64>>,
+    /// Dimensions of the map, in map coordinates
+    pub map_rect: Vec<Vec<i32>>,
+    /// Dimensions of the map, in continent coordinates
+    pub continent_rect: Vec<Vec<i32>>,
+    /// Points of interest, vistas and waypoints, indexed by ID
+    #[serde(default)]
+    pub points_of_interest: HashMap<i32, ContinentPointOfInterest>,
+    /// Renown hearts, indexed by ID
+    #[serde(default)]
+    pub tasks: HashMap<i32, ContinentTask>,
+    /// Sectors (areas), indexed by ID
+    #[serde(default)]
+    pub sectors: HashMap<i32, ContinentSector>,
+    /// Hero challenges
+    #[serde(default)]
+    pub skill_challenges: Vec<ContinentSkillChallenge>
+}
+
+/// Point of interest, vista or waypoint of a map
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ContinentPointOfInterest {
+    /// Point of interest ID
+    pub id: i32,
+    /// Localized name, not present for vistas
+    #[serde(default)]
+    pub name: String,
+    /// Type (`landmark`, `waypoint`, `vista` or `unlock`)
+    #[serde(rename = "type")]
+    pub poi_type: String,
+    /// Floor the point of interest is in
+    pub floor: i32,
+    /// Coordinates of the point of interest
+    pub coord: Vec<f64>,
+    /// Chat link of the point of interest
+    #[serde(default)]
+    pub chat_link: String,
+    /// Icon URL, for `unlock` points of interest
+    #[serde(default)]
+    pub icon: Option<String>
+}
+
+/// Region of a continent floor
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ContinentRegion {
+    /// Region ID
+    pub id: i32,
+    /// Localized name of the region
+    pub name: String,
+    /// Coordinates of the region label
+    pub label_coord: Vec<f64>,
+    /// Dimensions of the region, in continent coordinates
+    pub continent_rect: Vec<Vec<i32>>,
+    /// Maps in the region, indexed by map ID
+    #[serde(default)]
+    pub maps: HashMap<i32, ContinentMap>
+}
+
+/// Sector (area) of a map
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ContinentSector {
+    /// Sector ID
+    pub id: i32,
+    /// Localized name of the sector
+    #[serde(default)]
+    pub name: String,
+    /// Level of the sector
+    pub level: i32,
+    /// Coordinates of the sector label
+    pub coord: Vec<f64>,
+    /// Polygon delimiting the sector
+    pub bounds: Vec<Vec<f64>>,
+    /// Chat link of the sector
+    #[serde(default)]
+    pub chat_link: String
+}
+
+/// Hero challenge of a map
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ContinentSkillChallenge {
+    /// Hero point ID, as listed in the hero points of a character. Not
+    /// present for some challenges
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Coordinates of the challenge
+    pub coord: Vec<f64>
+}
+
+/// Renown heart of a map
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ContinentTask {
+    /// Task ID
+    pub id: i32,
+    /// Localized objective of the task
+    pub objective: String,
+    /// Level of the task
+    pub level: i32,
+    /// Coordinates of the task
+    pub coord: Vec<f64>,
+    /// Polygon delimiting the task area
+    #[serde(default)]
+    pub bounds: Vec<Vec<f64>>,
+    /// Chat link of the task
+    #[serde(default)]
+    pub chat_link: String
 }
 
 /// A character's crafting discipline
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CraftingDiscipline {
     /// Name of the discipline
-    discipline: String,
+    pub discipline: String,
     /// Current crafting level for the given discipline and character
-    rating: i32,
+    pub rating: i32,
     /// Describes if the given discipline is currently active on the character
-    active: bool
+    pub active: bool
 }
 
 /// Daily achievement item
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DailyAchievement {
     /// Achievement ID
-    id: i32,
+    pub id: i32,
     /// Level requirement for the daily to appear
-    level: DailyAchievementLevel,
+    pub level: DailyAchievementLevel,
     /// Which Guild Wars 2 campaigns are required to see this daily achievement
-    required_access: Vec<String>
+    pub required_access: Vec<String>
 }
 
 /// Level range for the daily achievement
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DailyAchievementLevel {
     /// Minimum level. Any character below this level will not see the
     /// daily achievemtn
-    min: i32,
+    pub min: i32,
     /// Maximum level. Any character above this level will not see the
     /// daily achievemtn
-    max: i32
+    pub max: i32
 }
 
 /// Daily achievements
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DailyAchievements {
     /// PvE daily achievements
-    pve: Vec<DailyAchievement>,
+    pub pve: Vec<DailyAchievement>,
     /// PvP daily achievements
-    pvp: Vec<DailyAchievement>,
+    pub pvp: Vec<DailyAchievement>,
     /// WvW daily achievements
-    wvw: Vec<DailyAchievement>,
+    pub wvw: Vec<DailyAchievement>,
     /// Fractals daily achievements
-    fractals: Vec<DailyAchievement>,
+    pub fractals: Vec<DailyAchievement>,
     /// Special daily achievements
-    special: Vec<DailyAchievement>
+    pub special: Vec<DailyAchievement>
+}
+
+/// Guild emblem foreground or background
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EmblemLayers {
+    /// ID of the foreground or background, as used in `GuildEmblemLayer`
+    pub id: i32,
+    /// URLs of the images to composite, from bottom to top
+    pub layers: Vec<String>
 }
 
 /// Piece of equipment on a character
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Equipment {
     /// Item ID
-    id: i32,
+    pub id: i32,
     /// Equipment slot in which the item is slotted
-    slot: String,
+    pub slot: String,
     /// List of infusion item IDs on the piece of equipment
     #[serde(default)]
-    infusions: Vec<i32>,
+    pub infusions: Vec<i32>,
     /// List of upgrade component item IDs on the piece of equipment
     #[serde(default)]
-    upgrades: Vec<i32>,
+    pub upgrades: Vec<i32>,
     /// Skin ID for the given equipment piece
     #[serde(default)]
-    skin: i32,
+    pub skin: i32,
     /// Information on the stats chosen if the item offers an option for
     /// stats/prefix
     #[serde(default)]
-    stats: Option<EquipmentStats>,
+    pub stats: Option<EquipmentStats>,
     /// Describes which kind of binding the item has
     #[serde(default)]
-    binding: String,
+    pub binding: String,
     /// The amount of charges remaining on the item
     #[serde(default)]
-    charges: i32,
+    pub charges: i32,
     /// If bound, name of the character the item is bound to
     #[serde(default)]
-    bound_to: String,
+    pub bound_to: String,
     /// List of selected dyes for the piece. Values default to `None` if no
     /// dye is selected
     #[serde(default)]
-    dyes: Vec<Option<i32>>
+    pub dyes: Vec<Option<i32>>,
+    /// Where the item is stored (e.g. `Equipped` or `Armory`). Requires
+    /// schema version `2019-12-19T00:00:00Z` or later
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Equipment tabs the item is used in. Requires schema version
+    /// `2019-12-19T00:00:00Z` or later
+    #[serde(default)]
+    pub tabs: Vec<i32>
 }
 
 /// Summary of the stats on an item
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EquipmentAttributes {
     /// Amount of Power given
     #[serde(default)]
     #[serde(rename = "Power")]
-    power: i32,
+    pub power: i32,
     /// Amount of Precision given
     #[serde(default)]
     #[serde(rename = "Precision")]
-    precision: i32,
+    pub precision: i32,
     /// Amount of Toughness given
     #[serde(default)]
     #[serde(rename = "Toughness")]
-    toughness: i32,
+    pub toughness: i32,
     /// Amount of Vitality given
     #[serde(default)]
     #[serde(rename = "Vitality")]
-    vitality: i32,
+    pub vitality: i32,
     /// Amount of Condition Damage given
     #[serde(default)]
     #[serde(rename = "ConditionDamage")]
-    condition_damage: i32,
+    pub condition_damage: i32,
     /// Amount of Condition Duration given
     #[serde(default)]
     #[serde(rename = "ConditionDuration")]
-    condition_duration: i32,
+    pub condition_duration: i32,
     /// Amount of Critical Damage given
     #[serde(default)]
     #[serde(rename = "CritDamage")]
-    critical_damage: i32,
+    pub critical_damage: i32,
     /// Amount of Healing Power given
     #[serde(default)]
     #[serde(rename = "Healing")]
-    healing: i32,
+    pub healing: i32,
     /// Amount of Boon duration given
     #[serde(default)]
     #[serde(rename = "BoonDuration")]
-    boon_duration: i32
+    pub boon_duration: i32
 }
 
 /// Chosen stats of an equiped item
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EquipmentStats {
     /// Itemstat ID
-    id: i32,
+    pub id: i32,
     /// Summary of the stats on the item
     #[serde(default)]
-    attributes: Option<EquipmentAttributes>,
+    pub attributes: Option<EquipmentAttributes>,
+}
+
+/// Equipment template of a character
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EquipmentTab {
+    /// Index of the tab, starting at 1
+    pub tab: i32,
+    /// Name given to the tab
+    #[serde(default)]
+    pub name: String,
+    /// Whether this is the tab currently in use
+    pub is_active: bool,
+    /// Each piece of equipment in the tab
+    #[serde(default)]
+    pub equipment: Vec<Equipment>,
+    /// PvP equipment in the tab
+    #[serde(default)]
+    pub equipment_pvp: CharacterPvPEquipment
 }
 
 /// Details on currency exchange rate
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "commerce")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExchangeRate {
     /// Number of coins required for a single gem, or the number of coins
     /// obtained for a single gem
-    coins_per_gem: i32,
+    pub coins_per_gem: i32,
     /// Number of gems obtained for the specified quantity of coins, or the
     /// number of coins obtained for the specified quantity of gems
-    quantity: i32
+    pub quantity: i32
+}
+
+/// Guild details
+///
+/// Only the ID, name, tag and emblem are public; the rest of the details
+/// are only returned to the guild leader.
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Guild {
+    /// Guild ID
+    pub id: String,
+    /// Guild name
+    pub name: String,
+    /// Guild tag
+    pub tag: String,
+    /// Guild emblem (if any)
+    #[serde(default)]
+    pub emblem: Option<GuildEmblem>,
+    /// Guild level
+    #[serde(default)]
+    pub level: Option<i32>,
+    /// Message of the day
+    #[serde(default)]
+    pub motd: Option<String>,
+    /// Amount of influence
+    #[serde(default)]
+    pub influence: Option<i32>,
+    /// Amount of aetherium
+    #[serde(default)]
+    pub aetherium: Option<i32>,
+    /// Amount of favor
+    #[serde(default)]
+    pub favor: Option<i32>,
+    /// Amount of resonance
+    #[serde(default)]
+    pub resonance: Option<i32>,
+    /// Current number of members
+    #[serde(default)]
+    pub member_count: Option<i32>,
+    /// Maximum number of members
+    #[serde(default)]
+    pub member_capacity: Option<i32>
+}
+
+/// Guild emblem
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildEmblem {
+    /// Background of the emblem
+    pub background: GuildEmblemLayer,
+    /// Foreground of the emblem
+    pub foreground: GuildEmblemLayer,
+    /// Transformations applied to the emblem (e.g. FlipBackgroundHorizontal)
+    #[serde(default)]
+    pub flags: Vec<String>
+}
+
+/// Layer of a guild emblem
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildEmblemLayer {
+    /// ID of the background or foreground image
+    pub id: i32,
+    /// Dye color IDs applied to the layer
+    #[serde(default)]
+    pub colors: Vec<i32>
+}
+
+/// Event in the log of a guild
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(remote = "Self")]
+pub struct GuildLog {
+    /// ID of the log entry, increasing with time
+    pub id: i32,
+    /// Timestamp of the event
+    pub time: Timestamp,
+    /// Account name of the member that generated the event (if any)
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Details of the event, depending on its type
+    #[serde(flatten)]
+    pub entry: GuildLogEntry
+}
+
+#[cfg(feature = "guild")]
+known_fields_deserialize!(GuildLog, vec![
+    "id", "time", "user", "type", "invited_by", "declined_by", "kicked_by",
+    "changed_by", "old_rank", "new_rank", "item_id", "count", "operation",
+    "coins", "motd", "action", "upgrade_id", "recipe_id"
+]);
+
+/// Details of a guild log event, tagged by the `type` field
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GuildLogEntry {
+    /// `user` joined the guild
+    Joined,
+    /// `user` was invited to the guild
+    Invited {
+        /// Account name of the member that sent the invite
+        invited_by: String
+    },
+    /// `user` declined an invite to the guild
+    InviteDeclined {
+        /// Account name of the member that declined the invite, if it was
+        /// not `user`
+        #[serde(default)]
+        declined_by: Option<String>
+    },
+    /// `user` was kicked from the guild (or left if kicked by themselves)
+    Kick {
+        /// Account name of the member that kicked `user`
+        kicked_by: String
+    },
+    /// Rank of `user` was changed
+    RankChange {
+        /// Account name of the member that changed the rank (if any)
+        #[serde(default)]
+        changed_by: Option<String>,
+        /// Previous rank
+        old_rank: String,
+        /// New rank
+        new_rank: String
+    },
+    /// `user` deposited an item in the treasury
+    Treasury {
+        /// Item ID
+        item_id: i32,
+        /// Number of items deposited
+        count: i32
+    },
+    /// `user` deposited or withdrew items or coins from the stash
+    Stash {
+        /// `deposit`, `withdraw` or `move`
+        operation: String,
+        /// Item ID, `0` for coins
+        item_id: i32,
+        /// Number of items
+        count: i32,
+        /// Coins deposited or withdrawn
+        coins: i32
+    },
+    /// `user` changed the message of the day
+    Motd {
+        /// New message of the day
+        motd: String
+    },
+    /// Guild upgrade was queued, cancelled, completed or sped up
+    Upgrade {
+        /// `queued`, `cancelled`, `completed` or `sped_up`
+        action: String,
+        /// ID of the guild upgrade
+        upgrade_id: i32,
+        /// Recipe ID, for upgrades that are crafted
+        #[serde(default)]
+        recipe_id: Option<i32>,
+        /// Number of upgrades completed
+        #[serde(default)]
+        count: Option<i32>
+    },
+    /// Event type not covered by the variants above
+    #[serde(other)]
+    Unknown
+}
+
+/// Section (tab) of a guild's stash
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildStashSection {
+    /// ID of the guild upgrade that granted access to the section
+    pub upgrade_id: i32,
+    /// Number of slots in the section
+    pub size: i32,
+    /// Coins stored in the section
+    pub coins: i32,
+    /// Description set for the section
+    #[serde(default)]
+    pub note: String,
+    /// Contents of the section. Empty slots are `None`
+    pub inventory: Vec<Option<GuildStashSlot>>
+}
+
+/// Occupied slot in a guild stash section
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildStashSlot {
+    /// Item ID
+    pub id: i32,
+    /// Number of items in the slot
+    pub count: i32
+}
+
+/// Item in a guild's storage (decorations and other guild hall items)
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildStorageItem {
+    /// Guild upgrade ID of the item
+    pub id: i32,
+    /// Number of items stored
+    pub count: i32
+}
+
+/// PvP team of a guild
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct GuildTeam {
+    /// ID of the team within the guild
+    pub id: i32,
+    /// Members of the team
+    pub members: Vec<GuildTeamMember>,
+    /// Name of the team
+    pub name: String,
+    /// Results of all the matches played by the team
+    pub aggregate: PvPWinLoss,
+    /// Results per ladder (e.g. `ranked`, `unranked`)
+    #[serde(default)]
+    pub ladders: HashMap<String, PvPWinLoss>,
+    /// Most recent matches played by the team
+    #[serde(default)]
+    pub games: Vec<PvPGame>,
+    /// Results per PvP season
+    #[serde(default)]
+    pub seasons: Vec<GuildTeamSeason>
+}
+
+/// Member of a guild PvP team
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildTeamMember {
+    /// Account name of the member
+    pub name: String,
+    /// Role of the member in the team (`Captain` or `Member`)
+    pub role: String
+}
+
+/// Results of a guild PvP team in a season
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildTeamSeason {
+    /// PvP season ID
+    pub id: String,
+    /// Matches won
+    pub wins: i32,
+    /// Matches lost
+    pub losses: i32,
+    /// Rating of the team at the end of the season
+    pub rating: i32
+}
+
+/// Item in a guild's treasury
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildTreasuryItem {
+    /// Item ID
+    pub item_id: i32,
+    /// Number of items currently in the treasury
+    pub count: i32,
+    /// Upgrades that require this item
+    pub needed_by: Vec<GuildTreasuryNeed>
+}
+
+/// Guild upgrade requiring an item from the treasury
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildTreasuryNeed {
+    /// ID of the guild upgrade
+    pub upgrade_id: i32,
+    /// Total number of items required by the upgrade
+    pub count: i32
+}
+
+/// Guild upgrade definition
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(remote = "Self")]
+pub struct GuildUpgrade {
+    /// Upgrade ID
+    pub id: i32,
+    /// Localized name of the upgrade
+    pub name: String,
+    /// Localized description of the upgrade
+    #[serde(default)]
+    pub description: String,
+    /// URL of the upgrade icon
+    #[serde(default)]
+    pub icon: String,
+    /// Time it takes to build the upgrade, in minutes
+    #[serde(default)]
+    pub build_time: i32,
+    /// Guild level required to build the upgrade
+    #[serde(default)]
+    pub required_level: i32,
+    /// Guild experience granted when the upgrade is completed
+    #[serde(default)]
+    pub experience: i32,
+    /// IDs of the upgrades required before this one can be built
+    #[serde(default)]
+    pub prerequisites: Vec<i32>,
+    /// Costs of building the upgrade
+    #[serde(default)]
+    pub costs: Vec<GuildUpgradeCost>,
+    /// Type of the upgrade and its type-specific details
+    #[serde(flatten)]
+    pub kind: GuildUpgradeKind
+}
+
+#[cfg(feature = "guild")]
+known_fields_deserialize!(GuildUpgrade, vec![
+    "id", "name", "description", "icon", "build_time", "required_level",
+    "experience", "prerequisites", "costs", "type", "bag_max_items",
+    "bag_max_coins"
+]);
+
+/// Cost of building a guild upgrade
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildUpgradeCost {
+    /// Type of the cost (`Item`, `Collectible`, `Currency` or `Coins`)
+    #[serde(rename = "type")]
+    pub cost_type: String,
+    /// Name of the item or currency
+    #[serde(default)]
+    pub name: String,
+    /// Amount required
+    pub count: i32,
+    /// Item ID, for `Item` and `Collectible` costs
+    #[serde(default)]
+    pub item_id: Option<i32>
+}
+
+/// Type of a guild upgrade, tagged by the `type` field
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type")]
+pub enum GuildUpgradeKind {
+    /// Influence-like currency accumulated by the guild
+    AccumulatingCurrency,
+    /// Additional section of the guild stash
+    BankBag {
+        /// Number of slots in the section
+        bag_max_items: i32,
+        /// Maximum number of coins that can be stored in the section
+        bag_max_coins: i32
+    },
+    /// Temporary boost for guild members
+    Boost,
+    /// Reward that can be claimed by guild members
+    Claimable,
+    /// Consumable (e.g. banners)
+    Consumable,
+    /// Guild hall decoration
+    Decoration,
+    /// Guild hall claim
+    GuildHall,
+    /// Expedition to claim a guild hall
+    GuildHallExpedition,
+    /// Guild hall hub facility
+    Hub,
+    /// Upgrade queue
+    Queue,
+    /// Permanent unlock (e.g. arena, workshop levels)
+    Unlock,
+    /// Upgrade type not covered by the variants above
+    #[serde(other)]
+    Unknown
+}
+
+/// List of IDs returned by an endpoint
+///
+/// Deserialized from the plain JSON array returned by the API. IDs can be
+/// split in batches to feed them to the bulk fetchers (e.g.
+/// `get_achievements()`), which accept up to `MAX_BULK_IDS` IDs per request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdList<T> {
+    /// IDs returned by the API
+    pub ids: Vec<T>
+}
+
+/// Maximum number of IDs accepted by the API in a single bulk request
+pub const MAX_BULK_IDS: usize = 200;
+
+impl<T> IdList<T> {
+    /// Create a new list from the given IDs
+    pub fn new(ids: Vec<T>) -> IdList<T> {
+        IdList {
+            ids: ids
+        }
+    }
+
+    /// Total number of IDs in the list
+    pub fn count(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Obtain the IDs in the list
+    pub fn ids(&self) -> &[T] {
+        &self.ids
+    }
+
+    /// Consume the list and obtain the inner vector of IDs
+    pub fn into_ids(self) -> Vec<T> {
+        self.ids
+    }
+}
+
+impl<T> IdList<T> where T: Clone {
+    /// Split the IDs in batches of at most `size` elements
+    ///
+    /// Each batch can be passed directly to the bulk fetcher of the endpoint.
+    /// A `size` of `0` or greater than `MAX_BULK_IDS` is clamped to
+    /// `MAX_BULK_IDS`.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Maximum number of IDs per batch
+    pub fn batches(&self, size: usize) -> Vec<Vec<T>> {
+        let size = if size == 0 || size > MAX_BULK_IDS {
+            MAX_BULK_IDS
+        } else {
+            size
+        };
+
+        self.ids.chunks(size).map(|chunk| chunk.to_vec()).collect()
+    }
+}
+
+impl IdList<String> {
+    /// Split the IDs in batches of string slices
+    ///
+    /// Same as `batches()`, but borrowing the IDs so that they can be fed to
+    /// the bulk fetchers that expect `Vec<&str>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Maximum number of IDs per batch
+    pub fn str_batches(&self, size: usize) -> Vec<Vec<&str>> {
+        let ids: Vec<&str> = self.ids.iter().map(|id| id.as_str()).collect();
+        IdList::new(ids).batches(size)
+    }
+}
+
+impl<T> IntoIterator for IdList<T> {
+    type Item = T;
+    type IntoIter = ::std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ids.into_iter()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for IdList<T> where T: Deserialize<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<IdList<T>, D::Error>
+        where D: Deserializer<'de> {
+        Vec::deserialize(deserializer).map(IdList::new)
+    }
 }
 
 /// Shared inventory slot
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "account")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct InventorySlot {
     /// Item ID
-    id: i32,
+    pub id: i32,
     /// Number of this item in the stack
-    count: i32,
+    pub count: i32,
     /// Scope of the inventory slot
     #[serde(default)]
-    binding: String
+    pub binding: String
+}
+
+/// Item details
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Item {
+    /// Item ID
+    pub id: i32,
+    /// Chat link code
+    pub chat_link: String,
+    /// Item name
+    pub name: String,
+    /// URL to the item icon
+    #[serde(default)]
+    pub icon: String,
+    /// Item description
+    #[serde(default)]
+    pub description: String,
+    /// Item type (e.g. Armor, Consumable, CraftingMaterial, UpgradeComponent)
+    #[serde(rename = "type")]
+    pub item_type: String,
+    /// Item rarity (e.g. Junk, Basic, Fine, Masterwork, Rare, Exotic)
+    pub rarity: String,
+    /// Required level
+    pub level: i32,
+    /// Value in coins when selling to a vendor
+    pub vendor_value: i32,
+    /// Default skin ID (if any)
+    #[serde(default)]
+    pub default_skin: Option<i32>,
+    /// Flags applying to the item (e.g. AccountBound, NoSell,
+    /// SoulbindOnAcquire)
+    #[serde(default)]
+    pub flags: Vec<String>,
+    /// Game types in which the item is usable (e.g. Activity, Pve, Wvw)
+    #[serde(default)]
+    pub game_types: Vec<String>,
+    /// Races or professions that can use the item
+    #[serde(default)]
+    pub restrictions: Vec<String>
+}
+
+#[cfg(feature = "static")]
+impl Item {
+    /// Check whether the item has the given flag
+    ///
+    /// # Arguments
+    ///
+    /// * `flag` - Name of the flag (e.g. `AccountBound`)
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+
+    /// Check whether the item is bound to the account or a character as soon
+    /// as it is acquired, and thus cannot be traded
+    pub fn is_bound_on_acquire(&self) -> bool {
+        self.has_flag("AccountBound") || self.has_flag("SoulbindOnAcquire")
+    }
+}
+
+/// Combination of attributes that can be selected for an item
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemStat {
+    /// Itemstat ID
+    pub id: i32,
+    /// Localized name of the combination. May be empty
+    pub name: String,
+    /// Attributes given by the combination
+    pub attributes: Vec<ItemStatAttribute>
+}
+
+/// Attribute given by an itemstat combination
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemStatAttribute {
+    /// Name of the attribute (e.g. `Power` or `CritDamage`)
+    pub attribute: String,
+    /// Multiplier applied to the attribute points of the item
+    pub multiplier: f64,
+    /// Flat amount added to the attribute
+    pub value: i32
 }
 
 /// Revenant legend details
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Legend {
     /// Legend ID
-    id: String,
+    pub id: String,
     /// ID of the profession skill
-    swap: i32,
+    pub swap: i32,
     /// ID of the heal skill
-    heal: i32,
+    pub heal: i32,
     /// ID of the elite skill
-    elite: i32,
+    pub elite: i32,
     /// List of IDs of the utility skills
-    utilities: Vec<i32>
+    pub utilities: Vec<i32>
 }
 
 /// Mastery details
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Mastery {
     /// ID of the mastery
-    id: i32,
+    pub id: i32,
     /// Name of the selected mastery
-    name: String,
+    pub name: String,
     /// Written out requirements to unlock the mastery track
-    requirement: String,
+    pub requirement: String,
     /// Order in which the mastery track appears in a list
-    order: i32,
+    pub order: i32,
     /// Background URI for the mastery track
-    background: String,
+    pub background: String,
     /// In-game region in which the mastery track belongs
-    region: String,
+    pub region: String,
     /// Information of each mastery level
-    levels: Vec<MasteryLevel>
+    pub levels: Vec<MasteryLevel>
 }
 
 /// Information on mastery levels
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MasteryLevel {
     /// Name for the given mastery
-    name: String,
+    pub name: String,
     /// In-game description for the given mastery
-    description: String,
+    pub description: String,
     /// In-game instructions for the given mastery
-    instruction: String,
+    pub instruction: String,
     /// Icon URI for the mastery
-    icon: String,
+    pub icon: String,
     /// Amount of mastery points required to unlock the mastery
-    point_cost: i32,
+    pub point_cost: i32,
     /// Total amount of experience needed to train the given mastery level.
     /// This total is non-cumulative between levels
-    exp_cost: i32
+    pub exp_cost: i32
 }
 
 /// Outfit information
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Outfit {
     /// ID of the outfit
-    id: i32,
+    pub id: i32,
     /// Name of the outfit
-    name: String,
+    pub name: String,
     /// Icon URI of the selected outfit
-    icon: String,
+    pub icon: String,
     /// Item IDs which unlock this outfit
-    unlock_items: Vec<i32>
+    pub unlock_items: Vec<i32>
 }
 
 /// Pet information
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Pet {
     /// Pet ID
-    id: i32,
+    pub id: i32,
     /// Pet name
-    name: String,
+    pub name: String,
     /// Pet description
-    description: String,
+    pub description: String,
     /// Icon URI for the pet
-    icon: String,
+    pub icon: String,
     /// Skills of the pet
-    skills: Vec<PetSkill>
+    pub skills: Vec<PetSkill>
 }
 
 /// Pet skill details
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PetSkill {
     /// ID of the skill
-    id: i32
+    pub id: i32
 }
 
 /// Details on the given profession
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct Profession {
     /// Profession ID
-    id: String,
+    pub id: String,
     /// Name of the profession
-    name: String,
+    pub name: String,
     /// Icon URI for the profession
-    icon: String,
+    pub icon: String,
     /// Large icon URI for the profession
-    icon_big: String,
+    pub icon_big: String,
     /// List of specialization IDs
-    specializations: Vec<i32>,
+    pub specializations: Vec<i32>,
     /// List of training details
-    training: Vec<ProfessionTraining>,
+    pub training: Vec<ProfessionTraining>,
     /// Specific flags for the profession (NoRacialSkills, NoWeaponSwap)
     #[serde(default)]
-    flags: Vec<String>,
+    pub flags: Vec<String>,
     /// Skills available to the profession
-    skills: Vec<ProfessionSkill>,
+    pub skills: Vec<ProfessionSkill>,
     /// Weapon and weapon skills available to the profession
-    weapons: HashMap<String, ProfessionWeapon>
+    pub weapons: HashMap<String, ProfessionWeapon>
 }
 
 /// Class skills available to the profession
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProfessionSkill {
     /// ID of the skill
-    id: i32,
+    pub id: i32,
     /// Where the skill can be equipped
-    slot: String,
+    pub slot: String,
     /// Type of skill
     #[serde(rename = "type")]
-    skill_type: String
+    pub skill_type: String
 }
 
 /// Details on training for a given profession
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProfessionTraining {
     /// ID of the item type indicated by `category`
-    id: i32,
+    pub id: i32,
     /// Category of the training object, may be:
     /// Skills, Specializations, EliteSpecializations
-    category: String,
+    pub category: String,
     /// Name of the skill or specialization
-    name: String,
+    pub name: String,
     /// Track item details
-    track: Vec<ProfessionTrainingItem>
+    pub track: Vec<ProfessionTrainingItem>
 }
 
 /// Skills and traits belonging to a specific training track
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProfessionTrainingItem {
     /// Cost to train this item
-    cost: i32,
+    pub cost: i32,
     /// Type of item, either a skill or a trait
     #[serde(rename = "type")]
-    item_type: String,
+    pub item_type: String,
     /// Skill ID (only if type is "Skill")
     #[serde(default)]
-    skill_id: i32,
+    pub skill_id: i32,
     /// Trait ID (only if type is "Trait")
     #[serde(default)]
-    trait_id: i32
+    pub trait_id: i32
 }
 
 /// Weapon details for a given profession
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProfessionWeapon {
     /// ID of the required specialization to use this weapon
     #[serde(default)]
-    specialization: i32,
+    pub specialization: i32,
     /// List of weapon skills
-    skills: Vec<ProfessionWeaponSkill>,
+    pub skills: Vec<ProfessionWeaponSkill>,
     /// Where the weapon can be equipped
-    flags: Vec<String>
+    pub flags: Vec<String>
 }
 
 /// Weapon skills available to a profession
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProfessionWeaponSkill {
     /// ID of the skill
-    id: i32,
+    pub id: i32,
     /// Skill bar slot that this skill can be used in
-    slot: String,
+    pub slot: String,
     /// Offhand weapon type this skill requires to be equipped
     #[serde(default)]
-    offhand: String,
+    pub offhand: String,
     /// Elementalist attunement that this skill requires
     #[serde(default)]
-    attunement: String,
+    pub attunement: String,
     /// Name of the class the skill was stolen from (for Thief)
     #[serde(default)]
-    source: String
+    pub source: String
+}
+
+/// PvP match
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PvPGame {
+    /// ID of the match
+    pub id: String,
+    /// Map ID
+    pub map_id: i32,
+    /// Time at which the match started
+    pub started: Timestamp,
+    /// Time at which the match ended
+    pub ended: Timestamp,
+    /// Result of the match (e.g. `Victory`, `Defeat`, `Forfeit`)
+    pub result: String,
+    /// Team the player or guild team played in (`Red` or `Blue`)
+    pub team: String,
+    /// Profession played, not present for guild team matches
+    #[serde(default)]
+    pub profession: Option<String>,
+    /// Final scores of both teams
+    pub scores: PvPGameScores,
+    /// Type of rating of the match (e.g. `Ranked`, `Unranked`, `None`)
+    pub rating_type: String,
+    /// Change in rating caused by the match, for ranked matches
+    #[serde(default)]
+    pub rating_change: Option<i32>,
+    /// PvP season ID, for matches played during a season
+    #[serde(default)]
+    pub season: Option<String>
+}
+
+/// Final scores of a PvP match
+#[cfg(feature = "guild")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PvPGameScores {
+    /// Score of the red team
+    pub red: i32,
+    /// Score of the blue team
+    pub blue: i32
+}
+
+/// Entry of a PvP season leaderboard
+#[cfg(feature = "pvp")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PvPLeaderboardEntry {
+    /// Account name, or guild name for guild leaderboards
+    #[serde(default)]
+    pub name: String,
+    /// Rank in the leaderboard
+    pub rank: i32,
+    /// Guild ID, for guild leaderboards
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Team name, for team leaderboards
+    #[serde(default)]
+    pub team: Option<String>,
+    /// Team ID, for team leaderboards
+    #[serde(default)]
+    pub team_id: Option<i32>,
+    /// Date at which the player reached this rank
+    pub date: Timestamp,
+    /// Scores of the entry, as described in the season's leaderboard
+    /// settings
+    #[serde(default)]
+    pub scores: Vec<PvPLeaderboardScore>
+}
+
+/// Score of a PvP leaderboard entry
+#[cfg(feature = "pvp")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PvPLeaderboardScore {
+    /// ID of the score, as listed in the season's leaderboard settings
+    pub id: String,
+    /// Value of the score
+    pub value: i32
+}
+
+/// PvP statistics of an account
+#[cfg(feature = "pvp")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct PvPStats {
+    /// Current PvP rank
+    pub pvp_rank: i32,
+    /// Points earned towards the next rank
+    pub pvp_rank_points: i32,
+    /// Number of times the account has gone past the maximum rank
+    #[serde(default)]
+    pub pvp_rank_rollovers: i32,
+    /// Results of all the matches played
+    pub aggregate: PvPWinLoss,
+    /// Results per profession, keyed by profession name in lowercase
+    #[serde(default)]
+    pub professions: HashMap<String, PvPWinLoss>,
+    /// Results per ladder (e.g. `ranked`, `unranked`)
+    #[serde(default)]
+    pub ladders: HashMap<String, PvPWinLoss>
+}
+
+/// Aggregated results of PvP matches
+#[cfg(any(feature = "guild", feature = "pvp"))]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PvPWinLoss {
+    /// Matches won
+    #[serde(default)]
+    pub wins: i32,
+    /// Matches lost
+    #[serde(default)]
+    pub losses: i32,
+    /// Matches left before finishing
+    #[serde(default)]
+    pub desertions: i32,
+    /// Matches won due to the opposing team lacking players
+    #[serde(default)]
+    pub byes: i32,
+    /// Matches lost due to the own team lacking players
+    #[serde(default)]
+    pub forfeits: i32
+}
+
+/// Story quest
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Quest {
+    /// Quest ID
+    pub id: i32,
+    /// Localized name of the quest
+    pub name: String,
+    /// Minimum level required to start the quest
+    pub level: i32,
+    /// ID of the story the quest belongs to
+    pub story: i32,
+    /// Objectives of the quest, in order
+    #[serde(default)]
+    pub goals: Vec<QuestGoal>
+}
+
+/// Objective of a story quest
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuestGoal {
+    /// Text shown while the goal is in progress
+    pub active: String,
+    /// Text shown once the goal has been completed
+    pub complete: String
 }
 
 /// Playable race details
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Race {
     /// ID of the race
-    id: String,
+    pub id: String,
     /// Localized name of the race
-    name: String,
+    pub name: String,
     /// Racial skill IDs
-    skills: Vec<i32>
+    pub skills: Vec<i32>
 }
 
 /// Character progress in Super Adventure Box
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SABProgress {
     /// Describes which worlds, and in which difficulty, have been cleared
     #[serde(default)]
-    zones: Vec<SABZone>,
+    pub zones: Vec<SABZone>,
     /// Describes the unlocks on the given character
     #[serde(default)]
-    unlocks: Vec<SABUnlock>,
+    pub unlocks: Vec<SABUnlock>,
     /// Unlocked songs on the character
     #[serde(default)]
-    songs: Vec<SABSong>
+    pub songs: Vec<SABSong>
 }
 
 /// Specifies unlocked songs on the character
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SABSong {
     /// ID of the song
-    id: i32,
+    pub id: i32,
     /// Name of the song
-    name: String
+    pub name: String
 }
 
 /// Specifies unlocks on a character
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SABUnlock {
     /// ID of the unlock
-    id: i32,
+    pub id: i32,
     /// Name of the upgrade
-    name: String
+    pub name: String
 }
 
 /// Specifies which worlds, and in which difficulty, a character has cleared
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "characters")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SABZone {
     /// World ID
-    id: i32,
+    pub id: i32,
     /// Difficulty mode cleared
-    mode: String,
+    pub mode: String,
     /// World number
-    world: i32,
+    pub world: i32,
     /// Zone number
-    zone: i32
+    pub zone: i32
 }
 
 /// Skill usable by players in the game
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct Skill {
     /// Skill ID
-    id: i32,
-    name: String,
+    pub id: i32,
+    pub name: String,
     #[serde(default)]
-    description: String,
-    icon: String,
-    chat_link: String,
+    pub description: String,
+    pub icon: String,
+    pub chat_link: String,
     /// Skill type (Bundle, Elite, Heal, Profession, Utility, Weapon)
     #[serde(rename = "type")]
-    skill_type: String,
+    pub skill_type: String,
     /// Weapon the skill is on. Can be "None" if not applicable
-    weapon_type: String,
+    pub weapon_type: String,
     /// Professions that can use this skill
-    professions: Vec<String>,
+    pub professions: Vec<String>,
     /// Slot in which the skill fits into
     /// (Downed_[1-4], Pet, Profession_[1-5], Utility, Weapon_[1-5])
-    slot: String,
+    pub slot: String,
     /// Skill facts that describe the skill's effect
     #[serde(default)]
-    facts: Vec<SkillFact>,
+    pub facts: Vec<SkillFact>,
     /// Skill facts that may apply to the skill depending on the trait choices
     #[serde(default)]
-    traited_facts: Vec<SkillTraitedFact>,
+    pub traited_facts: Vec<SkillTraitedFact>,
 }
 
 /// Skill fact that describes the skill's effect
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct SkillFact {
-    text: String,
+    pub text: String,
     #[serde(default)]
-    icon: String,
+    pub icon: String,
     /// Defines additional fields of the object, can be:
     /// AttributeAdjust, Buff, ComboField, ComboFinisher,
     /// Damage, Distance, Duration, Heal, HealingADjust, NoData, Number,
     /// Percent, PrefixedBuff, Radius, Range, Recharge, Time, Unblockable
     #[serde(rename = "type")]
-    fact_type: String,
+    pub fact_type: String,
 
     // AttributeAdjust, Number, Range, Recharge, Unblockable
-    //TODO check Unblockable, it is boolean
     /// Amount that `target` gets adjusted, based on a level 80 character
     /// stats, or the number value as referenced by `text`, or the range of
-    /// the trait/skill, or the recharge time in seconds, or true if type
+    /// the trait/skill, or the recharge time in seconds, or `1` if type
     /// is "Unblockable"
-    #[serde(default)]
-    value: Option<i32>,
+    #[serde(default, deserialize_with = "compat::number_or_bool")]
+    pub value: Option<i32>,
 
     // AttributeAdjust
     /// Attribute this fact adjusts. A value of "Healing" indicates the fact
     /// is a heal, and Ferocity is encoded as "CritDamage"
     #[serde(default)]
-    target: Option<String>,
+    pub target: Option<String>,
 
     // Buff, PrefixedBuff
     /// Boon, condition, or effect referred to by the fact
     #[serde(default)]
-    status: Option<String>,
+    pub status: Option<String>,
     /// Description of status effect if any
     #[serde(default)]
-    description: Option<String>,
+    pub description: Option<String>,
     /// Number of stacks applied
     #[serde(default)]
-    apply_count: Option<i32>,
+    pub apply_count: Option<i32>,
 
     // Buff, Duration, PrefixedBuff, Time
     /// Duration of the effect in seconds, or the time value in seconds
     #[serde(default)]
-    duration: Option<i32>,
+    pub duration: Option<i32>,
 
     // ComboField
     /// Type of field (Air, Dark, Fire, Ice, Light, Lightning, Posion, Smoke,
     /// Ethereal, Water)
     #[serde(default)]
-    field_type: Option<String>,
+    pub field_type: Option<String>,
 
     // ComboFinisher
     /// Type of finisher (Blast, Leap, Projectile, Whirl)
     #[serde(default)]
-    finisher_type: Option<String>,
+    pub finisher_type: Option<String>,
 
     // ComboFinisher, Percent
     /// Percent chance that the finisher will trigger or the percentage value
     /// as referenced by `text`
     #[serde(default)]
-    percent: Option<i32>,
+    pub percent: Option<i32>,
 
     // Damage, Heal, HealingAdjust
     /// Amount of times the damage hits or number of times the heal is applied
     #[serde(default)]
-    hit_count: Option<i32>,
+    pub hit_count: Option<i32>,
 
     /// Damage multiplier value of the skill
     #[serde(default)]
-    dmg_multiplier: Option<f32>,
+    pub dmg_multiplier: Option<f32>,
 
     // Distance, Radius
     /// Distance value or radius value
     #[serde(default)]
-    distance: Option<i32>,
+    pub distance: Option<i32>,
 
     // PrefixedBuff
     /// Icon to show before the fact
     #[serde(default)]
-    prefix: Option<SkillFactPrefix>,
+    pub prefix: Option<SkillFactPrefix>,
+}
+
+#[cfg(feature = "static")]
+impl SkillFact {
+    /// Duration of the effect, for `Buff`, `Duration`, `PrefixedBuff` and
+    /// `Time` facts
+    #[cfg(feature = "chrono")]
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration.map(|seconds| Duration::seconds(seconds as i64))
+    }
+
+    /// Raw duration in seconds, as returned by the API
+    pub fn duration_seconds(&self) -> Option<i32> {
+        self.duration
+    }
+
+    /// Recharge time, for `Recharge` facts
+    #[cfg(feature = "chrono")]
+    pub fn recharge(&self) -> Option<Duration> {
+        self.recharge_seconds()
+            .map(|seconds| Duration::seconds(seconds as i64))
+    }
+
+    /// Raw recharge time in seconds, as returned by the API
+    pub fn recharge_seconds(&self) -> Option<i32> {
+        if self.fact_type == "Recharge" {
+            self.value
+        } else {
+            None
+        }
+    }
 }
 
 /// Icon to show before skill fact
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SkillFactPrefix {
-    text: String,
-    icon: String,
-    status: String,
-    description: String
+    pub text: String,
+    pub icon: String,
+    pub status: String,
+    pub description: String
 }
 
 /// Skill fact that describes the skill's effect, based on selected traits
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(remote = "Self")]
 pub struct SkillTraitedFact {
-    text: String,
-    #[serde(default)]
-    icon: String,
-    /// Defines additional fields of the object, can be:
-    /// AttributeAdjust, Buff, ComboField, ComboFinisher, Damage, Distance,
-    /// Duration, Heal, HealingADjust, NoData, Number, Percent, PrefixedBuff,
-    /// Radius, Range, Recharge, Time, Unblockable
-    #[serde(rename = "type")]
-    fact_type: String,
-
     /// Which trait has to be selected in order for this fact to take effect
-    requires_trait: i32,
+    pub requires_trait: i32,
     /// Array index of the facts object this fact overrides, if the trait
     /// specified in `requires_trait` is selected. If this field is omitted,
     /// then the fact contained within this object is to be appended to the
     /// existing `facts` array
     #[serde(default)]
-    overrides: Option<i32>,
-
-    // AttributeAdjust, Number, Range, Recharge, Unblockable
-    //TODO check Unblockable, it is boolean
-    /// Amount that `target` gets adjusted, based on a level 80 character
-    /// stats, or the number value as referenced by `text`, or the range of
-    /// the trait/skill, or the recharge time in seconds, or true if type
-    /// is "Unblockable"
-    #[serde(default)]
-    value: Option<i32>,
-
-    // AttributeAdjust
-    /// Attribute this fact adjusts. A value of "Healing" indicates the fact
-    /// is a heal, and Ferocity is encoded as "CritDamage"
-    #[serde(default)]
-    target: Option<String>,
-
-    // Buff, PrefixedBuff
-    /// Boon, condition, or effect referred to by the fact
-    #[serde(default)]
-    status: Option<String>,
-    /// Description of status effect if any
-    #[serde(default)]
-    description: Option<String>,
-    /// Number of stacks applied
-    #[serde(default)]
-    apply_count: Option<i32>,
-
-    // Buff, Duration, PrefixedBuff, Time
-    /// Duration of the effect in seconds, or the time value in seconds
-    #[serde(default)]
-    duration: Option<i32>,
-
-    // ComboField
-    /// Type of field (Air, Dark, Fire, Ice, Light, Lightning, Posion, Smoke,
-    /// Ethereal, Water)
-    #[serde(default)]
-    field_type: Option<String>,
-
-    // ComboFinisher
-    /// Type of finisher (Blast, Leap, Projectile, Whirl)
-    #[serde(default)]
-    finisher_type: Option<String>,
-
-    // ComboFinisher, Percent
-    /// Percent chance that the finisher will trigger or the percentage value
-    /// as referenced by `text`
-    #[serde(default)]
-    percent: Option<i32>,
-
-    // Damage, Heal, HealingAdjust
-    /// Amount of times the damage hits or number of times the heal is applied
-    #[serde(default)]
-    hit_count: Option<i32>,
-
-    /// Damage multiplier value of the skill
-    #[serde(default)]
-    dmg_multiplier: Option<f32>,
-
-    // Distance, Radius
-    /// Distance value or radius value
-    #[serde(default)]
-    distance: Option<i32>,
-
-    // PrefixedBuff
-    /// Icon to show before the fact
-    #[serde(default)]
-    prefix: Option<SkillFactPrefix>,
+    pub overrides: Option<i32>,
+    /// Fact applied when the trait is selected
+    #[serde(flatten)]
+    pub fact: SkillFact
 }
 
+#[cfg(feature = "static")]
+known_fields_deserialize!(SkillTraitedFact, {
+    let mut fields = vec!["requires_trait", "overrides"];
+    fields.extend_from_slice(compat::struct_fields::<SkillFact>());
+    fields
+});
+
 /// Specialization details
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Specialization {
     /// Specialization ID
-    id: i32,
+    pub id: i32,
     /// Name of the specialization
-    name: String,
+    pub name: String,
     /// Profession that this specialization belongs to
-    profession: String,
+    pub profession: String,
     /// Whether this is an elite specialization
-    elite: bool,
+    pub elite: bool,
     /// URI to the icon of the specialization
-    icon: String,
+    pub icon: String,
     /// URI to the background of the specialization
-    background: String,
+    pub background: String,
     /// IDs of minor traits in the specialization
-    minor_traits: Vec<i32>,
+    pub minor_traits: Vec<i32>,
     /// IDs of major traits in the specialization
-    major_traits: Vec<i32>
+    pub major_traits: Vec<i32>
+}
+
+/// Story of a story season
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Story {
+    /// Story ID
+    pub id: i32,
+    /// ID of the season the story belongs to
+    pub season: String,
+    /// Localized name of the story
+    pub name: String,
+    /// Localized description of the story
+    #[serde(default)]
+    pub description: String,
+    /// In-game date of the story
+    #[serde(default)]
+    pub timeline: String,
+    /// Minimum level required to start the story
+    pub level: i32,
+    /// Position of the story within its season
+    pub order: i32,
+    /// Chapters of the story
+    #[serde(default)]
+    pub chapters: Vec<StoryChapter>,
+    /// Races the story is restricted to. Empty if available to all races
+    #[serde(default)]
+    pub races: Vec<String>,
+    /// Additional flags (e.g. `RequiresUnlock`)
+    #[serde(default)]
+    pub flags: Vec<String>
+}
+
+#[cfg(feature = "static")]
+impl Story {
+    /// Check whether a character can play the story
+    ///
+    /// # Arguments
+    ///
+    /// * `race` - Race of the character (e.g. `Norn`)
+    /// * `level` - Level of the character
+    pub fn is_available_to(&self, race: &str, level: i32) -> bool {
+        self.is_available_to_race(race) && level >= self.level
+    }
+
+    /// Check whether the story can be played by characters of a race
+    ///
+    /// # Arguments
+    ///
+    /// * `race` - Race of the character (e.g. `Norn`)
+    pub fn is_available_to_race(&self, race: &str) -> bool {
+        self.races.is_empty() || self.races.iter().any(|r| r == race)
+    }
+
+    /// Names of the chapters of the story, in order
+    pub fn chapter_names(&self) -> Vec<&str> {
+        self.chapters.iter().map(|c| c.name.as_str()).collect()
+    }
+}
+
+/// Chapter of a story
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StoryChapter {
+    /// Localized name of the chapter
+    pub name: String
+}
+
+/// Season of the story journal (personal story or a Living World season)
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StorySeason {
+    /// Season ID
+    pub id: String,
+    /// Localized name of the season
+    pub name: String,
+    /// Position of the season in the story journal
+    pub order: i32,
+    /// IDs of the stories in the season
+    #[serde(default)]
+    pub stories: Vec<i32>
+}
+
+/// Subtoken created from an API key
+#[cfg(feature = "account")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Subtoken {
+    /// Token to use in authenticated requests
+    pub subtoken: String
+}
+
+#[cfg(feature = "account")]
+impl Subtoken {
+    /// Obtain the token to use in authenticated requests
+    pub fn token(&self) -> &str {
+        &self.subtoken
+    }
 }
 
 /// Item listed in the trading post
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "commerce")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TPItem {
     /// Item ID
-    id: i32,
+    pub id: i32,
     /// A list of all buy listings, ascending from lowest buy order
     #[serde(default)]
-    buys: Vec<TPItemListing>,
+    pub buys: Vec<TPItemListing>,
     /// A list of all sell listings, ascending from lowest sell offer
     #[serde(default)]
-    sells: Vec<TPItemListing>
+    pub sells: Vec<TPItemListing>
 }
 
 /// Information about an item in the trading post
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "commerce")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TPItemInfo {
     /// Number ID
-    id: i32,
+    pub id: i32,
     /// Whether a free to play account can purchase or sell the item in the
     /// trading post
     #[serde(default)]
-    whitelisted: bool,
+    pub whitelisted: bool,
     /// Buy information
-    buys: TPItemInfoPrice,
+    pub buys: TPItemInfoPrice,
     /// Sell information
-    sells: TPItemInfoPrice
+    pub sells: TPItemInfoPrice
 }
 
 /// Price information on an item
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "commerce")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TPItemInfoPrice {
     /// Highest buy order or lowest sell offer price in coins
-    unit_price: i32,
+    pub unit_price: i32,
     /// Amount of items being sold/bought
-    quantity: i32
+    pub quantity: i32
 }
 
 /// Trading post item listing details
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "commerce")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TPItemListing {
     /// Number of individual listings this object refers to (e.g. two players
     /// selling at the same price will end up in the same listing)
-    listings: i32,
+    pub listings: i32,
     /// Sell offer or buy order price in coins
-    unit_price: i32,
+    pub unit_price: i32,
     /// Amount of items being sold/bought in this listing
-    quantity: i32
+    pub quantity: i32
 }
 
 /// Trading post transactions for an account
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "commerce")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TPTransaction {
     /// ID of the transaction
-    id: i64,
+    pub id: i64,
     /// Item ID
-    item_id: i32,
+    pub item_id: i32,
     /// Price of the item in coins
-    price: i32,
+    pub price: i32,
     /// Quantity of the item
-    quantity: i32,
+    pub quantity: i32,
     /// Date of creation of the transaction
-    created: DateTime<Utc>,
+    pub created: Timestamp,
     /// Date of purchase (only for past transactions)
-    purchased: Option<DateTime<Utc>>
+    pub purchased: Option<Timestamp>
 }
 
 /// Trait details
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct Trait {
     /// ID of the trait
-    id: i32,
+    pub id: i32,
     /// Name of the trait
-    name: String,
+    pub name: String,
     /// Icon URL of the trait
-    icon: String,
+    pub icon: String,
     /// Description of the trait
-    description: String,
+    pub description: String,
     /// ID of the specialization this trait belongs to
-    specialization: i32,
+    pub specialization: i32,
     /// Trait's tier (Adept, Master, Grandmaster) in a scale 0-3
-    tier: i32,
+    pub tier: i32,
     /// Either "Major" or "Minor" depending on the trait's slot
-    slot: String,
+    pub slot: String,
     #[serde(default)]
-    facts: Vec<TraitFact>,
+    pub facts: Vec<TraitFact>,
     #[serde(default)]
-    traited_facts: Vec<TraitTraitedFact>,
+    pub traited_facts: Vec<TraitTraitedFact>,
     #[serde(default)]
-    skills: Vec<Skill>
+    pub skills: Vec<Skill>
 }
 
 /// Trait fact that describes the trait's effect
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TraitFact {
-    text: String,
+    pub text: String,
     #[serde(default)]
-    icon: String,
+    pub icon: String,
     /// Defines additional fields of the object, can be:
     /// AttributeAdjust, Buff, BuffConversion ComboField, ComboFinisher,
     /// Damage, Distance, Duration, Heal, HealingADjust, NoData, Number,
     /// Percent, PrefixedBuff, Radius, Range, Recharge, Time, Unblockable
     #[serde(rename = "type")]
-    fact_type: String,
+    pub fact_type: String,
 
     // AttributeAdjust, Number, Range, Recharge, Unblockable
-    //TODO check Unblockable, it is boolean
     /// Amount that `target` gets adjusted, based on a level 80 character
     /// stats, or the number value as referenced by `text`, or the range of
-    /// the trait/skill, or the recharge time in seconds, or true if type
+    /// the trait/skill, or the recharge time in seconds, or `1` if type
     /// is "Unblockable"
-    #[serde(default)]
-    value: Option<i32>,
+    #[serde(default, deserialize_with = "compat::number_or_bool")]
+    pub value: Option<i32>,
 
     // AttributeAdjust, BuffConversion
     /// Attribute this fact adjusts. A value of "Healing" indicates the fact
     /// is a heal, and Ferocity is encoded as "CritDamage"
     #[serde(default)]
-    target: Option<String>,
+    pub target: Option<String>,
 
     // Buff, PrefixedBuff
     /// Boon, condition, or effect referred to by the fact
     #[serde(default)]
-    status: Option<String>,
+    pub status: Option<String>,
     /// Description of status effect if any
     #[serde(default)]
-    description: Option<String>,
+    pub description: Option<String>,
     /// Number of stacks applied
     #[serde(default)]
-    apply_count: Option<i32>,
+    pub apply_count: Option<i32>,
 
     // Buff, Duration, PrefixedBuff, Time
     /// Duration of the effect in seconds, or the time value in seconds
     #[serde(default)]
-    duration: Option<i32>,
+    pub duration: Option<i32>,
 
     // BuffConversion
     /// Attribute that is used to calculate the attribute gain
     #[serde(default)]
-    source: Option<String>,
+    pub source: Option<String>,
 
     // ComboField
     /// Type of field (Air, Dark, Fire, Ice, Light, Lightning, Posion, Smoke,
     /// Ethereal, Water)
     #[serde(default)]
-    field_type: Option<String>,
+    pub field_type: Option<String>,
 
     // ComboFinisher
     /// Type of finisher (Blast, Leap, Projectile, Whirl)
     #[serde(default)]
-    finisher_type: Option<String>,
+    pub finisher_type: Option<String>,
 
     // ComboFinisher, Percent
     /// Percent chance that the finisher will trigger or the percentage value
     /// as referenced by `text`
     #[serde(default)]
-    percent: Option<i32>,
+    pub percent: Option<i32>,
 
     // Damage, Heal, HealingAdjust
     /// Amount of times the damage hits or number of times the heal is applied
     #[serde(default)]
-    hit_count: Option<i32>,
+    pub hit_count: Option<i32>,
 
     // Distance, Radius
     /// Distance value or radius value
     #[serde(default)]
-    distance: Option<i32>,
+    pub distance: Option<i32>,
 
     // PrefixedBuff
     /// Icon to show before the fact
     #[serde(default)]
-    prefix: Option<SkillFactPrefix>,
+    pub prefix: Option<SkillFactPrefix>,
+}
+
+#[cfg(feature = "static")]
+impl TraitFact {
+    /// Duration of the effect, for `Buff`, `Duration`, `PrefixedBuff` and
+    /// `Time` facts
+    #[cfg(feature = "chrono")]
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration.map(|seconds| Duration::seconds(seconds as i64))
+    }
+
+    /// Raw duration in seconds, as returned by the API
+    pub fn duration_seconds(&self) -> Option<i32> {
+        self.duration
+    }
+
+    /// Recharge time, for `Recharge` facts
+    #[cfg(feature = "chrono")]
+    pub fn recharge(&self) -> Option<Duration> {
+        self.recharge_seconds()
+            .map(|seconds| Duration::seconds(seconds as i64))
+    }
+
+    /// Raw recharge time in seconds, as returned by the API
+    pub fn recharge_seconds(&self) -> Option<i32> {
+        if self.fact_type == "Recharge" {
+            self.value
+        } else {
+            None
+        }
+    }
 }
 
 /// Trait fact that describes the trait's effect, based on selected traits
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "static")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(remote = "Self")]
 pub struct TraitTraitedFact {
-    text: String,
-    #[serde(default)]
-    icon: String,
-    /// Defines additional fields of the object, can be:
-    /// AttributeAdjust, Buff, BuffConversion ComboField, ComboFinisher,
-    /// Damage, Distance, Duration, Heal, HealingADjust, NoData, Number,
-    /// Percent, PrefixedBuff, Radius, Range, Recharge, Time, Unblockable
-    #[serde(rename = "type")]
-    fact_type: String,
-
     /// Which trait has to be selected in order for this fact to take effect
-    requires_trait: i32,
+    pub requires_trait: i32,
     /// Array index of the facts object this fact overrides, if the trait
     /// specified in `requires_trait` is selected. If this field is omitted,
     /// then the fact contained within this object is to be appended to the
     /// existing `facts` array
     #[serde(default)]
-    overrides: Option<i32>,
-
-    // AttributeAdjust, Number, Range, Recharge, Unblockable
-    //TODO check Unblockable, it is boolean
-    /// Amount that `target` gets adjusted, based on a level 80 character
-    /// stats, or the number value as referenced by `text`, or the range of
-    /// the trait/skill, or the recharge time in seconds, or true if type
-    /// is "Unblockable"
-    #[serde(default)]
-    value: Option<i32>,
-
-    // AttributeAdjust, BuffConversion
-    /// Attribute this fact adjusts. A value of "Healing" indicates the fact
-    /// is a heal, and Ferocity is encoded as "CritDamage"
-    #[serde(default)]
-    target: Option<String>,
-
-    // Buff, PrefixedBuff
-    /// Boon, condition, or effect referred to by the fact
-    #[serde(default)]
-    status: Option<String>,
-    /// Description of status effect if any
-    #[serde(default)]
-    description: Option<String>,
-    /// Number of stacks applied
-    #[serde(default)]
-    apply_count: Option<i32>,
-
-    // Buff, Duration, PrefixedBuff, Time
-    /// Duration of the effect in seconds, or the time value in seconds
-    #[serde(default)]
-    duration: Option<i32>,
-
-    // BuffConversion
-    /// Attribute that is used to calculate the attribute gain
-    #[serde(default)]
-    source: Option<String>,
-
-    // ComboField
-    /// Type of field (Air, Dark, Fire, Ice, Light, Lightning, Posion, Smoke,
-    /// Ethereal, Water)
-    #[serde(default)]
-    field_type: Option<String>,
-
-    // ComboFinisher
-    /// Type of finisher (Blast, Leap, Projectile, Whirl)
-    #[serde(default)]
-    finisher_type: Option<String>,
-
-    // ComboFinisher, Percent
-    /// Percent chance that the finisher will trigger or the percentage value
-    /// as referenced by `text`
-    #[serde(default)]
-    percent: Option<i32>,
-
-    // Damage, Heal, HealingAdjust
-    /// Amount of times the damage hits or number of times the heal is applied
-    #[serde(default)]
-    hit_count: Option<i32>,
-
-    // Distance, Radius
-    /// Distance value or radius value
-    #[serde(default)]
-    distance: Option<i32>,
-
-    // PrefixedBuff
-    /// Icon to show before the fact
-    #[serde(default)]
-    prefix: Option<SkillFactPrefix>,
+    pub overrides: Option<i32>,
+    /// Fact applied when the trait is selected
+    #[serde(flatten)]
+    pub fact: TraitFact
+}
+
+#[cfg(feature = "static")]
+known_fields_deserialize!(TraitTraitedFact, {
+    let mut fields = vec!["requires_trait", "overrides"];
+    fields.extend_from_slice(compat::struct_fields::<TraitFact>());
+    fields
+});
+
+/// WvW ability that can be trained with World XP
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWAbility {
+    /// Ability ID
+    pub id: i32,
+    /// Ability name
+    pub name: String,
+    /// Ability description
+    #[serde(default)]
+    pub description: String,
+    /// URL to the ability icon
+    #[serde(default)]
+    pub icon: String,
+    /// Ranks of the ability, in training order
+    #[serde(default)]
+    pub ranks: Vec<WvWAbilityRank>
+}
+
+/// Rank of a WvW ability
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWAbilityRank {
+    /// Ability points needed to train this rank
+    pub cost: i32,
+    /// Effect of the ability at this rank
+    pub effect: String
+}
+
+/// Current World vs World matchup
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWMatch {
+    /// Match ID, made of the region and tier (e.g. `2-1`)
+    pub id: String,
+    /// Time the match started at
+    pub start_time: Timestamp,
+    /// Time the match ends at
+    pub end_time: Timestamp,
+    /// War score of each team
+    pub scores: WvWTeamValues,
+    /// Main world of each team
+    pub worlds: WvWTeamValues,
+    /// Every world (including linked worlds) of each team
+    pub all_worlds: WvWTeamWorlds,
+    /// Deaths of each team
+    pub deaths: WvWTeamValues,
+    /// Kills of each team
+    pub kills: WvWTeamValues,
+    /// Victory points of each team, which decide the matchup
+    #[serde(default)]
+    pub victory_points: WvWTeamValues,
+    /// Skirmishes played so far, in order
+    #[serde(default)]
+    pub skirmishes: Vec<WvWSkirmish>,
+    /// State of each map of the match
+    #[serde(default)]
+    pub maps: Vec<WvWMatchMap>
+}
+
+/// Map bonus held by a team in a WvW match
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWMatchBonus {
+    /// Bonus type (e.g. `Bloodlust`)
+    #[serde(rename = "type")]
+    pub bonus_type: String,
+    /// Team holding the bonus (`Red`, `Green` or `Blue`)
+    pub owner: String
+}
+
+/// State of a map in a WvW match
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWMatchMap {
+    /// Map ID
+    pub id: i32,
+    /// Map type (e.g. `Center`, `RedHome`)
+    #[serde(rename = "type")]
+    pub map_type: String,
+    /// War score of each team in the map
+    pub scores: WvWTeamValues,
+    /// Bonuses currently active in the map
+    #[serde(default)]
+    pub bonuses: Vec<WvWMatchBonus>,
+    /// Objectives of the map
+    #[serde(default)]
+    pub objectives: Vec<WvWMatchObjective>,
+    /// Deaths of each team in the map
+    pub deaths: WvWTeamValues,
+    /// Kills of each team in the map
+    pub kills: WvWTeamValues
+}
+
+/// State of an objective in a WvW match
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWMatchObjective {
+    /// Objective ID
+    pub id: String,
+    /// Objective type (e.g. `Camp`, `Keep`)
+    #[serde(rename = "type")]
+    pub objective_type: String,
+    /// Team holding the objective (`Red`, `Green`, `Blue` or `Neutral`)
+    pub owner: String,
+    /// Time the objective was last captured at
+    pub last_flipped: Timestamp,
+    /// ID of the guild that claimed the objective, if any
+    #[serde(default)]
+    pub claimed_by: Option<String>,
+    /// Time the objective was claimed at, if claimed
+    #[serde(default)]
+    pub claimed_at: Option<Timestamp>,
+    /// War score given to the owner on every tick
+    pub points_tick: i32,
+    /// War score given for capturing the objective
+    pub points_capture: i32,
+    /// IDs of the guild upgrades slotted in the objective
+    #[serde(default)]
+    pub guild_upgrades: Vec<i32>,
+    /// Dolyaks delivered to the objective
+    #[serde(default)]
+    pub yaks_delivered: i32
+}
+
+/// WvW rank title
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWRank {
+    /// Rank ID
+    pub id: i32,
+    /// Title given at this rank
+    pub title: String,
+    /// Minimum WvW rank needed for the title
+    pub min_rank: i32
+}
+
+/// Skirmish of a WvW match
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWSkirmish {
+    /// Skirmish number, starting at 1
+    pub id: i32,
+    /// War score of each team in the skirmish
+    pub scores: WvWTeamValues,
+    /// War score of each team per map
+    #[serde(default)]
+    pub map_scores: Vec<WvWSkirmishMapScores>
+}
+
+/// War score of each team in a map during a skirmish
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWSkirmishMapScores {
+    /// Map type (e.g. `Center`, `RedHome`)
+    #[serde(rename = "type")]
+    pub map_type: String,
+    /// War score of each team
+    pub scores: WvWTeamValues
+}
+
+/// Value of each team in a WvW match
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct WvWTeamValues {
+    /// Value of the red team
+    pub red: i32,
+    /// Value of the blue team
+    pub blue: i32,
+    /// Value of the green team
+    pub green: i32
+}
+
+/// Worlds of each team in a WvW match
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct WvWTeamWorlds {
+    /// Worlds of the red team
+    pub red: Vec<i32>,
+    /// Worlds of the blue team
+    pub blue: Vec<i32>,
+    /// Worlds of the green team
+    pub green: Vec<i32>
+}
+
+/// Upgrades that can be applied to a WvW objective
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWUpgrade {
+    /// Upgrade ID
+    pub id: i32,
+    /// Tiers of the upgrade, in unlock order
+    #[serde(default)]
+    pub tiers: Vec<WvWUpgradeTier>
+}
+
+/// Single effect unlocked by a WvW objective upgrade tier
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWUpgradeEffect {
+    /// Effect name
+    pub name: String,
+    /// Effect description
+    #[serde(default)]
+    pub description: String,
+    /// URL to the effect icon
+    #[serde(default)]
+    pub icon: String
+}
+
+/// Tier of a WvW objective upgrade
+#[cfg(feature = "wvw")]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WvWUpgradeTier {
+    /// Tier name
+    pub name: String,
+    /// Number of dolyaks needed to reach this tier
+    pub yaks_required: i32,
+    /// Effects unlocked at this tier
+    #[serde(default)]
+    pub upgrades: Vec<WvWUpgradeEffect>
+}
+
+#[cfg(test)]
+mod tests {
+    use api_v2::types::{IdList, MAX_BULK_IDS};
+
+    #[test]
+    fn batches() {
+        let empty: IdList<i32> = IdList::new(Vec::new());
+        assert!(empty.batches(0).is_empty());
+
+        let full = IdList::new((0..200).collect::<Vec<i32>>());
+        let batches = full.batches(0);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), MAX_BULK_IDS);
+
+        let over = IdList::new((0..201).collect::<Vec<i32>>());
+        let batches = over.batches(0);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1], vec![200]);
+
+        assert_eq!(over.batches(500).len(), 2);
+        assert_eq!(over.batches(100).len(), 3);
+    }
+
+    #[test]
+    fn str_batches() {
+        let ids: Vec<String> = (0..201).map(|id| id.to_string()).collect();
+        let list = IdList::new(ids);
+
+        let batches = list.str_batches(0);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), MAX_BULK_IDS);
+        assert_eq!(batches[1], vec!["200"]);
+
+        let empty: IdList<String> = IdList::new(Vec::new());
+        assert!(empty.str_batches(10).is_empty());
+    }
 }
@@ -0,0 +1,398 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// World vs World endpoints
+
+use client::APIClient;
+use common::{APIError, Page, number_to_param};
+use api_v2::endpoint::{get_all, get_ids, get_many, get_one, get_page};
+use api_v2::types::{
+    IdList,
+    WvWAbility,
+    WvWMatch,
+    WvWRank,
+    WvWUpgrade
+};
+
+/// Obtain a list of all available WvW ability IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_wvw_ability_ids(
+    client: &APIClient
+) -> Result<IdList<i32>, APIError> {
+    get_ids::<WvWAbility>(client)
+}
+
+/// Obtain details for the specified WvW ability
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_wvw_ability(
+    client: &APIClient,
+    id: i32
+) -> Result<WvWAbility, APIError> {
+    get_one::<WvWAbility>(client, id)
+}
+
+/// Obtain details for the specified WvW abilities
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_wvw_abilities(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<WvWAbility>, APIError> {
+    get_many::<WvWAbility>(client, &ids)
+}
+
+/// Obtain details for all WvW abilities
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_wvw_abilities(
+    client: &APIClient
+) -> Result<Vec<WvWAbility>, APIError> {
+    get_all::<WvWAbility>(client)
+}
+
+/// Obtain a page of WvW abilities
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_wvw_abilities_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<WvWAbility>, APIError> {
+    get_page::<WvWAbility>(client, page, page_size)
+}
+
+/// Obtain a list of all current WvW match IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_wvw_match_ids(
+    client: &APIClient
+) -> Result<IdList<String>, APIError> {
+    get_ids::<WvWMatch>(client)
+}
+
+/// Obtain details for the specified WvW match
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_wvw_match(
+    client: &APIClient,
+    id: &str
+) -> Result<WvWMatch, APIError> {
+    get_one::<WvWMatch>(client, id.to_string())
+}
+
+/// Obtain details for the specified WvW matches
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_wvw_matches(
+    client: &APIClient,
+    ids: Vec<&str>
+) -> Result<Vec<WvWMatch>, APIError> {
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    get_many::<WvWMatch>(client, &ids)
+}
+
+/// Obtain details for all current WvW matches
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_wvw_matches(
+    client: &APIClient
+) -> Result<Vec<WvWMatch>, APIError> {
+    get_all::<WvWMatch>(client)
+}
+
+/// Obtain the current WvW match of a world
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `world` - ID of the world, e.g. the one of `Account::world`
+pub fn get_wvw_match_by_world(
+    client: &APIClient,
+    world: i32
+) -> Result<WvWMatch, APIError> {
+    client.get(
+        &format!("/v2/wvw/matches?{}", number_to_param("world", world)),
+        false
+    )
+}
+
+/// Obtain a list of all available WvW rank IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_wvw_rank_ids(
+    client: &APIClient
+) -> Result<IdList<i32>, APIError> {
+    get_ids::<WvWRank>(client)
+}
+
+/// Obtain details for the specified WvW rank
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_wvw_rank(
+    client: &APIClient,
+    id: i32
+) -> Result<WvWRank, APIError> {
+    get_one::<WvWRank>(client, id)
+}
+
+/// Obtain details for the specified WvW ranks
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_wvw_ranks(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<WvWRank>, APIError> {
+    get_many::<WvWRank>(client, &ids)
+}
+
+/// Obtain details for all WvW ranks
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_wvw_ranks(client: &APIClient) -> Result<Vec<WvWRank>, APIError> {
+    get_all::<WvWRank>(client)
+}
+
+/// Obtain a page of WvW ranks
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_wvw_ranks_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<WvWRank>, APIError> {
+    get_page::<WvWRank>(client, page, page_size)
+}
+
+/// Obtain a list of all available WvW objective upgrade IDs
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_wvw_upgrade_ids(
+    client: &APIClient
+) -> Result<IdList<i32>, APIError> {
+    get_ids::<WvWUpgrade>(client)
+}
+
+/// Obtain details for the specified WvW objective upgrade
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID to fetch from the server
+pub fn get_wvw_upgrade(
+    client: &APIClient,
+    id: i32
+) -> Result<WvWUpgrade, APIError> {
+    get_one::<WvWUpgrade>(client, id)
+}
+
+/// Obtain details for the specified WvW objective upgrades
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - IDs to fetch from the server
+pub fn get_wvw_upgrades(
+    client: &APIClient,
+    ids: Vec<i32>
+) -> Result<Vec<WvWUpgrade>, APIError> {
+    get_many::<WvWUpgrade>(client, &ids)
+}
+
+/// Obtain details for all WvW objective upgrades
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn get_all_wvw_upgrades(
+    client: &APIClient
+) -> Result<Vec<WvWUpgrade>, APIError> {
+    get_all::<WvWUpgrade>(client)
+}
+
+/// Obtain a page of WvW objective upgrades
+///
+/// Use `Page::has_next()` to know whether more pages are available.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of entries per page (max: 200)
+pub fn get_wvw_upgrades_page(
+    client: &APIClient,
+    page: i32,
+    page_size: i32
+) -> Result<Page<WvWUpgrade>, APIError> {
+    get_page::<WvWUpgrade>(client, page, page_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use fixtures::test_client;
+    use api_v2::wvw::*;
+
+    macro_rules! parse_test {
+        ($result:expr) => {
+            match $result {
+                Ok(_) => assert!(true),
+                Err(e) => panic!(e.description().to_string()),
+            };
+        }
+    }
+
+    #[test]
+    fn wvw_ability_ids() {
+        let client = test_client();
+        let result = get_wvw_ability_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_ability() {
+        let client = test_client();
+        let result = get_wvw_ability(&client, 2);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_abilities() {
+        let client = test_client();
+        let result = get_wvw_abilities(&client, vec![2, 3]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_match_ids() {
+        let client = test_client();
+        let result = get_wvw_match_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_match() {
+        let client = test_client();
+        let result = get_wvw_match(&client, "2-1");
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_matches() {
+        let client = test_client();
+        let result = get_wvw_matches(&client, vec!["1-1", "2-1"]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_match_by_world() {
+        let client = test_client();
+        let result = get_wvw_match_by_world(&client, 2003);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_rank_ids() {
+        let client = test_client();
+        let result = get_wvw_rank_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_rank() {
+        let client = test_client();
+        let result = get_wvw_rank(&client, 1);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_ranks() {
+        let client = test_client();
+        let result = get_wvw_ranks(&client, vec![1, 2]);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_upgrade_ids() {
+        let client = test_client();
+        let result = get_wvw_upgrade_ids(&client);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_upgrade() {
+        let client = test_client();
+        let result = get_wvw_upgrade(&client, 1);
+        parse_test!(result);
+    }
+
+    #[test]
+    fn wvw_upgrades() {
+        let client = test_client();
+        let result = get_wvw_upgrades(&client, vec![1, 2]);
+        parse_test!(result);
+    }
+}
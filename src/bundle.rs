@@ -0,0 +1,173 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Concurrent fetching of unrelated endpoints
+/// A failure in one of the calls does not affect the rest
+
+use std::any::Any;
+use std::thread;
+
+use client::APIClient;
+use common::APIError;
+
+
+/// Set of endpoint calls that can be fetched together
+///
+/// Implemented for tuples of up to 8 calls. Each call is a closure taking
+/// the client and returning the result of an endpoint function, so the
+/// calls may return different types.
+pub trait Bundle {
+    /// Tuple with the result of each call, in the same order
+    type Output;
+
+    /// Perform all the calls concurrently
+    fn fetch(self, client: &APIClient) -> Self::Output;
+}
+
+/// Convert the payload of a panicked call into an error
+pub(crate) fn panic_error(payload: Box<dyn Any + Send>) -> APIError {
+    let message = match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "unknown error".to_string()
+        }
+    };
+
+    APIError::new(format!("request failed: {}", message).as_str())
+}
+
+macro_rules! impl_bundle {
+    ($($call:ident $result:ident $var:ident $index:tt),+) => {
+        impl<$($call, $result),+> Bundle for ($($call,)+)
+            where $(
+                $call: FnOnce(&APIClient) -> Result<$result, APIError> + Send,
+                $result: Send
+            ),+ {
+            type Output = ($(Result<$result, APIError>,)+);
+
+            fn fetch(self, client: &APIClient) -> Self::Output {
+                let ($($var,)+) = self;
+
+                thread::scope(|scope| {
+                    let handles = ($(scope.spawn(move || $var(client)),)+);
+
+                    ($(
+                        handles.$index.join().unwrap_or_else(|payload| {
+                            Err(panic_error(payload))
+                        }),
+                    )+)
+                })
+            }
+        }
+    }
+}
+
+impl_bundle!(A RA a 0);
+impl_bundle!(A RA a 0, B RB b 1);
+impl_bundle!(A RA a 0, B RB b 1, C RC c 2);
+impl_bundle!(A RA a 0, B RB b 1, C RC c 2, D RD d 3);
+impl_bundle!(A RA a 0, B RB b 1, C RC c 2, D RD d 3, E RE e 4);
+impl_bundle!(A RA a 0, B RB b 1, C RC c 2, D RD d 3, E RE e 4, F RF f 5);
+impl_bundle!(
+    A RA a 0, B RB b 1, C RC c 2, D RD d 3, E RE e 4, F RF f 5, G RG g 6
+);
+impl_bundle!(
+    A RA a 0, B RB b 1, C RC c 2, D RD d 3, E RE e 4, F RF f 5, G RG g 6,
+    H RH h 7
+);
+
+/// Perform a set of endpoint calls concurrently
+///
+/// Each call runs in its own thread and its result is returned separately,
+/// so that an endpoint being down (or a call panicking) only affects that
+/// call. Panics are reported as an `APIError`.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `requests` - Tuple of closures performing each call
+///
+/// # Example
+///
+/// ```
+/// use tyria::bundle::try_fetch_bundle;
+/// use tyria::client::APIClient;
+/// use tyria::api_v2::account::{get_account, get_account_wallet};
+///
+/// let client = APIClient::new("en", Some("token".to_string()));
+/// let (account, wallet) = try_fetch_bundle(&client, (
+///     |c: &APIClient| get_account(c),
+///     |c: &APIClient| get_account_wallet(c)
+/// ));
+/// ```
+pub fn try_fetch_bundle<B: Bundle>(
+    client: &APIClient,
+    requests: B
+) -> B::Output {
+    requests.fetch(client)
+}
+
+#[cfg(test)]
+mod tests {
+    use client::APIClient;
+    use common::APIError;
+    #[cfg(feature = "account")]
+    use api_v2::account::{get_account, get_account_wallet};
+    use bundle::*;
+
+    #[test]
+    fn partial_failure() {
+        let client = APIClient::new("en", None);
+
+        let (first, second, third) = try_fetch_bundle(&client, (
+            |_: &APIClient| Ok(1),
+            |_: &APIClient| -> Result<String, APIError> {
+                Err(APIError::new("endpoint is down"))
+            },
+            |_: &APIClient| -> Result<bool, APIError> {
+                panic!("connection reset")
+            }
+        ));
+
+        assert_eq!(first.unwrap(), 1);
+        assert_eq!(second.unwrap_err().description(), "endpoint is down");
+        assert_eq!(
+            third.unwrap_err().description(),
+            "request failed: connection reset"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "account")]
+    fn unauthenticated_bundle() {
+        let client = APIClient::new("en", None);
+
+        let (account, wallet) = try_fetch_bundle(&client, (
+            |c: &APIClient| get_account(c),
+            |c: &APIClient| get_account_wallet(c)
+        ));
+
+        assert!(account.is_err());
+        assert!(wallet.is_err());
+    }
+}
@@ -0,0 +1,382 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Cache of API responses
+/// Responses are kept in a `Storage` backend, so the cache can be shared
+/// between runs and shipped pre-warmed in a single archive
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json;
+
+use storage::Storage;
+
+
+/// Namespace of the storage where responses are kept
+pub const CACHE_NAMESPACE: &'static str = "cache";
+
+/// Version of the archive format written by `Cache::export()`
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Cached response
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CacheEntry {
+    /// Time the response was stored, in seconds since the Unix epoch
+    pub stored_at: u64,
+    /// Body of the response
+    pub body: String,
+    /// Time the response is considered fresh, in seconds. When `None`, the
+    /// TTL of the cache applies
+    #[serde(default)]
+    pub ttl: Option<u64>
+}
+
+/// Cached response within an archive
+#[derive(Serialize, Deserialize, Debug)]
+struct ArchiveEntry {
+    /// Cache key of the response
+    key: String,
+    /// Cached response
+    #[serde(flatten)]
+    entry: CacheEntry
+}
+
+/// Single file containing every cached response
+#[derive(Serialize, Deserialize, Debug)]
+struct Archive {
+    /// Version of the archive format
+    version: u32,
+    /// Time the archive was written, in seconds since the Unix epoch
+    exported_at: u64,
+    /// Cached responses
+    entries: Vec<ArchiveEntry>
+}
+
+/// Cache of API responses
+///
+/// Only responses to requests that do not require authentication are
+/// cached. Cloning the cache is cheap, clones share the same storage.
+#[derive(Clone)]
+pub struct Cache {
+    /// Storage the responses are kept in
+    storage: Arc<dyn Storage>,
+    /// Time a response is considered fresh
+    ttl: Duration
+}
+
+/// Obtain the current time in seconds since the Unix epoch
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// Obtain the error returned for malformed data
+fn invalid_data<E: ToString>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
+
+impl Cache {
+    /// Create a new cache
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - Storage to keep the responses in
+    /// * `ttl` - Time a response is considered fresh
+    pub fn new(storage: Arc<dyn Storage>, ttl: Duration) -> Cache {
+        Cache {
+            storage: storage,
+            ttl: ttl
+        }
+    }
+
+    /// Obtain the time a response is considered fresh
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Obtain a cached response, regardless of its age
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Cache key of the response
+    pub fn entry(&self, key: &str) -> io::Result<Option<CacheEntry>> {
+        match self.storage.get(CACHE_NAMESPACE, key)? {
+            Some(value) => serde_json::from_slice(&value)
+                .map(Some)
+                .map_err(invalid_data),
+            None => Ok(None)
+        }
+    }
+
+    /// Obtain the body of a cached response, if it is still fresh
+    ///
+    /// Responses stored with their own TTL use it instead of the TTL of the
+    /// cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Cache key of the response
+    pub fn get(&self, key: &str) -> io::Result<Option<String>> {
+        let now = now();
+        let ttl = self.ttl.as_secs();
+
+        Ok(self.entry(key)?
+            .filter(|entry| now.saturating_sub(entry.stored_at)
+                    < entry.ttl.unwrap_or(ttl))
+            .map(|entry| entry.body))
+    }
+
+    /// Obtain the time a cached response remains fresh
+    ///
+    /// Returns a zero duration for stale responses and `None` for responses
+    /// that are not cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Cache key of the response
+    pub fn time_to_live(&self, key: &str) -> io::Result<Option<Duration>> {
+        let now = now();
+        let ttl = self.ttl.as_secs();
+
+        Ok(self.entry(key)?.map(|entry| {
+            let expires_at = entry.stored_at
+                .saturating_add(entry.ttl.unwrap_or(ttl));
+            Duration::from_secs(expires_at.saturating_sub(now))
+        }))
+    }
+
+    /// Mark every cached response as stale
+    ///
+    /// The responses are kept, so that they can still be exported, but
+    /// `get()` no longer returns them. Returns the number of responses
+    /// marked.
+    pub fn expire_all(&self) -> io::Result<usize> {
+        let mut expired = 0;
+
+        for key in self.storage.list(CACHE_NAMESPACE)? {
+            if let Some(mut entry) = self.entry(&key)? {
+                entry.ttl = Some(0);
+                self.put_entry(&key, &entry)?;
+                expired += 1;
+            }
+        }
+
+        Ok(expired)
+    }
+
+    /// Store the body of a response
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Cache key of the response
+    /// * `body` - Body of the response
+    pub fn put(&self, key: &str, body: &str) -> io::Result<()> {
+        self.put_with_ttl(key, body, None)
+    }
+
+    /// Store the body of a response along with its own TTL
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Cache key of the response
+    /// * `body` - Body of the response
+    /// * `ttl` - Time the response is considered fresh, or `None` to use
+    ///     the TTL of the cache
+    pub fn put_with_ttl(
+        &self,
+        key: &str,
+        body: &str,
+        ttl: Option<Duration>
+    ) -> io::Result<()> {
+        self.put_entry(key, &CacheEntry {
+            stored_at: now(),
+            body: body.to_string(),
+            ttl: ttl.map(|ttl| ttl.as_secs())
+        })
+    }
+
+    /// Store a response, keeping its original time
+    fn put_entry(&self, key: &str, entry: &CacheEntry) -> io::Result<()> {
+        let value = serde_json::to_vec(entry).map_err(invalid_data)?;
+        self.storage.put(CACHE_NAMESPACE, key, &value)
+    }
+
+    /// Write every cached response to a single archive file
+    ///
+    /// The archive is a JSON document containing the format version, the
+    /// time of the export and each response with its key and the time it
+    /// was stored. Returns the number of responses written.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - File to write the archive to
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> io::Result<usize> {
+        let mut entries = Vec::new();
+
+        for key in self.storage.list(CACHE_NAMESPACE)? {
+            if let Some(entry) = self.entry(&key)? {
+                entries.push(ArchiveEntry {
+                    key: key,
+                    entry: entry
+                });
+            }
+        }
+
+        let archive = Archive {
+            version: ARCHIVE_VERSION,
+            exported_at: now(),
+            entries: entries
+        };
+        let data = serde_json::to_vec(&archive).map_err(invalid_data)?;
+        fs::File::create(path)?.write_all(&data)?;
+
+        Ok(archive.entries.len())
+    }
+
+    /// Load the responses of an archive written by `export()`
+    ///
+    /// Responses keep the time they were originally stored, so their
+    /// freshness is not reset. Responses already cached under the same keys
+    /// are replaced. Returns the number of responses loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Archive file to read
+    pub fn import<P: AsRef<Path>>(&self, path: P) -> io::Result<usize> {
+        let mut data = Vec::new();
+        fs::File::open(path)?.read_to_end(&mut data)?;
+
+        let archive: Archive = serde_json::from_slice(&data)
+            .map_err(invalid_data)?;
+        if archive.version != ARCHIVE_VERSION {
+            return Err(invalid_data(format!(
+                "unsupported cache archive version: {}",
+                archive.version
+            )));
+        }
+
+        for item in &archive.entries {
+            self.put_entry(&item.key, &item.entry)?;
+        }
+
+        Ok(archive.entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use cache::*;
+    use storage::MemoryStorage;
+
+    #[test]
+    fn freshness() {
+        let cache = Cache::new(
+            Arc::new(MemoryStorage::new()),
+            Duration::from_secs(60)
+        );
+        assert_eq!(cache.get("en:/v2/build").unwrap(), None);
+
+        cache.put("en:/v2/build", "{\"id\": 1}").unwrap();
+        assert_eq!(
+            cache.get("en:/v2/build").unwrap(),
+            Some("{\"id\": 1}".to_string())
+        );
+
+        let expired = Cache::new(
+            Arc::new(MemoryStorage::new()),
+            Duration::from_secs(0)
+        );
+        expired.put("en:/v2/build", "{}").unwrap();
+        assert_eq!(expired.get("en:/v2/build").unwrap(), None);
+        assert!(expired.entry("en:/v2/build").unwrap().is_some());
+
+        expired.put_with_ttl(
+            "en:/v2/items",
+            "[]",
+            Some(Duration::from_secs(60))
+        ).unwrap();
+        assert!(expired.get("en:/v2/items").unwrap().is_some());
+    }
+
+    #[test]
+    fn expiration() {
+        let cache = Cache::new(
+            Arc::new(MemoryStorage::new()),
+            Duration::from_secs(60)
+        );
+        assert_eq!(cache.time_to_live("en:/v2/build").unwrap(), None);
+
+        cache.put("en:/v2/build", "{\"id\": 1}").unwrap();
+        cache.put_with_ttl("en:/v2/items", "[]", Some(Duration::from_secs(5)))
+            .unwrap();
+
+        let ttl = cache.time_to_live("en:/v2/build").unwrap().unwrap();
+        assert!(ttl > Duration::from_secs(55));
+        assert!(ttl <= Duration::from_secs(60));
+        assert!(
+            cache.time_to_live("en:/v2/items").unwrap().unwrap()
+                <= Duration::from_secs(5)
+        );
+
+        assert_eq!(cache.expire_all().unwrap(), 2);
+        assert_eq!(cache.get("en:/v2/build").unwrap(), None);
+        assert_eq!(
+            cache.time_to_live("en:/v2/items").unwrap(),
+            Some(Duration::from_secs(0))
+        );
+        assert!(cache.entry("en:/v2/items").unwrap().is_some());
+    }
+
+    #[test]
+    fn export_import() {
+        let path = env::temp_dir().join("tyria-cache-test.json");
+        let ttl = Duration::from_secs(3600);
+
+        let cache = Cache::new(Arc::new(MemoryStorage::new()), ttl);
+        cache.put("en:/v2/items?id=1", "{\"id\": 1}").unwrap();
+        cache.put("de:/v2/items?id=1", "{\"id\": 1}").unwrap();
+        assert_eq!(cache.export(&path).unwrap(), 2);
+
+        let restored = Cache::new(Arc::new(MemoryStorage::new()), ttl);
+        assert_eq!(restored.import(&path).unwrap(), 2);
+        assert_eq!(
+            restored.entry("en:/v2/items?id=1").unwrap(),
+            cache.entry("en:/v2/items?id=1").unwrap()
+        );
+
+        fs::write(&path, "{\"version\": 99, \"exported_at\": 0, \
+                          \"entries\": []}").unwrap();
+        assert!(restored.import(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
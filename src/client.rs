@@ -20,71 +20,638 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use hyper::header::LanguageTag;
-use reqwest;
-use reqwest::header::{Headers, AcceptLanguage, Authorization, qitem};
+#[cfg(feature = "drift")]
+use std::any;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
-macro_rules! get_request_url {
-    ($endpoint: expr) => {format!("https://api.guildwars2.com{}", $endpoint)}
+use reqwest::Proxy;
+use reqwest::blocking::Client;
+use reqwest::header::{
+    HeaderMap,
+    HeaderValue,
+    ACCEPT_LANGUAGE,
+    AUTHORIZATION,
+    ETAG,
+    IF_NONE_MATCH,
+    USER_AGENT
+};
+
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+
+use api_v2::endpoint::{self, BulkEndpoint, Endpoint};
+use api_v2::types::{APIKey, IdList, Scope};
+use cache::Cache;
+#[cfg(feature = "drift")]
+use drift::{self, DriftReport, StrictMode};
+use interceptor::{Interceptor, Request};
+use ratelimit::RateLimiter;
+use retry::{is_retryable, retry_after, RetryPolicy};
+use transport::{HttpTransport, Response, Transport};
+use common::{
+    append_param,
+    parse_body,
+    parse_status_body,
+    read_body,
+    string_to_param,
+    APIError,
+    APIErrorKind,
+    APIResponse,
+    CacheHeaders,
+    Page,
+    RequestDiagnostics,
+    ResponseMeta
+};
+
+/// Base URL of the API. Endpoint paths (e.g. `/v2/items`) are appended to it
+pub const API_URL: &'static str = "https://api.guildwars2.com";
+
+/// Schema version of the API introducing equipment and build tabs
+pub const SCHEMA_2019_12_19: &'static str = "2019-12-19T00:00:00Z";
+
+/// Schema version always pointing to the latest changes of the API
+pub const SCHEMA_LATEST: &'static str = "latest";
+
+/// Languages supported by the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// English
+    En,
+    /// Spanish
+    Es,
+    /// German
+    De,
+    /// French
+    Fr,
+    /// Chinese
+    Zh
+}
+
+impl Language {
+    /// Every language supported by the API
+    pub const ALL: [Language; 5] = [
+        Language::En,
+        Language::Es,
+        Language::De,
+        Language::Fr,
+        Language::Zh
+    ];
+
+    /// Obtain the code used by the API for the language
+    pub fn code(&self) -> &'static str {
+        match *self {
+            Language::En => "en",
+            Language::Es => "es",
+            Language::De => "de",
+            Language::Fr => "fr",
+            Language::Zh => "zh"
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Language {
+    type Err = APIError;
+
+    fn from_str(code: &str) -> Result<Language, APIError> {
+        Language::ALL.iter()
+            .find(|language| language.code().eq_ignore_ascii_case(code))
+            .cloned()
+            .ok_or_else(|| {
+                APIError::new(
+                    format!("unsupported language: {}", code).as_str()
+                )
+            })
+    }
+}
+
+/// Build the full URL of an API endpoint
+///
+/// # Arguments
+///
+/// * `endpoint` - Path of the endpoint, including the query string
+pub fn request_url(endpoint: &str) -> String {
+    format!("{}{}", API_URL, endpoint)
+}
+
+/// Make the value of a request header
+///
+/// # Arguments
+///
+/// * `value` - Text of the header
+fn header_value(value: &str) -> Result<HeaderValue, APIError> {
+    HeaderValue::from_str(value).map_err(|_| {
+        APIError::new(format!("invalid header value: {}", value).as_str())
+    })
 }
 
 /// Client in charge of performing requests to the API
+///
+/// The client can be shared between threads. Cloning it is cheap: clones
+/// share the transport (and its connection pool) and the rate limiter, while
+/// settings such as the language can be changed independently in each
+/// clone.
+#[derive(Clone)]
 pub struct APIClient {
     /// Locale to use for requests
     lang: String,
     /// API token to use in certain endpoints that require authentication
     token: Option<String>,
-    /// HTTP client
-    client: reqwest::Client
+    /// Transport performing the HTTP requests
+    transport: Arc<dyn Transport>,
+    /// Base URL the endpoint paths are appended to
+    base_url: String,
+    /// Value of the `User-Agent` header, if any
+    user_agent: Option<String>,
+    /// Headers sent in every request
+    default_headers: HeaderMap,
+    /// Hooks called around each request
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    /// Whether to also send the language as a `lang` query parameter
+    lang_param: bool,
+    /// Schema version to request with the `v` query parameter
+    schema_version: Option<String>,
+    /// Whether to record diagnostics for each request
+    diagnostics_enabled: bool,
+    /// Cache of responses to unauthenticated requests
+    cache: Option<Cache>,
+    /// Rate limiter applied to API requests
+    rate_limiter: Option<RateLimiter>,
+    /// Policy used to retry failed requests
+    retry_policy: Option<RetryPolicy>,
+    /// Whether to send `If-None-Match` with the last known ETag
+    conditional_requests: bool,
+    /// Maximum time to wait for each API request
+    timeout: Option<Duration>,
+    /// Last ETag and body received for each cache key
+    etags: Arc<Mutex<HashMap<String, TaggedBody>>>,
+    /// Details of each token, as returned by `/v2/tokeninfo`
+    token_info: Arc<Mutex<HashMap<String, Arc<APIKey>>>>,
+    /// How fields of responses unknown to their types are treated
+    #[cfg(feature = "drift")]
+    strict_mode: StrictMode,
+    /// Unknown fields found in strict mode
+    #[cfg(feature = "drift")]
+    drift_reports: Arc<Mutex<Vec<DriftReport>>>
+}
+
+/// Body of a response along with its ETag
+#[derive(Debug, Clone)]
+struct TaggedBody {
+    /// Value of the `ETag` header
+    etag: HeaderValue,
+    /// Body of the response
+    body: String
 }
 
 impl APIClient {
     /// Create a new API client
     ///
+    /// The HTTP client is created when the first request is performed, so
+    /// that failing to create it is reported as an error of that request.
+    ///
     /// # Arguments
     ///
     /// * `lang` - Language to use in the API calls
     /// * `token` - Optional token to use in authenticated endpoints
     pub fn new(lang: &str, token: Option<String>) -> APIClient {
+        APIClient::with_transport(
+            lang,
+            token,
+            Arc::new(HttpTransport::lazy())
+        )
+    }
+
+    /// Create a new API client using an existing HTTP client
+    ///
+    /// This allows sharing the connection pool and configuration (TLS,
+    /// timeouts, etc.) of an HTTP client already managed by the
+    /// application.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - Language to use in the API calls
+    /// * `token` - Optional token to use in authenticated endpoints
+    /// * `client` - HTTP client to perform the requests with
+    pub fn with_client(
+        lang: &str,
+        token: Option<String>,
+        client: Client
+    ) -> APIClient {
+        APIClient::with_transport(
+            lang,
+            token,
+            Arc::new(HttpTransport::new(client))
+        )
+    }
+
+    /// Create a new API client using a custom transport
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - Language to use in the API calls
+    /// * `token` - Optional token to use in authenticated endpoints
+    /// * `transport` - Transport to perform the requests with
+    pub fn with_transport(
+        lang: &str,
+        token: Option<String>,
+        transport: Arc<dyn Transport>
+    ) -> APIClient {
         APIClient {
             lang: lang.to_string(),
             token: token,
-            client: reqwest::Client::new().unwrap()
+            transport: transport,
+            base_url: API_URL.to_string(),
+            user_agent: None,
+            default_headers: HeaderMap::new(),
+            interceptors: Vec::new(),
+            lang_param: false,
+            schema_version: None,
+            diagnostics_enabled: false,
+            cache: None,
+            rate_limiter: Some(RateLimiter::api_default()),
+            retry_policy: Some(RetryPolicy::default()),
+            conditional_requests: false,
+            timeout: None,
+            etags: Arc::new(Mutex::new(HashMap::new())),
+            token_info: Arc::new(Mutex::new(HashMap::new())),
+            #[cfg(feature = "drift")]
+            strict_mode: StrictMode::Off,
+            #[cfg(feature = "drift")]
+            drift_reports: Arc::new(Mutex::new(Vec::new()))
+        }
+    }
+
+    /// Create a new API client for a supported language
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - Language to use in the API calls
+    /// * `token` - Optional token to use in authenticated endpoints
+    pub fn with_language(
+        language: Language,
+        token: Option<String>
+    ) -> APIClient {
+        APIClient::new(language.code(), token)
+    }
+
+    /// Obtain a clone of the client using a different language
+    ///
+    /// This is useful to fetch localized data (item names, descriptions,
+    /// etc.) in several languages with the same client, e.g.
+    /// `get_item(&client.localized(Language::De), id)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - Language to use in the API calls
+    pub fn localized(&self, language: Language) -> APIClient {
+        let mut client = self.clone();
+        client.lang = language.code().to_string();
+        client
+    }
+
+    /// Create a builder to configure a new API client
+    pub fn builder() -> APIClientBuilder {
+        APIClientBuilder::new()
+    }
+
+    /// Enable or disable sending the language as a `lang` query parameter
+    ///
+    /// The language is always sent in the `Accept-Language` header. When
+    /// enabled, it is also appended to the query string of every API
+    /// request, which some proxies and caches handle better than the header.
+    /// Endpoints that are not localized ignore the parameter. Disabled by
+    /// default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to send the `lang` parameter
+    pub fn set_lang_param(&mut self, enabled: bool) {
+        self.lang_param = enabled;
+    }
+
+    /// Set the headers sent in every request
+    ///
+    /// This includes downloads outside of the API. Headers set by the client
+    /// itself (language, token, etc.) take precedence over these.
+    ///
+    /// # Arguments
+    ///
+    /// * `headers` - Headers to send
+    pub fn set_default_headers(&mut self, headers: HeaderMap) {
+        self.default_headers = headers;
+    }
+
+    /// Obtain the headers sent in every request
+    pub fn default_headers(&self) -> &HeaderMap {
+        &self.default_headers
+    }
+
+    /// Set the transport performing the HTTP requests
+    ///
+    /// This allows serving canned responses in tests through a
+    /// `MockTransport`, without network access.
+    ///
+    /// # Arguments
+    ///
+    /// * `transport` - Transport to use
+    pub fn set_transport(&mut self, transport: Arc<dyn Transport>) {
+        self.transport = transport;
+    }
+
+    /// Register an interceptor called around every request
+    ///
+    /// Interceptors are called in the order they were registered and are
+    /// shared with clones of the client made afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `interceptor` - Interceptor to register
+    pub fn add_interceptor(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.interceptors.push(interceptor);
+    }
+
+    /// Set the schema version applied to every API request
+    ///
+    /// Some endpoints change the shape of their responses in newer schema
+    /// versions (e.g. characters gain equipment tabs in `2019-12-19`). The
+    /// version is sent as the `v` query parameter. Pass `None` to use the
+    /// default schema of the API, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Schema version, such as `SCHEMA_2019_12_19`
+    pub fn set_schema_version(&mut self, version: Option<&str>) {
+        self.schema_version = version.map(|version| version.to_string());
+    }
+
+    /// Obtain the schema version applied to requests, if any
+    pub fn schema_version(&self) -> Option<&str> {
+        self.schema_version.as_ref().map(|version| version.as_str())
+    }
+
+    /// Enable or disable recording diagnostics for each request
+    ///
+    /// The diagnostics of a request are returned in the `meta` of its
+    /// `APIResponse`. Diagnostics are disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to record diagnostics
+    pub fn set_diagnostics(&mut self, enabled: bool) {
+        self.diagnostics_enabled = enabled;
+    }
+
+    /// Set the cache used for requests made through `get()`
+    ///
+    /// Responses to authenticated requests are never cached. Responses are
+    /// kept for the time allowed by their `Cache-Control` or `Expires`
+    /// headers, falling back to the TTL of the cache when there are none.
+    /// A response that cannot be stored is reported as an error. Pass `None`
+    /// to disable caching, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - Cache to use
+    pub fn set_cache(&mut self, cache: Option<Cache>) {
+        self.cache = cache;
+    }
+
+    /// Obtain the cache used by the client, if any
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
+    /// Set the rate limiter applied to API requests
+    ///
+    /// By default, requests are limited to the 300 requests per minute
+    /// allowed by the API, waiting when the limit is reached. Pass `None` to
+    /// disable rate limiting. Downloads outside of the API are not limited.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_limiter` - Rate limiter to use
+    pub fn set_rate_limiter(&mut self, rate_limiter: Option<RateLimiter>) {
+        self.rate_limiter = rate_limiter;
+    }
+
+    /// Obtain the rate limiter used by the client, if any
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Set the policy used to retry failed requests
+    ///
+    /// Requests failing with `429 Too Many Requests`, a server error or a
+    /// connection error are retried with exponential backoff, honoring the
+    /// `Retry-After` header. By default, requests are retried twice. Pass
+    /// `None` to disable retries.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry_policy` - Retry policy to use
+    pub fn set_retry_policy(&mut self, retry_policy: Option<RetryPolicy>) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Obtain the retry policy used by the client, if any
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    /// Enable or disable conditional requests
+    ///
+    /// When enabled, the client remembers the `ETag` of each response to
+    /// unauthenticated requests made through `get()` and sends it back in
+    /// the `If-None-Match` header. If the API replies with `304 Not
+    /// Modified`, the remembered body is used, which saves bandwidth when
+    /// polling endpoints such as trading post prices. ETags are shared
+    /// between clones of the client. Disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to perform conditional requests
+    pub fn set_conditional_requests(&mut self, enabled: bool) {
+        self.conditional_requests = enabled;
+    }
+
+    /// Set how fields of responses unknown to their types are treated
+    ///
+    /// Strict mode applies to responses parsed through `get()` and the
+    /// generic endpoint methods (e.g. `get_one()`). Unknown fields are
+    /// reported through `take_drift_reports()`, and make the request fail
+    /// with `StrictMode::Deny`. This helps detecting fields added to the
+    /// API that the types do not cover yet. Disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Strict mode to use
+    #[cfg(feature = "drift")]
+    pub fn set_strict_mode(&mut self, mode: StrictMode) {
+        self.strict_mode = mode;
+    }
+
+    /// Obtain how fields of responses unknown to their types are treated
+    #[cfg(feature = "drift")]
+    pub fn strict_mode(&self) -> StrictMode {
+        self.strict_mode
+    }
+
+    /// Obtain the unknown fields found in strict mode since the last call
+    ///
+    /// Reports are shared between clones of the client.
+    #[cfg(feature = "drift")]
+    pub fn take_drift_reports(&self) -> Vec<DriftReport> {
+        match self.drift_reports.lock() {
+            Ok(mut reports) => reports.drain(..).collect(),
+            Err(_) => Vec::new()
+        }
+    }
+
+    /// Set the maximum time to wait for the server during each request
+    ///
+    /// The timeout applies to every endpoint, including the ones requested
+    /// through `make_request()`, and to each retry separately. It bounds
+    /// every read and write on the connection, so a slow response that keeps
+    /// sending data can take longer in total. The HTTP client cannot bound
+    /// the time spent connecting. Requests that time out fail with an error
+    /// of kind `APIErrorKind::Timeout`. Pass `None` to wait indefinitely,
+    /// which is the default.
+    ///
+    /// The timeout is passed to the transport, see
+    /// `Transport::get_with_timeout()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for each request
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Obtain the timeout of API requests, if any
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Obtain the language used in the API calls
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    /// Obtain the language used in the API calls, if supported by the API
+    pub fn language(&self) -> Option<Language> {
+        self.lang.parse().ok()
+    }
+
+    /// Check whether an API token has been configured
+    pub fn has_token(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Obtain a copy of the client using a different API token
+    ///
+    /// The copy shares the transport, cache, rate limiter and every other
+    /// setting with this client, so a single client can serve several
+    /// accounts without building new HTTP clients.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Token to use in authenticated endpoints
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use tyria::client::APIClient;
+    /// use tyria::api_v2::types::Account;
+    ///
+    /// let client = APIClient::new("en", None);
+    /// for token in &["first-token", "second-token"] {
+    ///     let account = client.with_token(token).get_single::<Account>();
+    /// }
+    /// ```
+    pub fn with_token(&self, token: &str) -> APIClient {
+        APIClient {
+            token: Some(token.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Check that the token has been granted the given permissions
+    ///
+    /// The details of the token are requested once and kept for subsequent
+    /// checks. Verifying the permissions before performing authenticated
+    /// requests gives an error listing all the missing permissions, instead
+    /// of a `403` on the first endpoint that requires one of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `scopes` - Permissions required by the application
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use tyria::client::APIClient;
+    /// use tyria::api_v2::types::Scope;
+    ///
+    /// let client = APIClient::new("en", Some("token".to_string()));
+    /// client.verify_scopes(&[Scope::Inventories, Scope::Characters])
+    ///     .unwrap();
+    /// ```
+    pub fn verify_scopes(&self, scopes: &[Scope]) -> Result<(), APIError> {
+        let token = match self.token {
+            Some(ref token) => token,
+            None => return Err(APIError::new("token is not configured"))
+        };
+
+        let cached = self.token_info.lock().ok()
+            .and_then(|info| info.get(token).cloned());
+        let key = match cached {
+            Some(key) => key,
+            None => {
+                let key = Arc::new(self.get_single::<APIKey>()?);
+                if let Ok(mut info) = self.token_info.lock() {
+                    info.insert(token.clone(), key.clone());
+                }
+                key
+            }
+        };
+
+        let missing = key.missing_scopes(scopes);
+        if missing.is_empty() {
+            return Ok(());
         }
+
+        let names: Vec<&str> = missing.iter()
+            .map(|scope| scope.name())
+            .collect();
+        Err(APIError::with_kind(
+            APIErrorKind::MissingPermission,
+            format!("token requires scopes: {}", names.join(", ")).as_str()
+        ))
     }
 
     /// Make an authenticated request to the API
     ///
     /// This expects the token to have been previously configured when
-    /// initialising the client
+    /// initialising the client, failing otherwise
     ///
     /// # Arguments
     ///
     /// * `url` - URL to make the request to
     pub fn make_authenticated_request(&self, url: &str)
-        -> reqwest::Result<reqwest::Response> {
-
-        let full_url = get_request_url!(url);
-        let mut headers = Headers::new();
+        -> Result<Response, APIError> {
 
-        // Set authentication
-        let token = self.token.to_owned();
-        headers.set(
-            Authorization(
-                format!("Bearer {}", token.expect("token is not configured"))
-            )
-        );
-
-        // Set language
-        let mut langtag: LanguageTag = Default::default();
-        langtag.language = Some(self.lang.to_owned());
-        headers.set(
-            AcceptLanguage(vec![
-                qitem(langtag),
-            ])
-        );
-
-        self.client.get(&full_url).headers(headers).send()
+        self.send_request(url, true, None).map(|(response, _)| response)
     }
 
     /// Make a request to the API
@@ -93,20 +660,1463 @@ impl APIClient {
     ///
     /// * `url` - URL to make the request to
     pub fn make_request(&self, url: &str)
-        -> reqwest::Result<reqwest::Response> {
+        -> Result<Response, APIError> {
 
-        let full_url = get_request_url!(url);
+        self.send_request(url, false, None).map(|(response, _)| response)
+    }
 
-        // Set language
-        let mut headers = Headers::new();
-        let mut langtag: LanguageTag = Default::default();
-        langtag.language = Some(self.lang.to_owned());
-        headers.set(
-            AcceptLanguage(vec![
-                qitem(langtag),
-            ])
-        );
+    /// Request any API endpoint and parse the response
+    ///
+    /// This is meant for endpoints or parameters not covered by the crate:
+    /// the request uses the language, token and settings of the client and
+    /// the response is parsed in the same way as in the rest of the
+    /// endpoints. Partial responses to bulk requests are considered valid.
+    /// Unauthenticated requests are served from the cache of the client, if
+    /// any, while the cached response is fresh.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Path of the endpoint, including the query string
+    /// * `authenticated` - Whether to send the API token
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # extern crate serde_json;
+    /// # extern crate tyria;
+    /// use tyria::client::APIClient;
+    /// use tyria::common::numbers_to_param;
+    ///
+    /// # fn main() {
+    /// let client = APIClient::new("en", None);
+    /// let ids = numbers_to_param("ids", &vec![1, 2]);
+    /// let url = format!("/v2/minis?{}", ids);
+    /// let minis: Vec<serde_json::Value> = client.get(&url, false).unwrap();
+    /// # }
+    /// ```
+    pub fn get<T>(
+        &self,
+        endpoint: &str,
+        authenticated: bool
+    ) -> Result<T, APIError> where T: DeserializeOwned {
+        self.get_with_headers(endpoint, authenticated)
+            .map(|response| response.data)
+    }
+
+    /// Request any API endpoint with a specific timeout
+    ///
+    /// Same as `get()`, but the request uses `timeout` regardless of the
+    /// timeout configured in the client (see `set_timeout()`).
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Path of the endpoint, including the query string
+    /// * `authenticated` - Whether to send the API token
+    /// * `timeout` - Maximum time to wait for the request
+    pub fn get_with_timeout<T>(
+        &self,
+        endpoint: &str,
+        authenticated: bool,
+        timeout: Duration
+    ) -> Result<T, APIError> where T: DeserializeOwned {
+        let mut client = self.clone();
+        client.timeout = Some(timeout);
+        client.get(endpoint, authenticated)
+    }
+
+    /// Obtain the data of an endpoint that does not take parameters
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use tyria::client::APIClient;
+    /// use tyria::api_v2::types::Account;
+    ///
+    /// let client = APIClient::new("en", Some("token".to_string()));
+    /// let account = client.get_single::<Account>();
+    /// ```
+    pub fn get_single<T: Endpoint>(&self) -> Result<T, APIError> {
+        endpoint::get_single(self)
+    }
+
+    /// Obtain a list of all the IDs of a bulk endpoint
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use tyria::client::APIClient;
+    /// use tyria::api_v2::types::Outfit;
+    ///
+    /// let client = APIClient::new("en", None);
+    /// let ids = client.ids::<Outfit>();
+    /// ```
+    pub fn ids<T: BulkEndpoint>(&self) -> Result<IdList<T::Id>, APIError> {
+        endpoint::get_ids::<T>(self)
+    }
+
+    /// Obtain the entry of a bulk endpoint with the specified ID
+    ///
+    /// `get()` requests a raw endpoint path, hence the different name.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID to fetch from the server
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use tyria::client::APIClient;
+    /// use tyria::api_v2::types::Outfit;
+    ///
+    /// let client = APIClient::new("en", None);
+    /// let outfit = client.get_one::<Outfit>(30);
+    /// ```
+    pub fn get_one<T: BulkEndpoint>(&self, id: T::Id) -> Result<T, APIError> {
+        endpoint::get_one(self, id)
+    }
+
+    /// Obtain the entries of a bulk endpoint with the specified IDs
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - IDs to fetch from the server
+    pub fn get_many<T: BulkEndpoint>(
+        &self,
+        ids: &[T::Id]
+    ) -> Result<Vec<T>, APIError> {
+        endpoint::get_many(self, ids)
+    }
+
+    /// Obtain the entry of a bulk endpoint in every supported language
+    ///
+    /// The entry is requested once per language in `Language::ALL`. Fails
+    /// if any of the requests fails. `AsyncClient::get_localized()`
+    /// performs the requests concurrently.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID to fetch from the server
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use tyria::client::{APIClient, Language};
+    /// use tyria::api_v2::types::Item;
+    ///
+    /// let client = APIClient::new("en", None);
+    /// let items = client.get_localized::<Item>(19721).unwrap();
+    /// let german = &items[&Language::De];
+    /// ```
+    pub fn get_localized<T: BulkEndpoint>(
+        &self,
+        id: T::Id
+    ) -> Result<HashMap<Language, T>, APIError> where T::Id: Clone {
+        Language::ALL.iter()
+            .map(|&language| {
+                self.localized(language)
+                    .get_one::<T>(id.clone())
+                    .map(|entry| (language, entry))
+            })
+            .collect()
+    }
+
+    /// Obtain all the entries of a bulk endpoint
+    pub fn get_all<T: BulkEndpoint>(&self) -> Result<Vec<T>, APIError> {
+        endpoint::get_all(self)
+    }
+
+    /// Obtain a page of entries of a bulk endpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - Index of the page, starting at 0
+    /// * `page_size` - Number of entries per page (max: 200)
+    pub fn get_page<T: BulkEndpoint>(
+        &self,
+        page: i32,
+        page_size: i32
+    ) -> Result<Page<T>, APIError> {
+        endpoint::get_page(self, page, page_size)
+    }
+
+    /// Perform a request and parse its response, keeping the caching headers
+    ///
+    /// Same as `get()`, but the `Cache-Control`, `Expires` and `Age` headers
+    /// sent by the API are returned along with the data, so that services
+    /// re-serving it can propagate the cache lifetimes. The rate limiting
+    /// and result count headers are also returned, to help consumers
+    /// throttle themselves and paginate. Responses served from the local
+    /// cache carry no headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Path of the endpoint, including the query string
+    /// * `authenticated` - Whether to send the API token
+    pub fn get_with_headers<T>(
+        &self,
+        endpoint: &str,
+        authenticated: bool
+    ) -> Result<APIResponse<T>, APIError> where T: DeserializeOwned {
+        self.fetch(endpoint, authenticated, true)
+    }
+
+    /// Perform a request, storing the response in the cache if configured
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Path of the endpoint, including the query string
+    /// * `authenticated` - Whether to send the API token
+    /// * `use_cached` - Whether a fresh cached response can be used instead
+    ///         of performing the request
+    pub(crate) fn fetch<T>(
+        &self,
+        endpoint: &str,
+        authenticated: bool,
+        use_cached: bool
+    ) -> Result<APIResponse<T>, APIError> where T: DeserializeOwned {
+        let cache = if authenticated { None } else { self.cache.as_ref() };
+        let key = self.cache_key(endpoint);
+
+        if let (Some(cache), true) = (cache, use_cached) {
+            // A cache that cannot be read is treated as a cache miss
+            if let Ok(Some(body)) = cache.get(&key) {
+                return Ok(APIResponse {
+                    data: parse_body(&body)?,
+                    cache: CacheHeaders::default(),
+                    meta: ResponseMeta {
+                        diagnostics: self.diagnostics(
+                            endpoint,
+                            Duration::from_secs(0),
+                            true,
+                            0
+                        ),
+                        ..Default::default()
+                    }
+                });
+            }
+        }
+
+        if authenticated && !self.has_token() {
+            return Err(APIError::new("token is not configured"));
+        }
+
+        let tagged = if self.conditional_requests && !authenticated {
+            self.etags.lock().ok().and_then(|etags| etags.get(&key).cloned())
+        } else {
+            None
+        };
+
+        let (mut response, diagnostics) = self.send_request(
+            endpoint,
+            authenticated,
+            tagged.as_ref().map(|tagged| tagged.etag.clone())
+        )?;
+
+        let (status, body) = match tagged {
+            Some(tagged) if *response.status() == StatusCode::NOT_MODIFIED => {
+                (StatusCode::OK, tagged.body)
+            },
+            _ => (*response.status(), read_body(&mut response)?)
+        };
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(APIError::rate_limited(retry_after(&response)));
+        }
+
+        if self.conditional_requests && !authenticated
+            && status == StatusCode::OK {
+
+            let etag = response.headers().get(ETAG).cloned();
+
+            if let (Some(etag), Ok(mut etags)) = (etag, self.etags.lock()) {
+                etags.insert(key.clone(), TaggedBody {
+                    etag: etag,
+                    body: body.clone()
+                });
+            }
+        }
+
+        let data = self.parse_status_body(endpoint, &status, &body)?;
+
+        let headers = CacheHeaders::from_response(&response);
+        if let Some(cache) = cache {
+            if status == StatusCode::OK {
+                cache.put_with_ttl(&key, &body, headers.ttl()).map_err(|e| {
+                    APIError::new(
+                        format!("failed to cache response: {}", e).as_str()
+                    )
+                })?;
+            }
+        }
+
+        Ok(APIResponse {
+            data: data,
+            cache: headers,
+            meta: ResponseMeta {
+                diagnostics: diagnostics,
+                ..ResponseMeta::from_response(&response)
+            }
+        })
+    }
+
+    /// Parse the body of a response according to its status code
+    ///
+    /// In strict mode, fields unknown to `T` are reported as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Path of the endpoint, for reporting
+    /// * `status` - HTTP status code of the response
+    /// * `body` - Raw body of the response
+    #[cfg_attr(not(feature = "drift"), allow(unused_variables))]
+    fn parse_status_body<T>(
+        &self,
+        endpoint: &str,
+        status: &StatusCode,
+        body: &str
+    ) -> Result<T, APIError> where T: DeserializeOwned {
+        let valid = vec![StatusCode::OK, StatusCode::PARTIAL_CONTENT];
+
+        #[cfg(feature = "drift")]
+        {
+            if self.strict_mode != StrictMode::Off && valid.contains(status)
+                && !body.trim().is_empty() {
+
+                return self.parse_strict(endpoint, body);
+            }
+        }
+
+        parse_status_body(
+            status,
+            body,
+            valid,
+            vec![
+                StatusCode::BAD_REQUEST,
+                StatusCode::UNAUTHORIZED,
+                StatusCode::FORBIDDEN,
+                StatusCode::NOT_FOUND
+            ]
+        )
+    }
+
+    /// Parse the body of a successful response, reporting unknown fields
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Path of the endpoint, for reporting
+    /// * `body` - Raw body of the response
+    #[cfg(feature = "drift")]
+    fn parse_strict<T>(
+        &self,
+        endpoint: &str,
+        body: &str
+    ) -> Result<T, APIError> where T: DeserializeOwned {
+        let (result, unknown_fields) = drift::deserialize::<T>(body);
+
+        if !unknown_fields.is_empty() {
+            if let Ok(mut reports) = self.drift_reports.lock() {
+                reports.push(DriftReport {
+                    endpoint: endpoint.to_string(),
+                    type_name: any::type_name::<T>(),
+                    unknown_fields: unknown_fields.clone(),
+                    missing_fields: Vec::new(),
+                    error: None
+                });
+            }
+
+            if self.strict_mode == StrictMode::Deny {
+                return Err(APIError::new(format!(
+                    "unknown fields in response: {}",
+                    unknown_fields.join(", ")
+                ).as_str()));
+            }
+        }
+
+        result.map_err(|e| {
+            APIError::new(format!("failed to parse response: {}", e).as_str())
+        })
+    }
+
+    /// Download a file outside of the API (e.g. from the render service)
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Full URL of the file
+    pub fn download(&self, url: &str) -> Result<Response, APIError> {
+        self.send(url, url, HeaderMap::new(), false)
+            .map(|(response, _)| response)
+    }
+
+    /// Make a request to the API
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL to make the request to
+    /// * `authenticated` - Whether to send the API token
+    /// * `if_none_match` - ETag to send in the `If-None-Match` header
+    fn send_request(
+        &self,
+        url: &str,
+        authenticated: bool,
+        if_none_match: Option<HeaderValue>
+    ) -> Result<(Response, Option<RequestDiagnostics>), APIError> {
+        let url = self.localize_url(url);
+        let full_url = format!("{}{}", self.base_url, url);
+        let mut headers = HeaderMap::new();
+
+        // Set authentication
+        if authenticated {
+            let token = self.token.as_ref()
+                .ok_or_else(|| APIError::new("token is not configured"))?;
+            headers.insert(
+                AUTHORIZATION,
+                header_value(&format!("Bearer {}", token))?
+            );
+        }
+
+        // Set language
+        headers.insert(ACCEPT_LANGUAGE, header_value(&self.lang)?);
+
+        if let Some(ref user_agent) = self.user_agent {
+            headers.insert(USER_AGENT, header_value(user_agent)?);
+        }
+
+        if let Some(etag) = if_none_match {
+            headers.insert(IF_NONE_MATCH, etag);
+        }
+
+        self.send(&url, &full_url, headers, true)
+    }
+
+    /// Obtain the cache key of an endpoint, which depends on the language
+    pub(crate) fn cache_key(&self, url: &str) -> String {
+        match self.schema_version {
+            Some(ref version) => format!("{}:{}:{}", self.lang, version, url),
+            None => format!("{}:{}", self.lang, url)
+        }
+    }
+
+    /// Add the `lang` and `v` parameters to an endpoint URL, if enabled
+    fn localize_url(&self, url: &str) -> String {
+        let mut url = url.to_string();
+
+        if self.lang_param {
+            url = append_param(&url, &string_to_param("lang", &self.lang));
+        }
+
+        if let Some(ref version) = self.schema_version {
+            url = append_param(&url, &string_to_param("v", version));
+        }
+
+        url
+    }
+
+    /// Wait until the rate limiter allows another request, if enabled
+    fn throttle(&self) {
+        if let Some(ref rate_limiter) = self.rate_limiter {
+            rate_limiter.acquire();
+        }
+    }
+
+    /// Send a GET request, along with its diagnostics if enabled
+    ///
+    /// Failed requests are retried according to the retry policy. When
+    /// `throttled` is set, each attempt waits for the rate limiter first,
+    /// and the rate limiter is paused for the delay requested by the API
+    /// when it rejects a request with `429 Too Many Requests`.
+    fn send(
+        &self,
+        url: &str,
+        full_url: &str,
+        headers: HeaderMap,
+        throttled: bool
+    ) -> Result<(Response, Option<RequestDiagnostics>), APIError> {
+        let start = Instant::now();
+        let max_retries = self.retry_policy
+            .map(|policy| policy.max_retries())
+            .unwrap_or(0);
+        let mut retries = 0;
+
+        loop {
+            if throttled {
+                self.throttle();
+            }
+
+            let mut request = Request {
+                url: full_url.to_string(),
+                headers: self.default_headers.clone()
+            };
+            request.headers.extend(headers.clone());
+
+            for interceptor in &self.interceptors {
+                interceptor.before(&mut request);
+            }
+
+            let response = match self.timeout {
+                Some(timeout) => self.transport.get_with_timeout(
+                    &request.url,
+                    request.headers.clone(),
+                    timeout
+                ),
+                None => self.transport
+                    .get(&request.url, request.headers.clone())
+            };
+
+            if let Ok(ref response) = response {
+                for interceptor in &self.interceptors {
+                    interceptor.after(&request, response);
+                }
+            }
+
+            let (transient, retry_after) = match response {
+                Ok(ref response) if is_retryable(response.status()) => {
+                    (true, retry_after(response))
+                },
+                Ok(_) => (false, None),
+                Err(_) => (true, None)
+            };
+
+            if let (true, Some(delay)) = (throttled, retry_after) {
+                if let Some(ref rate_limiter) = self.rate_limiter {
+                    rate_limiter.pause(delay);
+                }
+            }
+
+            if !transient || retries >= max_retries {
+                let diagnostics = self.diagnostics(
+                    url,
+                    start.elapsed(),
+                    false,
+                    retries
+                );
+                return response.map(|response| (response, diagnostics));
+            }
+
+            if let Some(policy) = self.retry_policy {
+                thread::sleep(policy.delay(retries, retry_after));
+            }
+            retries += 1;
+        }
+    }
+
+    /// Obtain the diagnostics of a request, if enabled
+    fn diagnostics(
+        &self,
+        url: &str,
+        total: Duration,
+        cache_hit: bool,
+        retries: u32
+    ) -> Option<RequestDiagnostics> {
+        if !self.diagnostics_enabled {
+            return None;
+        }
+
+        Some(RequestDiagnostics {
+            url: url.to_string(),
+            total: total,
+            cache_hit: cache_hit,
+            retries: retries
+        })
+    }
+}
+
+/// Builder of API clients
+///
+/// Settings that are not configured keep the defaults of
+/// `APIClient::new()`.
+///
+/// # Proxies
+///
+/// Requests are routed through the proxies given to `proxy()`, or else
+/// through the ones of the environment (e.g. `HTTPS_PROXY`). Traffic can
+/// also be sent straight to an intercepting server (e.g. a mock of the API
+/// in tests) by pointing `base_url()` to it.
+///
+/// # TLS
+///
+/// The TLS backend of the HTTP client is chosen with cargo features:
+/// `native-tls` (default) links the TLS library of the platform, while
+/// `rustls` needs no system library, which suits static or musl builds.
+/// `native-tls` is used when both are enabled. Without either of them,
+/// requests to `https` URLs such as `API_URL` fail.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use tyria::client::APIClient;
+///
+/// let client = APIClient::builder()
+///     .lang("de")
+///     .token("my-token")
+///     .timeout(Duration::from_secs(10))
+///     .user_agent("my-tool/1.0")
+///     .build()
+///     .unwrap();
+/// ```
+pub struct APIClientBuilder {
+    /// Locale to use for requests
+    lang: String,
+    /// API token to use in authenticated endpoints
+    token: Option<String>,
+    /// Maximum time to wait for the server during each request
+    timeout: Option<Duration>,
+    /// Whether the HTTP client decompresses gzip responses
+    gzip: Option<bool>,
+    /// Proxies to route the requests through
+    proxies: Vec<Proxy>,
+    /// Base URL the endpoint paths are appended to
+    base_url: String,
+    /// Value of the `User-Agent` header
+    user_agent: Option<String>,
+    /// Headers sent in every request
+    default_headers: HeaderMap,
+    /// Hooks called around each request
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    /// Cache of responses to unauthenticated requests
+    cache: Option<Cache>,
+    /// Rate limiter applied to API requests
+    rate_limiter: Option<RateLimiter>,
+    /// Schema version to request
+    schema_version: Option<String>,
+    /// HTTP client to perform the requests with
+    client: Option<Client>,
+    /// Transport to perform the requests with
+    transport: Option<Arc<dyn Transport>>,
+    /// How fields of responses unknown to their types are treated
+    #[cfg(feature = "drift")]
+    strict_mode: StrictMode
+}
+
+impl APIClientBuilder {
+    /// Create a new builder with the default settings
+    pub fn new() -> APIClientBuilder {
+        APIClientBuilder {
+            lang: "en".to_string(),
+            token: None,
+            timeout: None,
+            gzip: None,
+            proxies: Vec::new(),
+            base_url: API_URL.to_string(),
+            user_agent: None,
+            default_headers: HeaderMap::new(),
+            interceptors: Vec::new(),
+            cache: None,
+            rate_limiter: Some(RateLimiter::api_default()),
+            schema_version: None,
+            client: None,
+            transport: None,
+            #[cfg(feature = "drift")]
+            strict_mode: StrictMode::Off
+        }
+    }
+
+    /// Set the language to use in the API calls. Defaults to `en`
+    pub fn lang(mut self, lang: &str) -> APIClientBuilder {
+        self.lang = lang.to_string();
+        self
+    }
+
+    /// Set the language to use in the API calls
+    pub fn language(mut self, language: Language) -> APIClientBuilder {
+        self.lang = language.code().to_string();
+        self
+    }
+
+    /// Set the token to use in authenticated endpoints
+    pub fn token(mut self, token: &str) -> APIClientBuilder {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Set the maximum time to wait for the server during each request.
+    /// See `APIClient::set_timeout()`
+    pub fn timeout(mut self, timeout: Duration) -> APIClientBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enable or disable gzip compression of the responses
+    ///
+    /// When enabled, the HTTP client sends `Accept-Encoding: gzip` and
+    /// transparently decompresses the responses, which greatly reduces the
+    /// size of bulk endpoints such as items or prices. Enabled by default.
+    /// The HTTP client does not support `deflate`.
+    pub fn gzip(mut self, enabled: bool) -> APIClientBuilder {
+        self.gzip = Some(enabled);
+        self
+    }
+
+    /// Route the requests through a proxy (e.g. a corporate proxy, or a
+    /// tool inspecting the traffic)
+    ///
+    /// Can be called several times; proxies are tried in the order they are
+    /// added. Adding a proxy disables the ones of the environment.
+    ///
+    /// # Arguments
+    ///
+    /// * `proxy` - Proxy to add, e.g. `reqwest::Proxy::https(url)`
+    pub fn proxy(mut self, proxy: Proxy) -> APIClientBuilder {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Set the base URL the endpoint paths are appended to (e.g. to use a
+    /// proxy of the API). Defaults to `API_URL`
+    pub fn base_url(mut self, base_url: &str) -> APIClientBuilder {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set the value of the `User-Agent` header, which lets the API
+    /// operators identify the application
+    pub fn user_agent(mut self, user_agent: &str) -> APIClientBuilder {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Set the headers sent in every request
+    pub fn default_headers(mut self, headers: HeaderMap) -> APIClientBuilder {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Register an interceptor called around every request
+    pub fn interceptor(
+        mut self,
+        interceptor: Arc<dyn Interceptor>
+    ) -> APIClientBuilder {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// Set the cache of responses to unauthenticated requests
+    pub fn cache(mut self, cache: Cache) -> APIClientBuilder {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Set the rate limiter applied to API requests, `None` to disable it
+    pub fn rate_limiter(
+        mut self,
+        rate_limiter: Option<RateLimiter>
+    ) -> APIClientBuilder {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Set the schema version applied to every API request
+    pub fn schema_version(mut self, version: &str) -> APIClientBuilder {
+        self.schema_version = Some(version.to_string());
+        self
+    }
+
+    /// Use an existing HTTP client to perform the requests
+    ///
+    /// The client is used as is. Clones of a `reqwest::blocking::Client`
+    /// share their settings, so `timeout()`, `gzip()` and `proxy()` cannot
+    /// be combined with this, as they would also change the client of the
+    /// application; `build()` fails if they are set. Configure the HTTP
+    /// client before passing it instead.
+    pub fn http_client(mut self, client: Client) -> APIClientBuilder {
+        self.client = Some(client);
+        self
+    }
+
+    /// Use a custom transport to perform the requests
+    ///
+    /// The transport replaces the HTTP client, so `http_client()`, `gzip()`
+    /// and `proxy()` cannot be combined with this; `build()` fails if any of
+    /// them is set. `timeout()` is passed to the transport.
+    pub fn transport(
+        mut self,
+        transport: Arc<dyn Transport>
+    ) -> APIClientBuilder {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Set how fields of responses unknown to their types are treated.
+    /// Defaults to `StrictMode::Off`
+    #[cfg(feature = "drift")]
+    pub fn strict_mode(mut self, mode: StrictMode) -> APIClientBuilder {
+        self.strict_mode = mode;
+        self
+    }
+
+    /// Build the API client
+    pub fn build(self) -> Result<APIClient, APIError> {
+        let transport: Arc<dyn Transport> = match self.transport {
+            Some(transport) => {
+                if self.client.is_some() || self.gzip.is_some()
+                    || !self.proxies.is_empty() {
+
+                    return Err(APIError::new(
+                        "HTTP client, gzip and proxy settings cannot be \
+                         applied to a custom transport"
+                    ));
+                }
+
+                transport
+            },
+            None => match self.client {
+                Some(client) => {
+                    if self.timeout.is_some() || self.gzip.is_some()
+                        || !self.proxies.is_empty() {
+
+                        return Err(APIError::new(
+                            "timeout, gzip and proxy settings cannot be \
+                             applied to an existing HTTP client"
+                        ));
+                    }
+
+                    Arc::new(HttpTransport::new(client))
+                },
+                None => {
+                    let mut transport = HttpTransport::lazy()
+                        .gzip(self.gzip.unwrap_or(true));
+                    for proxy in self.proxies {
+                        transport = transport.proxy(proxy);
+                    }
+
+                    Arc::new(transport)
+                }
+            }
+        };
+
+        let mut client = APIClient::with_transport(
+            &self.lang,
+            self.token,
+            transport
+        );
+        client.base_url = self.base_url;
+        client.user_agent = self.user_agent;
+        client.default_headers = self.default_headers;
+        client.interceptors = self.interceptors;
+        client.cache = self.cache;
+        client.rate_limiter = self.rate_limiter;
+        client.schema_version = self.schema_version;
+        client.timeout = self.timeout;
+        #[cfg(feature = "drift")]
+        {
+            client.strict_mode = self.strict_mode;
+        }
+
+        Ok(client)
+    }
+}
+
+impl Default for APIClientBuilder {
+    fn default() -> APIClientBuilder {
+        APIClientBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use serde_json;
+    use cache::Cache;
+    use reqwest::{Proxy, StatusCode};
+    use reqwest::blocking::Client;
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use client::{
+        request_url,
+        APIClient,
+        Language,
+        TaggedBody,
+        SCHEMA_2019_12_19
+    };
+    #[cfg(feature = "static")]
+    use api_v2::types::{Color, Outfit};
+    use api_v2::types::Scope;
+    use common::{APIError, APIErrorKind};
+    use storage::{MemoryStorage, Storage};
+    use transport::{MockTransport, Response, Transport};
+
+    /// Transport that would take `delay` to answer, recording the timeouts
+    /// it receives instead of waiting
+    struct SlowTransport {
+        /// Time the transport takes to answer
+        delay: Duration,
+        /// Timeouts of the requests performed, in order
+        timeouts: Mutex<Vec<Option<Duration>>>
+    }
+
+    impl Transport for SlowTransport {
+        fn get(&self, _: &str, _: HeaderMap) -> Result<Response, APIError> {
+            self.timeouts.lock().unwrap().push(None);
+            Ok(Response::json("{}"))
+        }
+
+        fn get_with_timeout(
+            &self,
+            _: &str,
+            _: HeaderMap,
+            timeout: Duration
+        ) -> Result<Response, APIError> {
+            self.timeouts.lock().unwrap().push(Some(timeout));
+            if timeout < self.delay {
+                Err(APIError::with_kind(APIErrorKind::Timeout, "timed out"))
+            } else {
+                Ok(Response::json("{}"))
+            }
+        }
+    }
+
+    #[test]
+    fn shared_client() {
+        let client = APIClient::with_client(
+            "fr",
+            None,
+            Client::new()
+        );
+        assert_eq!(client.lang(), "fr");
+        assert!(!client.has_token());
+    }
+
+    #[test]
+    fn shareable() {
+        fn assert_shareable<T: Clone + Send + Sync>(_: &T) {}
+
+        let client = APIClient::new("en", Some("token".to_string()));
+        assert_shareable(&client);
+
+        let clone = client.clone();
+        assert_eq!(clone.lang(), "en");
+        assert!(clone.has_token());
+    }
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            request_url("/v2/items?ids=1"),
+            "https://api.guildwars2.com/v2/items?ids=1"
+        );
+    }
+
+    #[test]
+    fn missing_token() {
+        let client = APIClient::new("en", None);
+        let result = client.get::<serde_json::Value>("/v2/account", true);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cached_response() {
+        let cache = Cache::new(
+            Arc::new(MemoryStorage::new()),
+            Duration::from_secs(60)
+        );
+        cache.put("en:/v2/build", "{\"id\": 115267}").unwrap();
+
+        let mut client = APIClient::new("en", None);
+        client.set_cache(Some(cache));
+        client.set_diagnostics(true);
+
+        let build = client.get_with_headers::<serde_json::Value>(
+            "/v2/build",
+            false
+        ).unwrap();
+        assert_eq!(build.data["id"], 115267);
+        assert!(build.meta.diagnostics.unwrap().cache_hit);
+    }
+
+    #[test]
+    fn cache_failure() {
+        struct ReadOnlyStorage;
+
+        impl Storage for ReadOnlyStorage {
+            fn get(&self, _: &str, _: &str) -> io::Result<Option<Vec<u8>>> {
+                Ok(None)
+            }
+
+            fn put(&self, _: &str, _: &str, _: &[u8]) -> io::Result<()> {
+                Err(io::Error::new(io::ErrorKind::Other, "read-only"))
+            }
+
+            fn list(&self, _: &str) -> io::Result<Vec<String>> {
+                Ok(Vec::new())
+            }
+        }
+
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/build", Response::json(r#"{"id": 115267}"#));
+
+        let client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport)
+            .cache(Cache::new(
+                Arc::new(ReadOnlyStorage),
+                Duration::from_secs(60)
+            ))
+            .build()
+            .unwrap();
+
+        let error = client.get::<serde_json::Value>("/v2/build", false)
+            .unwrap_err();
+        assert!(error.description().contains("read-only"));
+    }
+
+    #[test]
+    #[cfg(feature = "static")]
+    fn gzip_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        // Serve a single gzip compressed response, returning the request
+        let server = thread::spawn(move || {
+            let body = include_bytes!("../fixtures/gzip/color.json.gz");
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\n\
+                 Content-Type: application/json; charset=utf-8\r\n\
+                 Content-Encoding: gzip\r\n\
+                 Content-Length: {}\r\n\
+                 Connection: close\r\n\r\n",
+                body.len()
+            ).unwrap();
+            stream.write_all(body).unwrap();
+
+            String::from_utf8(request).unwrap()
+        });
+
+        let client = APIClient::builder()
+            .base_url(&base_url)
+            .rate_limiter(None)
+            .gzip(true)
+            .build()
+            .unwrap();
+
+        let color: Color = client.get("/v2/colors?id=10", false).unwrap();
+        assert_eq!(color.id, 10);
+        assert_eq!(color.name, "Sky");
+
+        let request = server.join().unwrap().to_lowercase();
+        assert!(request.starts_with("get /v2/colors?id=10 "));
+        assert!(request.contains("\r\naccept-encoding: gzip\r\n"));
+    }
+
+    #[test]
+    fn proxied_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy_url = format!("http://{}", listener.local_addr().unwrap());
+
+        // Act as the proxy of a single request, returning the request
+        let server = thread::spawn(move || {
+            let body = br#"{"id": 115267}"#;
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\n\
+                 Content-Type: application/json; charset=utf-8\r\n\
+                 Content-Length: {}\r\n\
+                 Connection: close\r\n\r\n",
+                body.len()
+            ).unwrap();
+            stream.write_all(body).unwrap();
+
+            String::from_utf8(request).unwrap()
+        });
+
+        // The host cannot be resolved, so the request must go to the proxy
+        let client = APIClient::builder()
+            .base_url("http://api.tyria.invalid")
+            .rate_limiter(None)
+            .proxy(Proxy::http(proxy_url.as_str()).unwrap())
+            .build()
+            .unwrap();
+
+        let build: serde_json::Value = client.get("/v2/build", false).unwrap();
+        assert_eq!(build["id"], 115267);
+
+        let request = server.join().unwrap().to_lowercase();
+        assert!(
+            request.starts_with("get http://api.tyria.invalid/v2/build ")
+        );
+
+        let proxied = APIClient::builder()
+            .transport(Arc::new(MockTransport::new()))
+            .proxy(Proxy::http(proxy_url.as_str()).unwrap())
+            .build();
+        assert!(proxied.is_err());
+    }
+
+    #[test]
+    fn shared_etags() {
+        let mut client = APIClient::new("en", None);
+        client.set_conditional_requests(true);
+        let clone = client.clone();
+
+        client.etags.lock().unwrap().insert(
+            client.cache_key("/v2/commerce/prices?id=19721"),
+            TaggedBody {
+                etag: HeaderValue::from_static("\"abc\""),
+                body: "{}".to_string()
+            }
+        );
+
+        let etags = clone.etags.lock().unwrap();
+        assert!(etags.contains_key("en:/v2/commerce/prices?id=19721"));
+    }
+
+    #[test]
+    fn mock_transport() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/build", Response::json(r#"{"id": 115267}"#));
+
+        let client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let build: serde_json::Value = client.get("/v2/build", false).unwrap();
+        assert_eq!(build["id"], 115267);
+
+        let missing = client.get::<serde_json::Value>("/v2/items/1", false);
+        assert_eq!(missing.unwrap_err().description(), "no such endpoint");
+
+        assert_eq!(transport.requests(), vec!["/v2/build", "/v2/items/1"]);
+    }
+
+    #[test]
+    #[cfg(feature = "static")]
+    fn generic_endpoints() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/outfits", Response::json("[1, 2]"));
+        transport.respond("/v2/outfits?ids=30,45", Response::json("[]"));
+
+        let client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(client.ids::<Outfit>().unwrap().ids(), &[1, 2]);
+        assert!(client.get_many::<Outfit>(&[30, 45]).unwrap().is_empty());
+        assert!(client.get_one::<Outfit>(30).is_err());
+        assert_eq!(
+            transport.requests(),
+            vec!["/v2/outfits", "/v2/outfits?ids=30,45", "/v2/outfits?id=30"]
+        );
+    }
+
+    #[test]
+    fn token_override() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/account", Response::json("{}"));
+
+        let client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+        let account = client.with_token("token");
+
+        assert!(!client.has_token());
+        assert!(account.has_token());
+        assert!(client.get::<serde_json::Value>("/v2/account", true).is_err());
+        assert!(account.get::<serde_json::Value>("/v2/account", true).is_ok());
+        assert_eq!(transport.requests(), vec!["/v2/account"]);
+    }
+
+    #[test]
+    fn scopes() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/tokeninfo", Response::json(r#"{
+            "id": "X",
+            "name": "Key",
+            "permissions": ["account", "characters"]
+        }"#));
+
+        let client = APIClient::builder()
+            .token("token")
+            .rate_limiter(None)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        assert!(client.verify_scopes(&[Scope::Account]).is_ok());
+
+        let error = client.verify_scopes(
+            &[Scope::Inventories, Scope::Characters, Scope::Wallet]
+        ).unwrap_err();
+        assert_eq!(
+            error.description(),
+            "token requires scopes: inventories, wallet"
+        );
+        assert_eq!(error.kind(), APIErrorKind::MissingPermission);
+        assert_eq!(transport.requests(), vec!["/v2/tokeninfo"]);
+    }
+
+    #[test]
+    fn rate_limited() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("10"));
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/build", Response::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            headers,
+            b"{\"text\": \"too many requests\"}".to_vec()
+        ));
+
+        let mut client = APIClient::new("en", None);
+        client.set_transport(transport);
+        client.set_retry_policy(None);
+
+        let error = client.get::<serde_json::Value>("/v2/build", false)
+            .unwrap_err();
+        assert_eq!(error.kind(), APIErrorKind::RateLimited);
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(10)));
+        assert!(client.rate_limiter().unwrap().try_acquire().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "drift")]
+    fn strict_mode() {
+        use api_v2::types::GuildUpgrade;
+        use drift::StrictMode;
+
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/outfits?id=1", Response::json(r#"{
+            "id": 1,
+            "name": "Cook's Outfit",
+            "icon": "https://render.guildwars2.com/file/1.png",
+            "unlock_items": [],
+            "new_field": true
+        }"#));
+
+        let mut client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport.clone())
+            .strict_mode(StrictMode::Collect)
+            .build()
+            .unwrap();
+
+        assert!(client.get_one::<Outfit>(1).is_ok());
+        let reports = client.take_drift_reports();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].endpoint, "/v2/outfits?id=1");
+        assert_eq!(reports[0].unknown_fields, vec!["new_field"]);
+        assert!(client.take_drift_reports().is_empty());
+
+        client.set_strict_mode(StrictMode::Deny);
+        assert_eq!(
+            client.get_one::<Outfit>(1).unwrap_err().description(),
+            "unknown fields in response: new_field"
+        );
+
+        transport.respond("/v2/guild/upgrades?id=55", Response::json(r#"{
+            "id": 55,
+            "name": "Guild Vault",
+            "type": "BankBag",
+            "bag_max_items": 50,
+            "bag_max_coins": 300000,
+            "new_field": true
+        }"#));
+        assert_eq!(
+            client.get_one::<GuildUpgrade>(55).unwrap_err().description(),
+            "unknown fields in response: new_field"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "static")]
+    fn localized_entries() {
+        let transport = Arc::new(MockTransport::new());
+        for language in Language::ALL.iter() {
+            transport.respond(
+                &format!("/v2/outfits?id=1&lang={}", language),
+                Response::json(&format!(r#"{{
+                    "id": 1,
+                    "name": "{}",
+                    "icon": "",
+                    "unlock_items": []
+                }}"#, language))
+            );
+        }
+
+        let mut client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+        client.set_lang_param(true);
+
+        let outfits = client.get_localized::<Outfit>(1).unwrap();
+        assert_eq!(outfits.len(), Language::ALL.len());
+        assert_eq!(outfits[&Language::De].name, "de");
+        assert_eq!(transport.requests().len(), Language::ALL.len());
+
+        transport.respond("/v2/outfits?id=2&lang=en", Response::json("{}"));
+        assert!(client.get_localized::<Outfit>(2).is_err());
+    }
+
+    #[test]
+    fn lang_param() {
+        let mut client = APIClient::new("de", None);
+        assert_eq!(client.localize_url("/v2/items"), "/v2/items");
+
+        client.set_lang_param(true);
+        assert_eq!(client.localize_url("/v2/items"), "/v2/items?lang=de");
+        assert_eq!(
+            client.localize_url("/v2/items?ids=1"),
+            "/v2/items?ids=1&lang=de"
+        );
+    }
+
+    #[test]
+    fn builder() {
+        let client = APIClient::builder()
+            .lang("fr")
+            .token("token")
+            .base_url("http://localhost:8080/")
+            .rate_limiter(None)
+            .schema_version(SCHEMA_2019_12_19)
+            .timeout(Duration::from_secs(5))
+            .gzip(false)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.lang(), "fr");
+        assert!(client.has_token());
+        assert_eq!(client.base_url, "http://localhost:8080");
+        assert!(client.rate_limiter().is_none());
+        assert_eq!(client.schema_version(), Some(SCHEMA_2019_12_19));
+        assert_eq!(client.timeout(), Some(Duration::from_secs(5)));
+
+        let shared = APIClient::builder()
+            .http_client(Client::new())
+            .gzip(false)
+            .build();
+        assert!(shared.is_err());
+
+        let transport = Arc::new(MockTransport::new());
+        let custom = APIClient::builder()
+            .transport(transport.clone())
+            .gzip(true)
+            .build();
+        assert!(custom.is_err());
+        assert!(APIClient::builder().transport(transport).build().is_ok());
+    }
+
+    #[test]
+    fn request_timeout() {
+        let transport = Arc::new(SlowTransport {
+            delay: Duration::from_secs(2),
+            timeouts: Mutex::new(Vec::new())
+        });
+        let mut client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport.clone())
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        client.set_retry_policy(None);
+        assert_eq!(
+            client.make_request("/v2/build").unwrap_err().kind(),
+            APIErrorKind::Timeout
+        );
+
+        let result = client.get_with_timeout::<serde_json::Value>(
+            "/v2/build",
+            false,
+            Duration::from_secs(5)
+        );
+        assert!(result.is_ok());
+
+        client.set_timeout(None);
+        assert!(client.make_request("/v2/build").is_ok());
+
+        assert_eq!(*transport.timeouts.lock().unwrap(), vec![
+            Some(Duration::from_millis(50)),
+            Some(Duration::from_secs(5)),
+            None
+        ]);
+    }
+
+    #[test]
+    fn languages() {
+        assert_eq!("DE".parse::<Language>().unwrap(), Language::De);
+        assert!("pt".parse::<Language>().is_err());
+
+        let client = APIClient::with_language(Language::Fr, None);
+        assert_eq!(client.lang(), "fr");
+        assert_eq!(
+            client.localized(Language::Zh).language(),
+            Some(Language::Zh)
+        );
+        assert_eq!(APIClient::new("xx", None).language(), None);
+    }
+
+    #[test]
+    fn schema_version() {
+        let mut client = APIClient::new("en", None);
+        client.set_schema_version(Some(SCHEMA_2019_12_19));
+
+        assert_eq!(
+            client.localize_url("/v2/characters"),
+            "/v2/characters?v=2019-12-19T00%3A00%3A00Z"
+        );
+        assert_eq!(
+            client.cache_key("/v2/characters"),
+            "en:2019-12-19T00:00:00Z:/v2/characters"
+        );
+    }
+
+    #[test]
+    fn diagnostics() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/build", Response::json(r#"{"id": 115267}"#));
+
+        let mut client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport)
+            .build()
+            .unwrap();
+
+        let response = client.get_with_headers::<serde_json::Value>(
+            "/v2/build",
+            false
+        ).unwrap();
+        assert_eq!(response.meta.diagnostics, None);
 
-        self.client.get(&full_url).headers(headers).send()
+        client.set_diagnostics(true);
+        let response = client.get_with_headers::<serde_json::Value>(
+            "/v2/build",
+            false
+        ).unwrap();
+        let diagnostics = response.meta.diagnostics.unwrap();
+        assert_eq!(diagnostics.url, "/v2/build");
+        assert!(!diagnostics.cache_hit);
+        assert_eq!(diagnostics.retries, 0);
     }
 }
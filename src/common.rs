@@ -22,28 +22,201 @@
 
 /// Common utility code
 
-use reqwest::{Response, StatusCode};
+#[cfg(feature = "blocking")]
+use std::io::Read;
+
+#[cfg(feature = "chrono")]
+use chrono::SecondsFormat;
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "blocking")]
+use httpdate::parse_http_date;
+#[cfg(feature = "blocking")]
+use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
+use serde_json;
+
+use api_v2::types::Timestamp;
+#[cfg(feature = "blocking")]
+use retry::retry_after;
+#[cfg(feature = "blocking")]
+use transport::Response;
 
 
 /// API errors
 #[derive(Deserialize, Debug)]
 pub struct APIError {
     /// Error description provided by the API
-    text: String
+    text: String,
+    /// Kind of error, set when the error is created
+    #[serde(skip)]
+    kind: APIErrorKind,
+    /// Delay requested by the API before retrying, when rate limited
+    #[serde(skip)]
+    retry_after: Option<Duration>
 }
 
 /// Used when defining miscelaneous errors
 impl APIError {
     pub fn new(text: &str) -> APIError {
         APIError {
-            text: text.to_string()
+            text: text.to_string(),
+            kind: APIErrorKind::Other,
+            retry_after: None
+        }
+    }
+
+    /// Create an error of a specific kind
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Kind of the error
+    /// * `text` - Description of the error
+    pub fn with_kind(kind: APIErrorKind, text: &str) -> APIError {
+        APIError {
+            text: text.to_string(),
+            kind: kind,
+            retry_after: None
+        }
+    }
+
+    /// Create the error for a request rejected by the API
+    ///
+    /// The kind is obtained from the status code of the response and the
+    /// description given by the API.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - HTTP status code of the response
+    /// * `text` - Description given by the API
+    #[cfg(feature = "blocking")]
+    pub fn from_status(status: &StatusCode, text: &str) -> APIError {
+        APIError::with_kind(classify_error(status, text), text)
+    }
+
+    /// Create the error returned when the API rejects a request because of
+    /// its rate limit (`429 Too Many Requests`)
+    ///
+    /// # Arguments
+    ///
+    /// * `retry_after` - Delay requested in the `Retry-After` header
+    pub fn rate_limited(retry_after: Option<Duration>) -> APIError {
+        let text = match retry_after {
+            Some(delay) => format!(
+                "rate limit exceeded, retry after {} seconds",
+                delay.as_secs()
+            ),
+            None => "rate limit exceeded".to_string()
+        };
+
+        APIError {
+            text: text,
+            kind: APIErrorKind::RateLimited,
+            retry_after: retry_after
         }
     }
 
     pub fn description(&self) -> &str {
         self.text.as_str()
     }
+
+    /// Obtain the delay requested by the API before retrying, if the
+    /// request was rate limited and the API sent a `Retry-After` header
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+
+    /// Obtain the kind of error
+    pub fn kind(&self) -> APIErrorKind {
+        self.kind
+    }
+
+    /// Check whether the error is solved by issuing a new subtoken
+    ///
+    /// This is the case when the subtoken has expired or does not allow
+    /// the requested endpoint.
+    pub fn needs_new_subtoken(&self) -> bool {
+        match self.kind() {
+            APIErrorKind::ExpiredToken | APIErrorKind::UrlNotAllowed => true,
+            _ => false
+        }
+    }
+}
+
+/// Kinds of errors related to the token used in a request or to the request
+/// itself
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum APIErrorKind {
+    /// The token does not exist or has been deleted
+    InvalidToken,
+    /// The subtoken has expired
+    ExpiredToken,
+    /// The subtoken is restricted to other endpoints
+    UrlNotAllowed,
+    /// The token lacks a permission required by the endpoint
+    MissingPermission,
+    /// The request did not complete within the configured timeout
+    Timeout,
+    /// The API rejected the request because of its rate limit
+    RateLimited,
+    /// None of the IDs requested from a bulk endpoint exist
+    InvalidIds,
+    /// Any other error
+    Other
+}
+
+impl Default for APIErrorKind {
+    fn default() -> APIErrorKind {
+        APIErrorKind::Other
+    }
+}
+
+/// Error text returned by the API when none of the requested IDs exist
+#[cfg(feature = "blocking")]
+const ALL_IDS_INVALID: &'static str = "all ids provided are invalid";
+
+/// Obtain the kind of a request rejected by the API
+///
+/// Only `400 Bad Request`, `401 Unauthorized` and `403 Forbidden` responses
+/// are related to the token, and their kind depends on the description
+/// given by the API. A `404 Not Found` is only told apart when none of the
+/// requested IDs exist.
+///
+/// # Arguments
+///
+/// * `status` - HTTP status code of the response
+/// * `text` - Description given by the API
+#[cfg(feature = "blocking")]
+fn classify_error(status: &StatusCode, text: &str) -> APIErrorKind {
+    match *status {
+        StatusCode::TOO_MANY_REQUESTS => return APIErrorKind::RateLimited,
+        StatusCode::NOT_FOUND => {
+            return if text.trim().eq_ignore_ascii_case(ALL_IDS_INVALID) {
+                APIErrorKind::InvalidIds
+            } else {
+                APIErrorKind::Other
+            }
+        },
+        StatusCode::BAD_REQUEST
+            | StatusCode::UNAUTHORIZED
+            | StatusCode::FORBIDDEN => {},
+        _ => return APIErrorKind::Other
+    }
+
+    let text = text.trim().to_lowercase();
+
+    if text.starts_with("requires scope") {
+        APIErrorKind::MissingPermission
+    } else if text == "invalid access token" || text == "invalid key" {
+        APIErrorKind::InvalidToken
+    } else if text.contains("token") && text.ends_with("expired") {
+        APIErrorKind::ExpiredToken
+    } else if text.starts_with("url") && (text.contains("not allowed")
+            || text.contains("restricted")) {
+        APIErrorKind::UrlNotAllowed
+    } else {
+        APIErrorKind::Other
+    }
 }
 
 
@@ -96,16 +269,18 @@ pub fn numbers_to_param(param: &str, values: &Vec<i32>) -> String {
     result.push_str("=");
 
     // Separate with commas
-    for val in values {
-        result.push_str(&val.to_string());
-        result.push_str(",");
-    }
+    let values: Vec<String> = values.iter()
+        .map(|val| val.to_string())
+        .collect();
+    result.push_str(&values.join(","));
 
     result
 }
 
 /// Make a parameter out of a string
 ///
+/// The value is percent-encoded.
+///
 /// # Arguments
 ///
 /// * `param` - Name of the parameter
@@ -124,13 +299,15 @@ pub fn string_to_param(param: &str, value: &str) -> String {
     // Add parameter label
     result.push_str(param);
     result.push_str("=");
-    result.push_str(value);
+    result.push_str(&encode_param(value));
 
     result
 }
 
 /// Make a parameter out of a vectors of string
 ///
+/// Each value is percent-encoded, while the commas separating them are not.
+///
 /// # Arguments
 ///
 /// * `param` - Name of the parameter
@@ -150,17 +327,220 @@ pub fn strings_to_param(param: &str, values: &Vec<&str>) -> String {
     result.push_str(param);
     result.push_str("=");
 
-    for val in values {
-        result.push_str(val);
-        result.push_str(",");
+    let values: Vec<String> = values.iter()
+        .map(|val| encode_param(val))
+        .collect();
+    result.push_str(&values.join(","));
+
+    result
+}
+
+/// Percent-encode a value so that it can be used in a query string or as a
+/// segment of a path (e.g. a character name)
+///
+/// Every character except letters, digits, `-`, `.`, `_` and `~` is
+/// encoded.
+///
+/// # Arguments
+///
+/// * `value` - Value to encode
+pub fn encode_param(value: &str) -> String {
+    let mut result = String::new();
+
+    for byte in value.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9'
+                | b'-' | b'.' | b'_' | b'~' => result.push(byte as char),
+            _ => result.push_str(&format!("%{:02X}", byte))
+        }
     }
 
     result
 }
 
+/// Make a parameter out of a timestamp, in ISO-8601 format
+///
+/// # Arguments
+///
+/// * `param` - Name of the parameter
+/// * `value` - Timestamp to convert
+#[cfg(feature = "chrono")]
+pub fn timestamp_to_param(param: &str, value: &Timestamp) -> String {
+    string_to_param(param, &value.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// Make a parameter out of a timestamp, in ISO-8601 format
+///
+/// # Arguments
+///
+/// * `param` - Name of the parameter
+/// * `value` - Timestamp to convert
+#[cfg(not(feature = "chrono"))]
+pub fn timestamp_to_param(param: &str, value: &Timestamp) -> String {
+    string_to_param(param, value)
+}
+
+/// Builder of query strings
+///
+/// Parameters are added in order and their values are percent-encoded.
+///
+/// # Example
+///
+/// ```
+/// use tyria::common::QueryBuilder;
+///
+/// let query = QueryBuilder::new()
+///     .numbers("ids", &[1, 2, 3])
+///     .string("lang", "de")
+///     .build();
+/// assert_eq!(query, "ids=1,2,3&lang=de");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QueryBuilder {
+    /// Parameters added so far
+    params: Vec<String>
+}
+
+impl QueryBuilder {
+    /// Create a new empty query
+    pub fn new() -> QueryBuilder {
+        Default::default()
+    }
+
+    /// Add a numeric parameter
+    pub fn number(mut self, param: &str, value: i32) -> QueryBuilder {
+        self.params.push(number_to_param(param, value));
+        self
+    }
+
+    /// Add a parameter made of a list of numbers
+    pub fn numbers(mut self, param: &str, values: &[i32]) -> QueryBuilder {
+        self.params.push(numbers_to_param(param, &values.to_vec()));
+        self
+    }
+
+    /// Add a text parameter
+    pub fn string(mut self, param: &str, value: &str) -> QueryBuilder {
+        self.params.push(string_to_param(param, value));
+        self
+    }
+
+    /// Add a parameter made of a list of strings
+    pub fn strings(mut self, param: &str, values: &[&str]) -> QueryBuilder {
+        self.params.push(strings_to_param(param, &values.to_vec()));
+        self
+    }
+
+    /// Add a parameter already built by the `*_to_param()` functions
+    pub fn param(mut self, param: &str) -> QueryBuilder {
+        self.params.push(param.to_string());
+        self
+    }
+
+    /// Obtain the query string, without the leading `?`
+    pub fn build(&self) -> String {
+        self.params.join("&")
+    }
+}
+
+/// Append a parameter to the query string of a URL
+///
+/// # Arguments
+///
+/// * `url` - URL, with or without a query string
+/// * `param` - Parameter as built by the `*_to_param()` functions
+pub fn append_param(url: &str, param: &str) -> String {
+    let separator = if url.contains('?') { "&" } else { "?" };
+    format!("{}{}{}", url, separator, param)
+}
+
+/// Parse the body of a successful response
+///
+/// Some authenticated endpoints return an empty body for new accounts (e.g.
+/// no characters or an empty bank). In that case the body is interpreted as
+/// an empty list, an empty object or `null`, whichever fits `T`, so that
+/// empty collections are obtained instead of an error.
+///
+/// # Arguments
+///
+/// * `body` - Raw body of the response
+pub fn parse_body<T>(
+    body: &str
+) -> Result<T, APIError> where T: DeserializeOwned {
+    if body.trim().is_empty() {
+        for empty in &["[]", "{}", "null"] {
+            if let Ok(result) = serde_json::from_str::<T>(empty) {
+                return Ok(result);
+            }
+        }
+
+        return Err(APIError::new("empty response from the API"));
+    }
+
+    serde_json::from_str(body).map_err(|e| {
+        APIError::new(format!("failed to parse response: {}", e).as_str())
+    })
+}
+
+/// Read the whole body of a response
+///
+/// # Arguments
+///
+/// * `response` - Response from the API
+#[cfg(feature = "blocking")]
+pub fn read_body(response: &mut Response) -> Result<String, APIError> {
+    let mut body = String::new();
+    if let Err(e) = response.read_to_string(&mut body) {
+        return Err(APIError::new(
+            format!("failed to read response: {}", e).as_str()
+        ));
+    }
+
+    Ok(body)
+}
+
+/// Parse the body of an API response according to its status code
+///
+/// # Arguments
+///
+/// * `status` - HTTP status code of the response
+/// * `body` - Raw body of the response
+/// * `valid` - Valid HTTP codes that cause the data to be parsed
+/// * `invalid` - Invalid HTTP codes that obtain an `APIError` with a message
+///         from the API
+#[cfg(feature = "blocking")]
+pub fn parse_status_body<T>(
+    status: &StatusCode,
+    body: &str,
+    valid: Vec<StatusCode>,
+    invalid: Vec<StatusCode>
+) -> Result<T, APIError> where T: DeserializeOwned {
+    if valid.contains(status) {
+        return parse_body(body);
+
+    } else if *status == StatusCode::TOO_MANY_REQUESTS {
+        return Err(APIError::rate_limited(None));
+
+    } else if invalid.contains(status) {
+        return Err(match serde_json::from_str::<APIError>(body) {
+            Ok(error) => APIError::from_status(status, &error.text),
+            Err(_) => APIError::with_kind(
+                classify_error(status, ""),
+                format!("status code: {}", status).as_str()
+            )
+        });
+    }
+
+    Err(APIError::new(
+        format!("unknown status code: {}", status).as_str()
+    ))
+}
+
 /// Parse an API response into the appropriate type
 ///
-/// This expects to know the data type to use when parsing the JSON
+/// This expects to know the data type to use when parsing the JSON. A
+/// `429 Too Many Requests` response obtains an error of kind
+/// `APIErrorKind::RateLimited` carrying the `Retry-After` delay, if any.
 ///
 /// # Arguments
 ///
@@ -168,19 +548,489 @@ pub fn strings_to_param(param: &str, values: &Vec<&str>) -> String {
 /// * `valid` - Valid HTTP codes that cause the data to be parsed
 /// * `invalid` - Invalid HTTP codes that obtain an `APIError` with a message
 ///         from the API
+#[cfg(feature = "blocking")]
 pub fn parse_response<T>(
     response: &mut Response,
     valid: Vec<StatusCode>,
     invalid: Vec<StatusCode>
 ) -> Result<T, APIError> where T: DeserializeOwned {
-    if valid.contains(response.status()) {
-        return Ok(response.json::<T>().unwrap());
+    if *response.status() == StatusCode::TOO_MANY_REQUESTS
+        && !valid.contains(response.status()) {
 
-    } else if invalid.contains(response.status()) {
-        return Err(response.json::<APIError>().unwrap());
+        return Err(APIError::rate_limited(retry_after(response)));
     }
 
-    Err(APIError::new(
-        format!("unknown status code: {}", response.status()).as_str()
-    ))
+    let body = read_body(response)?;
+    parse_status_body(response.status(), &body, valid, invalid)
+}
+
+/// Single page of results from a paginated endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    /// Results contained in this page
+    pub items: Vec<T>,
+    /// Index of this page, starting at 0
+    pub page: i32,
+    /// Maximum number of results per page
+    pub page_size: i32,
+    /// Total number of pages, as reported by the API
+    pub page_total: Option<i32>,
+    /// Total number of results across all pages, as reported by the API
+    pub result_total: Option<i32>
+}
+
+impl<T> Page<T> {
+    /// Check whether there are pages after this one
+    pub fn has_next(&self) -> bool {
+        match self.page_total {
+            Some(total) => self.page + 1 < total,
+            None => self.items.len() as i32 == self.page_size
+        }
+    }
+}
+
+/// Make the parameters to request a page of results
+///
+/// # Arguments
+///
+/// * `page` - Index of the page, starting at 0
+/// * `page_size` - Number of results per page
+pub fn page_to_param(page: i32, page_size: i32) -> String {
+    QueryBuilder::new()
+        .number("page", page)
+        .number("page_size", page_size)
+        .build()
+}
+
+/// Obtain a header from a response as text
+#[cfg(feature = "blocking")]
+fn text_header(response: &Response, name: &str) -> Option<String> {
+    response.headers().get(name)
+        .map(|value| {
+            String::from_utf8_lossy(value.as_bytes()).trim().to_string()
+        })
+}
+
+/// Obtain a numeric header from a response
+#[cfg(feature = "blocking")]
+fn numeric_header(response: &Response, name: &str) -> Option<i32> {
+    text_header(response, name).and_then(|value| value.parse().ok())
+}
+
+/// HTTP caching headers sent by the API
+///
+/// Values are kept verbatim so that they can be forwarded as they are by
+/// services re-serving the data.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheHeaders {
+    /// Value of the `Cache-Control` header
+    pub cache_control: Option<String>,
+    /// Value of the `Expires` header
+    pub expires: Option<String>,
+    /// Value of the `Age` header, in seconds
+    pub age: Option<u64>,
+    /// Time given in the `Expires` header, if it could be parsed
+    pub expires_at: Option<SystemTime>
+}
+
+impl CacheHeaders {
+    /// Read the caching headers of a response
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - Response from the API
+    #[cfg(feature = "blocking")]
+    pub fn from_response(response: &Response) -> CacheHeaders {
+        CacheHeaders {
+            cache_control: text_header(response, "Cache-Control"),
+            expires: text_header(response, "Expires"),
+            age: text_header(response, "Age")
+                .and_then(|value| value.parse().ok()),
+            expires_at: text_header(response, "Expires")
+                .and_then(|value| parse_http_date(&value).ok())
+        }
+    }
+
+    /// Obtain the `max-age` directive of `Cache-Control`, in seconds
+    pub fn max_age(&self) -> Option<u64> {
+        self.cache_control.as_ref().and_then(|value| {
+            value.split(',')
+                .filter_map(|directive| {
+                    let mut parts = directive.trim().splitn(2, '=');
+                    match (parts.next(), parts.next()) {
+                        (Some(name), Some(seconds))
+                            if name.eq_ignore_ascii_case("max-age") =>
+                            seconds.trim().trim_matches('"').parse().ok(),
+                        _ => None
+                    }
+                })
+                .next()
+        })
+    }
+
+    /// Obtain the time the response remains fresh, in seconds
+    ///
+    /// This is `max-age` minus the time the response already spent in
+    /// upstream caches.
+    pub fn remaining_lifetime(&self) -> Option<u64> {
+        self.max_age()
+            .map(|max_age| max_age.saturating_sub(self.age.unwrap_or(0)))
+    }
+
+    /// Obtain the time the response may be cached for
+    ///
+    /// `no-store` and `no-cache` directives result in a zero duration.
+    /// Otherwise, `max-age` takes precedence over `Expires`, as in HTTP
+    /// caches. Returns `None` when the headers do not say.
+    pub fn ttl(&self) -> Option<Duration> {
+        let uncacheable = self.cache_control.as_ref().map_or(false, |value| {
+            value.split(',').any(|directive| {
+                let directive = directive.trim();
+                directive.eq_ignore_ascii_case("no-store")
+                    || directive.eq_ignore_ascii_case("no-cache")
+            })
+        });
+
+        if uncacheable {
+            return Some(Duration::from_secs(0));
+        }
+
+        match self.remaining_lifetime() {
+            Some(seconds) => Some(Duration::from_secs(seconds)),
+            None => self.expires_at.map(|expires_at| {
+                expires_at.duration_since(SystemTime::now())
+                    .unwrap_or(Duration::from_secs(0))
+            })
+        }
+    }
+}
+
+/// Timing information of a single request
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestDiagnostics {
+    /// Endpoint requested, without the host
+    pub url: String,
+    /// Time until the response headers were received, including retries.
+    /// The body of the response is read afterwards when parsing, so it is
+    /// not included
+    pub total: Duration,
+    /// Whether the response was served from a local cache
+    pub cache_hit: bool,
+    /// Number of times the request was retried
+    pub retries: u32
+}
+
+/// Rate limiting and result count headers sent by the API
+///
+/// Clients can use these to throttle themselves and to paginate through
+/// bulk endpoints. Headers not sent by the API are `None`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResponseMeta {
+    /// Value of the `X-Rate-Limit-Limit` header
+    pub rate_limit_limit: Option<i32>,
+    /// Value of the `X-Rate-Limit-Remaining` header
+    pub rate_limit_remaining: Option<i32>,
+    /// Value of the `X-Result-Count` header
+    pub result_count: Option<i32>,
+    /// Value of the `X-Result-Total` header
+    pub result_total: Option<i32>,
+    /// Value of the `X-Page-Size` header
+    pub page_size: Option<i32>,
+    /// Value of the `X-Page-Total` header
+    pub page_total: Option<i32>,
+    /// Diagnostics of the request, if enabled in the client
+    pub diagnostics: Option<RequestDiagnostics>
+}
+
+impl ResponseMeta {
+    /// Read the metadata headers of a response
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - Response from the API
+    #[cfg(feature = "blocking")]
+    pub fn from_response(response: &Response) -> ResponseMeta {
+        ResponseMeta {
+            rate_limit_limit: numeric_header(response, "X-Rate-Limit-Limit"),
+            rate_limit_remaining: numeric_header(
+                response,
+                "X-Rate-Limit-Remaining"
+            ),
+            result_count: numeric_header(response, "X-Result-Count"),
+            result_total: numeric_header(response, "X-Result-Total"),
+            page_size: numeric_header(response, "X-Page-Size"),
+            page_total: numeric_header(response, "X-Page-Total"),
+            diagnostics: None
+        }
+    }
+}
+
+/// Data parsed from an API response, along with its headers
+#[derive(Debug)]
+pub struct APIResponse<T> {
+    /// Parsed data
+    pub data: T,
+    /// Caching headers of the response
+    pub cache: CacheHeaders,
+    /// Rate limiting and result count headers of the response
+    pub meta: ResponseMeta
+}
+
+/// Parse a paginated API response
+///
+/// Same as `parse_response()`, but also reads the pagination headers
+/// (`X-Page-Size`, `X-Page-Total` and `X-Result-Total`) sent by the API.
+///
+/// # Arguments
+///
+/// * `response` - Response from the API
+/// * `page` - Index of the page that was requested
+/// * `page_size` - Page size that was requested
+/// * `valid` - Valid HTTP codes that cause the data to be parsed
+/// * `invalid` - Invalid HTTP codes that obtain an `APIError` with a message
+///         from the API
+#[cfg(feature = "blocking")]
+pub fn parse_page_response<T>(
+    response: &mut Response,
+    page: i32,
+    page_size: i32,
+    valid: Vec<StatusCode>,
+    invalid: Vec<StatusCode>
+) -> Result<Page<T>, APIError> where T: DeserializeOwned {
+    let page_total = numeric_header(response, "X-Page-Total");
+    let result_total = numeric_header(response, "X-Result-Total");
+    let page_size = numeric_header(response, "X-Page-Size")
+        .unwrap_or(page_size);
+
+    Ok(Page {
+        items: parse_response(response, valid, invalid)?,
+        page: page,
+        page_size: page_size,
+        page_total: page_total,
+        result_total: result_total
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use common::*;
+    #[cfg(feature = "blocking")]
+    use reqwest::header::{HeaderMap, HeaderValue};
+    #[cfg(all(feature = "account", feature = "characters"))]
+    use api_v2::types::{
+        BankSlot,
+        CharacterInventory,
+        CharacterSkills,
+        InventorySlot
+    };
+
+    #[test]
+    fn cache_headers() {
+        let headers = CacheHeaders {
+            cache_control: Some("public, max-age=300".to_string()),
+            expires: None,
+            age: Some(120),
+            expires_at: None
+        };
+        assert_eq!(headers.max_age(), Some(300));
+        assert_eq!(headers.remaining_lifetime(), Some(180));
+        assert_eq!(headers.ttl(), Some(Duration::from_secs(180)));
+
+        let headers = CacheHeaders {
+            expires_at: Some(SystemTime::now() + Duration::from_secs(600)),
+            ..CacheHeaders::default()
+        };
+        assert!(headers.ttl().unwrap() > Duration::from_secs(590));
+
+        let headers = CacheHeaders {
+            cache_control: Some("no-cache".to_string()),
+            ..CacheHeaders::default()
+        };
+        assert_eq!(headers.max_age(), None);
+        assert_eq!(headers.ttl(), Some(Duration::from_secs(0)));
+        assert_eq!(CacheHeaders::default().remaining_lifetime(), None);
+        assert_eq!(CacheHeaders::default().ttl(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn response_meta() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-rate-limit-limit", HeaderValue::from_static("600"));
+        headers.insert("x-result-count", HeaderValue::from_static("50"));
+        headers.insert("x-page-total", HeaderValue::from_static(" 3 "));
+        let response = Response::new(StatusCode::OK, headers, Vec::new());
+
+        let meta = ResponseMeta::from_response(&response);
+        assert_eq!(meta.rate_limit_limit, Some(600));
+        assert_eq!(meta.rate_limit_remaining, None);
+        assert_eq!(meta.result_count, Some(50));
+        assert_eq!(meta.page_total, Some(3));
+        assert_eq!(ResponseMeta::default().result_total, None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "account", feature = "characters"))]
+    fn empty_body() {
+        let names: Vec<String> = parse_body("").unwrap();
+        assert!(names.is_empty());
+
+        let bank: Vec<Option<BankSlot>> = parse_body("  \n").unwrap();
+        assert!(bank.is_empty());
+
+        let slot: Option<InventorySlot> = parse_body("").unwrap();
+        assert!(slot.is_none());
+
+        assert!(parse_body::<CharacterInventory>("").is_ok());
+    }
+
+    #[test]
+    #[cfg(all(feature = "account", feature = "characters"))]
+    fn empty_account() {
+        let bank: Vec<Option<BankSlot>> = parse_body("[null, null]").unwrap();
+        assert!(bank.iter().all(|slot| slot.is_none()));
+
+        let inventory: CharacterInventory = parse_body("{}").unwrap();
+        assert!(inventory.bags.is_empty());
+
+        assert!(parse_body::<CharacterSkills>(r#"{
+            "skills": {
+                "pve": {"heal": null, "utilities": [null, null, null],
+                        "elite": null},
+                "pvp": {"utilities": []},
+                "wvw": {}
+            }
+        }"#).is_ok());
+    }
+
+    #[test]
+    fn page_params() {
+        assert_eq!(page_to_param(2, 50), "page=2&page_size=50");
+        assert_eq!(append_param("/v2/items", "lang=de"), "/v2/items?lang=de");
+        assert_eq!(
+            append_param("/v2/items?ids=1", "lang=de"),
+            "/v2/items?ids=1&lang=de"
+        );
+    }
+
+    #[test]
+    fn list_params() {
+        assert_eq!(numbers_to_param("ids", &vec![1, 2, 42]), "ids=1,2,42");
+        assert_eq!(numbers_to_param("ids", &vec![]), "ids=");
+        assert_eq!(
+            strings_to_param("ids", &vec!["a b", "c,d"]),
+            "ids=a%20b,c%2Cd"
+        );
+        assert_eq!(
+            string_to_param("name", "Lady O'Neil"),
+            "name=Lady%20O%27Neil"
+        );
+        assert_eq!(
+            QueryBuilder::new()
+                .numbers("ids", &[1, 2])
+                .strings("urls", &["/v2/account"])
+                .build(),
+            "ids=1,2&urls=%2Fv2%2Faccount"
+        );
+    }
+
+    #[test]
+    fn invalid_body() {
+        assert!(parse_body::<Vec<i32>>("not json").is_err());
+    }
+
+    #[test]
+    fn encoded_params() {
+        assert_eq!(
+            encode_param("https://example.com/a b"),
+            "https%3A%2F%2Fexample.com%2Fa%20b"
+        );
+
+        #[cfg(feature = "chrono")]
+        let expire = "2027-01-02T03:04:05Z".parse().unwrap();
+        #[cfg(not(feature = "chrono"))]
+        let expire = "2027-01-02T03:04:05Z".to_string();
+        assert_eq!(
+            timestamp_to_param("expire", &expire),
+            "expire=2027-01-02T03%3A04%3A05Z"
+        );
+    }
+
+    #[test]
+    fn error_kinds() {
+        let limited = APIError::rate_limited(Some(Duration::from_secs(3)));
+        assert_eq!(limited.kind(), APIErrorKind::RateLimited);
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(3)));
+
+        let timeout = APIError::with_kind(APIErrorKind::Timeout, "too slow");
+        assert_eq!(timeout.kind(), APIErrorKind::Timeout);
+
+        assert_eq!(
+            APIError::new("request timed out after 5s").kind(),
+            APIErrorKind::Other
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn status_error_kinds() {
+        let expired = APIError::from_status(
+            &StatusCode::UNAUTHORIZED,
+            "Subtoken expired"
+        );
+        assert_eq!(expired.kind(), APIErrorKind::ExpiredToken);
+        assert!(expired.needs_new_subtoken());
+
+        let url = APIError::from_status(
+            &StatusCode::FORBIDDEN,
+            "url not allowed for this subtoken"
+        );
+        assert_eq!(url.kind(), APIErrorKind::UrlNotAllowed);
+        assert!(url.needs_new_subtoken());
+
+        let scope = parse_status_body::<i32>(
+            &StatusCode::FORBIDDEN,
+            r#"{"text": "requires scope inventories"}"#,
+            vec![StatusCode::OK],
+            vec![StatusCode::FORBIDDEN]
+        ).unwrap_err();
+        assert_eq!(scope.kind(), APIErrorKind::MissingPermission);
+        assert!(!scope.needs_new_subtoken());
+
+        assert_eq!(
+            APIError::from_status(
+                &StatusCode::UNAUTHORIZED,
+                "Invalid access token"
+            ).kind(),
+            APIErrorKind::InvalidToken
+        );
+        assert_eq!(
+            APIError::from_status(
+                &StatusCode::NOT_FOUND,
+                "no such id: expired token"
+            ).kind(),
+            APIErrorKind::Other
+        );
+        assert_eq!(
+            APIError::from_status(
+                &StatusCode::NOT_FOUND,
+                "All ids provided are invalid"
+            ).kind(),
+            APIErrorKind::InvalidIds
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn too_many_requests() {
+        assert_eq!(
+            parse_status_body::<i32>(
+                &StatusCode::TOO_MANY_REQUESTS,
+                r#"{"text": "too many requests"}"#,
+                vec![StatusCode::OK],
+                vec![StatusCode::NOT_FOUND]
+            ).unwrap_err().kind(),
+            APIErrorKind::RateLimited
+        );
+    }
 }
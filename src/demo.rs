@@ -0,0 +1,158 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Demo mode serving bundled sample data
+/// No API key or network access is needed, which allows building and
+/// showing account dashboards without asking users for a key
+
+use std::sync::Arc;
+
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+
+use client::APIClient;
+use common::APIError;
+use transport::{endpoint_of, Response, Transport};
+
+
+/// Token of the demo client, which is never sent to the API
+pub const DEMO_TOKEN: &'static str = "demo";
+
+/// Sample responses bundled with the crate, by endpoint
+const FIXTURES: [(&'static str, &'static str); 9] = [
+    ("/v2/account", include_str!("../fixtures/demo/account.json")),
+    ("/v2/account/bank", include_str!("../fixtures/demo/bank.json")),
+    ("/v2/account/dyes", include_str!("../fixtures/demo/dyes.json")),
+    ("/v2/account/inventory",
+     include_str!("../fixtures/demo/inventory.json")),
+    ("/v2/account/materials",
+     include_str!("../fixtures/demo/materials.json")),
+    ("/v2/account/raids", include_str!("../fixtures/demo/raids.json")),
+    ("/v2/account/wallet", include_str!("../fixtures/demo/wallet.json")),
+    ("/v2/characters", include_str!("../fixtures/demo/characters.json")),
+    ("/v2/tokeninfo", include_str!("../fixtures/demo/tokeninfo.json"))
+];
+
+/// Transport serving bundled sample data instead of performing requests
+///
+/// Responses are chosen by the path of the URL; the query string is
+/// ignored. Endpoints without sample data obtain a `404 Not Found`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DemoTransport;
+
+impl DemoTransport {
+    /// Create a new demo transport
+    pub fn new() -> DemoTransport {
+        DemoTransport
+    }
+
+    /// Obtain the endpoints that have sample data
+    pub fn endpoints(&self) -> Vec<&'static str> {
+        FIXTURES.iter().map(|&(endpoint, _)| endpoint).collect()
+    }
+}
+
+impl Transport for DemoTransport {
+    fn get(
+        &self,
+        url: &str,
+        _headers: HeaderMap
+    ) -> Result<Response, APIError> {
+        let endpoint = endpoint_of(url);
+        let path = endpoint.split('?').next().unwrap_or(endpoint);
+
+        Ok(match FIXTURES.iter().find(|&&(fixture, _)| fixture == path) {
+            Some(&(_, body)) => Response::json(body),
+            None => Response::new(
+                StatusCode::NOT_FOUND,
+                HeaderMap::new(),
+                format!(r#"{{"text": "no demo data for {}"}}"#, path)
+                    .into_bytes()
+            )
+        })
+    }
+}
+
+/// Create a client serving the bundled sample data
+///
+/// The client uses `DEMO_TOKEN` and has no rate limiter, so the endpoint
+/// functions can be used with it as with a real client. Dashboards can be
+/// built against the demo data and switched to a real `APIClient` later.
+///
+/// # Arguments
+///
+/// * `lang` - Language to use in the API calls
+pub fn demo_client(lang: &str) -> APIClient {
+    let mut client = APIClient::with_transport(
+        lang,
+        Some(DEMO_TOKEN.to_string()),
+        Arc::new(DemoTransport::new())
+    );
+    client.set_rate_limiter(None);
+
+    client
+}
+
+#[cfg(test)]
+mod tests {
+    use api_v2::types::APIKey;
+    use demo::*;
+
+    #[test]
+    fn fixtures() {
+        let client = demo_client("en");
+
+        assert_eq!(DemoTransport::new().endpoints().len(), 9);
+        assert!(client.has_token());
+
+        let info: APIKey = client.get("/v2/tokeninfo?v=latest", true)
+            .unwrap();
+        assert!(!info.permissions.is_empty());
+    }
+
+    #[test]
+    #[cfg(all(feature = "account", feature = "characters"))]
+    fn endpoints() {
+        use api_v2::account::*;
+        use api_v2::characters::get_character_names;
+
+        let client = demo_client("en");
+
+        assert!(get_account(&client).is_ok());
+        assert_eq!(get_account_bank(&client).unwrap().len(), 7);
+        assert!(get_account_dyes(&client).is_ok());
+        assert!(get_account_inventory(&client).is_ok());
+        assert!(get_account_materials(&client).is_ok());
+        assert!(get_account_raids(&client).is_ok());
+        assert!(get_account_wallet(&client).is_ok());
+        assert_eq!(get_character_names(&client).unwrap().len(), 3);
+        assert!(get_token_info(&client).is_ok());
+    }
+
+    #[test]
+    fn missing_endpoint() {
+        let client = demo_client("en");
+        let result = client.get::<Vec<i32>>("/v2/account/minis", true);
+
+        assert!(result.is_err());
+    }
+}
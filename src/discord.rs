@@ -0,0 +1,550 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Discord-ready formatting of high-level reports
+/// Requires the `discord` feature
+///
+/// Reports are converted to `Embed` structures that serialize to the embed
+/// objects accepted by Discord webhooks, or can be rendered as plain
+/// markdown for regular messages.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use api_v2::types::{
+    AccountAchievement,
+    DailyAchievement,
+    DailyAchievements,
+    WvWMatch,
+    WvWTeamValues
+};
+use helpers::account::{RaidWingClears, StrikeClears};
+
+
+/// Embed color used when everything has been completed
+pub const COLOR_COMPLETE: u32 = 0x2ecc71;
+/// Embed color used when there is still progress to be made
+pub const COLOR_INCOMPLETE: u32 = 0xe67e22;
+
+/// Maximum number of fields of an embed accepted by Discord
+pub const MAX_FIELDS: usize = 25;
+/// Maximum length of a field value accepted by Discord, in characters
+pub const MAX_FIELD_VALUE_LEN: usize = 1024;
+
+/// Discord embed
+#[derive(Serialize, Debug)]
+pub struct Embed {
+    /// Title of the embed
+    pub title: String,
+    /// Text shown below the title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Color of the embed border, as an RGB integer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    /// Fields of the embed
+    pub fields: Vec<EmbedField>
+}
+
+/// Single field of a Discord embed
+#[derive(Serialize, Debug)]
+pub struct EmbedField {
+    /// Name of the field
+    pub name: String,
+    /// Contents of the field
+    pub value: String,
+    /// Whether the field may be shown next to other inline fields
+    pub inline: bool
+}
+
+impl Embed {
+    /// Render the embed as Discord markdown
+    ///
+    /// The title is written in bold, followed by the description and one
+    /// underlined heading per field.
+    pub fn to_markdown(&self) -> String {
+        let mut lines = vec![format!("**{}**", self.title)];
+
+        if let Some(ref description) = self.description {
+            lines.push(description.clone());
+        }
+
+        for field in &self.fields {
+            lines.push(String::new());
+            lines.push(format!("__{}__", field.name));
+            lines.push(field.value.clone());
+        }
+
+        lines.join("\n")
+    }
+
+    /// Fit the fields of the embed within the limits of Discord
+    ///
+    /// Values longer than `MAX_FIELD_VALUE_LEN` are split between lines
+    /// into several fields, and lines that are still too long are
+    /// truncated. When there are more than `MAX_FIELDS` fields, the last
+    /// one counts the fields left out. The embeds built by this module are
+    /// already fitted.
+    pub fn fit(mut self) -> Embed {
+        let mut fields: Vec<EmbedField> = self.fields.into_iter()
+            .flat_map(split_field)
+            .collect();
+
+        if fields.len() > MAX_FIELDS {
+            let hidden = fields.len() - MAX_FIELDS + 1;
+            fields.truncate(MAX_FIELDS - 1);
+            fields.push(EmbedField {
+                name: "…".to_string(),
+                value: format!("{} more fields not shown", hidden),
+                inline: false
+            });
+        }
+
+        self.fields = fields;
+        self
+    }
+}
+
+/// Truncate a text to a number of characters, ending it with an ellipsis
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+
+    let mut result: String = text.chars().take(max - 1).collect();
+    result.push('…');
+    result
+}
+
+/// Split a field whose value is too long for Discord between its lines
+///
+/// The fields after the first are named after it, marked as continued.
+fn split_field(field: EmbedField) -> Vec<EmbedField> {
+    let mut values = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
+    let mut len = 0;
+
+    for line in field.value.split('\n') {
+        let line = truncate(line, MAX_FIELD_VALUE_LEN);
+        let line_len = line.chars().count();
+
+        // Lines are joined with a newline, which counts as a character
+        if !lines.is_empty() && len + 1 + line_len > MAX_FIELD_VALUE_LEN {
+            values.push(lines.join("\n"));
+            lines.clear();
+            len = 0;
+        }
+
+        len += line_len + if lines.is_empty() { 0 } else { 1 };
+        lines.push(line);
+    }
+    values.push(lines.join("\n"));
+
+    values.into_iter()
+        .enumerate()
+        .map(|(index, value)| EmbedField {
+            name: if index == 0 {
+                field.name.clone()
+            } else {
+                format!("{} (cont.)", field.name)
+            },
+            value: value,
+            inline: field.inline
+        })
+        .collect()
+}
+
+/// Turn an API identifier such as `vale_guardian` into `Vale Guardian`
+fn humanize(id: &str) -> String {
+    id.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Build a checklist line for an entry
+fn check_line(name: &str, done: bool) -> String {
+    format!("{} {}", if done { "✅" } else { "⬜" }, name)
+}
+
+/// Build an embed for weekly clears of groups of encounters
+///
+/// Each group is written as an inline field listing its encounters.
+///
+/// # Arguments
+///
+/// * `title` - Title of the embed
+/// * `noun` - What the encounters are called in the description
+/// * `groups` - ID, cleared encounters and remaining encounters of each
+///     group
+fn clears_embed(
+    title: &str,
+    noun: &str,
+    groups: Vec<(&str, &[&str], &[&str])>
+) -> Embed {
+    let total: usize = groups.iter()
+        .map(|&(_, cleared, remaining)| cleared.len() + remaining.len())
+        .sum();
+    let cleared: usize = groups.iter()
+        .map(|&(_, cleared, _)| cleared.len())
+        .sum();
+
+    let fields = groups.iter()
+        .map(|&(group, cleared, remaining)| {
+            let mut lines = Vec::new();
+            for encounter in cleared {
+                lines.push(check_line(&humanize(encounter), true));
+            }
+            for encounter in remaining {
+                lines.push(check_line(&humanize(encounter), false));
+            }
+
+            EmbedField {
+                name: humanize(group),
+                value: lines.join("\n"),
+                inline: true
+            }
+        })
+        .collect();
+
+    Embed {
+        title: title.to_string(),
+        description: Some(format!("{}/{} {} cleared", cleared, total, noun)),
+        color: Some(
+            if cleared == total { COLOR_COMPLETE } else { COLOR_INCOMPLETE }
+        ),
+        fields: fields
+    }.fit()
+}
+
+/// Build an embed for the weekly raid clears of an account
+///
+/// Each wing is written as an inline field listing its encounters.
+///
+/// # Arguments
+///
+/// * `clears` - Clears as obtained from `get_raid_clears()`
+pub fn raid_clears_embed(clears: &[RaidWingClears]) -> Embed {
+    clears_embed(
+        "Weekly raid clears",
+        "encounters",
+        clears.iter()
+            .map(|wing| (wing.wing, &wing.cleared[..], &wing.remaining[..]))
+            .collect()
+    )
+}
+
+/// Build an embed for the weekly strike mission clears of an account
+///
+/// Each campaign is written as an inline field listing its strike missions.
+///
+/// # Arguments
+///
+/// * `clears` - Clears as obtained from `strike_clears()`
+pub fn strike_clears_embed(clears: &[StrikeClears]) -> Embed {
+    clears_embed(
+        "Weekly strike mission clears",
+        "strike missions",
+        clears.iter()
+            .map(|campaign| {
+                (campaign.campaign, &campaign.cleared[..],
+                 &campaign.remaining[..])
+            })
+            .collect()
+    )
+}
+
+/// Build an embed for the progress on today's daily achievements
+///
+/// Categories without dailies are left out. Achievements missing from
+/// `names` are shown by ID.
+///
+/// # Arguments
+///
+/// * `dailies` - Dailies as obtained from `get_daily_achievements()`
+/// * `names` - Achievement names, indexed by achievement ID
+/// * `progress` - Progress as obtained from `get_account_achievements()`
+pub fn daily_progress_embed(
+    dailies: &DailyAchievements,
+    names: &HashMap<i32, String>,
+    progress: &[AccountAchievement]
+) -> Embed {
+    let categories: [(&str, &Vec<DailyAchievement>); 5] = [
+        ("PvE", &dailies.pve),
+        ("PvP", &dailies.pvp),
+        ("WvW", &dailies.wvw),
+        ("Fractals", &dailies.fractals),
+        ("Special", &dailies.special)
+    ];
+
+    let is_done = |id: i32| progress.iter().any(|a| a.id == id && a.done);
+    let mut total = 0;
+    let mut done = 0;
+
+    let fields = categories.iter()
+        .filter(|&&(_, list)| !list.is_empty())
+        .map(|&(category, list)| {
+            let lines: Vec<String> = list.iter()
+                .map(|daily| {
+                    let finished = is_done(daily.id);
+                    total += 1;
+                    if finished {
+                        done += 1;
+                    }
+
+                    let name = names.get(&daily.id)
+                        .cloned()
+                        .unwrap_or_else(|| format!("#{}", daily.id));
+                    check_line(&name, finished)
+                })
+                .collect();
+
+            EmbedField {
+                name: category.to_string(),
+                value: lines.join("\n"),
+                inline: false
+            }
+        })
+        .collect();
+
+    Embed {
+        title: "Daily achievements".to_string(),
+        description: Some(format!("{}/{} dailies completed", done, total)),
+        color: Some(
+            if done == total { COLOR_COMPLETE } else { COLOR_INCOMPLETE }
+        ),
+        fields: fields
+    }.fit()
+}
+
+/// Build an embed for a WvW matchup
+///
+/// Each team is written as an inline field named after its worlds, sorted
+/// by victory points. The team of `world` is starred, and the embed is
+/// colored as complete while that team leads. Worlds missing from `names`
+/// are shown by ID.
+///
+/// # Arguments
+///
+/// * `matchup` - Match as obtained from `get_wvw_match_by_world()`
+/// * `names` - World names, indexed by world ID
+/// * `world` - ID of the world to highlight
+pub fn wvw_matchup_embed(
+    matchup: &WvWMatch,
+    names: &HashMap<i32, String>,
+    world: i32
+) -> Embed {
+    let mut teams: [(&Vec<i32>, fn(&WvWTeamValues) -> i32); 3] = [
+        (&matchup.all_worlds.red, |values| values.red),
+        (&matchup.all_worlds.blue, |values| values.blue),
+        (&matchup.all_worlds.green, |values| values.green)
+    ];
+    teams.sort_by_key(|&(_, team)| Reverse((
+        team(&matchup.victory_points),
+        team(&matchup.scores)
+    )));
+
+    let fields = teams.iter()
+        .map(|&(worlds, team)| {
+            let name = worlds.iter()
+                .map(|id| {
+                    names.get(id).cloned().unwrap_or_else(|| format!("#{}", id))
+                })
+                .collect::<Vec<String>>()
+                .join(" + ");
+
+            EmbedField {
+                name: if worlds.contains(&world) {
+                    format!("⭐ {}", name)
+                } else {
+                    name
+                },
+                value: format!(
+                    "{} victory points\n{} war score\n{} kills / {} deaths",
+                    team(&matchup.victory_points),
+                    team(&matchup.scores),
+                    team(&matchup.kills),
+                    team(&matchup.deaths)
+                ),
+                inline: true
+            }
+        })
+        .collect();
+
+    Embed {
+        title: "WvW matchup".to_string(),
+        description: Some(
+            format!("Match {}, ends {}", matchup.id, matchup.end_time)
+        ),
+        color: Some(
+            if teams[0].0.contains(&world) {
+                COLOR_COMPLETE
+            } else {
+                COLOR_INCOMPLETE
+            }
+        ),
+        fields: fields
+    }.fit()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use serde_json;
+    use api_v2::types::{AccountAchievement, DailyAchievements, WvWMatch};
+    use helpers::account::{raid_clears, strike_clears};
+    use discord::*;
+
+    #[test]
+    fn raids() {
+        let cleared = vec!["vale_guardian".to_string()];
+        let embed = raid_clears_embed(&raid_clears(&cleared));
+
+        assert_eq!(embed.color, Some(COLOR_INCOMPLETE));
+        assert_eq!(embed.fields[0].name, "Spirit Vale");
+        assert!(embed.fields[0].value.starts_with("✅ Vale Guardian\n"));
+
+        let markdown = embed.to_markdown();
+        assert!(markdown.starts_with("**Weekly raid clears**\n1/"));
+        assert!(markdown.contains("__Spirit Vale__"));
+    }
+
+    #[test]
+    fn strikes() {
+        let cleared = vec!["boneskinner".to_string()];
+        let embed = strike_clears_embed(&strike_clears(&cleared));
+
+        assert_eq!(embed.title, "Weekly strike mission clears");
+        assert_eq!(
+            embed.description,
+            Some("1/14 strike missions cleared".to_string())
+        );
+        assert_eq!(embed.fields[0].name, "Icebrood Saga");
+        assert!(embed.fields[0].value.starts_with("✅ Boneskinner\n⬜ "));
+        assert!(embed.fields[2].value.ends_with("⬜ Temple Of Febe"));
+    }
+
+    #[test]
+    fn limits() {
+        let line = "x".repeat(300);
+        let field = |name: &str, value: String| EmbedField {
+            name: name.to_string(),
+            value: value,
+            inline: true
+        };
+
+        let embed = Embed {
+            title: "Limits".to_string(),
+            description: None,
+            color: None,
+            fields: vec![
+                field("Long", vec![line.as_str(); 4].join("\n")),
+                field("Line", "y".repeat(2000))
+            ]
+        }.fit();
+
+        let values: Vec<usize> = embed.fields.iter()
+            .map(|field| field.value.chars().count())
+            .collect();
+        assert_eq!(values, vec![300 * 3 + 2, 300, 1024]);
+        assert_eq!(embed.fields[1].name, "Long (cont.)");
+        assert!(embed.fields[1].inline);
+        assert!(embed.fields[2].value.ends_with("y…"));
+
+        let embed = Embed {
+            title: "Limits".to_string(),
+            description: None,
+            color: None,
+            fields: (0..30).map(|i| field(&i.to_string(), i.to_string()))
+                .collect()
+        }.fit();
+
+        assert_eq!(embed.fields.len(), MAX_FIELDS);
+        assert_eq!(embed.fields[23].name, "23");
+        assert_eq!(embed.fields[24].value, "6 more fields not shown");
+    }
+
+    #[test]
+    fn dailies() {
+        let dailies: DailyAchievements = serde_json::from_str(r#"{
+            "pve": [
+                {"id": 1, "level": {"min": 1, "max": 80},
+                 "required_access": []},
+                {"id": 2, "level": {"min": 1, "max": 80},
+                 "required_access": []}
+            ],
+            "pvp": [], "wvw": [], "fractals": [], "special": []
+        }"#).unwrap();
+        let progress: Vec<AccountAchievement> = serde_json::from_str(
+            r#"[{"id": 1, "current": 1, "max": 1, "done": true}]"#
+        ).unwrap();
+        let mut names = HashMap::new();
+        names.insert(1, "Daily Gatherer".to_string());
+
+        let embed = daily_progress_embed(&dailies, &names, &progress);
+        let json = serde_json::to_value(&embed).unwrap();
+
+        assert_eq!(embed.fields.len(), 1);
+        assert_eq!(embed.fields[0].value, "✅ Daily Gatherer\n⬜ #2");
+        assert_eq!(json["description"], "1/2 dailies completed");
+        assert_eq!(json["fields"][0]["inline"], false);
+    }
+
+    #[test]
+    fn matchup() {
+        let matchup: WvWMatch = serde_json::from_str(r#"{
+            "id": "2-1",
+            "start_time": "2017-06-09T18:00:00Z",
+            "end_time": "2017-06-16T18:00:00Z",
+            "scores": {"red": 1000, "blue": 900, "green": 1200},
+            "worlds": {"red": 2003, "blue": 2013, "green": 2101},
+            "all_worlds": {
+                "red": [2003, 2012], "blue": [2013], "green": [2101]
+            },
+            "deaths": {"red": 10, "blue": 20, "green": 30},
+            "kills": {"red": 40, "blue": 50, "green": 60},
+            "victory_points": {"red": 221, "blue": 187, "green": 221}
+        }"#).unwrap();
+        let mut names = HashMap::new();
+        names.insert(2003, "Gandara".to_string());
+        names.insert(2101, "Jade Sea".to_string());
+
+        let embed = wvw_matchup_embed(&matchup, &names, 2003);
+        let fields: Vec<&str> = embed.fields.iter()
+            .map(|field| field.name.as_str())
+            .collect();
+
+        assert_eq!(fields, vec!["Jade Sea", "⭐ Gandara + #2012", "#2013"]);
+        assert_eq!(
+            embed.fields[1].value,
+            "221 victory points\n1000 war score\n40 kills / 10 deaths"
+        );
+        assert_eq!(embed.color, Some(COLOR_INCOMPLETE));
+        assert!(embed.to_markdown().starts_with("**WvW matchup**\nMatch 2-1"));
+    }
+}
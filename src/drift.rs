@@ -0,0 +1,842 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Detection of changes in the API responses (schema drift)
+/// Requires the `drift` feature
+///
+/// A sample of each endpoint in the registry is fetched and deserialized
+/// into its type while recording the fields that the type does not know
+/// about. This detects new fields added to the API, as well as fields that
+/// are no longer returned, before they break applications.
+///
+/// The same checks can be applied to every response parsed by a client by
+/// enabling its strict mode (see `StrictMode`).
+
+use std::io::Read;
+
+use serde::de::DeserializeOwned;
+use serde_ignored;
+use serde_json::{self, Map, Value};
+
+use client::APIClient;
+use common::{encode_param, page_to_param};
+use api_v2::{compat, registry};
+use api_v2::types::*;
+
+
+/// How a client treats fields of a response that its type does not know
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StrictMode {
+    /// Unknown fields are ignored
+    Off,
+    /// Unknown fields are reported through `APIClient::take_drift_reports()`
+    Collect,
+    /// Unknown fields are reported and the request fails, as with
+    /// `#[serde(deny_unknown_fields)]`
+    Deny
+}
+
+impl Default for StrictMode {
+    fn default() -> StrictMode {
+        StrictMode::Off
+    }
+}
+
+/// Result of checking a single endpoint
+#[derive(Debug, Clone)]
+pub struct DriftReport {
+    /// Endpoint checked
+    pub endpoint: String,
+    /// Name of the type the response was deserialized into
+    pub type_name: &'static str,
+    /// Paths of the fields in the response that the type ignores (e.g.
+    /// `0.details.new_field`)
+    pub unknown_fields: Vec<String>,
+    /// Fields of the type absent from every entry of the response, which
+    /// may have been removed from the API (see `missing_fields()`)
+    pub missing_fields: Vec<String>,
+    /// Error obtained when the response could not be fetched or
+    /// deserialized (e.g. a required field was removed)
+    pub error: Option<String>
+}
+
+impl DriftReport {
+    /// Check whether the response matches the type exactly
+    pub fn is_clean(&self) -> bool {
+        self.unknown_fields.is_empty()
+            && self.missing_fields.is_empty()
+            && self.error.is_none()
+    }
+}
+
+/// Deserialize a response body, recording the fields that the type ignores
+///
+/// Returns the result of the deserialization along with the paths of the
+/// unknown fields.
+///
+/// # Arguments
+///
+/// * `body` - Raw body of the response
+pub fn deserialize<T>(
+    body: &str
+) -> (Result<T, serde_json::Error>, Vec<String>) where T: DeserializeOwned {
+    let mut unknown_fields = Vec::new();
+    let result = {
+        let mut deserializer = serde_json::Deserializer::from_str(body);
+        serde_ignored::deserialize(&mut deserializer, |path| {
+            unknown_fields.push(path.to_string())
+        })
+    };
+
+    (result, unknown_fields)
+}
+
+/// Check a response body against a type
+///
+/// Missing fields are not checked (see `missing_fields()`).
+///
+/// # Arguments
+///
+/// * `endpoint` - Endpoint the body was obtained from
+/// * `type_name` - Name of the type, for reporting
+/// * `body` - Raw body of the response
+pub fn check_body<T>(
+    endpoint: &str,
+    type_name: &'static str,
+    body: &str
+) -> DriftReport where T: DeserializeOwned {
+    let (result, unknown_fields) = deserialize::<T>(body);
+
+    DriftReport {
+        endpoint: endpoint.to_string(),
+        type_name: type_name,
+        unknown_fields: unknown_fields,
+        missing_fields: Vec::new(),
+        error: result.err().map(|e| e.to_string())
+    }
+}
+
+/// Fields of a type that are missing from a response
+///
+/// Returns the fields that are absent from every entry of the response,
+/// which may be optional fields that the API no longer returns. Only the
+/// top-level entries are checked: the response itself if it is an object,
+/// or the objects it contains if it is a list.
+///
+/// # Arguments
+///
+/// * `body` - Raw body of the response
+/// * `fields` - Names of the fields of the type of the entries
+pub fn missing_fields(body: &str, fields: &[&str]) -> Vec<String> {
+    let value: Value = match serde_json::from_str(body) {
+        Ok(value) => value,
+        Err(_) => return Vec::new()
+    };
+
+    let entries: Vec<&Map<String, Value>> = match value {
+        Value::Array(ref values) => values.iter()
+            .filter_map(|value| value.as_object())
+            .collect(),
+        Value::Object(ref map) => vec![map],
+        _ => Vec::new()
+    };
+
+    if entries.is_empty() {
+        return Vec::new();
+    }
+
+    fields.iter()
+        .filter(|field| {
+            !entries.iter().any(|entry| entry.contains_key(**field))
+        })
+        .map(|field| field.to_string())
+        .collect()
+}
+
+/// Fetch the body of an endpoint
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `endpoint` - Endpoint to fetch
+/// * `authenticated` - Whether the endpoint requires authentication
+fn fetch_body(
+    client: &APIClient,
+    endpoint: &str,
+    authenticated: bool
+) -> Result<String, String> {
+    let response = if authenticated {
+        client.make_authenticated_request(endpoint)
+    } else {
+        client.make_request(endpoint)
+    };
+
+    let mut body = String::new();
+    match response {
+        Ok(mut response) => {
+            if !response.status().is_success() {
+                Err(format!("unexpected status: {}", response.status()))
+            } else {
+                response.read_to_string(&mut body)
+                    .map(|_| body)
+                    .map_err(|e| e.to_string())
+            }
+        },
+        Err(e) => Err(e.description().to_string())
+    }
+}
+
+/// Report of an endpoint that could not be checked
+fn error_report(
+    endpoint: &str,
+    type_name: &'static str,
+    error: String
+) -> DriftReport {
+    DriftReport {
+        endpoint: endpoint.to_string(),
+        type_name: type_name,
+        unknown_fields: Vec::new(),
+        missing_fields: Vec::new(),
+        error: Some(error)
+    }
+}
+
+/// Fetch an endpoint and check its response against a type
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `endpoint` - Endpoint to fetch
+/// * `type_name` - Name of the type, for reporting
+/// * `authenticated` - Whether the endpoint requires authentication
+pub fn check_endpoint<T>(
+    client: &APIClient,
+    endpoint: &str,
+    type_name: &'static str,
+    authenticated: bool
+) -> DriftReport where T: DeserializeOwned {
+    match fetch_body(client, endpoint, authenticated) {
+        Ok(body) => check_body::<T>(endpoint, type_name, &body),
+        Err(e) => error_report(endpoint, type_name, e)
+    }
+}
+
+/// How the sample of an endpoint is obtained
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// The endpoint is fetched as is
+    Whole,
+    /// The first page of the endpoint is fetched, with as many entries as
+    /// a bulk request allows
+    Page,
+    /// The endpoint returns a list of IDs, and the first entry is fetched
+    /// by appending its ID to the path
+    First
+}
+
+/// Sample of an endpoint to check
+struct Sample {
+    /// Path of the endpoint in the registry. It may include the query
+    /// string required by the endpoint
+    path: &'static str,
+    /// How the sample is obtained
+    mode: Mode,
+    /// Name of the type, for reporting
+    type_name: &'static str,
+    /// Check of a response against the type
+    check: fn(&str, &'static str, &str) -> DriftReport,
+    /// Names of the fields of the entries of the response
+    fields: fn() -> &'static [&'static str]
+}
+
+/// Build the sample of an endpoint
+///
+/// Responses are checked against the given type, or a list of it when
+/// fetching a page. Missing fields are checked against the type of the
+/// entries, if given.
+macro_rules! sample {
+    (Page, $path: expr, $ty: ty) => {
+        Sample {
+            path: $path,
+            mode: Mode::Page,
+            type_name: concat!("Vec<", stringify!($ty), ">"),
+            check: check_body::<Vec<$ty>>,
+            fields: compat::struct_fields::<$ty>
+        }
+    };
+    ($mode: ident, $path: expr, $ty: ty) => {
+        sample!($mode, $path, $ty, $ty)
+    };
+    ($mode: ident, $path: expr, $ty: ty, $entry: ty) => {
+        Sample {
+            path: $path,
+            mode: Mode::$mode,
+            type_name: stringify!($ty),
+            check: check_body::<$ty>,
+            fields: compat::struct_fields::<$entry>
+        }
+    };
+}
+
+/// Endpoints of the registry that are not checked, as they have side
+/// effects
+static EXCLUDED: &'static [&'static str] = &["/v2/createsubtoken"];
+
+/// Sample of every endpoint of the registry, sorted by path
+static SAMPLES: &'static [Sample] = &[
+    sample!(Whole, "/v2/account", Account),
+    sample!(
+        Whole,
+        "/v2/account/achievements",
+        Vec<AccountAchievement>,
+        AccountAchievement
+    ),
+    sample!(Whole, "/v2/account/bank", Vec<Option<BankSlot>>, BankSlot),
+    sample!(Whole, "/v2/account/dailycrafting", Vec<String>),
+    sample!(Whole, "/v2/account/dungeons", Vec<String>),
+    sample!(Whole, "/v2/account/dyes", Vec<i32>),
+    sample!(
+        Whole,
+        "/v2/account/finishers",
+        Vec<AccountFinisher>,
+        AccountFinisher
+    ),
+    sample!(Whole, "/v2/account/home/cats", Vec<Cat>, Cat),
+    sample!(Whole, "/v2/account/home/nodes", Vec<String>),
+    sample!(
+        Whole,
+        "/v2/account/inventory",
+        Vec<Option<InventorySlot>>,
+        InventorySlot
+    ),
+    sample!(Whole, "/v2/account/mapchests", Vec<String>),
+    sample!(
+        Whole,
+        "/v2/account/masteries",
+        Vec<AccountMastery>,
+        AccountMastery
+    ),
+    sample!(
+        Whole,
+        "/v2/account/materials",
+        Vec<AccountMaterial>,
+        AccountMaterial
+    ),
+    sample!(Whole, "/v2/account/minis", Vec<i32>),
+    sample!(Whole, "/v2/account/outfits", Vec<i32>),
+    sample!(Whole, "/v2/account/raids", Vec<String>),
+    sample!(Whole, "/v2/account/recipes", Vec<i32>),
+    sample!(Whole, "/v2/account/skins", Vec<i32>),
+    sample!(Whole, "/v2/account/titles", Vec<i32>),
+    sample!(
+        Whole,
+        "/v2/account/wallet",
+        Vec<AccountCurrency>,
+        AccountCurrency
+    ),
+    sample!(Whole, "/v2/account/worldbosses", Vec<String>),
+    sample!(Page, "/v2/achievements", Achievement),
+    sample!(Page, "/v2/achievements/categories", AchievementCategory),
+    sample!(Whole, "/v2/achievements/daily", DailyAchievements),
+    sample!(Whole, "/v2/achievements/daily/tomorrow", DailyAchievements),
+    sample!(Page, "/v2/achievements/groups", AchievementGroup),
+    sample!(Page, "/v2/backstory/answers", BackstoryAnswer),
+    sample!(Page, "/v2/backstory/questions", BackstoryQuestion),
+    sample!(Page, "/v2/characters", Character),
+    sample!(Whole, "/v2/characters/:id", Character),
+    sample!(Whole, "/v2/characters/:id/backstory", CharacterBackstory),
+    sample!(Whole, "/v2/characters/:id/core", CharacterCore),
+    sample!(Whole, "/v2/characters/:id/crafting", CharacterCrafting),
+    sample!(Whole, "/v2/characters/:id/equipment", CharacterEquipment),
+    sample!(First, "/v2/characters/:id/equipmenttabs", EquipmentTab),
+    sample!(Whole, "/v2/characters/:id/heropoints", Vec<String>),
+    sample!(Whole, "/v2/characters/:id/inventory", CharacterInventory),
+    sample!(Whole, "/v2/characters/:id/quests", Vec<i32>),
+    sample!(Whole, "/v2/characters/:id/recipes", CharacterRecipes),
+    sample!(Whole, "/v2/characters/:id/sab", SABProgress),
+    sample!(Whole, "/v2/characters/:id/skills", CharacterSkills),
+    sample!(
+        Whole,
+        "/v2/characters/:id/specializations",
+        CharacterSpecializations
+    ),
+    sample!(Whole, "/v2/characters/:id/training", CharacterTraining),
+    sample!(Page, "/v2/colors", Color),
+    sample!(Whole, "/v2/commerce/exchange", Vec<String>),
+    sample!(
+        Whole,
+        "/v2/commerce/exchange/coins?quantity=100000",
+        ExchangeRate
+    ),
+    sample!(Whole, "/v2/commerce/exchange/gems?quantity=100", ExchangeRate),
+    sample!(Page, "/v2/commerce/listings", TPItem),
+    sample!(Page, "/v2/commerce/prices", TPItemInfo),
+    sample!(
+        Whole,
+        "/v2/commerce/transactions/current/buys",
+        Vec<TPTransaction>,
+        TPTransaction
+    ),
+    sample!(
+        Whole,
+        "/v2/commerce/transactions/current/sells",
+        Vec<TPTransaction>,
+        TPTransaction
+    ),
+    sample!(
+        Whole,
+        "/v2/commerce/transactions/history/buys",
+        Vec<TPTransaction>,
+        TPTransaction
+    ),
+    sample!(
+        Whole,
+        "/v2/commerce/transactions/history/sells",
+        Vec<TPTransaction>,
+        TPTransaction
+    ),
+    sample!(Page, "/v2/continents", Continent),
+    sample!(Page, "/v2/continents/:id/floors", ContinentFloor),
+    sample!(
+        First,
+        "/v2/continents/:id/floors/:floor/regions",
+        ContinentRegion
+    ),
+    sample!(
+        First,
+        "/v2/continents/:id/floors/:floor/regions/:region/maps",
+        ContinentMap
+    ),
+    sample!(
+        Page,
+        "/v2/continents/:id/floors/:floor/regions/:region/maps/:map/pois",
+        ContinentPointOfInterest
+    ),
+    sample!(
+        Page,
+        "/v2/continents/:id/floors/:floor/regions/:region/maps/:map/sectors",
+        ContinentSector
+    ),
+    sample!(
+        Page,
+        "/v2/continents/:id/floors/:floor/regions/:region/maps/:map/tasks",
+        ContinentTask
+    ),
+    sample!(Whole, "/v2/dailycrafting", Vec<String>),
+    sample!(Page, "/v2/emblem/backgrounds", EmblemLayers),
+    sample!(Page, "/v2/emblem/foregrounds", EmblemLayers),
+    sample!(Whole, "/v2/guild/:id", Guild),
+    sample!(Whole, "/v2/guild/:id/log", Vec<GuildLog>, GuildLog),
+    sample!(
+        Whole,
+        "/v2/guild/:id/stash",
+        Vec<GuildStashSection>,
+        GuildStashSection
+    ),
+    sample!(
+        Whole,
+        "/v2/guild/:id/storage",
+        Vec<GuildStorageItem>,
+        GuildStorageItem
+    ),
+    sample!(Whole, "/v2/guild/:id/teams", Vec<GuildTeam>, GuildTeam),
+    sample!(
+        Whole,
+        "/v2/guild/:id/treasury",
+        Vec<GuildTreasuryItem>,
+        GuildTreasuryItem
+    ),
+    sample!(Whole, "/v2/guild/:id/upgrades", Vec<i32>),
+    sample!(Page, "/v2/guild/upgrades", GuildUpgrade),
+    sample!(Page, "/v2/home/cats", Cat),
+    sample!(Whole, "/v2/home/nodes", Vec<String>),
+    sample!(Page, "/v2/items", Item),
+    sample!(Page, "/v2/itemstats", ItemStat),
+    sample!(Page, "/v2/legends", Legend),
+    sample!(Whole, "/v2/mapchests", Vec<String>),
+    sample!(Page, "/v2/masteries", Mastery),
+    sample!(Page, "/v2/outfits", Outfit),
+    sample!(Page, "/v2/pets", Pet),
+    sample!(Page, "/v2/professions", Profession),
+    sample!(Whole, "/v2/pvp/seasons", Vec<String>),
+    sample!(Whole, "/v2/pvp/seasons/:id/leaderboards", Vec<String>),
+    sample!(Whole, "/v2/pvp/seasons/:id/leaderboards/:board", Vec<String>),
+    sample!(
+        Page,
+        "/v2/pvp/seasons/:id/leaderboards/:board/:region",
+        PvPLeaderboardEntry
+    ),
+    sample!(Whole, "/v2/pvp/stats", PvPStats),
+    sample!(Page, "/v2/quests", Quest),
+    sample!(Page, "/v2/races", Race),
+    sample!(Page, "/v2/skills", Skill),
+    sample!(Page, "/v2/specializations", Specialization),
+    sample!(Page, "/v2/stories", Story),
+    sample!(Page, "/v2/stories/seasons", StorySeason),
+    sample!(Whole, "/v2/tokeninfo", APIKey),
+    sample!(Page, "/v2/traits", Trait),
+    sample!(Whole, "/v2/worldbosses", Vec<String>),
+    sample!(Page, "/v2/wvw/abilities", WvWAbility),
+    sample!(Page, "/v2/wvw/matches", WvWMatch),
+    sample!(Page, "/v2/wvw/ranks", WvWRank),
+    sample!(Page, "/v2/wvw/upgrades", WvWUpgrade)
+];
+
+/// Obtain the first ID listed by an endpoint, encoded for a path
+///
+/// Returns `None` if the endpoint lists no IDs.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `endpoint` - Endpoint returning a list of IDs
+/// * `authenticated` - Whether the endpoint requires authentication
+fn first_id(
+    client: &APIClient,
+    endpoint: &str,
+    authenticated: bool
+) -> Result<Option<String>, String> {
+    let body = fetch_body(client, endpoint, authenticated)?;
+    let ids: Vec<Value> = serde_json::from_str(&body)
+        .map_err(|e| e.to_string())?;
+
+    Ok(ids.first().map(|id| match *id {
+        Value::String(ref id) => encode_param(id),
+        ref id => id.to_string()
+    }))
+}
+
+/// Obtain the ID of the first guild led by the account of the client
+///
+/// Returns `None` if the client has no token or leads no guild.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+fn guild_id(client: &APIClient) -> Result<Option<String>, String> {
+    if !client.has_token() {
+        return Ok(None);
+    }
+
+    let body = fetch_body(client, "/v2/account", true)?;
+    let account: Value = serde_json::from_str(&body)
+        .map_err(|e| e.to_string())?;
+
+    Ok(account["guild_leader"].get(0)
+        .and_then(|id| id.as_str())
+        .map(encode_param))
+}
+
+/// Replace the parameters of a path with IDs obtained from the API
+///
+/// Each parameter is replaced with the first ID listed by the path that
+/// precedes it. Guilds are the exception, as they cannot be listed, and
+/// the first guild led by the account is used instead.
+///
+/// Returns `None` if there is no ID to use for a parameter.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `path` - Path of the endpoint, with parameters written as `:name`
+/// * `authenticated` - Whether the endpoint requires authentication
+fn resolve(
+    client: &APIClient,
+    path: &str,
+    authenticated: bool
+) -> Result<Option<String>, String> {
+    let mut resolved = String::new();
+
+    for segment in path.split('/').skip(1) {
+        let id = if !segment.starts_with(':') {
+            Some(segment.to_string())
+        } else if resolved == "/v2/guild" {
+            guild_id(client)?
+        } else {
+            first_id(client, &resolved, authenticated)?
+        };
+
+        match id {
+            Some(id) => {
+                resolved.push('/');
+                resolved.push_str(&id);
+            },
+            None => return Ok(None)
+        }
+    }
+
+    Ok(Some(resolved))
+}
+
+/// Fetch the sample of an endpoint and check it
+///
+/// Returns `None` if the sample cannot be obtained, e.g. when there is no
+/// entry to use for a parameter of the path.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `sample` - Sample to check
+/// * `authenticated` - Whether the endpoint requires authentication
+fn check_sample(
+    client: &APIClient,
+    sample: &Sample,
+    authenticated: bool
+) -> Option<DriftReport> {
+    let path = match resolve(client, sample.path, authenticated) {
+        Ok(Some(path)) => path,
+        Ok(None) => return None,
+        Err(e) => {
+            return Some(error_report(sample.path, sample.type_name, e))
+        }
+    };
+
+    let endpoint = match sample.mode {
+        Mode::Whole => path,
+        Mode::Page => format!(
+            "{}?{}",
+            path,
+            page_to_param(0, MAX_BULK_IDS as i32)
+        ),
+        Mode::First => match first_id(client, &path, authenticated) {
+            Ok(Some(id)) => format!("{}/{}", path, id),
+            Ok(None) => return None,
+            Err(e) => return Some(error_report(&path, sample.type_name, e))
+        }
+    };
+
+    Some(match fetch_body(client, &endpoint, authenticated) {
+        Ok(body) => {
+            let mut report = (sample.check)(&endpoint, sample.type_name, &body);
+            report.missing_fields = missing_fields(&body, (sample.fields)());
+            report
+        },
+        Err(e) => error_report(&endpoint, sample.type_name, e)
+    })
+}
+
+/// Check a sample of every endpoint in the registry
+///
+/// Authenticated endpoints are only checked if the client has a token.
+/// Endpoints with parameters in their path are checked with the first
+/// entry listed by the API (see `resolve()`), and skipped if there is none.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+pub fn check_all(client: &APIClient) -> Vec<DriftReport> {
+    registry::endpoints().iter()
+        .filter(|endpoint| !EXCLUDED.contains(&endpoint.path))
+        .filter(|endpoint| !endpoint.authenticated || client.has_token())
+        .filter_map(|endpoint| {
+            SAMPLES.iter()
+                .find(|sample| sample_path(sample) == endpoint.path)
+                .and_then(|sample| {
+                    check_sample(client, sample, endpoint.authenticated)
+                })
+        })
+        .collect()
+}
+
+/// Path of a sample, without its query string
+fn sample_path(sample: &Sample) -> &'static str {
+    sample.path.split('?').next().unwrap_or(sample.path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::sync::Arc;
+    use client::APIClient;
+    use transport::{MockTransport, Response};
+    use api_v2::types::{
+        AccountMaterial,
+        GuildLog,
+        GuildUpgrade,
+        Outfit,
+        Skill
+    };
+    use drift::*;
+
+    #[test]
+    fn unknown_and_missing_fields() {
+        let report = check_body::<Vec<AccountMaterial>>(
+            "/v2/account/materials",
+            "Vec<AccountMaterial>",
+            r#"[{"id": 1, "category": 5, "count": 3, "new_field": true}]"#
+        );
+        assert_eq!(report.unknown_fields, vec!["0.new_field"]);
+        assert!(report.error.is_none());
+        assert!(!report.is_clean());
+
+        let report = check_body::<Vec<AccountMaterial>>(
+            "/v2/account/materials",
+            "Vec<AccountMaterial>",
+            r#"[{"id": 1, "count": 3}]"#
+        );
+        assert!(report.error.is_some());
+    }
+
+    #[test]
+    fn flattened_fields() {
+        let report = check_body::<Vec<GuildLog>>(
+            "/v2/guild/:id/log",
+            "Vec<GuildLog>",
+            r#"[{
+                "id": 1, "time": "2017-01-01T00:00:00Z", "user": "A.1234",
+                "type": "kick", "kicked_by": "B.1234", "new_field": true
+            }]"#
+        );
+        assert_eq!(report.unknown_fields, vec!["0.new_field"]);
+        assert!(report.error.is_none());
+
+        let report = check_body::<GuildUpgrade>(
+            "/v2/guild/upgrades?id=55",
+            "GuildUpgrade",
+            r#"{
+                "id": 55, "name": "Stash", "type": "BankBag",
+                "bag_max_items": 50, "bag_max_coins": 100,
+                "costs": [{"type": "Coins", "count": 1, "new_cost": 1}],
+                "new_field": 2
+            }"#
+        );
+        assert_eq!(
+            report.unknown_fields,
+            vec!["costs.0.new_cost", "new_field"]
+        );
+
+        let report = check_body::<Skill>(
+            "/v2/skills?id=5516",
+            "Skill",
+            r#"{
+                "id": 5516, "name": "Skill", "icon": "", "chat_link": "",
+                "type": "Weapon", "weapon_type": "Sword", "professions": [],
+                "slot": "Weapon_1", "traited_facts": [{
+                    "requires_trait": 1, "text": "Range", "type": "Range",
+                    "value": 1200, "new_field": 3
+                }]
+            }"#
+        );
+        assert_eq!(report.unknown_fields, vec!["traited_facts.0.new_field"]);
+        assert!(report.error.is_none());
+    }
+
+    #[test]
+    fn removed_fields() {
+        let fields = compat::struct_fields::<Outfit>();
+
+        assert!(missing_fields(
+            r#"[
+                {"id": 1, "name": "A", "icon": "", "unlock_items": []},
+                {"id": 2, "name": "B", "icon": ""}
+            ]"#,
+            fields
+        ).is_empty());
+        assert_eq!(
+            missing_fields(r#"{"id": 1, "name": "A", "icon": ""}"#, fields),
+            vec!["unlock_items"]
+        );
+        assert!(missing_fields("[]", fields).is_empty());
+    }
+
+    #[test]
+    fn registry_samples() {
+        for endpoint in registry::endpoints() {
+            if EXCLUDED.contains(&endpoint.path) {
+                continue;
+            }
+
+            let sample = SAMPLES.iter()
+                .find(|sample| sample_path(sample) == endpoint.path)
+                .expect(&format!("no sample for {}", endpoint.path));
+
+            if endpoint.bulk {
+                assert_eq!(sample.mode, Mode::Page, "{}", endpoint.path);
+            }
+        }
+
+        for sample in SAMPLES {
+            assert!(
+                registry::find_endpoint(sample.path)
+                    .map_or(false, |e| e.path == sample_path(sample)),
+                "{} is not in the registry",
+                sample.path
+            );
+        }
+    }
+
+    #[test]
+    fn sample_paths() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/continents", Response::json("[1, 2]"));
+        transport.respond("/v2/continents/1/floors", Response::json("[0]"));
+        transport.respond(
+            "/v2/continents/1/floors/0/regions",
+            Response::json("[4, 5]")
+        );
+        transport.respond(
+            "/v2/pvp/seasons",
+            Response::json(r#"["2B2E80D3-0A57-4A3B"]"#)
+        );
+
+        let client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let reports = check_all(&client);
+        let requests = transport.requests();
+
+        assert!(requests.contains(
+            &"/v2/items?page=0&page_size=200".to_string()
+        ));
+        assert!(requests.contains(
+            &"/v2/continents/1/floors/0/regions/4".to_string()
+        ));
+        assert!(requests.contains(
+            &"/v2/pvp/seasons/2B2E80D3-0A57-4A3B/leaderboards".to_string()
+        ));
+        assert!(requests.iter().all(|request| {
+            !request.starts_with("/v2/account")
+                && !request.starts_with("/v2/characters")
+                && (!request.starts_with("/v2/guild/")
+                    || request.starts_with("/v2/guild/upgrades"))
+        }));
+        assert!(reports.iter().any(|report| {
+            report.endpoint == "/v2/continents/1/floors/0/regions/4"
+                && report.type_name == "ContinentRegion"
+        }));
+    }
+
+    #[test]
+    fn conformance() {
+        let token = env::var("TOKEN").ok();
+        let client = APIClient::new("en", token);
+
+        let drifted: Vec<DriftReport> = check_all(&client).into_iter()
+            .filter(|report| !report.is_clean())
+            .collect();
+
+        assert!(drifted.is_empty(), "schema drift detected: {:#?}", drifted);
+    }
+}
@@ -0,0 +1,303 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// CSV export of tabular account data
+/// Requires the `csv` feature
+
+use std::io::Write;
+
+use csv;
+
+use api_v2::types::{
+    AccountCurrency,
+    AccountMaterial,
+    BankSlot,
+    TPTransaction,
+    Timestamp
+};
+
+
+/// Row written for each wallet entry
+#[derive(Serialize)]
+struct WalletRow {
+    id: i32,
+    value: i32
+}
+
+/// Row written for each material storage entry
+#[derive(Serialize)]
+struct MaterialRow {
+    id: i32,
+    category: i32,
+    count: i32
+}
+
+/// Row written for each occupied bank slot
+#[derive(Serialize)]
+struct BankSlotRow<'a> {
+    slot: usize,
+    id: i32,
+    count: i32,
+    skin: i32,
+    upgrades: String,
+    infusions: String,
+    binding: &'a str,
+    charges: i32,
+    bound_to: &'a str
+}
+
+/// Row written for each trading post transaction
+#[derive(Serialize)]
+struct TransactionRow<'a> {
+    id: i64,
+    item_id: i32,
+    price: i32,
+    quantity: i32,
+    created: &'a Timestamp,
+    purchased: Option<&'a Timestamp>
+}
+
+/// Join a list of IDs in a single CSV field, separated by semicolons
+fn join_ids(ids: &Vec<i32>) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(";")
+}
+
+/// Write the currencies of an account's wallet as CSV
+///
+/// Columns: `id`, `value`
+///
+/// # Arguments
+///
+/// * `writer` - Destination of the CSV data
+/// * `wallet` - Wallet entries as obtained from `get_account_wallet()`
+pub fn wallet_to_csv<W: Write>(
+    writer: W,
+    wallet: &[AccountCurrency]
+) -> csv::Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+
+    for currency in wallet {
+        csv_writer.serialize(WalletRow {
+            id: currency.id,
+            value: currency.value
+        })?;
+    }
+
+    csv_writer.flush()?;
+    Ok(())
+}
+
+/// Write the contents of an account's material storage as CSV
+///
+/// Columns: `id`, `category`, `count`
+///
+/// # Arguments
+///
+/// * `writer` - Destination of the CSV data
+/// * `materials` - Materials as obtained from `get_account_materials()`
+pub fn materials_to_csv<W: Write>(
+    writer: W,
+    materials: &[AccountMaterial]
+) -> csv::Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+
+    for material in materials {
+        csv_writer.serialize(MaterialRow {
+            id: material.id,
+            category: material.category,
+            count: material.count
+        })?;
+    }
+
+    csv_writer.flush()?;
+    Ok(())
+}
+
+/// Write the contents of an account's bank as CSV
+///
+/// Empty slots are skipped, but the `slot` column keeps the original index
+/// of each item in the vault. Upgrades and infusions are written as
+/// semicolon-separated item IDs.
+///
+/// Columns: `slot`, `id`, `count`, `skin`, `upgrades`, `infusions`,
+/// `binding`, `charges`, `bound_to`
+///
+/// # Arguments
+///
+/// * `writer` - Destination of the CSV data
+/// * `bank` - Bank slots as obtained from `get_account_bank()`
+pub fn bank_to_csv<W: Write>(
+    writer: W,
+    bank: &[Option<BankSlot>]
+) -> csv::Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+
+    for (index, slot) in bank.iter().enumerate() {
+        if let Some(ref item) = *slot {
+            csv_writer.serialize(BankSlotRow {
+                slot: index,
+                id: item.id,
+                count: item.count,
+                skin: item.skin,
+                upgrades: join_ids(&item.upgrades),
+                infusions: join_ids(&item.infusions),
+                binding: &item.binding,
+                charges: item.charges,
+                bound_to: &item.bound_to
+            })?;
+        }
+    }
+
+    csv_writer.flush()?;
+    Ok(())
+}
+
+/// Write trading post transactions as CSV
+///
+/// Dates are written in RFC 3339 format. The `purchased` column is empty for
+/// current (unfulfilled) transactions.
+///
+/// Columns: `id`, `item_id`, `price`, `quantity`, `created`, `purchased`
+///
+/// # Arguments
+///
+/// * `writer` - Destination of the CSV data
+/// * `transactions` - Transactions as obtained from any of the
+///     `get_*_transactions()` functions
+pub fn transactions_to_csv<W: Write>(
+    writer: W,
+    transactions: &[TPTransaction]
+) -> csv::Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+
+    for transaction in transactions {
+        csv_writer.serialize(TransactionRow {
+            id: transaction.id,
+            item_id: transaction.item_id,
+            price: transaction.price,
+            quantity: transaction.quantity,
+            created: &transaction.created,
+            purchased: transaction.purchased.as_ref()
+        })?;
+    }
+
+    csv_writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use export::*;
+
+    /// Obtain the CSV written by an export function as a string
+    fn to_string<F>(export: F) -> String
+        where F: FnOnce(&mut Vec<u8>) -> csv::Result<()> {
+
+        let mut output = Vec::new();
+        export(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn wallet() {
+        let wallet: Vec<AccountCurrency> = serde_json::from_str(
+            r#"[{"id": 1, "value": 1234567}, {"id": 4, "value": 0}]"#
+        ).unwrap();
+
+        assert_eq!(
+            to_string(|output| wallet_to_csv(output, &wallet)),
+            "id,value\n1,1234567\n4,0\n"
+        );
+        assert_eq!(to_string(|output| wallet_to_csv(output, &[])), "");
+    }
+
+    #[test]
+    fn materials() {
+        let materials: Vec<AccountMaterial> = serde_json::from_str(
+            r#"[{"id": 19721, "category": 5, "count": 250}]"#
+        ).unwrap();
+
+        assert_eq!(
+            to_string(|output| materials_to_csv(output, &materials)),
+            "id,category,count\n19721,5,250\n"
+        );
+    }
+
+    #[test]
+    fn bank() {
+        let bank: Vec<Option<BankSlot>> = serde_json::from_str(r#"[
+            null,
+            {
+                "id": 30689,
+                "count": 1,
+                "skin": 4678,
+                "upgrades": [24815, 24618],
+                "infusions": [49432],
+                "binding": "Character",
+                "bound_to": "Eir \"Stegalkin\", the Huntress"
+            },
+            {"id": 19721, "count": 250, "charges": 3}
+        ]"#).unwrap();
+
+        assert_eq!(
+            to_string(|output| bank_to_csv(output, &bank)),
+            "slot,id,count,skin,upgrades,infusions,binding,charges,\
+             bound_to\n\
+             1,30689,1,4678,24815;24618,49432,Character,0,\
+             \"Eir \"\"Stegalkin\"\", the Huntress\"\n\
+             2,19721,250,0,,,,3,\n"
+        );
+    }
+
+    #[test]
+    fn transactions() {
+        let transactions: Vec<TPTransaction> = serde_json::from_str(r#"[
+            {
+                "id": 2041478574,
+                "item_id": 19721,
+                "price": 120,
+                "quantity": 25,
+                "created": "2017-06-08T19:42:35Z",
+                "purchased": "2017-06-09T08:01:12Z"
+            },
+            {
+                "id": 2041478575,
+                "item_id": 24,
+                "price": 1,
+                "quantity": 1,
+                "created": "2017-06-10T10:00:00Z"
+            }
+        ]"#).unwrap();
+
+        assert_eq!(
+            to_string(|output| transactions_to_csv(output, &transactions)),
+            "id,item_id,price,quantity,created,purchased\n\
+             2041478574,19721,120,25,2017-06-08T19:42:35Z,\
+             2017-06-09T08:01:12Z\n\
+             2041478575,24,1,1,2017-06-10T10:00:00Z,\n"
+        );
+    }
+}
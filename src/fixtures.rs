@@ -0,0 +1,434 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Recording and replaying of API responses
+/// Responses from the live API can be recorded to JSON fixtures and served
+/// back in tests, so that the parsing of every type is checked without
+/// network access or an API token
+///
+/// The endpoint tests in `api_v2` replay the fixtures in `fixtures/api`.
+/// To refresh them from the live API, run the tests with
+/// `TYRIA_FIXTURES=record` and a `TOKEN` with every permission. The
+/// character and guild tests request `CHAR_NAME` and `GUILD_ID`, which
+/// default to the ones stored in the fixtures.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::de::IgnoredAny;
+use reqwest::StatusCode;
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_LANGUAGE};
+use serde_json;
+
+use common::{encode_param, APIError};
+#[cfg(test)]
+use client::APIClient;
+use transport::{endpoint_of, HttpTransport, Response, Transport};
+
+
+/// Environment variable enabling recording in `fixture_transport()`
+pub const RECORD_VAR: &'static str = "TYRIA_FIXTURES";
+
+/// Response stored in a fixture file
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    /// Endpoint requested, including the query string
+    endpoint: String,
+    /// Language requested in the `Accept-Language` header, if any
+    #[serde(default)]
+    language: Option<String>,
+    /// HTTP status code of the response
+    status: u16,
+    /// Headers of the response
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    /// Raw body of the response
+    body: String,
+    /// Whether the body is valid JSON
+    #[serde(default)]
+    is_json: bool
+}
+
+/// Obtain the path of the fixture of an endpoint
+///
+/// Fixtures are stored in a directory per language, and named after the
+/// endpoint with every character other than letters, digits, `-`, `.`,
+/// `_` and `~` percent-encoded, so different endpoints never share a file.
+/// Requests without a language are stored directly in `dir`.
+///
+/// # Arguments
+///
+/// * `dir` - Directory containing the fixtures
+/// * `language` - Language requested, if any
+/// * `endpoint` - Path of the endpoint, including the query string
+pub fn fixture_path(
+    dir: &Path,
+    language: Option<&str>,
+    endpoint: &str
+) -> PathBuf {
+    let name = format!("{}.json", encode_param(endpoint));
+
+    match language {
+        Some(language) => dir.join(encode_param(language)).join(name),
+        None => dir.join(name)
+    }
+}
+
+/// Obtain the language requested in the headers of a request
+fn request_language(headers: &HeaderMap) -> Option<String> {
+    headers.get(ACCEPT_LANGUAGE)
+        .and_then(|languages| languages.to_str().ok())
+        .and_then(|languages| languages.split(',').next())
+        .and_then(|language| language.split(';').next())
+        .map(|language| language.trim().to_string())
+        .filter(|language| !language.is_empty())
+}
+
+/// Obtain the error to return when a fixture cannot be read or written
+fn fixture_error(context: &str, path: &Path, message: &str) -> APIError {
+    APIError::new(
+        format!("{} {}: {}", context, path.display(), message).as_str()
+    )
+}
+
+/// Transport storing each response received in a fixture file
+///
+/// Existing fixtures are overwritten. Bodies are stored as received, along
+/// with whether they are valid JSON.
+pub struct RecordingTransport {
+    /// Transport performing the actual requests
+    inner: Arc<dyn Transport>,
+    /// Directory to store the fixtures in
+    dir: PathBuf
+}
+
+impl RecordingTransport {
+    /// Create a new recording transport
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - Transport performing the actual requests
+    /// * `dir` - Directory to store the fixtures in
+    pub fn new<P: AsRef<Path>>(
+        inner: Arc<dyn Transport>,
+        dir: P
+    ) -> RecordingTransport {
+        RecordingTransport {
+            inner: inner,
+            dir: dir.as_ref().to_path_buf()
+        }
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn get(&self, url: &str, headers: HeaderMap) -> Result<Response, APIError> {
+        let language = request_language(&headers);
+        let mut response = self.inner.get(url, headers)?;
+        let endpoint = endpoint_of(url);
+        let path = fixture_path(
+            &self.dir,
+            language.as_ref().map(|language| language.as_str()),
+            endpoint
+        );
+
+        let mut body = Vec::new();
+        response.read_to_end(&mut body).map_err(|e| {
+            fixture_error("failed to read response for", &path, &e.to_string())
+        })?;
+
+        let fixture = Fixture {
+            endpoint: endpoint.to_string(),
+            language: language,
+            status: response.status().as_u16(),
+            headers: response.headers().iter()
+                .map(|(name, value)| {
+                    (
+                        name.to_string(),
+                        String::from_utf8_lossy(value.as_bytes()).into_owned()
+                    )
+                })
+                .collect(),
+            body: String::from_utf8_lossy(&body).into_owned(),
+            is_json: serde_json::from_slice::<IgnoredAny>(&body).is_ok()
+        };
+
+        let data = serde_json::to_string_pretty(&fixture).map_err(|e| {
+            fixture_error("failed to serialize", &path, &e.to_string())
+        })?;
+        fs::create_dir_all(path.parent().unwrap_or(&self.dir))
+            .and_then(|_| fs::write(&path, data))
+            .map_err(|e| {
+                fixture_error("failed to write", &path, &e.to_string())
+            })?;
+
+        Ok(Response::new(*response.status(), response.headers().clone(), body))
+    }
+}
+
+/// Transport serving the responses stored in fixture files
+///
+/// Requests to endpoints without a fixture fail, so that missing fixtures
+/// are not mistaken for missing resources.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    /// Directory containing the fixtures
+    dir: PathBuf
+}
+
+impl ReplayTransport {
+    /// Create a new replaying transport
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory containing the fixtures
+    pub fn new<P: AsRef<Path>>(dir: P) -> ReplayTransport {
+        ReplayTransport {
+            dir: dir.as_ref().to_path_buf()
+        }
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn get(&self, url: &str, headers: HeaderMap) -> Result<Response, APIError> {
+        let language = request_language(&headers);
+        let path = fixture_path(
+            &self.dir,
+            language.as_ref().map(|language| language.as_str()),
+            endpoint_of(url)
+        );
+
+        let data = fs::read(&path).map_err(|e| {
+            fixture_error("failed to read fixture", &path, &e.to_string())
+        })?;
+        let fixture: Fixture = serde_json::from_slice(&data).map_err(|e| {
+            fixture_error("failed to parse fixture", &path, &e.to_string())
+        })?;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in fixture.headers {
+            let name = HeaderName::from_bytes(name.as_bytes());
+            let value = HeaderValue::from_str(&value);
+            match (name, value) {
+                (Ok(name), Ok(value)) => {
+                    headers.append(name, value);
+                },
+                _ => return Err(fixture_error(
+                    "invalid header in fixture",
+                    &path,
+                    "not a valid HTTP header"
+                ))
+            }
+        }
+
+        let status = StatusCode::from_u16(fixture.status).map_err(|e| {
+            fixture_error("invalid status in fixture", &path, &e.to_string())
+        })?;
+
+        Ok(Response::new(status, headers, fixture.body.into_bytes()))
+    }
+}
+
+/// Obtain a transport for tests backed by fixtures
+///
+/// When the `TYRIA_FIXTURES` environment variable is set to `record`,
+/// requests are performed against the live API and their responses stored
+/// in `dir`. Otherwise, responses are replayed from `dir`.
+///
+/// # Arguments
+///
+/// * `dir` - Directory containing the fixtures
+///
+/// # Example
+///
+/// ```no_run
+/// use tyria::client::APIClient;
+/// use tyria::fixtures::fixture_transport;
+///
+/// let mut client = APIClient::new("en", None);
+/// client.set_transport(fixture_transport("fixtures").unwrap());
+/// ```
+pub fn fixture_transport<P: AsRef<Path>>(
+    dir: P
+) -> Result<Arc<dyn Transport>, APIError> {
+    match env::var(RECORD_VAR) {
+        Ok(ref mode) if mode == "record" => {
+            let client = Client::builder().build().map_err(|e| {
+                APIError::new(
+                    format!("failed to create HTTP client: {}", e).as_str()
+                )
+            })?;
+
+            Ok(Arc::new(RecordingTransport::new(
+                Arc::new(HttpTransport::new(client)),
+                dir
+            )))
+        },
+        _ => Ok(Arc::new(ReplayTransport::new(dir)))
+    }
+}
+
+
+/// Directory containing the fixtures of the endpoint tests
+#[cfg(test)]
+#[cfg_attr(not(feature = "endpoints"), allow(dead_code))]
+pub(crate) const TEST_FIXTURES: &'static str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/api");
+
+/// Obtain a client for the endpoint tests, backed by `TEST_FIXTURES`
+///
+/// The token is read from the `TOKEN` environment variable, which is only
+/// required when recording.
+#[cfg(test)]
+#[cfg_attr(not(feature = "endpoints"), allow(dead_code))]
+pub(crate) fn test_client() -> APIClient {
+    let token = env::var("TOKEN").unwrap_or_else(|_| "fixture".to_string());
+    let mut client = APIClient::new("en", Some(token));
+    client.set_transport(fixture_transport(TEST_FIXTURES).unwrap());
+    client.set_retry_policy(None);
+    client
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::io::Read;
+    use std::path::Path;
+    use std::sync::Arc;
+    use reqwest::header::{HeaderMap, HeaderValue, ACCEPT_LANGUAGE};
+    use fixtures::*;
+    use transport::{MockTransport, Response, Transport};
+
+    fn headers(language: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCEPT_LANGUAGE,
+            HeaderValue::from_str(language).unwrap()
+        );
+        headers
+    }
+
+    fn read_body(mut response: Response) -> String {
+        let mut body = String::new();
+        response.read_to_string(&mut body).unwrap();
+        body
+    }
+
+    #[test]
+    fn paths() {
+        let dir = Path::new("fixtures");
+
+        assert_eq!(
+            fixture_path(dir, Some("en"), "/v2/items?ids=1,2"),
+            dir.join("en").join("%2Fv2%2Fitems%3Fids%3D1%2C2.json")
+        );
+        assert_eq!(
+            fixture_path(dir, None, "/v2/build"),
+            dir.join("%2Fv2%2Fbuild.json")
+        );
+
+        let endpoints = [
+            "/v2/items?ids=1,2",
+            "/v2/items?ids=1_2",
+            "/v2/items/ids=1,2",
+            "/v2/items_ids_1_2"
+        ];
+        for (i, a) in endpoints.iter().enumerate() {
+            for b in &endpoints[i + 1..] {
+                assert_ne!(
+                    fixture_path(dir, Some("en"), a),
+                    fixture_path(dir, Some("en"), b)
+                );
+            }
+        }
+
+        assert_ne!(
+            fixture_path(dir, Some("en"), "/v2/items?id=1"),
+            fixture_path(dir, Some("de"), "/v2/items?id=1")
+        );
+    }
+
+    #[test]
+    fn record_replay() {
+        let dir = env::temp_dir().join("tyria-fixtures-test");
+        let _ = fs::remove_dir_all(&dir);
+
+        let mock = Arc::new(MockTransport::new());
+        mock.respond("/v2/build", Response::json(r#"{"id": 115267}"#));
+        mock.respond("/v2/items?id=1", Response::json(r#"{"id": 1}"#));
+        mock.respond("/v2/empty", Response::json(""));
+        mock.respond("/v2/text", Response::json("not found"));
+
+        let recording = RecordingTransport::new(mock, &dir);
+        for endpoint in &["/v2/build", "/v2/items?id=1"] {
+            for language in &["en", "de"] {
+                recording.get(
+                    &format!("https://api.guildwars2.com{}", endpoint),
+                    headers(language)
+                ).unwrap();
+            }
+        }
+        for endpoint in &["/v2/empty", "/v2/text"] {
+            recording.get(
+                &format!("https://api.guildwars2.com{}", endpoint),
+                HeaderMap::new()
+            ).unwrap();
+        }
+        assert!(fixture_path(&dir, Some("en"), "/v2/build").exists());
+        assert!(fixture_path(&dir, Some("de"), "/v2/build").exists());
+
+        let replay = ReplayTransport::new(&dir);
+        let response = replay
+            .get("https://api.guildwars2.com/v2/build", headers("de"))
+            .unwrap();
+        assert_eq!(read_body(response), r#"{"id": 115267}"#);
+
+        let response = replay
+            .get("https://api.guildwars2.com/v2/empty", HeaderMap::new())
+            .unwrap();
+        assert_eq!(read_body(response), "");
+
+        let response = replay
+            .get("https://api.guildwars2.com/v2/text", HeaderMap::new())
+            .unwrap();
+        assert_eq!(read_body(response), "not found");
+
+        let data = fs::read_to_string(
+            fixture_path(&dir, None, "/v2/text")
+        ).unwrap();
+        assert!(data.contains(r#""is_json": false"#));
+
+        assert!(replay
+            .get("https://api.guildwars2.com/v2/build", headers("fr"))
+            .is_err());
+        assert!(replay
+            .get("https://api.guildwars2.com/v2/items", headers("en"))
+            .is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,493 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Account-wide helpers
+/// These require an API key to obtain the data
+
+use std::collections::HashSet;
+
+use client::APIClient;
+use common::APIError;
+use api_v2::account::{
+    get_account_cats,
+    get_account_dailycrafting,
+    get_account_mapchests,
+    get_account_nodes,
+    get_account_raids,
+    get_account_recipes,
+    get_account_worldbosses
+};
+use api_v2::characters::{
+    get_character_crafting,
+    get_character_names,
+    get_character_recipes
+};
+use api_v2::daily::{
+    get_dailycrafting_ids,
+    get_mapchest_ids,
+    get_worldboss_ids
+};
+use api_v2::home::{get_cat_ids, get_node_ids};
+
+
+/// Raid wings and their encounters, as identified by the API
+pub const RAID_WINGS: [(&'static str, &'static [&'static str]); 7] = [
+    ("spirit_vale",
+     &["vale_guardian", "spirit_woods", "gorseval", "sabetha"]),
+    ("salvation_pass",
+     &["slothasor", "bandit_trio", "matthias"]),
+    ("stronghold_of_the_faithful",
+     &["escort", "keep_construct", "twisted_castle", "xera"]),
+    ("bastion_of_the_penitent",
+     &["cairn", "mursaat_overseer", "samarog", "deimos"]),
+    ("hall_of_chains",
+     &["soulless_horror", "river_of_souls", "statues_of_grenth",
+       "voice_in_the_void"]),
+    ("mythwright_gambit",
+     &["conjured_amalgamate", "twin_largos", "qadim"]),
+    ("the_key_of_ahdashim",
+     &["gate", "adina", "sabir", "qadim_the_peerless"])
+];
+
+/// Strike missions of each campaign
+///
+/// The API does not report strike mission clears, so these identifiers are
+/// only used by `strike_clears()`.
+pub const STRIKE_MISSIONS: [(&'static str, &'static [&'static str]); 3] = [
+    ("icebrood_saga",
+     &["shiverpeaks_pass", "voice_and_claw", "fraenir_of_jormag",
+       "boneskinner", "whisper_of_jormag", "forging_steel", "cold_war"]),
+    ("end_of_dragons",
+     &["aetherblade_hideout", "xunlai_jade_junkyard", "kaineng_overlook",
+       "harvest_temple", "old_lions_court"]),
+    ("secrets_of_the_obscure",
+     &["cosmic_observatory", "temple_of_febe"])
+];
+
+/// Weekly clears of a raid wing
+#[derive(Debug)]
+pub struct RaidWingClears {
+    /// Wing ID
+    pub wing: &'static str,
+    /// Encounters cleared this week
+    pub cleared: Vec<&'static str>,
+    /// Encounters not cleared yet this week
+    pub remaining: Vec<&'static str>
+}
+
+impl RaidWingClears {
+    /// Check whether all the encounters of the wing have been cleared
+    pub fn is_complete(&self) -> bool {
+        self.remaining.is_empty()
+    }
+}
+
+/// Weekly clears of the strike missions of a campaign
+#[derive(Debug)]
+pub struct StrikeClears {
+    /// Campaign ID
+    pub campaign: &'static str,
+    /// Strike missions cleared this week
+    pub cleared: Vec<&'static str>,
+    /// Strike missions not cleared yet this week
+    pub remaining: Vec<&'static str>
+}
+
+impl StrikeClears {
+    /// Check whether all the strike missions of the campaign have been
+    /// cleared
+    pub fn is_complete(&self) -> bool {
+        self.remaining.is_empty()
+    }
+}
+
+/// Rewards not yet obtained since daily reset
+#[derive(Debug)]
+pub struct DailyResetProgress {
+    /// Time-gated items not crafted yet
+    pub dailycrafting: Vec<String>,
+    /// Hero's Choice chests not opened yet
+    pub mapchests: Vec<String>,
+    /// World bosses not defeated yet
+    pub worldbosses: Vec<String>
+}
+
+impl DailyResetProgress {
+    /// Check whether every daily reward has been obtained
+    pub fn is_complete(&self) -> bool {
+        self.dailycrafting.is_empty()
+            && self.mapchests.is_empty()
+            && self.worldbosses.is_empty()
+    }
+}
+
+/// Home instance unlocks the account is missing
+#[derive(Debug)]
+pub struct HomeUnlocks {
+    /// Cats not unlocked yet
+    pub cats: Vec<i32>,
+    /// Gathering nodes not unlocked yet
+    pub nodes: Vec<String>
+}
+
+impl HomeUnlocks {
+    /// Check whether everything in the home instance has been unlocked
+    pub fn is_complete(&self) -> bool {
+        self.cats.is_empty() && self.nodes.is_empty()
+    }
+}
+
+/// Crafting discipline unlocked by a character
+#[derive(Debug)]
+pub struct KnownDiscipline {
+    /// Name of the discipline
+    pub discipline: String,
+    /// Current crafting level for the discipline
+    pub rating: i32,
+    /// Whether the discipline is currently active on the character
+    pub active: bool
+}
+
+/// Recipes and crafting disciplines of a single character
+#[derive(Debug)]
+pub struct CharacterRecipeKnowledge {
+    /// Character name
+    pub name: String,
+    /// Recipe IDs unlocked by the character
+    pub recipes: HashSet<i32>,
+    /// Crafting disciplines unlocked by the character
+    pub disciplines: Vec<KnownDiscipline>
+}
+
+/// Character that is able to craft a given recipe
+#[derive(Debug)]
+pub struct RecipeKnower<'a> {
+    /// Character name
+    pub name: &'a str,
+    /// Disciplines of the character the recipe can be crafted with
+    pub disciplines: Vec<&'a KnownDiscipline>
+}
+
+/// Recipe knowledge of a whole account
+#[derive(Debug)]
+pub struct RecipeKnowledge {
+    /// Recipe IDs unlocked account-wide
+    pub account: HashSet<i32>,
+    /// Knowledge of each of the characters in the account
+    pub characters: Vec<CharacterRecipeKnowledge>
+}
+
+impl RecipeKnowledge {
+    /// Check whether the recipe is known by the account or any character
+    ///
+    /// # Arguments
+    ///
+    /// * `recipe` - ID of the recipe
+    pub fn is_known(&self, recipe: i32) -> bool {
+        self.account.contains(&recipe)
+            || self.characters.iter().any(|c| c.recipes.contains(&recipe))
+    }
+
+    /// Obtain the characters able to craft the given recipe
+    ///
+    /// A character can craft the recipe if it has unlocked it or if the
+    /// recipe is unlocked account-wide, as long as the character has one of
+    /// the disciplines the recipe belongs to. Recipe disciplines are obtained
+    /// from the recipe details; if `disciplines` is empty, every discipline
+    /// of the character is considered.
+    ///
+    /// # Arguments
+    ///
+    /// * `recipe` - ID of the recipe
+    /// * `disciplines` - Disciplines the recipe can be crafted with
+    pub fn known_by(
+        &self,
+        recipe: i32,
+        disciplines: &[&str]
+    ) -> Vec<RecipeKnower> {
+        let account_wide = self.account.contains(&recipe);
+
+        self.characters.iter()
+            .filter(|c| account_wide || c.recipes.contains(&recipe))
+            .map(|c| RecipeKnower {
+                name: &c.name,
+                disciplines: c.disciplines.iter()
+                    .filter(|d| {
+                        disciplines.is_empty()
+                            || disciplines.contains(&d.discipline.as_str())
+                    })
+                    .collect()
+            })
+            .filter(|knower| !knower.disciplines.is_empty())
+            .collect()
+    }
+}
+
+/// Build the weekly clears of each raid wing
+///
+/// # Arguments
+///
+/// * `cleared` - Encounters as obtained from `get_account_raids()`
+pub fn raid_clears(cleared: &[String]) -> Vec<RaidWingClears> {
+    RAID_WINGS.iter()
+        .map(|&(wing, encounters)| {
+            let (done, remaining) = encounters.iter()
+                .partition(|e| cleared.iter().any(|c| c == *e));
+
+            RaidWingClears {
+                wing: wing,
+                cleared: done,
+                remaining: remaining
+            }
+        })
+        .collect()
+}
+
+/// Build the weekly clears of the strike missions of each campaign
+///
+/// # Arguments
+///
+/// * `cleared` - IDs of the strike missions cleared this week, as listed in
+///     `STRIKE_MISSIONS` (e.g. tracked from combat logs)
+pub fn strike_clears(cleared: &[String]) -> Vec<StrikeClears> {
+    STRIKE_MISSIONS.iter()
+        .map(|&(campaign, missions)| {
+            let (done, remaining) = missions.iter()
+                .partition(|m| cleared.iter().any(|c| c == *m));
+
+            StrikeClears {
+                campaign: campaign,
+                cleared: done,
+                remaining: remaining
+            }
+        })
+        .collect()
+}
+
+/// Obtain the weekly clears of each raid wing for the account
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_raid_clears(
+    client: &APIClient
+) -> Result<Vec<RaidWingClears>, APIError> {
+    Ok(raid_clears(&get_account_raids(client)?))
+}
+
+/// Obtain the entries of a list that are not done yet
+fn remaining<T: PartialEq>(all: Vec<T>, done: &[T]) -> Vec<T> {
+    all.into_iter().filter(|id| !done.contains(id)).collect()
+}
+
+/// Obtain the daily rewards the account has not obtained yet
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_daily_reset_progress(
+    client: &APIClient
+) -> Result<DailyResetProgress, APIError> {
+    Ok(DailyResetProgress {
+        dailycrafting: remaining(
+            get_dailycrafting_ids(client)?.into_iter().collect(),
+            &get_account_dailycrafting(client)?
+        ),
+        mapchests: remaining(
+            get_mapchest_ids(client)?.into_iter().collect(),
+            &get_account_mapchests(client)?
+        ),
+        worldbosses: remaining(
+            get_worldboss_ids(client)?.into_iter().collect(),
+            &get_account_worldbosses(client)?
+        )
+    })
+}
+
+/// Obtain the home instance cats and nodes the account has not unlocked
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_missing_home_unlocks(
+    client: &APIClient
+) -> Result<HomeUnlocks, APIError> {
+    let cats: Vec<i32> = get_account_cats(client)?.iter()
+        .map(|cat| cat.id)
+        .collect();
+
+    Ok(HomeUnlocks {
+        cats: remaining(get_cat_ids(client)?.into_iter().collect(), &cats),
+        nodes: remaining(
+            get_node_ids(client)?.into_iter().collect(),
+            &get_account_nodes(client)?
+        )
+    })
+}
+
+/// Obtain the recipe knowledge of the account and all its characters
+///
+/// This performs two requests per character in the account, in addition to
+/// the account recipes and character names.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_recipe_knowledge(
+    client: &APIClient
+) -> Result<RecipeKnowledge, APIError> {
+    let account = get_account_recipes(client)?;
+    let mut characters = Vec::new();
+
+    for name in get_character_names(client)? {
+        let recipes = get_character_recipes(client, &name)?;
+        let crafting = get_character_crafting(client, &name)?;
+
+        characters.push(CharacterRecipeKnowledge {
+            name: name,
+            recipes: recipes.recipes.into_iter().collect(),
+            disciplines: crafting.crafting.into_iter()
+                .map(|d| KnownDiscipline {
+                    discipline: d.discipline,
+                    rating: d.rating,
+                    active: d.active
+                })
+                .collect()
+        });
+    }
+
+    Ok(RecipeKnowledge {
+        account: account.into_iter().collect(),
+        characters: characters
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use client::APIClient;
+    use helpers::account::*;
+
+    fn setup_client() -> APIClient {
+        match env::var("TOKEN") {
+            Ok(token) => APIClient::new("en", Some(token.to_string())),
+            Err(_) => panic!("Need a token to test endpoint"),
+        }
+    }
+
+    fn character(
+        name: &str,
+        recipes: Vec<i32>,
+        disciplines: Vec<&str>
+    ) -> CharacterRecipeKnowledge {
+        CharacterRecipeKnowledge {
+            name: name.to_string(),
+            recipes: recipes.into_iter().collect(),
+            disciplines: disciplines.into_iter()
+                .map(|d| KnownDiscipline {
+                    discipline: d.to_string(),
+                    rating: 400,
+                    active: true
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn known_by() {
+        let knowledge = RecipeKnowledge {
+            account: vec![1].into_iter().collect(),
+            characters: vec![
+                character("Alpha", vec![2], vec!["Chef"]),
+                character("Beta", vec![], vec!["Weaponsmith", "Huntsman"]),
+            ]
+        };
+
+        assert!(knowledge.is_known(1));
+        assert!(knowledge.is_known(2));
+        assert!(!knowledge.is_known(3));
+
+        let knowers = knowledge.known_by(1, &["Huntsman"]);
+        assert_eq!(knowers.len(), 1);
+        assert_eq!(knowers[0].name, "Beta");
+        assert_eq!(knowers[0].disciplines.len(), 1);
+
+        let knowers = knowledge.known_by(2, &[]);
+        assert_eq!(knowers.len(), 1);
+        assert_eq!(knowers[0].name, "Alpha");
+
+        assert!(knowledge.known_by(2, &["Huntsman"]).is_empty());
+    }
+
+    #[test]
+    fn clears() {
+        let cleared = vec!["gorseval".to_string(), "xera".to_string()];
+        let wings = raid_clears(&cleared);
+
+        assert_eq!(wings.len(), RAID_WINGS.len());
+        assert_eq!(wings[0].cleared, vec!["gorseval"]);
+        assert_eq!(wings[0].remaining.len(), 3);
+        assert_eq!(wings[2].cleared, vec!["xera"]);
+        assert!(!wings[1].is_complete());
+
+        let cleared = vec![
+            "cosmic_observatory".to_string(),
+            "temple_of_febe".to_string()
+        ];
+        let campaigns = strike_clears(&cleared);
+
+        assert_eq!(campaigns.len(), STRIKE_MISSIONS.len());
+        assert!(campaigns[0].cleared.is_empty());
+        assert!(campaigns[2].is_complete());
+    }
+
+    #[test]
+    fn daily_reset() {
+        let all = vec![
+            "admiral_taidha_covington".to_string(),
+            "claw_of_jormag".to_string(),
+            "shadow_behemoth".to_string()
+        ];
+        let done = vec!["claw_of_jormag".to_string()];
+
+        let progress = DailyResetProgress {
+            dailycrafting: Vec::new(),
+            mapchests: Vec::new(),
+            worldbosses: remaining(all, &done)
+        };
+        assert_eq!(progress.worldbosses.len(), 2);
+        assert!(!progress.is_complete());
+    }
+
+    #[test]
+    fn recipe_knowledge() {
+        let client = setup_client();
+        match get_recipe_knowledge(&client) {
+            Ok(_) => assert!(true),
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+}
@@ -0,0 +1,511 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Achievement helpers
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use client::APIClient;
+use common::APIError;
+use api_v2::account::{get_account, get_account_achievements};
+use api_v2::achievements::get_achievements;
+use api_v2::types::{AccountAchievement, Achievement, IdList};
+
+
+/// Step in the unlock path of an achievement
+#[derive(Debug)]
+pub struct AchievementStep {
+    /// Achievement ID
+    pub id: i32,
+    /// Achievement name
+    pub name: String,
+    /// Current progress of the account towards the achievement
+    pub current: i32,
+    /// Amount needed to complete the achievement, `0` if unknown
+    pub max: i32,
+    /// Whether the account has completed the achievement
+    pub done: bool
+}
+
+/// Achievement points of an account
+#[derive(Debug)]
+pub struct AccountPoints {
+    /// Points earned from achievements, including repeats
+    pub achievements: i64,
+    /// Points earned from daily achievements
+    pub daily: i64,
+    /// Points earned from monthly achievements
+    pub monthly: i64
+}
+
+impl AccountPoints {
+    /// Total achievement points, as shown in game
+    pub fn total(&self) -> i64 {
+        self.achievements + self.daily + self.monthly
+    }
+}
+
+/// Achievement in progress suggested for completion
+#[derive(Debug)]
+pub struct AchievementSuggestion {
+    /// Achievement ID
+    pub id: i32,
+    /// Achievement name
+    pub name: String,
+    /// Current progress of the account towards the achievement
+    pub current: i32,
+    /// Amount needed to complete the achievement
+    pub max: i32,
+    /// Progress towards completion, from 0 to 1
+    pub completion: f64,
+    /// Points awarded by the tiers not reached yet
+    pub remaining_points: i64,
+    /// Points awarded per remaining step
+    pub points_per_step: f64
+}
+
+/// Compute the points earned in an achievement
+///
+/// Points are awarded for each tier reached (all of them if the achievement
+/// is done). Repeatable achievements award the points of every tier again
+/// for each repetition, up to `point_cap`.
+///
+/// # Arguments
+///
+/// * `achievement` - Achievement details
+/// * `progress` - Progress of the account in the achievement
+pub fn achievement_points(
+    achievement: &Achievement,
+    progress: &AccountAchievement
+) -> i64 {
+    let tier_points: i64 = achievement.tiers.iter()
+        .map(|tier| tier.points as i64)
+        .sum();
+
+    let current: i64 = if progress.done {
+        tier_points
+    } else {
+        achievement.tiers.iter()
+            .filter(|tier| progress.current >= tier.count)
+            .map(|tier| tier.points as i64)
+            .sum()
+    };
+
+    let repeatable = achievement.flags.iter().any(|f| f == "Repeatable");
+    if !repeatable {
+        return current;
+    }
+
+    let earned = current + progress.repeated as i64 * tier_points;
+    if achievement.point_cap > 0 {
+        earned.min(achievement.point_cap as i64)
+    } else {
+        earned
+    }
+}
+
+/// Compute the points earned in all the achievements of an account
+///
+/// Achievements missing from `achievements` do not add any points.
+///
+/// # Arguments
+///
+/// * `achievements` - Achievement details, indexed by ID
+/// * `progress` - Progress as obtained from `get_account_achievements()`
+pub fn account_achievement_points(
+    achievements: &HashMap<i32, Achievement>,
+    progress: &[AccountAchievement]
+) -> i64 {
+    progress.iter()
+        .filter_map(|p| {
+            achievements.get(&p.id).map(|a| achievement_points(a, p))
+        })
+        .sum()
+}
+
+/// Obtain the achievement points of the account
+///
+/// Daily and monthly points require the `progression` scope in the token,
+/// and are `0` otherwise.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_account_points(
+    client: &APIClient
+) -> Result<AccountPoints, APIError> {
+    let account = get_account(client)?;
+    let progress = get_account_achievements(client)?;
+
+    let ids: Vec<i32> = progress.iter().map(|p| p.id).collect();
+    let mut achievements = HashMap::new();
+
+    for batch in IdList::new(ids).batches(0) {
+        for achievement in get_achievements(client, batch)? {
+            achievements.insert(achievement.id, achievement);
+        }
+    }
+
+    Ok(AccountPoints {
+        achievements: account_achievement_points(&achievements, &progress),
+        daily: account.daily_ap as i64,
+        monthly: account.monthly_ap as i64
+    })
+}
+
+/// Rank the achievements in progress, closest to completion first
+///
+/// Only achievements with some progress, a known goal and points left to
+/// earn are suggested. Ties in completion are broken by the points awarded
+/// per remaining step.
+///
+/// # Arguments
+///
+/// * `achievements` - Achievement details, indexed by ID
+/// * `progress` - Progress as obtained from `get_account_achievements()`
+pub fn nearly_done(
+    achievements: &HashMap<i32, Achievement>,
+    progress: &[AccountAchievement]
+) -> Vec<AchievementSuggestion> {
+    let mut suggestions: Vec<AchievementSuggestion> = progress.iter()
+        .filter(|p| !p.done && p.current > 0 && p.current < p.max)
+        .filter_map(|p| achievements.get(&p.id).map(|a| (a, p)))
+        .map(|(achievement, p)| {
+            let remaining_points: i64 = achievement.tiers.iter()
+                .filter(|tier| tier.count > p.current)
+                .map(|tier| tier.points as i64)
+                .sum();
+
+            AchievementSuggestion {
+                id: achievement.id,
+                name: achievement.name.clone(),
+                current: p.current,
+                max: p.max,
+                completion: p.current as f64 / p.max as f64,
+                remaining_points: remaining_points,
+                points_per_step: remaining_points as f64
+                    / (p.max - p.current) as f64
+            }
+        })
+        .filter(|suggestion| suggestion.remaining_points > 0)
+        .collect();
+
+    suggestions.sort_by(|a, b| {
+        b.completion.partial_cmp(&a.completion)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| {
+                b.points_per_step.partial_cmp(&a.points_per_step)
+                    .unwrap_or(Ordering::Equal)
+            })
+    });
+
+    suggestions
+}
+
+/// Obtain the achievements in progress closest to completion
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `limit` - Maximum number of suggestions, `0` for all of them
+pub fn get_nearly_done(
+    client: &APIClient,
+    limit: usize
+) -> Result<Vec<AchievementSuggestion>, APIError> {
+    let progress = get_account_achievements(client)?;
+
+    let ids: Vec<i32> = progress.iter()
+        .filter(|p| !p.done && p.current > 0)
+        .map(|p| p.id)
+        .collect();
+    let mut achievements = HashMap::new();
+
+    for batch in IdList::new(ids).batches(0) {
+        for achievement in get_achievements(client, batch)? {
+            achievements.insert(achievement.id, achievement);
+        }
+    }
+
+    let mut suggestions = nearly_done(&achievements, &progress);
+    if limit > 0 {
+        suggestions.truncate(limit);
+    }
+
+    Ok(suggestions)
+}
+
+/// Order an achievement and its prerequisites in unlock order
+///
+/// Prerequisites always come before the achievements that require them, and
+/// the achievement itself is the last element. Prerequisites missing from
+/// `achievements` are skipped and cycles are ignored.
+///
+/// # Arguments
+///
+/// * `id` - ID of the achievement to obtain the path for
+/// * `achievements` - Achievement details, indexed by ID
+pub fn prerequisite_order(
+    id: i32,
+    achievements: &HashMap<i32, Achievement>
+) -> Vec<i32> {
+    fn visit(
+        id: i32,
+        achievements: &HashMap<i32, Achievement>,
+        visited: &mut HashSet<i32>,
+        order: &mut Vec<i32>
+    ) {
+        if !visited.insert(id) {
+            return;
+        }
+
+        if let Some(achievement) = achievements.get(&id) {
+            for prerequisite in &achievement.prerequisites {
+                visit(*prerequisite, achievements, visited, order);
+            }
+
+            order.push(id);
+        }
+    }
+
+    let mut order = Vec::new();
+    visit(id, achievements, &mut HashSet::new(), &mut order);
+
+    order
+}
+
+/// Obtain an achievement and all of its prerequisites, recursively
+///
+/// Each level of prerequisites is fetched with a single bulk request.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `id` - ID of the achievement
+pub fn get_prerequisite_chain(
+    client: &APIClient,
+    id: i32
+) -> Result<HashMap<i32, Achievement>, APIError> {
+    let mut achievements = HashMap::new();
+    let mut pending = vec![id];
+
+    while !pending.is_empty() {
+        let mut next = Vec::new();
+
+        for batch in IdList::new(pending).batches(0) {
+            for achievement in get_achievements(client, batch)? {
+                for prerequisite in &achievement.prerequisites {
+                    if !achievements.contains_key(prerequisite) {
+                        next.push(*prerequisite);
+                    }
+                }
+
+                achievements.insert(achievement.id, achievement);
+            }
+        }
+
+        next.sort();
+        next.dedup();
+        next.retain(|id| !achievements.contains_key(id));
+        pending = next;
+    }
+
+    Ok(achievements)
+}
+
+/// Obtain the unlock path of an achievement with the account's progress
+///
+/// The path is ordered so that every achievement comes after its
+/// prerequisites, ending with the requested achievement.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `id` - ID of the achievement
+pub fn get_achievement_path(
+    client: &APIClient,
+    id: i32
+) -> Result<Vec<AchievementStep>, APIError> {
+    let mut achievements = get_prerequisite_chain(client, id)?;
+    let progress: HashMap<i32, _> = get_account_achievements(client)?
+        .into_iter()
+        .map(|a| (a.id, a))
+        .collect();
+
+    Ok(prerequisite_order(id, &achievements).into_iter()
+        .filter_map(|id| achievements.remove(&id))
+        .map(|achievement| {
+            let (current, max, done) = match progress.get(&achievement.id) {
+                Some(p) => (p.current, p.max, p.done),
+                None => (0, 0, false)
+            };
+
+            AchievementStep {
+                id: achievement.id,
+                name: achievement.name,
+                current: current,
+                max: max,
+                done: done
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::env;
+    use serde_json;
+    use client::APIClient;
+    use api_v2::types::{AccountAchievement, Achievement};
+    use helpers::achievements::*;
+
+    fn setup_client() -> APIClient {
+        match env::var("TOKEN") {
+            Ok(token) => APIClient::new("en", Some(token.to_string())),
+            Err(_) => panic!("Need a token to test endpoint"),
+        }
+    }
+
+    fn achievement(id: i32, prerequisites: Vec<i32>) -> (i32, Achievement) {
+        let achievement = serde_json::from_str(&format!(r#"{{
+            "id": {},
+            "name": "Achievement {}",
+            "description": "",
+            "requirement": "",
+            "locked_text": "",
+            "type": "Default",
+            "flags": [],
+            "tiers": [],
+            "prerequisites": {:?}
+        }}"#, id, id, prerequisites)).unwrap();
+
+        (id, achievement)
+    }
+
+    fn tiered(flags: &str, point_cap: i32) -> Achievement {
+        serde_json::from_str(&format!(r#"{{
+            "id": 1,
+            "name": "Tiered",
+            "description": "",
+            "requirement": "",
+            "locked_text": "",
+            "type": "Default",
+            "flags": [{}],
+            "tiers": [
+                {{"count": 1, "points": 1}},
+                {{"count": 5, "points": 4}},
+                {{"count": 10, "points": 5}}
+            ],
+            "point_cap": {}
+        }}"#, flags, point_cap)).unwrap()
+    }
+
+    fn progress(current: i32, done: bool, repeated: i32) -> AccountAchievement {
+        serde_json::from_str(&format!(
+            r#"{{"id": 1, "current": {}, "max": 10, "done": {},
+                "repeated": {}}}"#,
+            current, done, repeated
+        )).unwrap()
+    }
+
+    #[test]
+    fn suggestions() {
+        let mut achievements = HashMap::new();
+        achievements.insert(1, tiered("", 0));
+
+        let mut other = tiered("", 0);
+        other.id = 2;
+        achievements.insert(2, other);
+
+        let mut near = progress(9, false, 0);
+        near.id = 2;
+        let progress = vec![progress(3, false, 0), near];
+
+        let suggestions = nearly_done(&achievements, &progress);
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].id, 2);
+        assert_eq!(suggestions[0].remaining_points, 5);
+        assert_eq!(suggestions[1].remaining_points, 9);
+        assert!((suggestions[1].points_per_step - 9.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn order() {
+        let achievements: HashMap<i32, Achievement> = vec![
+            achievement(4, vec![2, 3]),
+            achievement(3, vec![1]),
+            achievement(2, vec![1, 99]),
+            achievement(1, vec![]),
+        ].into_iter().collect();
+
+        assert_eq!(prerequisite_order(4, &achievements), vec![1, 2, 3, 4]);
+        assert_eq!(prerequisite_order(3, &achievements), vec![1, 3]);
+        assert!(prerequisite_order(99, &achievements).is_empty());
+    }
+
+    #[test]
+    fn cycles() {
+        let achievements: HashMap<i32, Achievement> = vec![
+            achievement(1, vec![2]),
+            achievement(2, vec![1]),
+        ].into_iter().collect();
+
+        assert_eq!(prerequisite_order(1, &achievements), vec![2, 1]);
+    }
+
+    #[test]
+    fn points() {
+        let single = tiered("", 0);
+        assert_eq!(achievement_points(&single, &progress(0, false, 0)), 0);
+        assert_eq!(achievement_points(&single, &progress(7, false, 0)), 5);
+        assert_eq!(achievement_points(&single, &progress(10, true, 0)), 10);
+
+        let repeatable = tiered(r#""Repeatable""#, 25);
+        assert_eq!(achievement_points(&repeatable, &progress(5, false, 1)), 15);
+        assert_eq!(achievement_points(&repeatable, &progress(5, false, 9)), 25);
+
+        let uncapped = tiered(r#""Repeatable""#, 0);
+        assert_eq!(achievement_points(&uncapped, &progress(0, false, 3)), 30);
+    }
+
+    #[test]
+    fn account_points() {
+        let client = setup_client();
+        match get_account_points(&client) {
+            Ok(points) => assert!(points.total() >= points.achievements),
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+
+    #[test]
+    fn achievement_path() {
+        let client = setup_client();
+        match get_achievement_path(&client, 2258) {
+            Ok(_) => assert!(true),
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+}
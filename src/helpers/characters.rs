@@ -0,0 +1,616 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Character-specific helpers
+/// These require an API key to obtain the data
+
+use client::APIClient;
+use common::APIError;
+use api_v2::characters::{
+    get_character_backstory,
+    get_character_core,
+    get_character_quests,
+    get_character_sab
+};
+use api_v2::story::{
+    get_backstory_answers,
+    get_backstory_questions,
+    get_quest_ids,
+    get_quests,
+    get_stories,
+    get_story_ids,
+    get_story_season_ids,
+    get_story_seasons,
+    sort_stories
+};
+use api_v2::types::{
+    BackstoryAnswer,
+    BackstoryQuestion,
+    CharacterBackstory,
+    CharacterCore,
+    Quest,
+    SABProgress,
+    Story
+};
+
+
+/// Difficulty modes available in Super Adventure Box
+pub const SAB_MODES: [&'static str; 3] = ["infantile", "normal", "tribulation"];
+
+/// Released Super Adventure Box zones, as `(world, zone)` pairs
+pub const SAB_ZONES: [(i32, i32); 7] = [
+    (1, 1), (1, 2), (1, 3), (1, 4),
+    (2, 1), (2, 2), (2, 3)
+];
+
+/// Names of the Super Adventure Box upgrades reported by the API
+pub const SAB_UNLOCKS: [&'static str; 12] = [
+    "chain_stick",
+    "slingshot",
+    "whip",
+    "mini_bomb",
+    "candle",
+    "glove_of_wisdom",
+    "bauble_purse",
+    "moto_breath",
+    "moto_finger",
+    "health_vessel_1",
+    "health_vessel_2",
+    "bauble_tote_bag"
+];
+
+/// Names of the Super Adventure Box songs reported by the API
+pub const SAB_SONGS: [&'static str; 3] = [
+    "secret_song",
+    "gatekeeper_lullaby",
+    "shatter_serenade"
+];
+
+/// Backstory answer of a character, resolved to readable text
+#[derive(Debug)]
+pub struct BackstoryEntry {
+    /// Question ID
+    pub question_id: i32,
+    /// Localized title of the question
+    pub question: String,
+    /// Answer ID
+    pub answer_id: String,
+    /// Localized title of the answer
+    pub answer: String,
+    /// Localized text shown in the character's biography
+    pub journal: String
+}
+
+/// Zone of Super Adventure Box
+#[derive(Debug, PartialEq)]
+pub struct SABZoneNumber {
+    /// World number
+    pub world: i32,
+    /// Zone number within the world
+    pub zone: i32
+}
+
+/// Completion of a single Super Adventure Box difficulty mode
+#[derive(Debug)]
+pub struct SABModeReport {
+    /// Difficulty mode
+    pub mode: &'static str,
+    /// Zones cleared in this mode
+    pub cleared: Vec<SABZoneNumber>,
+    /// Zones not yet cleared in this mode
+    pub remaining: Vec<SABZoneNumber>
+}
+
+/// Super Adventure Box completion of a character
+#[derive(Debug)]
+pub struct SABReport {
+    /// Completion of each difficulty mode, in `SAB_MODES` order
+    pub modes: Vec<SABModeReport>,
+    /// Upgrades not yet unlocked
+    pub missing_unlocks: Vec<&'static str>,
+    /// Songs not yet unlocked
+    pub missing_songs: Vec<&'static str>
+}
+
+impl SABReport {
+    /// Check whether every zone, upgrade and song has been completed
+    pub fn is_complete(&self) -> bool {
+        self.modes.iter().all(|m| m.remaining.is_empty())
+            && self.missing_unlocks.is_empty()
+            && self.missing_songs.is_empty()
+    }
+}
+
+/// Progress of a character in a single story
+#[derive(Debug)]
+pub struct StoryProgress {
+    /// Story ID
+    pub story_id: i32,
+    /// Localized name of the story
+    pub name: String,
+    /// ID of the season the story belongs to
+    pub season: String,
+    /// Names of the chapters (quests) finished, in story order
+    pub finished: Vec<String>,
+    /// Names of the chapters (quests) not finished yet, in story order
+    pub remaining: Vec<String>
+}
+
+impl StoryProgress {
+    /// Check whether every chapter of the story has been finished
+    pub fn is_complete(&self) -> bool {
+        self.remaining.is_empty()
+    }
+}
+
+/// Next story chapter a character can play
+#[derive(Debug)]
+pub struct StoryStep {
+    /// Story ID
+    pub story_id: i32,
+    /// Quest ID of the chapter
+    pub quest_id: i32,
+    /// Localized name of the chapter
+    pub name: String,
+    /// Minimum level required to play the chapter
+    pub level: i32
+}
+
+/// Story completion of a character
+#[derive(Debug)]
+pub struct StoryReport {
+    /// Progress in each story available to the character's race, grouped by
+    /// season
+    pub stories: Vec<StoryProgress>,
+    /// Backstory answer IDs chosen at character creation
+    pub backstory: Vec<String>,
+    /// First chapter not finished yet whose level requirement is met
+    pub next: Option<StoryStep>
+}
+
+/// Build a completion report from the SAB progress of a character
+///
+/// Progress is compared against `SAB_MODES`, `SAB_ZONES`, `SAB_UNLOCKS` and
+/// `SAB_SONGS`. Unlocks and songs are matched by name.
+///
+/// # Arguments
+///
+/// * `progress` - Progress as obtained from `get_character_sab()`
+pub fn sab_report(progress: &SABProgress) -> SABReport {
+    let modes = SAB_MODES.iter()
+        .map(|mode| {
+            let (cleared, remaining) = SAB_ZONES.iter()
+                .map(|&(world, zone)| SABZoneNumber {
+                    world: world,
+                    zone: zone
+                })
+                .partition(|number| {
+                    progress.zones.iter().any(|z| {
+                        z.mode == *mode
+                            && z.world == number.world
+                            && z.zone == number.zone
+                    })
+                });
+
+            SABModeReport {
+                mode: mode,
+                cleared: cleared,
+                remaining: remaining
+            }
+        })
+        .collect();
+
+    SABReport {
+        modes: modes,
+        missing_unlocks: SAB_UNLOCKS.iter()
+            .filter(|name| !progress.unlocks.iter().any(|u| u.name == **name))
+            .cloned()
+            .collect(),
+        missing_songs: SAB_SONGS.iter()
+            .filter(|name| !progress.songs.iter().any(|s| s.name == **name))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Build a story completion report for a character
+///
+/// Stories restricted to other races are left out. Seasons keep the order
+/// in which they first appear in `stories` (see `sort_stories()`), and the
+/// stories of each season are sorted by their position in the season.
+/// Chapters are the quests of each story, sorted by level.
+///
+/// # Arguments
+///
+/// * `core` - Core information as obtained from `get_character_core()`
+/// * `backstory` - Backstory as obtained from `get_character_backstory()`
+/// * `completed` - Quest IDs as obtained from `get_character_quests()`
+/// * `stories` - Story catalogue as obtained from `get_stories()`
+/// * `quests` - Quest catalogue as obtained from `get_quests()`
+pub fn story_report(
+    core: &CharacterCore,
+    backstory: &CharacterBackstory,
+    completed: &[i32],
+    stories: &[Story],
+    quests: &[Quest]
+) -> StoryReport {
+    let mut seasons: Vec<&str> = Vec::new();
+    for story in stories {
+        if !seasons.contains(&story.season.as_str()) {
+            seasons.push(&story.season);
+        }
+    }
+
+    let mut available: Vec<&Story> = stories.iter()
+        .filter(|s| s.is_available_to_race(&core.race))
+        .collect();
+    available.sort_by_key(|s| {
+        let season = seasons.iter().position(|id| *id == s.season);
+        (season, s.order)
+    });
+
+    let mut next = None;
+    let progress = available.iter()
+        .map(|story| {
+            let mut chapters: Vec<&Quest> = quests.iter()
+                .filter(|q| q.story == story.id)
+                .collect();
+            chapters.sort_by_key(|q| (q.level, q.id));
+
+            let mut finished = Vec::new();
+            let mut remaining = Vec::new();
+
+            for quest in chapters {
+                if completed.contains(&quest.id) {
+                    finished.push(quest.name.clone());
+                    continue;
+                }
+
+                if next.is_none() && quest.level <= core.level {
+                    next = Some(StoryStep {
+                        story_id: story.id,
+                        quest_id: quest.id,
+                        name: quest.name.clone(),
+                        level: quest.level
+                    });
+                }
+                remaining.push(quest.name.clone());
+            }
+
+            StoryProgress {
+                story_id: story.id,
+                name: story.name.clone(),
+                season: story.season.clone(),
+                finished: finished,
+                remaining: remaining
+            }
+        })
+        .collect();
+
+    StoryReport {
+        stories: progress,
+        backstory: backstory.backstory.clone(),
+        next: next
+    }
+}
+
+/// Obtain the Super Adventure Box completion report of a character
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name` - Name of the character
+pub fn get_sab_report(
+    client: &APIClient,
+    name: &str
+) -> Result<SABReport, APIError> {
+    Ok(sab_report(&get_character_sab(client, name)?))
+}
+
+/// Resolve the backstory answers of a character to readable text
+///
+/// Entries are sorted in the order the questions are asked during character
+/// creation. Answers that are missing from `answers` or whose question is
+/// missing from `questions` are skipped.
+///
+/// # Arguments
+///
+/// * `backstory` - Backstory as obtained from `get_character_backstory()`
+/// * `questions` - Questions as obtained from `get_backstory_questions()`
+/// * `answers` - Answers as obtained from `get_backstory_answers()`
+pub fn resolve_backstory(
+    backstory: &CharacterBackstory,
+    questions: &[BackstoryQuestion],
+    answers: &[BackstoryAnswer]
+) -> Vec<BackstoryEntry> {
+    let mut entries: Vec<(i32, BackstoryEntry)> = backstory.backstory.iter()
+        .filter_map(|id| answers.iter().find(|a| &a.id == id))
+        .filter_map(|answer| {
+            questions.iter()
+                .find(|q| q.id == answer.question)
+                .map(|question| (question.order, BackstoryEntry {
+                    question_id: question.id,
+                    question: question.title.clone(),
+                    answer_id: answer.id.clone(),
+                    answer: answer.title.clone(),
+                    journal: answer.journal.clone()
+                }))
+        })
+        .collect();
+
+    entries.sort_by_key(|&(order, _)| order);
+    entries.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Obtain the backstory answers of a character as readable text
+///
+/// Only the questions and answers chosen by the character are fetched.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name` - Name of the character
+pub fn get_backstory_entries(
+    client: &APIClient,
+    name: &str
+) -> Result<Vec<BackstoryEntry>, APIError> {
+    let backstory = get_character_backstory(client, name)?;
+    if backstory.backstory.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let answers = get_backstory_answers(
+        client,
+        backstory.backstory.iter().map(|id| id.as_str()).collect()
+    )?;
+
+    let mut question_ids: Vec<i32> = answers.iter()
+        .map(|answer| answer.question)
+        .collect();
+    question_ids.sort();
+    question_ids.dedup();
+    let questions = get_backstory_questions(client, question_ids)?;
+
+    Ok(resolve_backstory(&backstory, &questions, &answers))
+}
+
+/// Obtain the story completion report of a character
+///
+/// This fetches the whole story, season and quest catalogues in addition to
+/// the character details. Stories are reported in story journal order.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name` - Name of the character
+pub fn get_story_report(
+    client: &APIClient,
+    name: &str
+) -> Result<StoryReport, APIError> {
+    let core = get_character_core(client, name)?;
+    let backstory = get_character_backstory(client, name)?;
+    let completed = get_character_quests(client, name)?;
+
+    let mut stories = Vec::new();
+    for batch in get_story_ids(client)?.batches(0) {
+        stories.extend(get_stories(client, batch)?);
+    }
+
+    let mut seasons = Vec::new();
+    for batch in get_story_season_ids(client)?.str_batches(0) {
+        seasons.extend(get_story_seasons(client, batch)?);
+    }
+    sort_stories(&seasons, &mut stories);
+
+    let mut quests = Vec::new();
+    for batch in get_quest_ids(client)?.batches(0) {
+        quests.extend(get_quests(client, batch)?);
+    }
+
+    Ok(story_report(&core, &backstory, &completed, &stories, &quests))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use serde_json;
+    use client::APIClient;
+    use api_v2::types::{
+        BackstoryAnswer,
+        BackstoryQuestion,
+        CharacterBackstory,
+        CharacterCore,
+        Quest,
+        SABProgress,
+        SABSong,
+        SABUnlock,
+        SABZone,
+        Story
+    };
+    use helpers::characters::*;
+
+    fn setup_client() -> APIClient {
+        match env::var("TOKEN") {
+            Ok(token) => APIClient::new("en", Some(token.to_string())),
+            Err(_) => panic!("Need a token to test endpoint"),
+        }
+    }
+
+    fn set_name() -> String {
+        match env::var("CHAR_NAME") {
+            Ok(name) => name,
+            Err(_) => panic!("Need a character name to test endpoint"),
+        }
+    }
+
+    #[test]
+    fn report() {
+        let progress: SABProgress = serde_json::from_str(r#"{
+            "zones": [
+                {"id": 1, "mode": "normal", "world": 1, "zone": 1},
+                {"id": 2, "mode": "normal", "world": 1, "zone": 2},
+                {"id": 1, "mode": "infantile", "world": 1, "zone": 1}
+            ],
+            "unlocks": [{"id": 1, "name": "chain_stick"}],
+            "songs": [{"id": 2, "name": "gatekeeper_lullaby"}]
+        }"#).unwrap();
+
+        let report = sab_report(&progress);
+
+        assert!(!report.is_complete());
+        assert_eq!(report.modes.len(), 3);
+        assert_eq!(report.modes[0].cleared.len(), 1);
+        assert_eq!(report.modes[1].cleared.len(), 2);
+        assert_eq!(report.modes[1].remaining[0],
+                   SABZoneNumber { world: 1, zone: 3 });
+        assert_eq!(report.modes[2].remaining.len(), 7);
+        assert_eq!(report.missing_unlocks.len(), SAB_UNLOCKS.len() - 1);
+        assert_eq!(report.missing_songs,
+                   vec!["secret_song", "shatter_serenade"]);
+    }
+
+    #[test]
+    fn complete_report() {
+        let progress = SABProgress {
+            zones: SAB_MODES.iter()
+                .flat_map(|mode| {
+                    SAB_ZONES.iter().map(move |&(world, zone)| SABZone {
+                        id: world,
+                        mode: mode.to_string(),
+                        world: world,
+                        zone: zone
+                    })
+                })
+                .collect(),
+            unlocks: SAB_UNLOCKS.iter()
+                .enumerate()
+                .map(|(id, name)| SABUnlock {
+                    id: id as i32,
+                    name: name.to_string()
+                })
+                .collect(),
+            songs: SAB_SONGS.iter()
+                .enumerate()
+                .map(|(id, name)| SABSong {
+                    id: id as i32,
+                    name: name.to_string()
+                })
+                .collect()
+        };
+
+        let report = sab_report(&progress);
+
+        assert!(report.is_complete());
+        assert!(report.modes.iter().all(|m| m.cleared.len() == 7));
+    }
+
+    #[test]
+    fn backstory() {
+        let backstory: CharacterBackstory = serde_json::from_str(
+            r#"{"backstory": ["26-122", "7-54", "99-1"]}"#
+        ).unwrap();
+        let questions: Vec<BackstoryQuestion> = serde_json::from_str(r#"[
+            {"id": 7, "title": "My Personality", "description": "",
+             "answers": ["7-53", "7-54"], "order": 0},
+            {"id": 26, "title": "My Regret", "description": "",
+             "answers": ["26-122"], "order": 3, "races": ["Human"]}
+        ]"#).unwrap();
+        let answers: Vec<BackstoryAnswer> = serde_json::from_str(r#"[
+            {"id": "26-122", "title": "Unknown Parents", "description": "",
+             "journal": "I never knew my parents.", "question": 26},
+            {"id": "7-54", "title": "Dignity", "description": "",
+             "journal": "I am dignified.", "question": 7}
+        ]"#).unwrap();
+
+        let entries = resolve_backstory(&backstory, &questions, &answers);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].question, "My Personality");
+        assert_eq!(entries[0].answer, "Dignity");
+        assert_eq!(entries[1].answer_id, "26-122");
+    }
+
+    #[test]
+    fn character_sab_report() {
+        let client = setup_client();
+        match get_sab_report(&client, &set_name()) {
+            Ok(_) => assert!(true),
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+
+    #[test]
+    fn stories() {
+        let core: CharacterCore = serde_json::from_str(r#"{
+            "name": "Char", "race": "Norn", "gender": "Female",
+            "profession": "Ranger", "level": 20, "age": 100,
+            "created": "2017-07-01T10:00:00Z", "deaths": 0
+        }"#).unwrap();
+        let backstory: CharacterBackstory = serde_json::from_str(
+            r#"{"backstory": ["7-54"]}"#
+        ).unwrap();
+        let stories: Vec<Story> = serde_json::from_str(r#"[
+            {"id": 2, "season": "S1", "name": "Second", "level": 10,
+             "order": 1, "chapters": []},
+            {"id": 1, "season": "S1", "name": "First", "level": 1,
+             "order": 0, "races": ["Norn"], "chapters": []},
+            {"id": 3, "season": "S1", "name": "Charr only", "level": 1,
+             "order": 2, "races": ["Charr"], "chapters": []}
+        ]"#).unwrap();
+        let quests: Vec<Quest> = serde_json::from_str(r#"[
+            {"id": 10, "name": "Intro", "level": 1, "story": 1},
+            {"id": 11, "name": "Hunt", "level": 2, "story": 1},
+            {"id": 20, "name": "Later", "level": 30, "story": 2},
+            {"id": 30, "name": "Charr", "level": 1, "story": 3}
+        ]"#).unwrap();
+
+        let report = story_report(&core, &backstory, &[10], &stories, &quests);
+
+        assert_eq!(report.stories.len(), 2);
+        assert_eq!(report.stories[0].name, "First");
+        assert_eq!(report.stories[0].finished, vec!["Intro"]);
+        assert_eq!(report.stories[0].remaining, vec!["Hunt"]);
+        assert_eq!(report.backstory, vec!["7-54"]);
+
+        match report.next {
+            Some(ref step) => assert_eq!(step.quest_id, 11),
+            None => panic!("expected a next step"),
+        }
+
+        let report = story_report(
+            &core, &backstory, &[10, 11], &stories, &quests
+        );
+        assert!(report.stories[0].is_complete());
+        assert!(report.next.is_none());
+    }
+
+    #[test]
+    fn story_progress() {
+        let client = setup_client();
+        match get_story_report(&client, &set_name()) {
+            Ok(_) => assert!(true),
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+}
@@ -0,0 +1,871 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Trading post helpers
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::thread;
+
+#[cfg(feature = "chrono")]
+use chrono::NaiveDate;
+
+use bundle::panic_error;
+use client::APIClient;
+use common::{APIError, APIErrorKind};
+use api_v2::commerce::{get_coin_exchange, get_gem_exchange, get_pricings};
+use api_v2::types::{
+    ExchangeRate,
+    IdList,
+    TPItem,
+    TPItemListing,
+    TPTransaction,
+    Timestamp
+};
+
+
+/// Listing fee charged when posting a sell offer, in percent
+pub const LISTING_FEE_PERCENT: i64 = 5;
+
+/// Exchange fee charged when a sell offer is fulfilled, in percent
+pub const EXCHANGE_FEE_PERCENT: i64 = 10;
+
+/// Default coin quantities used for exchange rate tables (1g to 10000g)
+pub const DEFAULT_COIN_LADDER: [i32; 5] =
+    [10000, 100000, 1000000, 10000000, 100000000];
+
+/// Default gem quantities used for exchange rate tables
+pub const DEFAULT_GEM_LADDER: [i32; 6] = [10, 100, 400, 800, 2000, 4000];
+
+/// Realized profit or loss for a single item
+///
+/// Only the units that could be matched between a buy and a later sell are
+/// taken into account.
+#[derive(Debug)]
+pub struct ItemProfit {
+    /// Item ID
+    pub item_id: i32,
+    /// Number of units matched between buys and sells
+    pub quantity: i64,
+    /// Coins spent buying the matched units
+    pub cost: i64,
+    /// Coins obtained selling the matched units, before fees
+    pub revenue: i64,
+    /// Trading post fees paid for the matched units
+    pub fees: i64,
+    /// Net profit (negative on loss) after fees
+    pub profit: i64,
+    /// Units sold without a matching purchase in the history (e.g. looted)
+    pub unmatched_sold: i64
+}
+
+/// Trading post volume for a single day
+/// Requires the `chrono` feature
+#[cfg(feature = "chrono")]
+#[derive(Debug)]
+pub struct DailyVolume {
+    /// Day of the transactions (UTC)
+    pub date: NaiveDate,
+    /// Number of units bought
+    pub bought: i64,
+    /// Coins spent buying
+    pub buy_value: i64,
+    /// Number of units sold
+    pub sold: i64,
+    /// Coins obtained selling, before fees
+    pub sell_value: i64
+}
+
+/// Summary of the trading post history of an account
+/// Requires the `chrono` feature
+#[cfg(feature = "chrono")]
+#[derive(Debug)]
+pub struct TransactionSummary {
+    /// Realized profit per item, sorted by item ID
+    pub items: Vec<ItemProfit>,
+    /// Total fees paid for all the sell transactions
+    pub total_fees: i64,
+    /// Total realized profit over all the items
+    pub total_profit: i64,
+    /// Volume per day, sorted by date
+    pub volume: Vec<DailyVolume>
+}
+
+/// Current trading post prices of an item
+#[derive(Debug)]
+pub struct ItemPrice {
+    /// Item ID
+    pub item_id: i32,
+    /// Highest buy order, i.e. coins obtained when selling instantly
+    pub buy: i32,
+    /// Lowest sell offer, i.e. coins paid when buying instantly
+    pub sell: i32
+}
+
+/// Exchange rate for a given quantity
+#[derive(Debug)]
+pub struct ExchangeRateStep {
+    /// Quantity of coins or gems being exchanged
+    pub quantity: i32,
+    /// Coins paid per gem (coins to gems) or obtained per gem (gems to coins)
+    pub coins_per_gem: i32,
+    /// Gems or coins obtained for `quantity`
+    pub obtained: i32
+}
+
+/// Exchange rates in both directions for a ladder of quantities
+#[derive(Debug)]
+pub struct ExchangeRateTable {
+    /// Rates when exchanging coins for gems, in the order requested
+    pub coins_to_gems: Vec<ExchangeRateStep>,
+    /// Rates when exchanging gems for coins, in the order requested
+    pub gems_to_coins: Vec<ExchangeRateStep>
+}
+
+/// Result of exchanging a quantity and immediately exchanging it back
+#[derive(Debug)]
+pub struct RoundTrip {
+    /// Quantity initially exchanged (coins or gems)
+    pub start: i32,
+    /// Quantity of the other currency obtained in the first exchange
+    pub intermediate: i32,
+    /// Quantity of the initial currency obtained back
+    pub end: i32,
+    /// Quantity lost in the round trip
+    pub loss: i32,
+    /// Loss relative to the initial quantity, in percent
+    pub loss_percent: f64
+}
+
+/// Round trips for a ladder of coin and gem quantities
+#[derive(Debug)]
+pub struct RoundTripTable {
+    /// Round trips starting with coins (coins to gems to coins)
+    pub coins: Vec<RoundTrip>,
+    /// Round trips starting with gems (gems to coins to gems)
+    pub gems: Vec<RoundTrip>
+}
+
+/// Change in the quantity listed at a single price
+#[derive(Debug, PartialEq)]
+pub struct PriceLevelChange {
+    /// Price of the level in coins
+    pub unit_price: i32,
+    /// Quantity listed in the older snapshot, 0 if the level was added
+    pub before: i32,
+    /// Quantity listed in the newer snapshot, 0 if the level was removed
+    pub after: i32
+}
+
+impl PriceLevelChange {
+    /// Check whether the price level did not exist in the older snapshot
+    pub fn is_added(&self) -> bool {
+        self.before == 0
+    }
+
+    /// Check whether the price level no longer exists in the newer snapshot
+    pub fn is_removed(&self) -> bool {
+        self.after == 0
+    }
+
+    /// Obtain the change in quantity (negative if it decreased)
+    pub fn delta(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+}
+
+/// Changes in one side (buy orders or sell offers) of the listings
+#[derive(Debug)]
+pub struct ListingSideDelta {
+    /// Price levels whose quantity changed, sorted by price
+    pub changes: Vec<PriceLevelChange>,
+    /// Net change in the quantity listed over all the price levels
+    pub net_quantity: i64
+}
+
+/// Changes between two snapshots of the listings of an item
+#[derive(Debug)]
+pub struct ListingDelta {
+    /// Item ID
+    pub item_id: i32,
+    /// Changes in buy orders
+    pub buys: ListingSideDelta,
+    /// Changes in sell offers
+    pub sells: ListingSideDelta
+}
+
+/// Compute the changes between two snapshots of one side of the listings
+fn listing_side_delta(
+    before: &[TPItemListing],
+    after: &[TPItemListing]
+) -> ListingSideDelta {
+    let mut levels: BTreeMap<i32, (i32, i32)> = BTreeMap::new();
+
+    for listing in before {
+        levels.entry(listing.unit_price).or_insert((0, 0)).0 +=
+            listing.quantity;
+    }
+    for listing in after {
+        levels.entry(listing.unit_price).or_insert((0, 0)).1 +=
+            listing.quantity;
+    }
+
+    let changes: Vec<PriceLevelChange> = levels.into_iter()
+        .filter(|&(_, (before, after))| before != after)
+        .map(|(unit_price, (before, after))| PriceLevelChange {
+            unit_price: unit_price,
+            before: before,
+            after: after
+        })
+        .collect();
+    let net_quantity = changes.iter().map(|change| change.delta()).sum();
+
+    ListingSideDelta {
+        changes: changes,
+        net_quantity: net_quantity
+    }
+}
+
+/// Compute the changes between two snapshots of the listings of an item
+///
+/// Returns `None` if the snapshots belong to different items.
+///
+/// # Arguments
+///
+/// * `before` - Older snapshot, as obtained from `get_listing()`
+/// * `after` - Newer snapshot of the same item
+pub fn listing_delta(before: &TPItem, after: &TPItem) -> Option<ListingDelta> {
+    if before.id != after.id {
+        return None;
+    }
+
+    Some(ListingDelta {
+        item_id: after.id,
+        buys: listing_side_delta(&before.buys, &after.buys),
+        sells: listing_side_delta(&before.sells, &after.sells)
+    })
+}
+
+/// Compute the fees charged by the trading post when selling
+///
+/// Both the listing and exchange fees are rounded to the nearest coin and
+/// have a minimum of 1 copper each.
+///
+/// # Arguments
+///
+/// * `total` - Total price of the sale in coins
+pub fn trading_fees(total: i64) -> i64 {
+    if total <= 0 {
+        return 0;
+    }
+
+    let listing = (total * LISTING_FEE_PERCENT + 50) / 100;
+    let exchange = (total * EXCHANGE_FEE_PERCENT + 50) / 100;
+
+    listing.max(1) + exchange.max(1)
+}
+
+/// Obtain the date in which a transaction took place
+fn transaction_date(transaction: &TPTransaction) -> &Timestamp {
+    match transaction.purchased {
+        Some(ref date) => date,
+        None => &transaction.created
+    }
+}
+
+/// Compute realized profit per item from the transaction history
+///
+/// Buys and sells of the same item are matched in chronological order (first
+/// in, first out). A sold unit is only matched with units bought before it.
+///
+/// # Arguments
+///
+/// * `buys` - Fulfilled buy transactions (`get_history_buy_transactions()`)
+/// * `sells` - Fulfilled sell transactions (`get_history_sell_transactions()`)
+pub fn realized_profits(
+    buys: &[TPTransaction],
+    sells: &[TPTransaction]
+) -> Vec<ItemProfit> {
+    // Events per item in chronological order, `true` for buys
+    let mut events: BTreeMap<i32, Vec<(&Timestamp, bool, &TPTransaction)>> =
+        BTreeMap::new();
+
+    for buy in buys {
+        events.entry(buy.item_id)
+            .or_insert_with(Vec::new)
+            .push((transaction_date(buy), true, buy));
+    }
+
+    for sell in sells {
+        events.entry(sell.item_id)
+            .or_insert_with(Vec::new)
+            .push((transaction_date(sell), false, sell));
+    }
+
+    let mut result = Vec::new();
+
+    for (item_id, mut item_events) in events {
+        // Buys go first when they happened at the same instant
+        item_events.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+
+        let mut profit = ItemProfit {
+            item_id: item_id,
+            quantity: 0,
+            cost: 0,
+            revenue: 0,
+            fees: 0,
+            profit: 0,
+            unmatched_sold: 0
+        };
+
+        // Pending bought units as (unit price, quantity)
+        let mut lots: VecDeque<(i64, i64)> = VecDeque::new();
+
+        for (_, is_buy, transaction) in item_events {
+            let price = transaction.price as i64;
+            let mut remaining = transaction.quantity as i64;
+
+            if is_buy {
+                lots.push_back((price, remaining));
+                continue;
+            }
+
+            let mut matched = 0;
+
+            while remaining > 0 {
+                let (lot_price, lot_quantity) = match lots.pop_front() {
+                    Some(lot) => lot,
+                    None => break
+                };
+
+                let taken = remaining.min(lot_quantity);
+                profit.cost += lot_price * taken;
+                matched += taken;
+                remaining -= taken;
+
+                if lot_quantity > taken {
+                    lots.push_front((lot_price, lot_quantity - taken));
+                }
+            }
+
+            let revenue = price * matched;
+            profit.quantity += matched;
+            profit.revenue += revenue;
+            profit.fees += trading_fees(revenue);
+            profit.unmatched_sold += remaining;
+        }
+
+        profit.profit = profit.revenue - profit.fees - profit.cost;
+        result.push(profit);
+    }
+
+    result
+}
+
+/// Compute the total fees paid for the given sell transactions
+///
+/// # Arguments
+///
+/// * `sells` - Fulfilled sell transactions
+pub fn total_fees(sells: &[TPTransaction]) -> i64 {
+    sells.iter()
+        .map(|sell| trading_fees(sell.price as i64 * sell.quantity as i64))
+        .sum()
+}
+
+/// Compute the trading post volume per day
+/// Requires the `chrono` feature
+///
+/// # Arguments
+///
+/// * `buys` - Fulfilled buy transactions
+/// * `sells` - Fulfilled sell transactions
+#[cfg(feature = "chrono")]
+pub fn daily_volume(
+    buys: &[TPTransaction],
+    sells: &[TPTransaction]
+) -> Vec<DailyVolume> {
+    let mut days: BTreeMap<NaiveDate, DailyVolume> = BTreeMap::new();
+
+    for (transactions, is_buy) in vec![(buys, true), (sells, false)] {
+        for transaction in transactions {
+            let date = transaction_date(transaction).naive_utc().date();
+            let quantity = transaction.quantity as i64;
+            let value = transaction.price as i64 * quantity;
+
+            let day = days.entry(date).or_insert(DailyVolume {
+                date: date,
+                bought: 0,
+                buy_value: 0,
+                sold: 0,
+                sell_value: 0
+            });
+
+            if is_buy {
+                day.bought += quantity;
+                day.buy_value += value;
+            } else {
+                day.sold += quantity;
+                day.sell_value += value;
+            }
+        }
+    }
+
+    days.into_iter().map(|(_, volume)| volume).collect()
+}
+
+/// Summarize the trading post history of an account
+/// Requires the `chrono` feature
+///
+/// # Arguments
+///
+/// * `buys` - Fulfilled buy transactions (`get_history_buy_transactions()`)
+/// * `sells` - Fulfilled sell transactions (`get_history_sell_transactions()`)
+#[cfg(feature = "chrono")]
+pub fn summarize_transactions(
+    buys: &[TPTransaction],
+    sells: &[TPTransaction]
+) -> TransactionSummary {
+    let items = realized_profits(buys, sells);
+    let total_profit = items.iter().map(|item| item.profit).sum();
+
+    TransactionSummary {
+        items: items,
+        total_fees: total_fees(sells),
+        total_profit: total_profit,
+        volume: daily_volume(buys, sells)
+    }
+}
+
+/// Obtain current trading post prices for the given items
+///
+/// IDs are requested in batches of `MAX_BULK_IDS`. Items that cannot be
+/// traded (e.g. account bound) are not present in the result.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - Item IDs to obtain the prices for
+pub fn get_item_prices(
+    client: &APIClient,
+    ids: &[i32]
+) -> Result<HashMap<i32, ItemPrice>, APIError> {
+    let mut unique: Vec<i32> = ids.to_vec();
+    unique.sort();
+    unique.dedup();
+
+    let mut prices = HashMap::new();
+
+    for batch in IdList::new(unique).batches(0) {
+        let pricings = match get_pricings(client, batch) {
+            Ok(pricings) => pricings,
+            Err(ref e) if e.kind() == APIErrorKind::InvalidIds => continue,
+            Err(e) => return Err(e)
+        };
+
+        for pricing in pricings {
+            prices.insert(pricing.id, ItemPrice {
+                item_id: pricing.id,
+                buy: pricing.buys.unit_price,
+                sell: pricing.sells.unit_price
+            });
+        }
+    }
+
+    Ok(prices)
+}
+
+/// Build an exchange rate step from an API result
+fn exchange_step(quantity: i32, rate: ExchangeRate) -> ExchangeRateStep {
+    ExchangeRateStep {
+        quantity: quantity,
+        coins_per_gem: rate.coins_per_gem,
+        obtained: rate.quantity
+    }
+}
+
+/// Obtain exchange rates for a ladder of coin and gem quantities
+///
+/// The rate depends on the quantity being exchanged, so a request is issued
+/// for each of the quantities. Requests are performed concurrently, one thread
+/// per quantity. Fails with the first error returned by the API.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `coins` - Coin quantities to exchange for gems (see `DEFAULT_COIN_LADDER`)
+/// * `gems` - Gem quantities to exchange for coins (see `DEFAULT_GEM_LADDER`)
+pub fn get_exchange_rate_table(
+    client: &APIClient,
+    coins: &[i32],
+    gems: &[i32]
+) -> Result<ExchangeRateTable, APIError> {
+    thread::scope(|scope| {
+        let coin_handles: Vec<_> = coins.iter()
+            .map(|&quantity| scope.spawn(move || {
+                get_coin_exchange(client, quantity)
+                    .map(|rate| exchange_step(quantity, rate))
+            }))
+            .collect();
+
+        let gem_handles: Vec<_> = gems.iter()
+            .map(|&quantity| scope.spawn(move || {
+                get_gem_exchange(client, quantity)
+                    .map(|rate| exchange_step(quantity, rate))
+            }))
+            .collect();
+
+        let coins_to_gems = coin_handles.into_iter()
+            .map(|handle| {
+                handle.join()
+                    .unwrap_or_else(|payload| Err(panic_error(payload)))
+            })
+            .collect::<Result<Vec<ExchangeRateStep>, APIError>>()?;
+
+        let gems_to_coins = gem_handles.into_iter()
+            .map(|handle| {
+                handle.join()
+                    .unwrap_or_else(|payload| Err(panic_error(payload)))
+            })
+            .collect::<Result<Vec<ExchangeRateStep>, APIError>>()?;
+
+        Ok(ExchangeRateTable {
+            coins_to_gems: coins_to_gems,
+            gems_to_coins: gems_to_coins
+        })
+    })
+}
+
+/// Compute the loss of a round trip exchange
+///
+/// # Arguments
+///
+/// * `start` - Quantity initially exchanged
+/// * `intermediate` - Quantity obtained in the first exchange
+/// * `end` - Quantity obtained when exchanging `intermediate` back
+pub fn round_trip(start: i32, intermediate: i32, end: i32) -> RoundTrip {
+    let loss = start - end;
+
+    RoundTrip {
+        start: start,
+        intermediate: intermediate,
+        end: end,
+        loss: loss,
+        loss_percent: if start > 0 {
+            loss as f64 * 100.0 / start as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Obtain the round trip of exchanging coins for gems and back
+///
+/// Performs up to two sequential requests, as the rate of the second
+/// exchange depends on the gems obtained in the first one.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `coins` - Coins to exchange
+pub fn get_coin_round_trip(
+    client: &APIClient,
+    coins: i32
+) -> Result<RoundTrip, APIError> {
+    let gems = get_coin_exchange(client, coins)?.quantity;
+    let end = if gems > 0 {
+        get_gem_exchange(client, gems)?.quantity
+    } else {
+        0
+    };
+
+    Ok(round_trip(coins, gems, end))
+}
+
+/// Obtain the round trip of exchanging gems for coins and back
+///
+/// Performs up to two sequential requests, as the rate of the second
+/// exchange depends on the coins obtained in the first one.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `gems` - Gems to exchange
+pub fn get_gem_round_trip(
+    client: &APIClient,
+    gems: i32
+) -> Result<RoundTrip, APIError> {
+    let coins = get_gem_exchange(client, gems)?.quantity;
+    let end = if coins > 0 {
+        get_coin_exchange(client, coins)?.quantity
+    } else {
+        0
+    };
+
+    Ok(round_trip(gems, coins, end))
+}
+
+/// Obtain round trips for a ladder of coin and gem quantities
+///
+/// Each quantity is processed in its own thread. Fails with the first error
+/// returned by the API.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `coins` - Initial coin quantities (see `DEFAULT_COIN_LADDER`)
+/// * `gems` - Initial gem quantities (see `DEFAULT_GEM_LADDER`)
+pub fn get_round_trip_table(
+    client: &APIClient,
+    coins: &[i32],
+    gems: &[i32]
+) -> Result<RoundTripTable, APIError> {
+    thread::scope(|scope| {
+        let coin_handles: Vec<_> = coins.iter()
+            .map(|&quantity| scope.spawn(move || {
+                get_coin_round_trip(client, quantity)
+            }))
+            .collect();
+
+        let gem_handles: Vec<_> = gems.iter()
+            .map(|&quantity| scope.spawn(move || {
+                get_gem_round_trip(client, quantity)
+            }))
+            .collect();
+
+        let coin_trips = coin_handles.into_iter()
+            .map(|handle| {
+                handle.join()
+                    .unwrap_or_else(|payload| Err(panic_error(payload)))
+            })
+            .collect::<Result<Vec<RoundTrip>, APIError>>()?;
+
+        let gem_trips = gem_handles.into_iter()
+            .map(|handle| {
+                handle.join()
+                    .unwrap_or_else(|payload| Err(panic_error(payload)))
+            })
+            .collect::<Result<Vec<RoundTrip>, APIError>>()?;
+
+        Ok(RoundTripTable {
+            coins: coin_trips,
+            gems: gem_trips
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use reqwest::StatusCode;
+    use reqwest::header::HeaderMap;
+    use serde_json;
+    use client::APIClient;
+    use api_v2::types::{TPItem, TPTransaction};
+    use helpers::commerce::*;
+    use transport::{MockTransport, Response};
+
+    fn transaction(
+        id: i64,
+        item_id: i32,
+        price: i32,
+        quantity: i32,
+        purchased: &str
+    ) -> TPTransaction {
+        serde_json::from_str(&format!(
+            r#"{{"id": {}, "item_id": {}, "price": {}, "quantity": {},
+                "created": "{}", "purchased": "{}"}}"#,
+            id, item_id, price, quantity, purchased, purchased
+        )).unwrap()
+    }
+
+    #[test]
+    fn listings() {
+        let before: TPItem = serde_json::from_str(r#"{
+            "id": 19684,
+            "buys": [
+                {"listings": 1, "unit_price": 100, "quantity": 250},
+                {"listings": 2, "unit_price": 99, "quantity": 500}
+            ],
+            "sells": [
+                {"listings": 1, "unit_price": 120, "quantity": 100}
+            ]
+        }"#).unwrap();
+        let after: TPItem = serde_json::from_str(r#"{
+            "id": 19684,
+            "buys": [
+                {"listings": 1, "unit_price": 101, "quantity": 50},
+                {"listings": 2, "unit_price": 99, "quantity": 500}
+            ],
+            "sells": [
+                {"listings": 1, "unit_price": 120, "quantity": 40}
+            ]
+        }"#).unwrap();
+
+        let delta = listing_delta(&before, &after).unwrap();
+        assert_eq!(delta.buys.changes.len(), 2);
+        assert!(delta.buys.changes[0].is_removed());
+        assert!(delta.buys.changes[1].is_added());
+        assert_eq!(delta.buys.net_quantity, -200);
+        assert_eq!(delta.sells.changes[0].delta(), -60);
+
+        let other: TPItem = serde_json::from_str(r#"{"id": 1}"#).unwrap();
+        assert!(listing_delta(&before, &other).is_none());
+    }
+
+    #[test]
+    fn fees() {
+        assert_eq!(trading_fees(0), 0);
+        assert_eq!(trading_fees(1), 2);
+        assert_eq!(trading_fees(100), 15);
+        assert_eq!(trading_fees(1000), 150);
+    }
+
+    #[test]
+    fn profits() {
+        let buys = vec![
+            transaction(1, 19684, 100, 5, "2017-06-01T10:00:00+00:00"),
+            transaction(2, 19684, 120, 5, "2017-06-02T10:00:00+00:00"),
+        ];
+        let sells = vec![
+            transaction(3, 19684, 200, 7, "2017-06-03T10:00:00+00:00"),
+            transaction(4, 19709, 50, 2, "2017-06-03T10:00:00+00:00"),
+        ];
+
+        let items = realized_profits(&buys, &sells);
+        assert_eq!(items.len(), 2);
+
+        let first = &items[0];
+        assert_eq!(first.item_id, 19684);
+        assert_eq!(first.quantity, 7);
+        assert_eq!(first.cost, 5 * 100 + 2 * 120);
+        assert_eq!(first.revenue, 1400);
+        assert_eq!(first.fees, 210);
+        assert_eq!(first.profit, 1400 - 210 - 740);
+        assert_eq!(first.unmatched_sold, 0);
+
+        let second = &items[1];
+        assert_eq!(second.quantity, 0);
+        assert_eq!(second.unmatched_sold, 2);
+    }
+
+    #[test]
+    fn sells_before_buys_are_unmatched() {
+        let buys = vec![
+            transaction(1, 19684, 100, 1, "2017-06-02T10:00:00+00:00"),
+        ];
+        let sells = vec![
+            transaction(2, 19684, 200, 1, "2017-06-01T10:00:00+00:00"),
+        ];
+
+        let items = realized_profits(&buys, &sells);
+        assert_eq!(items[0].quantity, 0);
+        assert_eq!(items[0].unmatched_sold, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn volume() {
+        let buys = vec![
+            transaction(1, 19684, 100, 5, "2017-06-01T10:00:00+00:00"),
+            transaction(2, 19684, 100, 5, "2017-06-01T20:00:00+00:00"),
+        ];
+        let sells = vec![
+            transaction(3, 19684, 200, 2, "2017-06-02T10:00:00+00:00"),
+        ];
+
+        let volume = daily_volume(&buys, &sells);
+        assert_eq!(volume.len(), 2);
+        assert_eq!(volume[0].bought, 10);
+        assert_eq!(volume[0].buy_value, 1000);
+        assert_eq!(volume[1].sold, 2);
+        assert_eq!(volume[1].sell_value, 400);
+    }
+
+    #[test]
+    fn exchange_rate_table() {
+        let client = APIClient::new("en", None);
+        let result = get_exchange_rate_table(
+            &client,
+            &DEFAULT_COIN_LADDER,
+            &DEFAULT_GEM_LADDER
+        );
+
+        match result {
+            Ok(table) => {
+                assert_eq!(table.coins_to_gems.len(), DEFAULT_COIN_LADDER.len());
+                assert_eq!(table.gems_to_coins.len(), DEFAULT_GEM_LADDER.len());
+            },
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+
+    #[test]
+    fn round_trip_loss() {
+        let trip = round_trip(1000000, 3000, 800000);
+        assert_eq!(trip.loss, 200000);
+        assert_eq!(trip.loss_percent, 20.0);
+
+        assert_eq!(round_trip(0, 0, 0).loss_percent, 0.0);
+    }
+
+    #[test]
+    fn round_trip_table() {
+        let client = APIClient::new("en", None);
+        let result = get_round_trip_table(
+            &client,
+            &DEFAULT_COIN_LADDER,
+            &DEFAULT_GEM_LADDER
+        );
+
+        match result {
+            Ok(table) => {
+                assert!(table.coins.iter().all(|trip| trip.loss >= 0));
+                assert!(table.gems.iter().all(|trip| trip.loss >= 0));
+            },
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+
+    #[test]
+    fn invalid_item_prices() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/commerce/prices?ids=1,2", Response::new(
+            StatusCode::NOT_FOUND,
+            HeaderMap::new(),
+            br#"{"text": "all ids provided are invalid"}"#.to_vec()
+        ));
+        transport.respond("/v2/commerce/prices?ids=3", Response::new(
+            StatusCode::BAD_REQUEST,
+            HeaderMap::new(),
+            br#"{"text": "invalid id"}"#.to_vec()
+        ));
+
+        let client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport)
+            .build()
+            .unwrap();
+
+        assert!(get_item_prices(&client, &[2, 1, 1]).unwrap().is_empty());
+        assert!(get_item_prices(&client, &[3]).is_err());
+    }
+}
@@ -0,0 +1,366 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Dye collection helpers
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::f64;
+
+use client::APIClient;
+use common::APIError;
+use api_v2::account::get_account_dyes;
+use api_v2::items::{get_color_ids, get_colors};
+use api_v2::types::{Color, ColorDetails, IdList};
+use helpers::commerce::{get_item_prices, ItemPrice};
+
+
+/// Hue families dyes are grouped in, in display order
+pub const HUE_GROUPS: [&'static str; 8] = [
+    "Red", "Orange", "Yellow", "Green", "Blue", "Purple", "Brown", "Gray"
+];
+
+/// Armor material a dye is rendered on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DyeMaterial {
+    Cloth,
+    Leather,
+    Metal,
+    /// Falls back to cloth for colors without a specific fur rendering
+    Fur
+}
+
+/// Unlocked dyes of a single hue family
+#[derive(Debug)]
+pub struct DyeGroup {
+    /// Name of the hue family, one of `HUE_GROUPS`
+    pub name: &'static str,
+    /// Color IDs in the family, from darkest to lightest
+    pub colors: Vec<i32>
+}
+
+/// Cost of unlocking a dye color that the account does not have yet
+#[derive(Debug)]
+pub struct DyeCost {
+    /// Color ID
+    pub color_id: i32,
+    /// Localized name of the color
+    pub name: String,
+    /// ID of the dye item that unlocks the color, if any
+    pub item_id: Option<i32>,
+    /// Lowest sell offer in the trading post, i.e. coins paid when buying
+    /// the dye instantly. `None` if the dye cannot be bought
+    pub price: Option<i32>
+}
+
+/// Obtain the locked colors, sorted from cheapest to most expensive
+///
+/// Colors whose dye cannot be bought in the trading post (no unlock item,
+/// account bound or without sell offers) are placed at the end, sorted by
+/// name.
+///
+/// # Arguments
+///
+/// * `colors` - Colors as obtained from `get_colors()`
+/// * `unlocked` - Color IDs as obtained from `get_account_dyes()`
+/// * `prices` - Prices of the dye items, indexed by item ID
+pub fn locked_dye_costs(
+    colors: &[Color],
+    unlocked: &[i32],
+    prices: &HashMap<i32, ItemPrice>
+) -> Vec<DyeCost> {
+    let unlocked: HashSet<&i32> = unlocked.iter().collect();
+
+    let mut costs: Vec<DyeCost> = colors.iter()
+        .filter(|color| !unlocked.contains(&color.id))
+        .map(|color| DyeCost {
+            color_id: color.id,
+            name: color.name.clone(),
+            item_id: color.item,
+            price: color.item
+                .and_then(|item| prices.get(&item))
+                .map(|price| price.sell)
+                .and_then(|sell| if sell > 0 { Some(sell) } else { None })
+        })
+        .collect();
+
+    costs.sort_by(|a, b| {
+        match (a.price, b.price) {
+            (Some(x), Some(y)) => x.cmp(&y),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.name.cmp(&b.name)
+        }
+    });
+
+    costs
+}
+
+/// Convert an RGB color to HSL
+///
+/// Returns the hue in degrees (`0.0` to `360.0`), and the saturation and
+/// lightness (`0.0` to `1.0`).
+///
+/// # Arguments
+///
+/// * `rgb` - Red, green and blue components, from 0 to 255
+pub fn rgb_to_hsl(rgb: [i32; 3]) -> (f64, f64, f64) {
+    let r = rgb[0] as f64 / 255.0;
+    let g = rgb[1] as f64 / 255.0;
+    let b = rgb[2] as f64 / 255.0;
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+
+    if delta < f64::EPSILON {
+        return (0.0, 0.0, lightness);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        60.0 * (((g - b) / delta) % 6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    (if hue < 0.0 { hue + 360.0 } else { hue }, saturation, lightness)
+}
+
+/// Obtain the hue family of a color, one of `HUE_GROUPS`
+///
+/// Colors with little saturation are gray, and dark oranges and yellows
+/// are brown.
+///
+/// # Arguments
+///
+/// * `rgb` - Red, green and blue components, from 0 to 255
+pub fn hue_group(rgb: [i32; 3]) -> &'static str {
+    let (hue, saturation, lightness) = rgb_to_hsl(rgb);
+
+    if saturation < 0.15 || lightness < 0.05 || lightness > 0.95 {
+        return "Gray";
+    }
+
+    match hue {
+        h if h < 15.0 || h >= 330.0 => "Red",
+        h if h < 70.0 && lightness < 0.35 => "Brown",
+        h if h < 45.0 => "Orange",
+        h if h < 70.0 => "Yellow",
+        h if h < 165.0 => "Green",
+        h if h < 260.0 => "Blue",
+        _ => "Purple"
+    }
+}
+
+/// Obtain the rendering of a color on a material
+fn rendering(color: &Color, material: DyeMaterial) -> &ColorDetails {
+    match material {
+        DyeMaterial::Cloth => &color.cloth,
+        DyeMaterial::Leather => &color.leather,
+        DyeMaterial::Metal => &color.metal,
+        DyeMaterial::Fur => color.fur.as_ref().unwrap_or(&color.cloth)
+    }
+}
+
+/// Group unlocked dyes by hue family as rendered on a material
+///
+/// Groups are returned in `HUE_GROUPS` order and empty groups are omitted.
+///
+/// # Arguments
+///
+/// * `colors` - Colors as obtained from `get_colors()`
+/// * `unlocked` - Color IDs as obtained from `get_account_dyes()`
+/// * `material` - Material to group the dyes by
+pub fn group_dyes(
+    colors: &[Color],
+    unlocked: &[i32],
+    material: DyeMaterial
+) -> Vec<DyeGroup> {
+    let mut grouped: HashMap<&'static str, Vec<(f64, i32)>> = HashMap::new();
+
+    for color in colors.iter().filter(|c| unlocked.contains(&c.id)) {
+        let rgb = &rendering(color, material).rgb;
+        if rgb.len() < 3 {
+            continue;
+        }
+
+        let rgb = [rgb[0], rgb[1], rgb[2]];
+        let (_, _, lightness) = rgb_to_hsl(rgb);
+        grouped.entry(hue_group(rgb))
+            .or_insert_with(Vec::new)
+            .push((lightness, color.id));
+    }
+
+    HUE_GROUPS.iter()
+        .filter_map(|name| grouped.remove(name).map(|mut colors| {
+            colors.sort_by(|a, b| {
+                a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal)
+            });
+
+            DyeGroup {
+                name: name,
+                colors: colors.into_iter().map(|(_, id)| id).collect()
+            }
+        }))
+        .collect()
+}
+
+/// Obtain the unlocked dyes of the account grouped by hue family
+///
+/// This fetches the details of every unlocked color.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `material` - Material to group the dyes by
+pub fn get_dye_groups(
+    client: &APIClient,
+    material: DyeMaterial
+) -> Result<Vec<DyeGroup>, APIError> {
+    let unlocked = get_account_dyes(client)?;
+
+    let mut colors = Vec::new();
+    for batch in IdList::new(unlocked.clone()).batches(0) {
+        colors.extend(get_colors(client, batch)?);
+    }
+
+    Ok(group_dyes(&colors, &unlocked, material))
+}
+
+/// Obtain the cost of unlocking each dye color missing from the account
+///
+/// This fetches the whole color catalogue and the prices of the dye items.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_locked_dye_costs(
+    client: &APIClient
+) -> Result<Vec<DyeCost>, APIError> {
+    let unlocked = get_account_dyes(client)?;
+
+    let mut colors = Vec::new();
+    for batch in get_color_ids(client)?.batches(0) {
+        colors.extend(get_colors(client, batch)?);
+    }
+
+    let items: Vec<i32> = colors.iter()
+        .filter(|color| !unlocked.contains(&color.id))
+        .filter_map(|color| color.item)
+        .collect();
+    let prices = get_item_prices(client, &items)?;
+
+    Ok(locked_dye_costs(&colors, &unlocked, &prices))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use serde_json;
+    use api_v2::types::Color;
+    use helpers::commerce::ItemPrice;
+    use helpers::dyes::*;
+
+    fn color(id: i32, name: &str, item: Option<i32>) -> Color {
+        rendered(id, name, item, [0, 0, 0])
+    }
+
+    fn rendered(
+        id: i32,
+        name: &str,
+        item: Option<i32>,
+        rgb: [i32; 3]
+    ) -> Color {
+        let details = format!(
+            r#"{{"brightness": 0, "contrast": 1, "hue": 0,
+                 "saturation": 0, "lightness": 1,
+                 "rgb": [{}, {}, {}]}}"#,
+            rgb[0], rgb[1], rgb[2]
+        );
+        let item = match item {
+            Some(item) => format!(r#", "item": {}"#, item),
+            None => String::new()
+        };
+
+        serde_json::from_str(&format!(
+            r#"{{"id": {}, "name": "{}", "base_rgb": [0, 0, 0],
+                 "cloth": {d}, "leather": {d}, "metal": {d}{}}}"#,
+            id, name, item, d = details
+        )).unwrap()
+    }
+
+    #[test]
+    fn cheapest_first() {
+        let colors = vec![
+            color(1, "Dye Remover", None),
+            color(2, "Abyss", Some(20)),
+            color(3, "Celestial", Some(30)),
+            color(4, "Black", Some(40)),
+            color(5, "Unpriced", Some(50))
+        ];
+        let mut prices = HashMap::new();
+        prices.insert(20, ItemPrice { item_id: 20, buy: 1, sell: 50000 });
+        prices.insert(30, ItemPrice { item_id: 30, buy: 1, sell: 300 });
+        prices.insert(40, ItemPrice { item_id: 40, buy: 1, sell: 100 });
+        prices.insert(50, ItemPrice { item_id: 50, buy: 1, sell: 0 });
+
+        let costs = locked_dye_costs(&colors, &[4], &prices);
+        let ids: Vec<i32> = costs.iter().map(|c| c.color_id).collect();
+
+        assert_eq!(ids, vec![3, 2, 1, 5]);
+        assert_eq!(costs[0].price, Some(300));
+        assert_eq!(costs[3].price, None);
+    }
+
+    #[test]
+    fn hues() {
+        assert_eq!(hue_group([200, 30, 30]), "Red");
+        assert_eq!(hue_group([230, 140, 20]), "Orange");
+        assert_eq!(hue_group([90, 55, 20]), "Brown");
+        assert_eq!(hue_group([40, 160, 60]), "Green");
+        assert_eq!(hue_group([30, 60, 200]), "Blue");
+        assert_eq!(hue_group([120, 40, 160]), "Purple");
+        assert_eq!(hue_group([128, 128, 128]), "Gray");
+    }
+
+    #[test]
+    fn groups() {
+        let colors = vec![
+            rendered(1, "Blood", None, [120, 10, 10]),
+            rendered(2, "Red", None, [220, 40, 40]),
+            rendered(3, "Sky", None, [100, 150, 240]),
+            rendered(4, "Locked", None, [230, 20, 20])
+        ];
+
+        let groups = group_dyes(&colors, &[3, 2, 1], DyeMaterial::Fur);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].name, "Red");
+        assert_eq!(groups[0].colors, vec![1, 2]);
+        assert_eq!(groups[1].name, "Blue");
+    }
+}
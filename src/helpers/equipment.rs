@@ -0,0 +1,245 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Comparison of gear sets
+/// These help managing several equipment sets on the same character
+
+use std::collections::BTreeMap;
+
+use api_v2::types::{Equipment, EquipmentAttributes};
+
+
+/// Change in a single equipment slot between two gear sets
+#[derive(Debug)]
+pub struct SlotChange {
+    /// Equipment slot (e.g. `Helm`, `WeaponA1`)
+    pub slot: String,
+    /// Item ID in the original set, if the slot was occupied
+    pub from_item: Option<i32>,
+    /// Item ID in the new set, if the slot is occupied
+    pub to_item: Option<i32>,
+    /// Itemstat ID selected in the original set
+    pub from_stats: Option<i32>,
+    /// Itemstat ID selected in the new set
+    pub to_stats: Option<i32>,
+    /// Infusions in the original set that are not in the new one
+    pub missing_infusions: Vec<i32>,
+    /// Upgrade components in the original set that are not in the new one
+    pub missing_upgrades: Vec<i32>
+}
+
+impl SlotChange {
+    /// Check whether a different item is in the slot
+    pub fn is_swap(&self) -> bool {
+        self.from_item != self.to_item
+    }
+
+    /// Check whether the selected stats of the slot changed
+    pub fn has_stat_change(&self) -> bool {
+        self.from_stats != self.to_stats
+    }
+}
+
+/// Differences between two gear sets
+#[derive(Debug)]
+pub struct EquipmentDiff {
+    /// Slots that changed, sorted by slot name
+    pub changes: Vec<SlotChange>,
+    /// Net change of each attribute (e.g. `Power`), as reported by the
+    /// selected stats. Attributes that do not change are left out
+    pub attributes: BTreeMap<&'static str, i32>
+}
+
+impl EquipmentDiff {
+    /// Check whether both sets are the same
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.attributes.is_empty()
+    }
+}
+
+/// Obtain the attributes given by a set of stats, keyed by API name
+pub(crate) fn attribute_values(
+    attributes: &EquipmentAttributes
+) -> [(&'static str, i32); 9] {
+    [
+        ("Power", attributes.power),
+        ("Precision", attributes.precision),
+        ("Toughness", attributes.toughness),
+        ("Vitality", attributes.vitality),
+        ("ConditionDamage", attributes.condition_damage),
+        ("ConditionDuration", attributes.condition_duration),
+        ("CritDamage", attributes.critical_damage),
+        ("Healing", attributes.healing),
+        ("BoonDuration", attributes.boon_duration)
+    ]
+}
+
+/// Add the attributes of a piece of equipment to the totals
+fn add_attributes(
+    totals: &mut BTreeMap<&'static str, i32>,
+    piece: &Equipment,
+    sign: i32
+) {
+    let attributes = piece.stats.as_ref()
+        .and_then(|stats| stats.attributes.as_ref());
+
+    if let Some(attributes) = attributes {
+        for &(name, value) in attribute_values(attributes).iter() {
+            *totals.entry(name).or_insert(0) += sign * value;
+        }
+    }
+}
+
+/// Obtain the elements of `from` that are not in `to`, counting duplicates
+fn missing(from: &[i32], to: &[i32]) -> Vec<i32> {
+    let mut remaining = to.to_vec();
+
+    from.iter()
+        .filter(|id| {
+            match remaining.iter().position(|r| r == *id) {
+                Some(index) => {
+                    remaining.remove(index);
+                    false
+                },
+                None => true
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+/// Compare two gear sets slot by slot
+///
+/// Slots are matched by name. A slot is reported if the item, the selected
+/// stats, or the slotted upgrades or infusions differ. Missing infusions and
+/// upgrades are those present in `from` that would be lost when switching
+/// to `to`.
+///
+/// # Arguments
+///
+/// * `from` - Original gear set (e.g. as obtained from
+///     `get_character_equipment()`)
+/// * `to` - Gear set to compare against
+pub fn diff_equipment(from: &[Equipment], to: &[Equipment]) -> EquipmentDiff {
+    let mut slots: BTreeMap<&str, (Option<&Equipment>, Option<&Equipment>)> =
+        BTreeMap::new();
+    let mut attributes = BTreeMap::new();
+
+    for piece in from {
+        slots.entry(&piece.slot).or_insert((None, None)).0 = Some(piece);
+        add_attributes(&mut attributes, piece, -1);
+    }
+
+    for piece in to {
+        slots.entry(&piece.slot).or_insert((None, None)).1 = Some(piece);
+        add_attributes(&mut attributes, piece, 1);
+    }
+
+    let empty = Vec::new();
+    let changes = slots.into_iter()
+        .map(|(slot, (old, new))| {
+            let stats = |piece: Option<&Equipment>| {
+                piece.and_then(|p| p.stats.as_ref()).map(|s| s.id)
+            };
+            let infusions = |piece: Option<&Equipment>| {
+                piece.map(|p| &p.infusions).unwrap_or(&empty).clone()
+            };
+            let upgrades = |piece: Option<&Equipment>| {
+                piece.map(|p| &p.upgrades).unwrap_or(&empty).clone()
+            };
+
+            SlotChange {
+                slot: slot.to_string(),
+                from_item: old.map(|p| p.id),
+                to_item: new.map(|p| p.id),
+                from_stats: stats(old),
+                to_stats: stats(new),
+                missing_infusions: missing(&infusions(old), &infusions(new)),
+                missing_upgrades: missing(&upgrades(old), &upgrades(new))
+            }
+        })
+        .filter(|change| {
+            change.is_swap()
+                || change.has_stat_change()
+                || !change.missing_infusions.is_empty()
+                || !change.missing_upgrades.is_empty()
+        })
+        .collect();
+
+    attributes.retain(|_, value| *value != 0);
+
+    EquipmentDiff {
+        changes: changes,
+        attributes: attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use api_v2::types::Equipment;
+    use helpers::equipment::*;
+
+    fn gear(json: &str) -> Vec<Equipment> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn diff() {
+        let from = gear(r#"[
+            {"id": 1, "slot": "Helm", "infusions": [49432, 49432],
+             "stats": {"id": 161, "attributes": {"Power": 63}}},
+            {"id": 2, "slot": "Coat", "upgrades": [24836]},
+            {"id": 3, "slot": "Boots"}
+        ]"#);
+        let to = gear(r#"[
+            {"id": 1, "slot": "Helm", "infusions": [49432],
+             "stats": {"id": 160, "attributes": {"Power": 45,
+                                                 "Vitality": 45}}},
+            {"id": 4, "slot": "Coat", "upgrades": [24836]},
+            {"id": 3, "slot": "Boots"}
+        ]"#);
+
+        let diff = diff_equipment(&from, &to);
+        assert_eq!(diff.changes.len(), 2);
+
+        let coat = &diff.changes[0];
+        assert_eq!(coat.slot, "Coat");
+        assert!(coat.is_swap());
+        assert!(coat.missing_upgrades.is_empty());
+
+        let helm = &diff.changes[1];
+        assert!(!helm.is_swap());
+        assert!(helm.has_stat_change());
+        assert_eq!(helm.missing_infusions, vec![49432]);
+
+        assert_eq!(diff.attributes.get("Power"), Some(&-18));
+        assert_eq!(diff.attributes.get("Vitality"), Some(&45));
+        assert!(diff.attributes.get("Precision").is_none());
+    }
+
+    #[test]
+    fn same_gear() {
+        let set = r#"[{"id": 1, "slot": "Helm", "infusions": [49432]}]"#;
+        assert!(diff_equipment(&gear(set), &gear(set)).is_empty());
+    }
+}
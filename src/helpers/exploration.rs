@@ -0,0 +1,255 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Exploration helpers
+/// These require an API key to obtain the data
+
+use client::APIClient;
+use common::APIError;
+use api_v2::characters::get_character_heropoints;
+use api_v2::continents::continents;
+use api_v2::types::{ContinentFloor, ContinentMap};
+
+
+/// Regions of Tyria (continent 1, floor 1) whose maps count towards world
+/// completion: Shiverpeak Mountains, Ascalon, Kryta, Maguuma Jungle and
+/// Ruins of Orr
+pub const WORLD_COMPLETION_REGIONS: [i32; 5] = [1, 2, 3, 4, 5];
+
+/// Exploration progress of a character in a single map
+///
+/// Totals and hero points are exact. The API does not expose which hearts,
+/// points of interest, waypoints and vistas a character has completed, so
+/// `estimated_progress` is an estimate.
+#[derive(Debug)]
+pub struct MapExploration {
+    /// Map ID
+    pub map_id: i32,
+    /// Localized name of the map
+    pub name: String,
+    /// Hero points unlocked by the character in the map (exact)
+    pub hero_points_done: usize,
+    /// Hero points available in the map (exact)
+    pub hero_points_total: usize,
+    /// Renown hearts in the map (exact)
+    pub tasks: usize,
+    /// Points of interest in the map (exact)
+    pub landmarks: usize,
+    /// Waypoints in the map (exact)
+    pub waypoints: usize,
+    /// Vistas in the map (exact)
+    pub vistas: usize,
+    /// Estimated completion of the map, from 0 to 1 (estimated)
+    pub estimated_progress: f64
+}
+
+impl MapExploration {
+    /// Obtain the number of objectives required to complete the map
+    pub fn objectives(&self) -> usize {
+        self.hero_points_total + self.tasks + self.landmarks
+            + self.waypoints + self.vistas
+    }
+}
+
+/// Estimated progress of a character towards the Gift of Exploration
+#[derive(Debug)]
+pub struct ExplorationEstimate {
+    /// Progress in each map counting towards world completion, sorted by
+    /// map ID
+    pub maps: Vec<MapExploration>,
+    /// Hero points unlocked in those maps (exact)
+    pub hero_points_done: usize,
+    /// Hero points available in those maps (exact)
+    pub hero_points_total: usize,
+    /// Estimated world completion, from 0 to 1 (estimated)
+    pub estimated_progress: f64
+}
+
+/// Count the points of interest of a given type in a map
+fn count_pois(map: &ContinentMap, poi_type: &str) -> usize {
+    map.points_of_interest.values()
+        .filter(|poi| poi.poi_type == poi_type)
+        .count()
+}
+
+/// Obtain a ratio, or 0 if there is nothing to divide
+fn ratio(done: usize, total: usize) -> f64 {
+    if total == 0 { 0.0 } else { done as f64 / total as f64 }
+}
+
+/// Estimate the exploration progress of a character
+///
+/// Hero points are compared exactly against the hero challenges of each
+/// map. The rest of the objectives of a map are assumed to be completed in
+/// the same proportion as its hero points. Maps without hero points use the
+/// proportion of hero points over all the maps. Maps without any objective
+/// (e.g. instances) are skipped.
+///
+/// # Arguments
+///
+/// * `floor` - Floor 1 of continent 1, as obtained with
+///     `continents().continent(1).floor(1).get()`
+/// * `heropoints` - Hero points as obtained from `get_character_heropoints()`
+pub fn exploration_estimate(
+    floor: &ContinentFloor,
+    heropoints: &[String]
+) -> ExplorationEstimate {
+    let mut maps: Vec<MapExploration> = floor.regions.values()
+        .filter(|region| WORLD_COMPLETION_REGIONS.contains(&region.id))
+        .flat_map(|region| region.maps.values())
+        .map(|map| {
+            let challenges: Vec<&String> = map.skill_challenges.iter()
+                .filter_map(|challenge| challenge.id.as_ref())
+                .collect();
+
+            MapExploration {
+                map_id: map.id,
+                name: map.name.clone(),
+                hero_points_done: challenges.iter()
+                    .filter(|id| heropoints.contains(id))
+                    .count(),
+                hero_points_total: challenges.len(),
+                tasks: map.tasks.len(),
+                landmarks: count_pois(map, "landmark"),
+                waypoints: count_pois(map, "waypoint"),
+                vistas: count_pois(map, "vista"),
+                estimated_progress: 0.0
+            }
+        })
+        .filter(|map| map.objectives() > 0)
+        .collect();
+    maps.sort_by_key(|map| map.map_id);
+
+    let hero_points_done = maps.iter().map(|m| m.hero_points_done).sum();
+    let hero_points_total = maps.iter().map(|m| m.hero_points_total).sum();
+    let overall = ratio(hero_points_done, hero_points_total);
+
+    let mut done = 0.0;
+    let mut objectives = 0;
+
+    for map in &mut maps {
+        let map_ratio = if map.hero_points_total > 0 {
+            ratio(map.hero_points_done, map.hero_points_total)
+        } else {
+            overall
+        };
+        let rest = (map.objectives() - map.hero_points_total) as f64;
+        let map_done = map.hero_points_done as f64 + rest * map_ratio;
+
+        map.estimated_progress = map_done / map.objectives() as f64;
+        done += map_done;
+        objectives += map.objectives();
+    }
+
+    ExplorationEstimate {
+        maps: maps,
+        hero_points_done: hero_points_done,
+        hero_points_total: hero_points_total,
+        estimated_progress: if objectives > 0 {
+            done / objectives as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Estimate the exploration progress of a character
+///
+/// See `exploration_estimate()` for the heuristics used.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name` - Name of the character
+pub fn get_exploration_estimate(
+    client: &APIClient,
+    name: &str
+) -> Result<ExplorationEstimate, APIError> {
+    let floor = continents().continent(1).floor(1).get(client)?;
+    let heropoints = get_character_heropoints(client, name)?;
+
+    Ok(exploration_estimate(&floor, &heropoints))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use api_v2::types::ContinentFloor;
+    use helpers::exploration::*;
+
+    #[test]
+    fn estimate() {
+        let floor: ContinentFloor = serde_json::from_str(r#"{
+            "id": 1,
+            "texture_dims": [32768, 32768],
+            "regions": {
+                "4": {
+                    "id": 4, "name": "Maguuma Jungle",
+                    "label_coord": [0, 0], "continent_rect": [[0, 0], [1, 1]],
+                    "maps": {
+                        "35": {
+                            "id": 35, "name": "Metrica Province",
+                            "min_level": 1, "max_level": 15,
+                            "default_floor": 1, "map_rect": [[0, 0], [1, 1]],
+                            "continent_rect": [[0, 0], [1, 1]],
+                            "points_of_interest": {
+                                "1": {"id": 1, "type": "landmark",
+                                      "floor": 1, "coord": [0, 0]},
+                                "2": {"id": 2, "type": "waypoint",
+                                      "floor": 1, "coord": [0, 0]},
+                                "3": {"id": 3, "type": "vista",
+                                      "floor": 1, "coord": [0, 0]}
+                            },
+                            "tasks": {
+                                "4": {"id": 4, "objective": "Help",
+                                      "level": 2, "coord": [0, 0]}
+                            },
+                            "skill_challenges": [
+                                {"id": "0-1", "coord": [0, 0]},
+                                {"id": "0-2", "coord": [0, 0]}
+                            ]
+                        },
+                        "36": {
+                            "id": 36, "name": "Empty instance",
+                            "min_level": 1, "max_level": 80,
+                            "default_floor": 1, "map_rect": [[0, 0], [1, 1]],
+                            "continent_rect": [[0, 0], [1, 1]]
+                        }
+                    }
+                },
+                "10": {
+                    "id": 10, "name": "Heart of Maguuma",
+                    "label_coord": [0, 0], "continent_rect": [[0, 0], [1, 1]],
+                    "maps": {}
+                }
+            }
+        }"#).unwrap();
+
+        let estimate = exploration_estimate(&floor, &["0-1".to_string()]);
+        assert_eq!(estimate.maps.len(), 1);
+        assert_eq!(estimate.maps[0].objectives(), 6);
+        assert_eq!(estimate.hero_points_done, 1);
+        assert_eq!(estimate.hero_points_total, 2);
+        assert!((estimate.estimated_progress - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,219 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Locale-aware formatting of numbers and coins
+/// Useful when presenting API results to players in their own language
+
+use client::APIClient;
+
+
+/// Formatting conventions of one of the languages supported by the API
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    /// Separator between the integer and decimal parts
+    pub decimal_separator: &'static str,
+    /// Separator between groups of thousands
+    pub thousands_separator: &'static str,
+    /// Separator placed between a number and the percent sign
+    pub percent_separator: &'static str,
+    /// Abbreviations for gold, silver and copper, in that order
+    pub coin_units: [&'static str; 3]
+}
+
+impl Locale {
+    /// Obtain the conventions for the given language
+    ///
+    /// Languages not supported by the API fall back to English.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - Language code (`en`, `es`, `de`, `fr` or `zh`)
+    pub fn new(lang: &str) -> Locale {
+        match lang {
+            "de" => Locale {
+                decimal_separator: ",",
+                thousands_separator: ".",
+                percent_separator: " ",
+                coin_units: ["G", "S", "K"]
+            },
+            "es" => Locale {
+                decimal_separator: ",",
+                thousands_separator: ".",
+                percent_separator: " ",
+                coin_units: ["o", "p", "c"]
+            },
+            "fr" => Locale {
+                decimal_separator: ",",
+                thousands_separator: "\u{202f}",
+                percent_separator: "\u{202f}",
+                coin_units: ["po", "pa", "pc"]
+            },
+            "zh" => Locale {
+                decimal_separator: ".",
+                thousands_separator: ",",
+                percent_separator: "",
+                coin_units: ["金", "银", "铜"]
+            },
+            _ => Locale {
+                decimal_separator: ".",
+                thousands_separator: ",",
+                percent_separator: "",
+                coin_units: ["g", "s", "c"]
+            }
+        }
+    }
+
+    /// Obtain the conventions for the language configured in the client
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client whose language will be used
+    pub fn for_client(client: &APIClient) -> Locale {
+        Locale::new(client.lang())
+    }
+
+    /// Format an integer quantity, grouping thousands
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Quantity to format
+    pub fn format_number(&self, value: i64) -> String {
+        let digits = value.unsigned_abs().to_string();
+        let mut result = String::new();
+
+        if value < 0 {
+            result.push('-');
+        }
+
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index) % 3 == 0 {
+                result.push_str(self.thousands_separator);
+            }
+
+            result.push(digit);
+        }
+
+        result
+    }
+
+    /// Format an amount of coins as gold, silver and copper
+    ///
+    /// Units with a value of zero are omitted, except for copper when the
+    /// whole amount is zero (e.g. `12g 5c` or `0c` in English).
+    ///
+    /// # Arguments
+    ///
+    /// * `copper` - Amount of coins, in copper
+    pub fn format_coins(&self, copper: i64) -> String {
+        let amount = copper.unsigned_abs();
+        let values = [amount / 10000, amount / 100 % 100, amount % 100];
+
+        let mut parts: Vec<String> = values.iter()
+            .zip(self.coin_units.iter())
+            .enumerate()
+            .filter(|&(_, (value, _))| *value > 0)
+            .map(|(index, (value, unit))| {
+                if index == 0 {
+                    format!("{}{}", self.format_number(*value as i64), unit)
+                } else {
+                    format!("{}{}", value, unit)
+                }
+            })
+            .collect();
+
+        if parts.is_empty() {
+            parts.push(format!("0{}", self.coin_units[2]));
+        }
+
+        if copper < 0 {
+            format!("-{}", parts.join(" "))
+        } else {
+            parts.join(" ")
+        }
+    }
+
+    /// Format a percentage
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Percentage to format, where `100.0` means 100%
+    /// * `decimals` - Number of decimal digits to show
+    pub fn format_percent(&self, value: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let mut parts = formatted.splitn(2, '.');
+
+        let integer: i64 = parts.next()
+            .and_then(|part| part.parse().ok())
+            .unwrap_or(0);
+        let mut result = self.format_number(integer);
+
+        if let Some(fraction) = parts.next() {
+            result.push_str(self.decimal_separator);
+            result.push_str(fraction);
+        }
+
+        // Avoid showing negative zero after rounding
+        if value < 0.0 && formatted.chars().any(|c| c != '0' && c != '.') {
+            result.insert(0, '-');
+        }
+
+        format!("{}{}%", result, self.percent_separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use helpers::format::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Locale::new("en").format_number(1234567), "1,234,567");
+        assert_eq!(Locale::new("de").format_number(-1234), "-1.234");
+        assert_eq!(Locale::new("en").format_number(999), "999");
+        assert_eq!(Locale::new("xx"), Locale::new("en"));
+        assert_eq!(
+            Locale::new("en").format_number(i64::min_value()),
+            "-9,223,372,036,854,775,808"
+        );
+    }
+
+    #[test]
+    fn coins() {
+        let en = Locale::new("en");
+        assert_eq!(en.format_coins(12345678), "1,234g 56s 78c");
+        assert_eq!(en.format_coins(120005), "12g 5c");
+        assert_eq!(en.format_coins(0), "0c");
+        assert_eq!(en.format_coins(-250), "-2s 50c");
+        assert_eq!(Locale::new("de").format_coins(10203), "1G 2S 3K");
+        assert_eq!(
+            en.format_coins(i64::min_value()),
+            "-922,337,203,685,477g 58s 8c"
+        );
+    }
+
+    #[test]
+    fn percents() {
+        assert_eq!(Locale::new("en").format_percent(12.345, 1), "12.3%");
+        assert_eq!(Locale::new("es").format_percent(1500.0, 2), "1.500,00 %");
+        assert_eq!(Locale::new("en").format_percent(-5.0, 0), "-5%");
+    }
+}
@@ -0,0 +1,576 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Guild helpers
+/// These require an API key of the guild leader to obtain the data
+
+use std::collections::{BTreeMap, HashMap};
+use std::slice;
+
+use client::APIClient;
+use common::APIError;
+use api_v2::guild::{
+    get_all_guild_upgrades,
+    get_guild,
+    get_guild_treasury,
+    get_guild_unlocked_upgrades,
+    get_guild_upgrades
+};
+use api_v2::types::{
+    Guild,
+    GuildTreasuryItem,
+    GuildUpgrade,
+    GuildUpgradeCost,
+    IdList
+};
+use helpers::commerce::{get_item_prices, ItemPrice};
+
+
+/// Name of the aetherium cost in upgrade definitions
+pub const AETHERIUM_COST: &'static str = "Aetherium";
+
+/// Name of the favor cost in upgrade definitions
+pub const FAVOR_COST: &'static str = "Guild Favor";
+
+/// Guild currency spent on upgrades
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GuildCurrency {
+    /// Aetherium, produced by the guild hall mine
+    Aetherium,
+    /// Guild favor, earned from guild missions
+    Favor
+}
+
+impl GuildCurrency {
+    /// Identify the guild currency paid by an upgrade cost
+    ///
+    /// Returns `None` for costs that are not guild currencies.
+    ///
+    /// # Arguments
+    ///
+    /// * `cost` - Cost as listed in the upgrade definition
+    pub fn of(cost: &GuildUpgradeCost) -> Option<GuildCurrency> {
+        if cost.cost_type != "Currency" {
+            return None;
+        }
+
+        match cost.name.as_str() {
+            AETHERIUM_COST => Some(GuildCurrency::Aetherium),
+            FAVOR_COST => Some(GuildCurrency::Favor),
+            _ => None
+        }
+    }
+}
+
+/// Compute the amount of a guild currency required by an upgrade
+///
+/// # Arguments
+///
+/// * `upgrade` - Upgrade definition
+/// * `currency` - Currency to add up
+pub fn currency_cost(upgrade: &GuildUpgrade, currency: GuildCurrency) -> i32 {
+    upgrade.costs.iter()
+        .filter(|cost| GuildCurrency::of(cost) == Some(currency))
+        .map(|cost| cost.count)
+        .sum()
+}
+
+/// Item still needed to build a set of guild upgrades
+#[derive(Debug)]
+pub struct ItemShortfall {
+    /// Item ID
+    pub item_id: i32,
+    /// Name of the item, as listed in the upgrade costs
+    pub name: String,
+    /// Number of items required by the upgrades
+    pub required: i32,
+    /// Number of items already deposited in the treasury
+    pub in_treasury: i32,
+    /// Number of items still missing
+    pub missing: i32,
+    /// Lowest sell offer in the trading post, if priced and tradeable
+    pub unit_price: Option<i32>
+}
+
+impl ItemShortfall {
+    /// Coins needed to buy the missing items in the trading post
+    pub fn cost(&self) -> Option<i64> {
+        self.unit_price.map(|price| price as i64 * self.missing as i64)
+    }
+}
+
+/// Compute the items still missing from the treasury to build upgrades
+///
+/// Item costs of all the upgrades are added up and compared against the
+/// treasury contents. Costs that are not items (coins, aetherium, favor)
+/// are not taken into account. The result is sorted by item ID and only
+/// contains items that are still missing.
+///
+/// # Arguments
+///
+/// * `upgrades` - Upgrades to build, as obtained from `get_guild_upgrades()`
+/// * `treasury` - Treasury as obtained from `get_guild_treasury()`
+pub fn treasury_shortfall(
+    upgrades: &[GuildUpgrade],
+    treasury: &[GuildTreasuryItem]
+) -> Vec<ItemShortfall> {
+    let mut required: BTreeMap<i32, (String, i32)> = BTreeMap::new();
+
+    for upgrade in upgrades {
+        for cost in &upgrade.costs {
+            if let Some(item_id) = cost.item_id {
+                let entry = required.entry(item_id)
+                    .or_insert((cost.name.clone(), 0));
+                entry.1 += cost.count;
+            }
+        }
+    }
+
+    required.into_iter()
+        .map(|(item_id, (name, count))| {
+            let in_treasury = treasury.iter()
+                .find(|item| item.item_id == item_id)
+                .map(|item| item.count)
+                .unwrap_or(0);
+
+            ItemShortfall {
+                item_id: item_id,
+                name: name,
+                required: count,
+                in_treasury: in_treasury,
+                missing: (count - in_treasury).max(0),
+                unit_price: None
+            }
+        })
+        .filter(|shortfall| shortfall.missing > 0)
+        .collect()
+}
+
+/// Set the trading post prices of the missing items
+///
+/// Items without sell offers are left without price.
+///
+/// # Arguments
+///
+/// * `shortfall` - Missing items as obtained from `treasury_shortfall()`
+/// * `prices` - Prices as obtained from `get_item_prices()`
+pub fn price_shortfall(
+    shortfall: &mut [ItemShortfall],
+    prices: &HashMap<i32, ItemPrice>
+) {
+    for item in shortfall.iter_mut() {
+        item.unit_price = prices.get(&item.item_id)
+            .map(|price| price.sell)
+            .and_then(|sell| if sell > 0 { Some(sell) } else { None });
+    }
+}
+
+/// Obtain the items still missing from the treasury to build upgrades
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token of the guild leader
+/// * `id` - ID of the guild
+/// * `upgrades` - IDs of the upgrades to build
+/// * `with_prices` - Whether to obtain trading post prices for the items
+pub fn get_treasury_shortfall(
+    client: &APIClient,
+    id: &str,
+    upgrades: &[i32],
+    with_prices: bool
+) -> Result<Vec<ItemShortfall>, APIError> {
+    let mut definitions = Vec::new();
+    for batch in IdList::new(upgrades.to_vec()).batches(0) {
+        definitions.extend(get_guild_upgrades(client, batch)?);
+    }
+
+    let treasury = get_guild_treasury(client, id)?;
+    let mut shortfall = treasury_shortfall(&definitions, &treasury);
+
+    if with_prices {
+        let ids: Vec<i32> = shortfall.iter().map(|item| item.item_id).collect();
+        price_shortfall(&mut shortfall, &get_item_prices(client, &ids)?);
+    }
+
+    Ok(shortfall)
+}
+
+/// Favor and aetherium a guild hall can hold, by guild level
+///
+/// Each entry contains the minimum guild level of the tier, followed by the
+/// favor cap and the aetherium cap of the tier. Entries are sorted by level.
+pub const CAPACITY_BY_LEVEL: &'static [(i32, i32, i32)] = &[
+    (0, 2000, 5000),
+    (10, 4000, 10000),
+    (20, 6000, 15000),
+    (30, 8000, 20000),
+    (40, 10000, 25000),
+    (50, 12000, 30000),
+    (60, 15000, 35000)
+];
+
+/// Obtain the capacity tier that applies to a guild level
+fn capacity(level: i32) -> (i32, i32) {
+    CAPACITY_BY_LEVEL.iter()
+        .rev()
+        .find(|&&(min_level, _, _)| level >= min_level)
+        .map(|&(_, favor, aetherium)| (favor, aetherium))
+        .unwrap_or((CAPACITY_BY_LEVEL[0].1, CAPACITY_BY_LEVEL[0].2))
+}
+
+/// Compute the maximum amount of favor a guild can hold
+///
+/// # Arguments
+///
+/// * `level` - Level of the guild
+pub fn favor_cap(level: i32) -> i32 {
+    capacity(level).0
+}
+
+/// Compute the maximum amount of aetherium a guild can hold
+///
+/// # Arguments
+///
+/// * `level` - Level of the guild
+pub fn aetherium_cap(level: i32) -> i32 {
+    capacity(level).1
+}
+
+/// Upgrade a guild has the level and prerequisites for
+#[derive(Debug)]
+pub struct EligibleUpgrade {
+    /// Upgrade ID
+    pub upgrade_id: i32,
+    /// Localized name of the upgrade
+    pub name: String,
+    /// Favor still missing to pay for the upgrade
+    pub missing_favor: i32,
+    /// Aetherium still missing to pay for the upgrade
+    pub missing_aetherium: i32,
+    /// Items still missing from the treasury to build the upgrade
+    pub missing_items: Vec<ItemShortfall>
+}
+
+impl EligibleUpgrade {
+    /// Check whether the upgrade can be built right away
+    pub fn can_build(&self) -> bool {
+        self.missing_favor == 0
+            && self.missing_aetherium == 0
+            && self.missing_items.is_empty()
+    }
+}
+
+/// Guild hall progress of a guild
+#[derive(Debug)]
+pub struct GuildProgress {
+    /// Guild ID
+    pub guild_id: String,
+    /// Guild name
+    pub name: String,
+    /// Guild level
+    pub level: i32,
+    /// Amount of favor available
+    pub favor: i32,
+    /// Maximum amount of favor at the current level
+    pub favor_cap: i32,
+    /// Amount of aetherium available
+    pub aetherium: i32,
+    /// Maximum amount of aetherium at the current level
+    pub aetherium_cap: i32,
+    /// IDs of the upgrades already unlocked
+    pub unlocked: Vec<i32>,
+    /// Upgrades that can be queued, sorted by ID
+    pub eligible: Vec<EligibleUpgrade>,
+    /// IDs of the upgrades that still require a higher level or other
+    /// upgrades, sorted
+    pub locked: Vec<i32>
+}
+
+impl GuildProgress {
+    /// Create the progress of a guild from its level and currencies
+    ///
+    /// The progress does not contain any upgrade information. Use
+    /// `guild_progress()` to take upgrades into account.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - ID of the guild
+    /// * `name` - Name of the guild
+    /// * `level` - Level of the guild
+    /// * `favor` - Amount of favor available
+    /// * `aetherium` - Amount of aetherium available
+    pub fn new(
+        guild_id: &str,
+        name: &str,
+        level: i32,
+        favor: i32,
+        aetherium: i32
+    ) -> GuildProgress {
+        GuildProgress {
+            guild_id: guild_id.to_string(),
+            name: name.to_string(),
+            level: level,
+            favor: favor,
+            favor_cap: favor_cap(level),
+            aetherium: aetherium,
+            aetherium_cap: aetherium_cap(level),
+            unlocked: Vec::new(),
+            eligible: Vec::new(),
+            locked: Vec::new()
+        }
+    }
+
+    /// Favor that can still be accumulated before reaching the cap
+    pub fn favor_room(&self) -> i32 {
+        (self.favor_cap - self.favor).max(0)
+    }
+
+    /// Aetherium that can still be accumulated before reaching the cap
+    pub fn aetherium_room(&self) -> i32 {
+        (self.aetherium_cap - self.aetherium).max(0)
+    }
+
+    /// Obtain the eligible upgrades that can be built right away
+    pub fn buildable(&self) -> Vec<&EligibleUpgrade> {
+        self.eligible.iter()
+            .filter(|upgrade| upgrade.can_build())
+            .collect()
+    }
+}
+
+/// Compute the guild hall progress of a guild
+///
+/// An upgrade is eligible when it is not unlocked yet, the guild has the
+/// required level and every prerequisite is unlocked. Favor, aetherium and
+/// treasury items are then compared against the costs of each eligible
+/// upgrade on its own.
+///
+/// # Arguments
+///
+/// * `guild` - Guild details as obtained from `get_guild()` with the token
+///     of the guild leader
+/// * `unlocked` - IDs as obtained from `get_guild_unlocked_upgrades()`
+/// * `upgrades` - Definitions as obtained from `get_all_guild_upgrades()`
+/// * `treasury` - Treasury as obtained from `get_guild_treasury()`
+pub fn guild_progress(
+    guild: &Guild,
+    unlocked: &[i32],
+    upgrades: &[GuildUpgrade],
+    treasury: &[GuildTreasuryItem]
+) -> GuildProgress {
+    let level = guild.level.unwrap_or(0);
+    let favor = guild.favor.unwrap_or(0);
+    let aetherium = guild.aetherium.unwrap_or(0);
+
+    let mut eligible = Vec::new();
+    let mut locked = Vec::new();
+
+    for upgrade in upgrades {
+        if unlocked.contains(&upgrade.id) {
+            continue;
+        }
+
+        let ready = upgrade.required_level <= level
+            && upgrade.prerequisites.iter().all(|id| unlocked.contains(id));
+        if !ready {
+            locked.push(upgrade.id);
+            continue;
+        }
+
+        let favor_cost = currency_cost(upgrade, GuildCurrency::Favor);
+        let aetherium_cost = currency_cost(upgrade, GuildCurrency::Aetherium);
+
+        eligible.push(EligibleUpgrade {
+            upgrade_id: upgrade.id,
+            name: upgrade.name.clone(),
+            missing_favor: (favor_cost - favor).max(0),
+            missing_aetherium: (aetherium_cost - aetherium).max(0),
+            missing_items: treasury_shortfall(
+                slice::from_ref(upgrade),
+                treasury
+            )
+        });
+    }
+
+    eligible.sort_by_key(|upgrade| upgrade.upgrade_id);
+    locked.sort();
+
+    let mut progress = GuildProgress::new(
+        &guild.id,
+        &guild.name,
+        level,
+        favor,
+        aetherium
+    );
+    progress.unlocked = unlocked.to_vec();
+    progress.eligible = eligible;
+    progress.locked = locked;
+
+    progress
+}
+
+/// Obtain the guild hall progress of a guild
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token of the guild leader
+/// * `id` - ID of the guild
+pub fn get_guild_progress(
+    client: &APIClient,
+    id: &str
+) -> Result<GuildProgress, APIError> {
+    let guild = get_guild(client, id)?;
+    if guild.level.is_none() {
+        return Err(APIError::new(
+            "guild progress requires the token of the guild leader"
+        ));
+    }
+
+    let unlocked = get_guild_unlocked_upgrades(client, id)?;
+    let upgrades = get_all_guild_upgrades(client)?;
+    let treasury = get_guild_treasury(client, id)?;
+
+    Ok(guild_progress(&guild, &unlocked, &upgrades, &treasury))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use serde_json;
+    use api_v2::types::{Guild, GuildTreasuryItem, GuildUpgrade};
+    use helpers::commerce::ItemPrice;
+    use helpers::guild::*;
+
+    #[test]
+    fn shortfall() {
+        let upgrades: Vec<GuildUpgrade> = serde_json::from_str(r#"[
+            {"id": 1, "name": "A", "type": "Unlock", "costs": [
+                {"type": "Item", "name": "Plank", "count": 100,
+                 "item_id": 10},
+                {"type": "Coins", "count": 5000}
+            ]},
+            {"id": 2, "name": "B", "type": "Unlock", "costs": [
+                {"type": "Item", "name": "Plank", "count": 50,
+                 "item_id": 10},
+                {"type": "Item", "name": "Ingot", "count": 20,
+                 "item_id": 20},
+                {"type": "Item", "name": "Dust", "count": 5,
+                 "item_id": 30}
+            ]}
+        ]"#).unwrap();
+        let treasury: Vec<GuildTreasuryItem> = serde_json::from_str(r#"[
+            {"item_id": 10, "count": 120, "needed_by": []},
+            {"item_id": 30, "count": 10, "needed_by": []}
+        ]"#).unwrap();
+
+        let mut shortfall = treasury_shortfall(&upgrades, &treasury);
+        assert_eq!(shortfall.len(), 2);
+        assert_eq!(shortfall[0].item_id, 10);
+        assert_eq!(shortfall[0].required, 150);
+        assert_eq!(shortfall[0].missing, 30);
+        assert_eq!(shortfall[1].missing, 20);
+
+        let mut prices = HashMap::new();
+        prices.insert(10, ItemPrice { item_id: 10, buy: 1, sell: 25 });
+        price_shortfall(&mut shortfall, &prices);
+
+        assert_eq!(shortfall[0].cost(), Some(750));
+        assert_eq!(shortfall[1].cost(), None);
+    }
+
+    #[test]
+    fn caps() {
+        assert_eq!(favor_cap(0), 2000);
+        assert_eq!(favor_cap(9), 2000);
+        assert_eq!(favor_cap(10), 4000);
+        assert_eq!(aetherium_cap(45), 25000);
+        assert_eq!(aetherium_cap(69), 35000);
+        assert_eq!(aetherium_cap(-1), 5000);
+
+        let progress = GuildProgress::new("guild", "Guild", 20, 7000, 100);
+        assert_eq!(progress.favor_cap, 6000);
+        assert_eq!(progress.favor_room(), 0);
+        assert_eq!(progress.aetherium_room(), 14900);
+    }
+
+    #[test]
+    fn progress() {
+        let guild: Guild = serde_json::from_str(r#"{
+            "id": "guild", "name": "Guild", "tag": "GLD",
+            "level": 10, "favor": 100, "aetherium": 50
+        }"#).unwrap();
+        let upgrades: Vec<GuildUpgrade> = serde_json::from_str(r#"[
+            {"id": 1, "name": "A", "type": "Unlock", "required_level": 1,
+             "costs": []},
+            {"id": 2, "name": "B", "type": "Unlock", "required_level": 5,
+             "prerequisites": [1], "costs": [
+                {"type": "Currency", "name": "Guild Favor", "count": 80},
+                {"type": "Item", "name": "Plank", "count": 10,
+                 "item_id": 10}
+            ]},
+            {"id": 3, "name": "C", "type": "Unlock", "required_level": 5,
+             "prerequisites": [1], "costs": [
+                {"type": "Currency", "name": "Aetherium", "count": 200}
+            ]},
+            {"id": 4, "name": "D", "type": "Unlock", "required_level": 20,
+             "costs": []},
+            {"id": 5, "name": "E", "type": "Unlock", "required_level": 1,
+             "prerequisites": [3], "costs": []}
+        ]"#).unwrap();
+        let treasury: Vec<GuildTreasuryItem> = serde_json::from_str(r#"[
+            {"item_id": 10, "count": 10, "needed_by": []}
+        ]"#).unwrap();
+
+        let progress = guild_progress(&guild, &[1], &upgrades, &treasury);
+
+        assert_eq!(progress.level, 10);
+        assert_eq!(progress.favor_cap, 4000);
+        assert_eq!(progress.locked, vec![4, 5]);
+        assert_eq!(progress.eligible.len(), 2);
+        assert!(progress.eligible[0].can_build());
+        assert_eq!(progress.eligible[1].missing_aetherium, 150);
+        assert_eq!(progress.buildable().len(), 1);
+    }
+
+    #[test]
+    fn currencies() {
+        let upgrade: GuildUpgrade = serde_json::from_str(r#"{
+            "id": 1, "name": "A", "type": "Unlock", "costs": [
+                {"type": "Currency", "name": "Guild Favor", "count": 30},
+                {"type": "Currency", "name": "Aetherium", "count": 10},
+                {"type": "Currency", "name": "Aetherium", "count": 5},
+                {"type": "Item", "name": "Aetherium", "count": 99,
+                 "item_id": 10},
+                {"type": "Coins", "count": 5000}
+            ]
+        }"#).unwrap();
+
+        assert_eq!(GuildCurrency::of(&upgrade.costs[0]),
+                   Some(GuildCurrency::Favor));
+        assert_eq!(GuildCurrency::of(&upgrade.costs[3]), None);
+        assert_eq!(GuildCurrency::of(&upgrade.costs[4]), None);
+        assert_eq!(currency_cost(&upgrade, GuildCurrency::Favor), 30);
+        assert_eq!(currency_cost(&upgrade, GuildCurrency::Aetherium), 15);
+    }
+}
@@ -0,0 +1,168 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Core game mechanics helpers
+
+use api_v2::types::{
+    Skill,
+    SkillFact,
+    Trait,
+    TraitFact
+};
+
+
+/// Apply traited facts over a list of base facts
+///
+/// Overrides are applied first, so that the indices in `overrides` always
+/// refer to the original list. Facts without a valid `overrides` index are
+/// appended afterwards in the order they are listed.
+fn apply_traited_facts<'a, F, I>(
+    base: &'a [F],
+    traited: I,
+    traits: &[i32]
+) -> Vec<&'a F> where I: Iterator<Item = (i32, Option<i32>, &'a F)> {
+    let mut facts: Vec<&F> = base.iter().collect();
+    let mut appended = Vec::new();
+
+    for (requires_trait, overrides, fact) in traited {
+        if !traits.contains(&requires_trait) {
+            continue;
+        }
+
+        match overrides {
+            Some(index) if index >= 0 && (index as usize) < base.len() => {
+                facts[index as usize] = fact;
+            },
+            _ => appended.push(fact)
+        }
+    }
+
+    facts.extend(appended);
+    facts
+}
+
+/// Obtain the final facts of a skill for the given selected traits
+///
+/// Traited facts whose `requires_trait` is among the selected traits either
+/// replace the base fact at index `overrides` or are appended to the list.
+///
+/// # Arguments
+///
+/// * `skill` - Skill to obtain the facts for
+/// * `traits` - IDs of the traits currently selected
+pub fn resolve_skill_facts<'a>(
+    skill: &'a Skill,
+    traits: &[i32]
+) -> Vec<&'a SkillFact> {
+    apply_traited_facts(
+        &skill.facts,
+        skill.traited_facts.iter()
+            .map(|t| (t.requires_trait, t.overrides, &t.fact)),
+        traits
+    )
+}
+
+/// Obtain the final facts of a trait for the given selected traits
+///
+/// Same semantics as `resolve_skill_facts()`.
+///
+/// # Arguments
+///
+/// * `selected` - Trait to obtain the facts for
+/// * `traits` - IDs of the traits currently selected
+pub fn resolve_trait_facts<'a>(
+    selected: &'a Trait,
+    traits: &[i32]
+) -> Vec<&'a TraitFact> {
+    apply_traited_facts(
+        &selected.facts,
+        selected.traited_facts.iter()
+            .map(|t| (t.requires_trait, t.overrides, &t.fact)),
+        traits
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    #[cfg(feature = "chrono")]
+    use chrono::Duration;
+    use api_v2::types::Skill;
+    use helpers::mechanics::*;
+
+    fn skill() -> Skill {
+        serde_json::from_str(r#"{
+            "id": 5516,
+            "name": "Conjure Fiery Greatsword",
+            "icon": "",
+            "chat_link": "[&BowVAAA=]",
+            "type": "Elite",
+            "weapon_type": "None",
+            "professions": ["Elementalist"],
+            "slot": "Elite",
+            "facts": [
+                {"text": "Recharge", "type": "Recharge", "value": 180},
+                {"text": "Duration", "type": "Duration", "duration": 60}
+            ],
+            "traited_facts": [
+                {"text": "Recharge", "type": "Recharge", "value": 144,
+                 "requires_trait": 1, "overrides": 0},
+                {"text": "Might", "type": "Buff", "status": "Might",
+                 "duration": 10, "requires_trait": 2},
+                {"text": "Duration", "type": "Duration", "duration": 90,
+                 "requires_trait": 3, "overrides": 1}
+            ]
+        }"#).unwrap()
+    }
+
+    #[test]
+    fn no_traits() {
+        let skill = skill();
+        let facts = resolve_skill_facts(&skill, &[]);
+
+        assert_eq!(facts.len(), 2);
+        assert_eq!(facts[0].value, Some(180));
+    }
+
+    #[test]
+    fn overrides_and_appends() {
+        let skill = skill();
+        let facts = resolve_skill_facts(&skill, &[1, 2]);
+
+        assert_eq!(facts.len(), 3);
+        assert_eq!(facts[0].value, Some(144));
+        assert_eq!(facts[1].duration, Some(60));
+        assert_eq!(facts[2].status, Some("Might".to_string()));
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn fact_durations() {
+        let skill = skill();
+        let facts = resolve_skill_facts(&skill, &[3]);
+
+        assert_eq!(facts[0].recharge(), Some(Duration::seconds(180)));
+        assert_eq!(facts[0].duration(), None);
+        assert_eq!(facts[1].recharge(), None);
+        assert_eq!(facts[1].duration_seconds(), Some(90));
+    }
+}
@@ -0,0 +1,56 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Helpers computing derived information from API results
+
+#[cfg(all(feature = "account", feature = "characters", feature = "static"))]
+pub mod account;
+#[cfg(all(feature = "account", feature = "static"))]
+pub mod achievements;
+#[cfg(all(feature = "characters", feature = "static"))]
+pub mod characters;
+#[cfg(feature = "commerce")]
+pub mod commerce;
+#[cfg(all(feature = "account", feature = "commerce", feature = "static"))]
+pub mod dyes;
+#[cfg(feature = "characters")]
+pub mod equipment;
+#[cfg(all(feature = "characters", feature = "static"))]
+pub mod exploration;
+pub mod format;
+#[cfg(all(feature = "commerce", feature = "guild"))]
+pub mod guild;
+#[cfg(feature = "static")]
+pub mod mechanics;
+#[cfg(all(
+    feature = "account",
+    feature = "characters",
+    feature = "commerce",
+    feature = "static"
+))]
+pub mod search;
+#[cfg(all(feature = "characters", feature = "static"))]
+pub mod stats;
+#[cfg(all(feature = "account", feature = "characters", feature = "commerce"))]
+pub mod upgrades;
+#[cfg(all(feature = "account", feature = "commerce", feature = "static"))]
+pub mod valuation;
@@ -0,0 +1,286 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Item search across the storage of an account
+/// These require an API key to obtain the data
+
+use std::collections::HashSet;
+
+use client::APIClient;
+use common::APIError;
+use api_v2::account::{
+    get_account_bank,
+    get_account_inventory,
+    get_account_materials
+};
+use api_v2::characters::{get_character_inventory, get_character_names};
+use api_v2::types::{
+    AccountMaterial,
+    BankSlot,
+    CharacterInventory,
+    InventorySlot
+};
+use helpers::valuation::get_item_details;
+
+
+/// Place in the account where an item is stored
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemLocation {
+    /// Bank slot, by index
+    Bank(usize),
+    /// Material storage, by material category ID
+    Materials(i32),
+    /// Shared inventory slot, by index
+    SharedInventory(usize),
+    /// Slot in the bags of a character
+    Character {
+        /// Character name
+        name: String,
+        /// Index of the bag
+        bag: usize,
+        /// Index of the slot in the bag
+        slot: usize
+    }
+}
+
+/// Stack of a searched item found in the account
+#[derive(Debug)]
+pub struct FoundItem {
+    /// Item ID
+    pub item_id: i32,
+    /// Where the stack is stored
+    pub location: ItemLocation,
+    /// Number of items in the stack
+    pub count: i32
+}
+
+/// Storage of an account to search items in
+#[derive(Debug)]
+pub struct AccountStorage {
+    /// Bank slots, as obtained from `get_account_bank()`
+    pub bank: Vec<Option<BankSlot>>,
+    /// Materials, as obtained from `get_account_materials()`
+    pub materials: Vec<AccountMaterial>,
+    /// Shared inventory, as obtained from `get_account_inventory()`
+    pub shared: Vec<Option<InventorySlot>>,
+    /// Inventory of each character, by character name
+    pub characters: Vec<(String, CharacterInventory)>
+}
+
+impl AccountStorage {
+    /// Obtain the IDs of all the items stored in the account
+    pub fn item_ids(&self) -> HashSet<i32> {
+        self.locate(|_| true).into_iter().map(|found| found.item_id).collect()
+    }
+
+    /// Locate the stacks of the items matching a predicate
+    ///
+    /// # Arguments
+    ///
+    /// * `matches` - Returns whether the given item ID is searched for
+    pub fn locate<F>(&self, matches: F) -> Vec<FoundItem>
+        where F: Fn(i32) -> bool {
+
+        let mut found = Vec::new();
+
+        for (index, slot) in self.bank.iter().enumerate() {
+            if let Some(ref slot) = *slot {
+                if matches(slot.id) {
+                    found.push(FoundItem {
+                        item_id: slot.id,
+                        location: ItemLocation::Bank(index),
+                        count: slot.count
+                    });
+                }
+            }
+        }
+
+        for material in &self.materials {
+            if material.count > 0 && matches(material.id) {
+                found.push(FoundItem {
+                    item_id: material.id,
+                    location: ItemLocation::Materials(material.category),
+                    count: material.count
+                });
+            }
+        }
+
+        for (index, slot) in self.shared.iter().enumerate() {
+            if let Some(ref slot) = *slot {
+                if matches(slot.id) {
+                    found.push(FoundItem {
+                        item_id: slot.id,
+                        location: ItemLocation::SharedInventory(index),
+                        count: slot.count
+                    });
+                }
+            }
+        }
+
+        for &(ref name, ref inventory) in &self.characters {
+            for (bag_index, bag) in inventory.bags.iter().enumerate() {
+                for (index, slot) in bag.inventory.iter().enumerate() {
+                    if let Some(ref slot) = *slot {
+                        if matches(slot.id) {
+                            found.push(FoundItem {
+                                item_id: slot.id,
+                                location: ItemLocation::Character {
+                                    name: name.clone(),
+                                    bag: bag_index,
+                                    slot: index
+                                },
+                                count: slot.count
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        found
+    }
+}
+
+/// Obtain the bank, materials, shared inventory and character bags of the
+/// account
+///
+/// This performs one request per character in addition to the account
+/// storage requests.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_account_storage(
+    client: &APIClient
+) -> Result<AccountStorage, APIError> {
+    let mut characters = Vec::new();
+    for name in get_character_names(client)? {
+        let inventory = get_character_inventory(client, &name)?;
+        characters.push((name, inventory));
+    }
+
+    Ok(AccountStorage {
+        bank: get_account_bank(client)?,
+        materials: get_account_materials(client)?,
+        shared: get_account_inventory(client)?,
+        characters: characters
+    })
+}
+
+/// Locate an item across the whole account
+///
+/// If `name_or_id` is a number, it is used as the item ID. Otherwise, the
+/// details of the items stored in the account are fetched and the item is
+/// matched by name, ignoring case. All the items sharing that name are
+/// located.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name_or_id` - Item ID or name of the item to search
+pub fn find_in_account(
+    client: &APIClient,
+    name_or_id: &str
+) -> Result<Vec<FoundItem>, APIError> {
+    let storage = get_account_storage(client)?;
+
+    if let Ok(id) = name_or_id.trim().parse::<i32>() {
+        return Ok(storage.locate(|item| item == id));
+    }
+
+    let ids: Vec<i32> = storage.item_ids().into_iter().collect();
+    let name = name_or_id.trim().to_lowercase();
+    let matching: HashSet<i32> = get_item_details(client, &ids)?
+        .into_iter()
+        .filter(|&(_, ref item)| item.name.to_lowercase() == name)
+        .map(|(id, _)| id)
+        .collect();
+
+    Ok(storage.locate(|item| matching.contains(&item)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use serde_json;
+    use client::APIClient;
+    use helpers::search::*;
+
+    fn setup_client() -> APIClient {
+        match env::var("TOKEN") {
+            Ok(token) => APIClient::new("en", Some(token.to_string())),
+            Err(_) => panic!("Need a token to test endpoint"),
+        }
+    }
+
+    #[test]
+    fn locate() {
+        let storage = AccountStorage {
+            bank: serde_json::from_str(
+                r#"[null, {"id": 19721, "count": 250}]"#
+            ).unwrap(),
+            materials: serde_json::from_str(
+                r#"[{"id": 19721, "category": 5, "count": 0},
+                    {"id": 19700, "category": 5, "count": 3}]"#
+            ).unwrap(),
+            shared: serde_json::from_str(
+                r#"[{"id": 19721, "count": 1}]"#
+            ).unwrap(),
+            characters: vec![(
+                "Char".to_string(),
+                serde_json::from_str(r#"{"bags": [
+                    {"id": 8932, "size": 20, "inventory": [
+                        null, null, {"id": 19721, "count": 5}
+                    ]}
+                ]}"#).unwrap()
+            )]
+        };
+
+        let found = storage.locate(|id| id == 19721);
+        let locations: Vec<&ItemLocation> = found.iter()
+            .map(|f| &f.location)
+            .collect();
+
+        assert_eq!(locations, vec![
+            &ItemLocation::Bank(1),
+            &ItemLocation::SharedInventory(0),
+            &ItemLocation::Character {
+                name: "Char".to_string(),
+                bag: 0,
+                slot: 2
+            }
+        ]);
+        assert_eq!(found.iter().map(|f| f.count).sum::<i32>(), 256);
+        assert_eq!(storage.item_ids().len(), 2);
+    }
+
+    #[test]
+    fn find() {
+        let client = setup_client();
+        match find_in_account(&client, "Glob of Ectoplasm") {
+            Ok(_) => assert!(true),
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+}
@@ -0,0 +1,217 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Attribute combination helpers
+
+use api_v2::types::{EquipmentAttributes, ItemStat};
+use helpers::equipment::attribute_values;
+
+
+/// Common prefix names of attribute combinations, as
+/// `(prefix, major attributes, minor attributes)`
+///
+/// Attributes use the names returned by the API, so Ferocity is
+/// `CritDamage`, Expertise is `ConditionDuration` and Concentration is
+/// `BoonDuration`.
+pub const STAT_PREFIXES: [(&'static str, &'static [&'static str],
+                           &'static [&'static str]); 35] = [
+    ("Apothecary's", &["Healing"], &["Toughness", "ConditionDamage"]),
+    ("Assassin's", &["Precision"], &["Power", "CritDamage"]),
+    ("Berserker's", &["Power"], &["Precision", "CritDamage"]),
+    ("Captain's", &["Precision"], &["Power", "Toughness"]),
+    ("Carrion", &["ConditionDamage"], &["Power", "Vitality"]),
+    ("Cavalier's", &["Toughness"], &["Power", "CritDamage"]),
+    ("Celestial", &["Power", "Precision", "Toughness", "Vitality",
+                    "ConditionDamage", "ConditionDuration", "CritDamage",
+                    "Healing", "BoonDuration"], &[]),
+    ("Cleric's", &["Healing"], &["Power", "Toughness"]),
+    ("Commander's", &["Power", "Precision"], &["Toughness", "BoonDuration"]),
+    ("Dire", &["ConditionDamage"], &["Toughness", "Vitality"]),
+    ("Diviner's", &["Power", "BoonDuration"], &["Precision", "CritDamage"]),
+    ("Dragon's", &["Power", "CritDamage"], &["Precision", "Vitality"]),
+    ("Grieving", &["Power", "ConditionDamage"], &["Precision", "CritDamage"]),
+    ("Harrier's", &["Power"], &["Healing", "BoonDuration"]),
+    ("Knight's", &["Toughness"], &["Power", "Precision"]),
+    ("Magi's", &["Healing"], &["Precision", "Vitality"]),
+    ("Marauder", &["Power", "Precision"], &["Vitality", "CritDamage"]),
+    ("Marshal's", &["Power", "Healing"], &["Precision", "ConditionDamage"]),
+    ("Minstrel's", &["Toughness", "Healing"], &["Vitality", "BoonDuration"]),
+    ("Nomad's", &["Toughness"], &["Vitality", "Healing"]),
+    ("Plaguedoctor's", &["Vitality", "ConditionDamage"],
+     &["Healing", "BoonDuration"]),
+    ("Rabid", &["ConditionDamage"], &["Precision", "Toughness"]),
+    ("Rampager's", &["Precision"], &["Power", "ConditionDamage"]),
+    ("Ritualist's", &["Vitality", "ConditionDamage"],
+     &["ConditionDuration", "BoonDuration"]),
+    ("Sentinel's", &["Vitality"], &["Power", "Toughness"]),
+    ("Seraph", &["Precision", "ConditionDamage"],
+     &["Healing", "BoonDuration"]),
+    ("Settler's", &["Toughness"], &["ConditionDamage", "Healing"]),
+    ("Shaman's", &["Vitality"], &["ConditionDamage", "Healing"]),
+    ("Sinister", &["ConditionDamage"], &["Power", "Precision"]),
+    ("Soldier's", &["Power"], &["Toughness", "Vitality"]),
+    ("Trailblazer's", &["Toughness", "ConditionDamage"],
+     &["Vitality", "ConditionDuration"]),
+    ("Valkyrie", &["Power"], &["Vitality", "CritDamage"]),
+    ("Viper's", &["Power", "ConditionDamage"],
+     &["Precision", "ConditionDuration"]),
+    ("Wanderer's", &["Power", "Vitality"], &["Toughness", "BoonDuration"]),
+    ("Zealot's", &["Power"], &["Precision", "Healing"])
+];
+
+/// Check whether two lists contain the same attributes, in any order
+fn same_attributes(expected: &[&str], actual: &[&str]) -> bool {
+    expected.len() == actual.len()
+        && expected.iter().all(|attribute| actual.contains(attribute))
+}
+
+/// Obtain the prefix name of a combination of major and minor attributes
+///
+/// Attributes can be listed in any order.
+///
+/// # Arguments
+///
+/// * `major` - Attributes receiving the largest bonus
+/// * `minor` - Rest of the attributes
+pub fn prefix_for(major: &[&str], minor: &[&str]) -> Option<&'static str> {
+    STAT_PREFIXES.iter()
+        .find(|&&(_, prefix_major, prefix_minor)| {
+            same_attributes(prefix_major, major)
+                && same_attributes(prefix_minor, minor)
+        })
+        .map(|&(prefix, _, _)| prefix)
+}
+
+/// Split weighted attributes into major (largest weight) and minor ones
+fn split_attributes<'a>(
+    weights: &[(&'a str, f64)]
+) -> Option<(Vec<&'a str>, Vec<&'a str>)> {
+    let largest = weights.iter()
+        .map(|&(_, weight)| weight)
+        .fold(None, |largest: Option<f64>, weight| {
+            Some(largest.map_or(weight, |value| value.max(weight)))
+        })?;
+
+    let (major, minor): (Vec<_>, Vec<_>) = weights.iter()
+        .filter(|&&(_, weight)| weight > 0.0)
+        .partition(|&&(_, weight)| (largest - weight).abs() < 1e-6);
+
+    Some((
+        major.into_iter().map(|&(name, _)| name).collect(),
+        minor.into_iter().map(|&(name, _)| name).collect()
+    ))
+}
+
+/// Obtain the name players use for an itemstat
+///
+/// The prefix is resolved from the attribute multipliers of the itemstat.
+/// If the combination is not known, the name returned by the API is used
+/// instead, unless it is empty.
+///
+/// # Arguments
+///
+/// * `stat` - Itemstat as obtained from `get_itemstat()`
+pub fn itemstat_prefix(stat: &ItemStat) -> Option<String> {
+    let weights: Vec<(&str, f64)> = stat.attributes.iter()
+        .map(|a| (a.attribute.as_str(), a.multiplier))
+        .collect();
+
+    split_attributes(&weights)
+        .and_then(|(major, minor)| prefix_for(&major, &minor))
+        .map(|prefix| prefix.to_string())
+        .or_else(|| {
+            if stat.name.is_empty() {
+                None
+            } else {
+                Some(stat.name.clone())
+            }
+        })
+}
+
+/// Obtain the prefix name of the attributes of an equipped item
+///
+/// # Arguments
+///
+/// * `attributes` - Attributes as listed in the equipment of a character
+pub fn attributes_prefix(
+    attributes: &EquipmentAttributes
+) -> Option<&'static str> {
+    let weights: Vec<(&str, f64)> = attribute_values(attributes).iter()
+        .map(|&(name, value)| (name, value as f64))
+        .collect();
+
+    split_attributes(&weights).and_then(|(major, minor)| {
+        prefix_for(&major, &minor)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use api_v2::types::{EquipmentAttributes, ItemStat};
+    use helpers::stats::*;
+
+    #[test]
+    fn prefixes() {
+        assert_eq!(
+            prefix_for(&["Power"], &["CritDamage", "Precision"]),
+            Some("Berserker's")
+        );
+        assert_eq!(
+            prefix_for(&["Precision"], &["Power", "CritDamage"]),
+            Some("Assassin's")
+        );
+        assert_eq!(prefix_for(&["Power"], &["Precision"]), None);
+    }
+
+    #[test]
+    fn itemstat() {
+        let stat: ItemStat = serde_json::from_str(r#"{
+            "id": 1067,
+            "name": "",
+            "attributes": [
+                {"attribute": "Power", "multiplier": 0.3, "value": 0},
+                {"attribute": "ConditionDamage", "multiplier": 0.3,
+                 "value": 0},
+                {"attribute": "Precision", "multiplier": 0.165, "value": 0},
+                {"attribute": "ConditionDuration", "multiplier": 0.165,
+                 "value": 0}
+            ]
+        }"#).unwrap();
+        assert_eq!(itemstat_prefix(&stat), Some("Viper's".to_string()));
+
+        let unknown: ItemStat = serde_json::from_str(r#"{
+            "id": 1, "name": "Custom",
+            "attributes": [{"attribute": "Power", "multiplier": 0.35,
+                            "value": 0}]
+        }"#).unwrap();
+        assert_eq!(itemstat_prefix(&unknown), Some("Custom".to_string()));
+    }
+
+    #[test]
+    fn equipment() {
+        let attributes: EquipmentAttributes = serde_json::from_str(
+            r#"{"Power": 63, "Vitality": 45, "CritDamage": 45}"#
+        ).unwrap();
+        assert_eq!(attributes_prefix(&attributes), Some("Valkyrie"));
+    }
+}
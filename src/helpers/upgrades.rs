@@ -0,0 +1,294 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Upgrade components slotted in gear
+/// These help deciding which upgrades are worth extracting
+
+use std::collections::{BTreeMap, HashMap};
+
+use client::APIClient;
+use common::APIError;
+use api_v2::account::get_account_bank;
+use api_v2::characters::{get_character_equipment, get_character_inventory};
+use api_v2::types::{BankSlot, CharacterInventory, Equipment};
+use helpers::commerce::{get_item_prices, ItemPrice};
+
+
+/// Kind of slot an upgrade component occupies
+///
+/// Runes and sigils both occupy upgrade slots and cannot be told apart
+/// without the item details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UpgradeKind {
+    /// Rune, sigil or other upgrade component
+    Upgrade,
+    /// Infusion
+    Infusion
+}
+
+/// Occurrences of an upgrade component in a set of gear
+#[derive(Debug)]
+pub struct UpgradeCount {
+    /// Item ID of the upgrade component
+    pub item_id: i32,
+    /// Kind of slot the component is placed in
+    pub kind: UpgradeKind,
+    /// Number of times the component is slotted
+    pub count: i32,
+    /// Highest buy order in the trading post, if tradeable
+    pub buy_price: Option<i32>,
+    /// Lowest sell offer in the trading post, if tradeable
+    pub sell_price: Option<i32>
+}
+
+impl UpgradeCount {
+    /// Coins obtained when selling all the occurrences instantly
+    pub fn value(&self) -> i64 {
+        self.buy_price.map_or(0, |price| price as i64 * self.count as i64)
+    }
+}
+
+/// Upgrade components found in a set of gear
+#[derive(Debug)]
+pub struct UpgradeSummary {
+    /// Each distinct upgrade component, sorted by descending value
+    pub upgrades: Vec<UpgradeCount>,
+    /// Sum of the value of all the upgrade components
+    pub total_value: i64
+}
+
+/// Count the upgrades and infusions of each piece of gear
+fn count_upgrades<'a, I>(gear: I) -> Vec<UpgradeCount>
+    where I: Iterator<Item = (&'a Vec<i32>, &'a Vec<i32>)> {
+    let mut counts: BTreeMap<(i32, UpgradeKind), i32> = BTreeMap::new();
+
+    for (upgrades, infusions) in gear {
+        for id in upgrades {
+            *counts.entry((*id, UpgradeKind::Upgrade)).or_insert(0) += 1;
+        }
+
+        for id in infusions {
+            *counts.entry((*id, UpgradeKind::Infusion)).or_insert(0) += 1;
+        }
+    }
+
+    counts.into_iter()
+        .map(|((item_id, kind), count)| UpgradeCount {
+            item_id: item_id,
+            kind: kind,
+            count: count,
+            buy_price: None,
+            sell_price: None
+        })
+        .collect()
+}
+
+/// Obtain the upgrade components slotted in equipped gear
+///
+/// # Arguments
+///
+/// * `equipment` - Equipment as obtained from `get_character_equipment()`
+pub fn equipment_upgrades(equipment: &[Equipment]) -> Vec<UpgradeCount> {
+    count_upgrades(equipment.iter().map(|e| (&e.upgrades, &e.infusions)))
+}
+
+/// Obtain the upgrade components slotted in gear stored in the bags
+///
+/// # Arguments
+///
+/// * `inventory` - Inventory as obtained from `get_character_inventory()`
+pub fn inventory_upgrades(inventory: &CharacterInventory) -> Vec<UpgradeCount> {
+    count_upgrades(
+        inventory.bags.iter()
+            .flat_map(|bag| bag.inventory.iter())
+            .filter_map(|slot| slot.as_ref())
+            .map(|slot| (&slot.upgrades, &slot.infusions))
+    )
+}
+
+/// Obtain the upgrade components slotted in gear stored in the bank
+///
+/// # Arguments
+///
+/// * `bank` - Bank slots as obtained from `get_account_bank()`
+pub fn bank_upgrades(bank: &[Option<BankSlot>]) -> Vec<UpgradeCount> {
+    count_upgrades(
+        bank.iter()
+            .filter_map(|slot| slot.as_ref())
+            .map(|slot| (&slot.upgrades, &slot.infusions))
+    )
+}
+
+/// Merge upgrade counts and price them
+///
+/// Counts of the same component in the same kind of slot are added up.
+/// Components that are not present in `prices` are kept with no price and
+/// do not add to the total value.
+///
+/// # Arguments
+///
+/// * `counts` - Upgrade counts to merge
+/// * `prices` - Trading post prices, as obtained from `get_item_prices()`
+pub fn summarize_upgrades(
+    counts: Vec<UpgradeCount>,
+    prices: &HashMap<i32, ItemPrice>
+) -> UpgradeSummary {
+    let mut merged: Vec<UpgradeCount> = Vec::new();
+
+    for count in counts {
+        let position = merged.iter()
+            .position(|m| m.item_id == count.item_id && m.kind == count.kind);
+
+        match position {
+            Some(index) => merged[index].count += count.count,
+            None => merged.push(count)
+        }
+    }
+
+    for upgrade in merged.iter_mut() {
+        if let Some(price) = prices.get(&upgrade.item_id) {
+            upgrade.buy_price = Some(price.buy);
+            upgrade.sell_price = Some(price.sell);
+        }
+    }
+
+    merged.sort_by(|a, b| {
+        b.value().cmp(&a.value()).then(a.item_id.cmp(&b.item_id))
+    });
+
+    UpgradeSummary {
+        total_value: merged.iter().map(|u| u.value()).sum(),
+        upgrades: merged
+    }
+}
+
+/// Price the upgrade counts using current trading post prices
+fn price_upgrades(
+    client: &APIClient,
+    counts: Vec<UpgradeCount>
+) -> Result<UpgradeSummary, APIError> {
+    let ids: Vec<i32> = counts.iter().map(|c| c.item_id).collect();
+    let prices = get_item_prices(client, &ids)?;
+
+    Ok(summarize_upgrades(counts, &prices))
+}
+
+/// Obtain the upgrade components in the equipment and bags of a character
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+/// * `name` - Name of the character
+pub fn get_character_upgrade_summary(
+    client: &APIClient,
+    name: &str
+) -> Result<UpgradeSummary, APIError> {
+    let equipment = get_character_equipment(client, name)?;
+    let inventory = get_character_inventory(client, name)?;
+
+    let mut counts = equipment_upgrades(&equipment.equipment);
+    counts.extend(inventory_upgrades(&inventory));
+
+    price_upgrades(client, counts)
+}
+
+/// Obtain the upgrade components in the gear stored in the bank
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_bank_upgrade_summary(
+    client: &APIClient
+) -> Result<UpgradeSummary, APIError> {
+    let bank = get_account_bank(client)?;
+
+    price_upgrades(client, bank_upgrades(&bank))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::env;
+    use serde_json;
+    use client::APIClient;
+    use api_v2::types::BankSlot;
+    use helpers::commerce::ItemPrice;
+    use helpers::upgrades::*;
+
+    fn setup_client() -> APIClient {
+        match env::var("TOKEN") {
+            Ok(token) => APIClient::new("en", Some(token.to_string())),
+            Err(_) => panic!("Need a token to test endpoint"),
+        }
+    }
+
+    fn bank() -> Vec<Option<BankSlot>> {
+        serde_json::from_str(r#"[
+            {"id": 30684, "count": 1, "upgrades": [24554, 24554],
+             "infusions": [49432]},
+            null,
+            {"id": 30699, "count": 1, "upgrades": [24554],
+             "infusions": [49432, 49432]},
+            {"id": 19721, "count": 250}
+        ]"#).unwrap()
+    }
+
+    #[test]
+    fn counts() {
+        let counts = bank_upgrades(&bank());
+
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0].item_id, 24554);
+        assert_eq!(counts[0].kind, UpgradeKind::Upgrade);
+        assert_eq!(counts[0].count, 3);
+        assert_eq!(counts[1].item_id, 49432);
+        assert_eq!(counts[1].kind, UpgradeKind::Infusion);
+        assert_eq!(counts[1].count, 3);
+    }
+
+    #[test]
+    fn summary() {
+        let mut prices = HashMap::new();
+        prices.insert(24554, ItemPrice { item_id: 24554, buy: 100, sell: 120 });
+
+        let mut counts = bank_upgrades(&bank());
+        counts.extend(bank_upgrades(&bank()));
+        let summary = summarize_upgrades(counts, &prices);
+
+        assert_eq!(summary.upgrades.len(), 2);
+        assert_eq!(summary.upgrades[0].item_id, 24554);
+        assert_eq!(summary.upgrades[0].count, 6);
+        assert_eq!(summary.upgrades[1].buy_price, None);
+        assert_eq!(summary.total_value, 600);
+    }
+
+    #[test]
+    fn bank_upgrade_summary() {
+        let client = setup_client();
+        match get_bank_upgrade_summary(&client) {
+            Ok(_) => assert!(true),
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+}
@@ -0,0 +1,347 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Valuation of the items stored in an account
+/// Tradeable items are valued at trading post prices, while bound items can
+/// only be sold to vendors
+
+use std::collections::{BTreeMap, HashMap};
+
+use client::APIClient;
+use common::APIError;
+use api_v2::account::{
+    get_account_bank,
+    get_account_inventory,
+    get_account_materials
+};
+use api_v2::items::get_items;
+use api_v2::types::{
+    AccountMaterial,
+    BankSlot,
+    IdList,
+    InventorySlot,
+    Item
+};
+use helpers::commerce::{get_item_prices, ItemPrice};
+
+
+/// Whether an item can be converted to coins through the trading post
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Liquidity {
+    /// Item can be sold in the trading post
+    Liquid,
+    /// Item is bound or not listed in the trading post, so it can at most
+    /// be sold to a vendor
+    Illiquid
+}
+
+/// Stack of items found in the account
+#[derive(Debug)]
+pub struct ItemStack {
+    /// Item ID
+    pub item_id: i32,
+    /// Number of items in the stack
+    pub count: i32,
+    /// Whether this particular stack has been bound to the account or a
+    /// character
+    pub bound: bool
+}
+
+/// Value of all the stacks of an item with the same liquidity
+#[derive(Debug)]
+pub struct ItemValuation {
+    /// Item ID
+    pub item_id: i32,
+    /// Total number of items
+    pub count: i32,
+    /// Whether the items can be sold in the trading post
+    pub liquidity: Liquidity,
+    /// Value of a single item: highest buy order for liquid items, vendor
+    /// value for illiquid items
+    pub unit_value: i32,
+    /// Value of all the items
+    pub value: i64
+}
+
+/// Value of the items in an account, split by liquidity
+#[derive(Debug)]
+pub struct Valuation {
+    /// Value of each item, sorted by descending value
+    pub items: Vec<ItemValuation>,
+    /// Value of the items that can be sold in the trading post
+    pub liquid_value: i64,
+    /// Value of the items that can only be sold to vendors
+    pub illiquid_value: i64
+}
+
+impl Valuation {
+    /// Total value of the items
+    pub fn total_value(&self) -> i64 {
+        self.liquid_value + self.illiquid_value
+    }
+}
+
+/// Obtain the item stacks in the bank
+///
+/// # Arguments
+///
+/// * `bank` - Bank slots as obtained from `get_account_bank()`
+pub fn bank_stacks(bank: &[Option<BankSlot>]) -> Vec<ItemStack> {
+    bank.iter()
+        .filter_map(|slot| slot.as_ref())
+        .map(|slot| ItemStack {
+            item_id: slot.id,
+            count: slot.count,
+            bound: !slot.binding.is_empty()
+        })
+        .collect()
+}
+
+/// Obtain the item stacks in the shared inventory slots
+///
+/// # Arguments
+///
+/// * `inventory` - Slots as obtained from `get_account_inventory()`
+pub fn inventory_stacks(inventory: &[Option<InventorySlot>]) -> Vec<ItemStack> {
+    inventory.iter()
+        .filter_map(|slot| slot.as_ref())
+        .map(|slot| ItemStack {
+            item_id: slot.id,
+            count: slot.count,
+            bound: !slot.binding.is_empty()
+        })
+        .collect()
+}
+
+/// Obtain the item stacks in the material storage
+///
+/// # Arguments
+///
+/// * `materials` - Materials as obtained from `get_account_materials()`
+pub fn material_stacks(materials: &[AccountMaterial]) -> Vec<ItemStack> {
+    materials.iter()
+        .filter(|material| material.count > 0)
+        .map(|material| ItemStack {
+            item_id: material.id,
+            count: material.count,
+            bound: false
+        })
+        .collect()
+}
+
+/// Value item stacks, splitting liquid and illiquid value
+///
+/// A stack is liquid when neither the stack nor the item are bound and the
+/// item has buy orders in the trading post. Illiquid stacks are valued at
+/// their vendor value, or zero if the item cannot be sold (`NoSell`). Items
+/// missing from `items` are considered illiquid with no value.
+///
+/// # Arguments
+///
+/// * `stacks` - Item stacks to value
+/// * `items` - Item details, indexed by ID
+/// * `prices` - Trading post prices, as obtained from `get_item_prices()`
+pub fn value_items(
+    stacks: &[ItemStack],
+    items: &HashMap<i32, Item>,
+    prices: &HashMap<i32, ItemPrice>
+) -> Valuation {
+    let mut counts: BTreeMap<(i32, Liquidity), (i32, i32)> = BTreeMap::new();
+
+    for stack in stacks {
+        let item = items.get(&stack.item_id);
+        let buy = prices.get(&stack.item_id)
+            .map(|price| price.buy)
+            .unwrap_or(0);
+        let bound = stack.bound
+            || item.map_or(true, |item| item.is_bound_on_acquire());
+
+        let (liquidity, unit_value) = if !bound && buy > 0 {
+            (Liquidity::Liquid, buy)
+        } else {
+            match item {
+                Some(item) if !item.has_flag("NoSell") => {
+                    (Liquidity::Illiquid, item.vendor_value)
+                },
+                _ => (Liquidity::Illiquid, 0)
+            }
+        };
+
+        counts.entry((stack.item_id, liquidity))
+            .or_insert((0, unit_value))
+            .0 += stack.count;
+    }
+
+    let mut valuations: Vec<ItemValuation> = counts.into_iter()
+        .map(|((item_id, liquidity), (count, unit_value))| ItemValuation {
+            item_id: item_id,
+            count: count,
+            liquidity: liquidity,
+            unit_value: unit_value,
+            value: unit_value as i64 * count as i64
+        })
+        .collect();
+
+    valuations.sort_by(|a, b| {
+        b.value.cmp(&a.value).then(a.item_id.cmp(&b.item_id))
+    });
+
+    let value_of = |liquidity| -> i64 {
+        valuations.iter()
+            .filter(|v| v.liquidity == liquidity)
+            .map(|v| v.value)
+            .sum()
+    };
+
+    Valuation {
+        liquid_value: value_of(Liquidity::Liquid),
+        illiquid_value: value_of(Liquidity::Illiquid),
+        items: valuations
+    }
+}
+
+/// Obtain item details for the given items, indexed by ID
+///
+/// IDs are requested in batches of `MAX_BULK_IDS`.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests
+/// * `ids` - Item IDs to obtain the details for
+pub fn get_item_details(
+    client: &APIClient,
+    ids: &[i32]
+) -> Result<HashMap<i32, Item>, APIError> {
+    let mut unique: Vec<i32> = ids.to_vec();
+    unique.sort();
+    unique.dedup();
+
+    let mut items = HashMap::new();
+
+    for batch in IdList::new(unique).batches(0) {
+        for item in get_items(client, batch)? {
+            items.insert(item.id, item);
+        }
+    }
+
+    Ok(items)
+}
+
+/// Obtain the value of the bank, material storage and shared inventory
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing API requests. Requires
+///     authentication token
+pub fn get_account_valuation(
+    client: &APIClient
+) -> Result<Valuation, APIError> {
+    let mut stacks = bank_stacks(&get_account_bank(client)?);
+    stacks.extend(material_stacks(&get_account_materials(client)?));
+    stacks.extend(inventory_stacks(&get_account_inventory(client)?));
+
+    let ids: Vec<i32> = stacks.iter().map(|stack| stack.item_id).collect();
+    let items = get_item_details(client, &ids)?;
+    let prices = get_item_prices(client, &ids)?;
+
+    Ok(value_items(&stacks, &items, &prices))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::env;
+    use serde_json;
+    use client::APIClient;
+    use api_v2::types::Item;
+    use helpers::commerce::ItemPrice;
+    use helpers::valuation::*;
+
+    fn setup_client() -> APIClient {
+        match env::var("TOKEN") {
+            Ok(token) => APIClient::new("en", Some(token.to_string())),
+            Err(_) => panic!("Need a token to test endpoint"),
+        }
+    }
+
+    fn item(id: i32, vendor_value: i32, flags: &str) -> (i32, Item) {
+        let item = serde_json::from_str(&format!(r#"{{
+            "id": {},
+            "chat_link": "",
+            "name": "Item {}",
+            "type": "CraftingMaterial",
+            "rarity": "Basic",
+            "level": 0,
+            "vendor_value": {},
+            "flags": [{}]
+        }}"#, id, id, vendor_value, flags)).unwrap();
+
+        (id, item)
+    }
+
+    fn stack(item_id: i32, count: i32, bound: bool) -> ItemStack {
+        ItemStack {
+            item_id: item_id,
+            count: count,
+            bound: bound
+        }
+    }
+
+    #[test]
+    fn liquidity_split() {
+        let items: HashMap<i32, Item> = vec![
+            item(1, 8, ""),
+            item(2, 100, r#""AccountBound""#),
+            item(3, 50, r#""AccountBound", "NoSell""#),
+            item(4, 10, ""),
+        ].into_iter().collect();
+
+        let mut prices = HashMap::new();
+        prices.insert(1, ItemPrice { item_id: 1, buy: 20, sell: 25 });
+        prices.insert(2, ItemPrice { item_id: 2, buy: 999, sell: 999 });
+
+        let valuation = value_items(&[
+            stack(1, 10, false),
+            stack(1, 5, true),
+            stack(2, 2, false),
+            stack(3, 7, false),
+            stack(4, 3, false),
+            stack(5, 1, false),
+        ], &items, &prices);
+
+        assert_eq!(valuation.liquid_value, 200);
+        assert_eq!(valuation.illiquid_value, 40 + 200 + 30);
+        assert_eq!(valuation.total_value(), 470);
+        assert_eq!(valuation.items[0].item_id, 1);
+        assert_eq!(valuation.items[0].liquidity, Liquidity::Liquid);
+        assert_eq!(valuation.items.len(), 6);
+    }
+
+    #[test]
+    fn account_valuation() {
+        let client = setup_client();
+        match get_account_valuation(&client) {
+            Ok(_) => assert!(true),
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+}
@@ -0,0 +1,102 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Hooks around the requests performed by the client
+/// Interceptors are registered in the `APIClient` to observe or modify every
+/// request (e.g. logging, metrics or rotating tokens) without wrapping the
+/// endpoint functions
+
+use reqwest::header::HeaderMap;
+
+use transport::Response;
+
+
+/// Request about to be sent by the client
+#[derive(Debug, Clone)]
+pub struct Request {
+    /// Full URL of the request, including the query string
+    pub url: String,
+    /// Headers sent in the request
+    pub headers: HeaderMap
+}
+
+/// Hooks called by the client around each request
+///
+/// Both methods do nothing by default, so that implementors only need to
+/// provide the ones they use. Interceptors are called in the order they were
+/// registered, once per attempt when a request is retried.
+pub trait Interceptor: Send + Sync {
+    /// Called before sending a request, which may be modified
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Request about to be sent
+    fn before(&self, request: &mut Request) {
+        let _ = request;
+    }
+
+    /// Called after receiving a response
+    ///
+    /// Requests that fail without a response (e.g. connection errors) are
+    /// not reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Request that was sent
+    /// * `response` - Response received
+    fn after(&self, request: &Request, response: &Response) {
+        let _ = (request, response);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use reqwest::header::HeaderMap;
+    use interceptor::{Interceptor, Request};
+
+    struct Recorder {
+        urls: Mutex<Vec<String>>
+    }
+
+    impl Interceptor for Recorder {
+        fn before(&self, request: &mut Request) {
+            self.urls.lock().unwrap().push(request.url.clone());
+            request.url.push_str("?v=latest");
+        }
+    }
+
+    #[test]
+    fn before() {
+        let recorder = Recorder { urls: Mutex::new(Vec::new()) };
+        let mut request = Request {
+            url: "https://api.guildwars2.com/v2/items".to_string(),
+            headers: HeaderMap::new()
+        };
+
+        recorder.before(&mut request);
+
+        assert_eq!(request.url, "https://api.guildwars2.com/v2/items?v=latest");
+        assert_eq!(recorder.urls.lock().unwrap().len(), 1);
+    }
+}
@@ -20,16 +20,65 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+#[cfg(feature = "chrono")]
 extern crate chrono;
-extern crate hyper;
+#[cfg(feature = "blocking")]
+extern crate httpdate;
+#[cfg(feature = "blocking")]
 extern crate reqwest;
 
 #[macro_use]
 extern crate serde_derive;
 
+#[macro_use]
 extern crate serde;
 extern crate serde_json;
+#[cfg(feature = "drift")]
+extern crate serde_ignored;
+
+#[cfg(feature = "csv")]
+extern crate csv;
+#[cfg(feature = "sprites")]
+extern crate image;
 
 pub mod common;
-pub mod client;
+#[cfg(feature = "blocking")]
+pub mod demo;
 pub mod api_v2;
+pub mod cache;
+pub mod ratelimit;
+pub mod storage;
+
+#[cfg(feature = "blocking")]
+pub mod fixtures;
+#[cfg(feature = "blocking")]
+pub mod interceptor;
+#[cfg(feature = "blocking")]
+pub mod retry;
+#[cfg(feature = "blocking")]
+pub mod transport;
+#[cfg(feature = "blocking")]
+pub mod client;
+#[cfg(feature = "blocking")]
+pub mod bundle;
+#[cfg(feature = "blocking")]
+pub mod helpers;
+#[cfg(feature = "blocking")]
+pub mod refresh;
+#[cfg(feature = "blocking")]
+pub mod render;
+
+#[cfg(feature = "async")]
+pub mod tasks;
+
+#[cfg(all(feature = "csv", feature = "account", feature = "commerce"))]
+pub mod export;
+
+#[cfg(feature = "sprites")]
+pub mod sprites;
+
+#[cfg(feature = "discord")]
+pub mod discord;
+
+#[cfg(feature = "drift")]
+pub mod drift;
@@ -0,0 +1,214 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Client-side rate limiting
+/// The API allows a limited number of requests per minute for each client,
+/// so requests are throttled before reaching that limit
+
+use std::cmp;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+
+/// Number of requests per minute allowed by the API
+pub const API_REQUESTS_PER_MINUTE: u32 = 300;
+
+/// Tokens available in the bucket
+struct Bucket {
+    /// Tokens currently available, possibly fractional
+    tokens: f64,
+    /// Time the tokens were last refilled
+    refilled_at: Instant,
+    /// Time until which no tokens are handed out
+    paused_until: Option<Instant>
+}
+
+/// Token bucket limiting the rate of requests
+///
+/// Up to `capacity` requests can be performed in a burst, after which
+/// tokens are refilled at a steady rate. Cloning the limiter is cheap,
+/// clones share the same bucket.
+#[derive(Clone)]
+pub struct RateLimiter {
+    /// Maximum number of tokens in the bucket
+    capacity: u32,
+    /// Time it takes to refill the whole bucket
+    period: Duration,
+    /// Bucket shared between clones
+    bucket: Arc<Mutex<Bucket>>
+}
+
+impl RateLimiter {
+    /// Create a new rate limiter, starting with a full bucket
+    ///
+    /// A `capacity` of `0` would never hand out a token, so it is raised to
+    /// `1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of requests allowed per period
+    /// * `period` - Time it takes to refill the whole bucket
+    pub fn new(capacity: u32, period: Duration) -> RateLimiter {
+        let capacity = cmp::max(capacity, 1);
+
+        RateLimiter {
+            capacity: capacity,
+            period: period,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: capacity as f64,
+                refilled_at: Instant::now(),
+                paused_until: None
+            }))
+        }
+    }
+
+    /// Create a rate limiter matching the limits of the API
+    pub fn api_default() -> RateLimiter {
+        RateLimiter::new(API_REQUESTS_PER_MINUTE, Duration::from_secs(60))
+    }
+
+    /// Obtain the number of requests allowed per period
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Obtain the time it takes to refill the whole bucket
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Take a token if one is available, without waiting
+    ///
+    /// Returns the time to wait for the next token otherwise.
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        let mut bucket = match self.bucket.lock() {
+            Ok(bucket) => bucket,
+            // A poisoned bucket does not block requests
+            Err(_) => return Ok(())
+        };
+
+        let now = Instant::now();
+        if let Some(until) = bucket.paused_until {
+            if until > now {
+                return Err(until - now);
+            }
+            // Tokens are only refilled from the end of the pause
+            bucket.paused_until = None;
+            bucket.refilled_at = until;
+        }
+
+        let rate = self.capacity as f64 / self.period.as_secs_f64();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate)
+            .min(self.capacity as f64);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else if rate > 0.0 {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        } else {
+            Err(self.period)
+        }
+    }
+
+    /// Stop handing out tokens for some time
+    ///
+    /// Used when the API rejects a request because of its rate limit, so
+    /// that every clone of the limiter waits for the delay requested by the
+    /// API. The tokens left in the bucket are discarded.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - Time to wait before handing out tokens again
+    pub fn pause(&self, duration: Duration) {
+        if let Ok(mut bucket) = self.bucket.lock() {
+            let until = Instant::now() + duration;
+            if bucket.paused_until.map_or(true, |paused| paused < until) {
+                bucket.paused_until = Some(until);
+            }
+            bucket.tokens = 0.0;
+        }
+    }
+
+    /// Take a token, waiting until one is available
+    pub fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            thread::sleep(wait);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+    use ratelimit::*;
+
+    #[test]
+    fn burst() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.clone().try_acquire().is_ok());
+
+        let wait = limiter.try_acquire().unwrap_err();
+        assert!(wait > Duration::from_secs(25));
+        assert!(wait <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let limiter = RateLimiter::new(0, Duration::from_millis(10));
+        assert_eq!(limiter.capacity(), 1);
+        assert!(limiter.try_acquire().is_ok());
+
+        let wait = limiter.try_acquire().unwrap_err();
+        assert!(wait <= Duration::from_millis(10));
+        limiter.acquire();
+    }
+
+    #[test]
+    fn refill() {
+        let limiter = RateLimiter::new(10, Duration::from_millis(100));
+        for _ in 0..10 {
+            limiter.acquire();
+        }
+
+        let start = Instant::now();
+        limiter.acquire();
+        assert!(start.elapsed() >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn pause() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(60));
+        limiter.clone().pause(Duration::from_secs(5));
+
+        let wait = limiter.try_acquire().unwrap_err();
+        assert!(wait > Duration::from_secs(4));
+        assert!(wait <= Duration::from_secs(5));
+
+        limiter.pause(Duration::from_secs(1));
+        assert!(limiter.try_acquire().unwrap_err() > Duration::from_secs(4));
+    }
+}
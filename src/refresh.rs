@@ -0,0 +1,457 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Periodic refreshing of endpoints in the background
+/// Each endpoint is polled in its own thread and its latest value is shared
+/// through cheap handles
+
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+#[cfg(feature = "async")]
+use serde_json;
+
+use client::APIClient;
+#[cfg(feature = "async")]
+use common::APIError;
+
+
+/// Latest state of a refreshed endpoint
+struct Snapshot<T> {
+    /// Last value obtained successfully
+    value: Option<Arc<T>>,
+    /// Time the value was obtained
+    updated_at: Option<Instant>,
+    /// Description of the error of the last attempt, if it failed
+    error: Option<String>
+}
+
+/// Build of the game, as returned by `/v2/build`
+#[cfg(feature = "async")]
+#[derive(Deserialize)]
+struct Build {
+    /// ID of the build
+    id: i32
+}
+
+/// Handle to the latest value of a refreshed endpoint
+///
+/// Cloning the handle is cheap, clones share the same value. The value is
+/// kept after the refresher is stopped.
+pub struct Handle<T> {
+    /// State shared with the refreshing thread
+    snapshot: Arc<RwLock<Snapshot<T>>>
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Handle<T> {
+        Handle {
+            snapshot: self.snapshot.clone()
+        }
+    }
+}
+
+impl<T> Handle<T> {
+    /// Create a handle without a value
+    fn new() -> Handle<T> {
+        Handle {
+            snapshot: Arc::new(RwLock::new(Snapshot {
+                value: None,
+                updated_at: None,
+                error: None
+            }))
+        }
+    }
+
+    /// Obtain the last value obtained successfully, if any
+    pub fn latest(&self) -> Option<Arc<T>> {
+        self.snapshot.read().ok().and_then(|s| s.value.clone())
+    }
+
+    /// Obtain the time the last value was obtained, if any
+    pub fn updated_at(&self) -> Option<Instant> {
+        self.snapshot.read().ok().and_then(|s| s.updated_at)
+    }
+
+    /// Obtain the description of the error of the last attempt, if it failed
+    pub fn last_error(&self) -> Option<String> {
+        self.snapshot.read().ok().and_then(|s| s.error.clone())
+    }
+
+    /// Store the result of an attempt
+    fn update(&self, result: Result<T, String>) {
+        if let Ok(mut snapshot) = self.snapshot.write() {
+            match result {
+                Ok(value) => {
+                    snapshot.value = Some(Arc::new(value));
+                    snapshot.updated_at = Some(Instant::now());
+                    snapshot.error = None;
+                },
+                Err(error) => snapshot.error = Some(error)
+            }
+        }
+    }
+}
+
+/// Keeps a set of endpoints up to date in background threads
+///
+/// Each endpoint is requested right away and then once per interval. When
+/// the client has a cache, the responses are stored in it as well, so the
+/// rest of the application gets fresh data without waiting for requests.
+/// Dropping the refresher stops every thread.
+///
+/// # Example
+///
+/// ```no_run
+/// # extern crate serde_json;
+/// # extern crate tyria;
+/// use std::time::Duration;
+/// use tyria::client::APIClient;
+/// use tyria::refresh::Refresher;
+///
+/// # fn main() {
+/// let client = APIClient::new("en", None);
+/// let mut refresher = Refresher::new(&client);
+/// let matches = refresher.watch::<serde_json::Value>(
+///     "/v2/wvw/matches?ids=all",
+///     false,
+///     Duration::from_secs(30)
+/// );
+///
+/// if let Some(matches) = matches.latest() {
+///     println!("{}", matches);
+/// }
+/// # }
+/// ```
+pub struct Refresher {
+    /// Client used to perform the requests
+    client: APIClient,
+    /// Whether the threads must stop, with a condition to wake them up
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    /// Refreshing threads
+    threads: Vec<JoinHandle<()>>
+}
+
+impl Refresher {
+    /// Create a new refresher without endpoints
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests. It is
+    ///     cloned, so later changes to it do not affect the refresher
+    pub fn new(client: &APIClient) -> Refresher {
+        Refresher {
+            client: client.clone(),
+            stop: Arc::new((Mutex::new(false), Condvar::new())),
+            threads: Vec::new()
+        }
+    }
+
+    /// Start refreshing an endpoint periodically
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Path of the endpoint, including the query string
+    /// * `authenticated` - Whether to send the API token
+    /// * `interval` - Time between requests
+    pub fn watch<T>(
+        &mut self,
+        endpoint: &str,
+        authenticated: bool,
+        interval: Duration
+    ) -> Handle<T> where T: DeserializeOwned + Send + Sync + 'static {
+        let handle = Handle::new();
+        let updated = handle.clone();
+        let client = self.client.clone();
+        let stop = self.stop.clone();
+        let endpoint = endpoint.to_string();
+
+        self.threads.push(thread::spawn(move || {
+            loop {
+                let result = client.fetch(&endpoint, authenticated, false)
+                    .map(|response| response.data)
+                    .map_err(|e| e.description().to_string());
+                updated.update(result);
+
+                if !wait(&stop, interval) {
+                    return;
+                }
+            }
+        }));
+
+        handle
+    }
+
+    /// Keep cached responses of static endpoints fresh
+    ///
+    /// Every `interval`, the endpoints whose cached response expires within
+    /// `margin` (or is not cached) are requested again, so that the cache
+    /// never serves stale data. `/v2/build` is checked as well, and every
+    /// cached response is expired when the game build changes, since
+    /// static data (items, skills, etc.) may change along with it.
+    ///
+    /// Fails when the client has no cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - Paths of the endpoints, including the query string
+    /// * `margin` - Time before the expiry of a response to refresh it
+    /// * `interval` - Time between checks
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use tyria::cache::Cache;
+    /// use tyria::client::APIClient;
+    /// use tyria::refresh::Refresher;
+    /// use tyria::storage::MemoryStorage;
+    ///
+    /// let client = APIClient::builder()
+    ///     .cache(Cache::new(
+    ///         Arc::new(MemoryStorage::new()),
+    ///         Duration::from_secs(3600)
+    ///     ))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let mut refresher = Refresher::new(&client);
+    /// refresher.keep_cached(
+    ///     &["/v2/professions?ids=all", "/v2/races?ids=all"],
+    ///     Duration::from_secs(300),
+    ///     Duration::from_secs(60)
+    /// ).unwrap();
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn keep_cached(
+        &mut self,
+        endpoints: &[&str],
+        margin: Duration,
+        interval: Duration
+    ) -> Result<(), APIError> {
+        let cache = match self.client.cache() {
+            Some(cache) => cache.clone(),
+            None => return Err(APIError::new("client has no cache"))
+        };
+        let client = self.client.clone();
+        let stop = self.stop.clone();
+        let endpoints: Vec<String> = endpoints.iter()
+            .map(|endpoint| endpoint.to_string())
+            .collect();
+
+        self.threads.push(thread::spawn(move || {
+            let mut build = None;
+
+            loop {
+                // Failed requests are retried on the next check
+                if let Ok(response) = client.fetch::<Build>(
+                    "/v2/build",
+                    false,
+                    false
+                ) {
+                    let id = response.data.id;
+                    if build.map_or(false, |build| build != id) {
+                        let _ = cache.expire_all();
+                    }
+                    build = Some(id);
+                }
+
+                for endpoint in &endpoints {
+                    let key = client.cache_key(endpoint);
+                    let stale = match cache.time_to_live(&key) {
+                        Ok(Some(ttl)) => ttl <= margin,
+                        _ => true
+                    };
+
+                    if stale {
+                        let _ = client.fetch::<serde_json::Value>(
+                            endpoint,
+                            false,
+                            false
+                        );
+                    }
+                }
+
+                if !wait(&stop, interval) {
+                    return;
+                }
+            }
+        }));
+
+        Ok(())
+    }
+
+    /// Stop every refreshing thread, waiting for them to finish
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    /// Signal the threads to stop and wait for them
+    fn shutdown(&mut self) {
+        {
+            let &(ref stopped, ref condvar) = &*self.stop;
+            if let Ok(mut stopped) = stopped.lock() {
+                *stopped = true;
+            }
+            condvar.notify_all();
+        }
+
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Wait for the next refresh
+///
+/// Returns `false` when the refresher has been stopped in the meantime.
+///
+/// # Arguments
+///
+/// * `stop` - Whether the threads must stop, with a condition to wake them
+/// * `interval` - Time to wait
+fn wait(stop: &(Mutex<bool>, Condvar), interval: Duration) -> bool {
+    let &(ref stopped, ref condvar) = stop;
+
+    let guard = match stopped.lock() {
+        Ok(guard) => guard,
+        Err(_) => return false
+    };
+
+    match condvar.wait_timeout_while(guard, interval, |stopped| !*stopped) {
+        Ok((guard, _)) => !*guard,
+        Err(_) => false
+    }
+}
+
+impl Drop for Refresher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "async")]
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+    #[cfg(feature = "async")]
+    use cache::Cache;
+    use client::APIClient;
+    use refresh::*;
+    #[cfg(feature = "async")]
+    use storage::MemoryStorage;
+    #[cfg(feature = "async")]
+    use transport::{MockTransport, Response};
+
+    /// Wait up to a second for a condition to hold
+    fn wait_for<F: Fn() -> bool>(condition: F) -> bool {
+        for _ in 0..100 {
+            if condition() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        false
+    }
+
+    #[test]
+    fn handle_update() {
+        let handle: Handle<i32> = Handle::new();
+        let clone = handle.clone();
+        assert!(handle.latest().is_none());
+
+        clone.update(Ok(42));
+        assert_eq!(*handle.latest().unwrap(), 42);
+        assert!(handle.updated_at().is_some());
+
+        clone.update(Err("endpoint is down".to_string()));
+        assert_eq!(*handle.latest().unwrap(), 42);
+        assert_eq!(handle.last_error().unwrap(), "endpoint is down");
+    }
+
+    #[test]
+    fn stop_refresher() {
+        // Authenticated requests without a token fail before connecting
+        let client = APIClient::new("en", None);
+        let mut refresher = Refresher::new(&client);
+        let handle = refresher.watch::<Vec<i32>>(
+            "/v2/account/minis",
+            true,
+            Duration::from_secs(3600)
+        );
+
+        wait_for(|| handle.last_error().is_some());
+
+        refresher.stop();
+        assert!(handle.latest().is_none());
+        assert_eq!(handle.last_error().unwrap(), "token is not configured");
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn keep_cached() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("/v2/build", Response::json(r#"{"id": 1}"#));
+        transport.respond("/v2/races", Response::json(r#"["Human"]"#));
+
+        let client = APIClient::builder()
+            .cache(Cache::new(
+                Arc::new(MemoryStorage::new()),
+                Duration::from_secs(3600)
+            ))
+            .rate_limiter(None)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+        let requests = |endpoint: &str| transport.requests().iter()
+            .filter(|requested| *requested == endpoint)
+            .count();
+
+        let mut refresher = Refresher::new(&client);
+        refresher.keep_cached(
+            &["/v2/races"],
+            Duration::from_secs(60),
+            Duration::from_millis(10)
+        ).unwrap();
+
+        // Fresh responses are not requested again
+        assert!(wait_for(|| requests("/v2/build") >= 3));
+        assert_eq!(requests("/v2/races"), 1);
+
+        transport.respond("/v2/build", Response::json(r#"{"id": 2}"#));
+        assert!(wait_for(|| requests("/v2/races") == 2));
+        refresher.stop();
+
+        let uncached = APIClient::new("en", None);
+        assert!(Refresher::new(&uncached).keep_cached(
+            &["/v2/races"],
+            Duration::from_secs(60),
+            Duration::from_secs(60)
+        ).is_err());
+    }
+}
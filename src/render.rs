@@ -0,0 +1,115 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Render service helpers
+/// Icons and other images referenced by the API are served by the render
+/// service rather than the API itself
+
+use std::io::Read;
+
+use client::APIClient;
+use common::APIError;
+
+use reqwest::StatusCode;
+
+/// Base URL of the files in the render service
+pub const RENDER_FILE_URL: &'static str = "https://render.guildwars2.com/file";
+
+/// Build the URL of a file in the render service
+///
+/// # Arguments
+///
+/// * `signature` - Signature of the file
+/// * `file_id` - ID of the file
+/// * `format` - Image format (`png` or `jpg`)
+pub fn render_url(signature: &str, file_id: i32, format: &str) -> String {
+    format!("{}/{}/{}.{}", RENDER_FILE_URL, signature, file_id, format)
+}
+
+/// Download a file from the render service
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing requests
+/// * `url` - URL of the file, as found in the `icon` of API results
+pub fn get_render_file(
+    client: &APIClient,
+    url: &str
+) -> Result<Vec<u8>, APIError> {
+    if !url.starts_with(RENDER_FILE_URL) {
+        return Err(APIError::new(
+            format!("not a render service URL: {}", url).as_str()
+        ));
+    }
+
+    let mut response = client.download(url)?;
+
+    if *response.status() != StatusCode::OK {
+        return Err(APIError::new(
+            format!("unknown status code: {}", response.status()).as_str()
+        ));
+    }
+
+    let mut data = Vec::new();
+    match response.read_to_end(&mut data) {
+        Ok(_) => Ok(data),
+        Err(e) => Err(APIError::new(
+            format!("failed to read render file: {}", e).as_str()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use client::APIClient;
+    #[cfg(feature = "static")]
+    use api_v2::mechanics::get_skill;
+    use render::*;
+
+    #[test]
+    fn url() {
+        assert_eq!(
+            render_url("943538394A94A491C8632FBEF6203C2013443555", 102478, "png"),
+            "https://render.guildwars2.com/file/\
+             943538394A94A491C8632FBEF6203C2013443555/102478.png"
+        );
+    }
+
+    #[test]
+    fn invalid_url() {
+        let client = APIClient::new("en", None);
+        let result = get_render_file(&client, "https://example.com/a.png");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "static")]
+    fn render_file() {
+        let client = APIClient::new("en", None);
+        let skill = get_skill(&client, 5516).unwrap();
+
+        match get_render_file(&client, &skill.icon) {
+            Ok(data) => assert!(!data.is_empty()),
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+}
@@ -0,0 +1,149 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Retrying of failed requests
+/// Requests that fail for transient reasons (rate limiting, server errors or
+/// connection problems) are retried after an increasing delay
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use transport::Response;
+
+
+/// Policy deciding when and how often to retry a request
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt
+    max_retries: u32,
+    /// Delay before the first retry, doubled on each subsequent retry
+    base_delay: Duration,
+    /// Maximum delay between retries
+    max_delay: Duration
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy::new(2, Duration::from_millis(500), Duration::from_secs(30))
+    }
+}
+
+impl RetryPolicy {
+    /// Create a new retry policy
+    ///
+    /// # Arguments
+    ///
+    /// * `max_retries` - Maximum number of retries after the first attempt
+    /// * `base_delay` - Delay before the first retry, doubled on each
+    ///     subsequent retry
+    /// * `max_delay` - Maximum delay between retries
+    pub fn new(
+        max_retries: u32,
+        base_delay: Duration,
+        max_delay: Duration
+    ) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: max_retries,
+            base_delay: base_delay,
+            max_delay: max_delay
+        }
+    }
+
+    /// Obtain the maximum number of retries after the first attempt
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Obtain the delay before a retry
+    ///
+    /// The delay requested by the API, if any, takes precedence over the
+    /// exponential backoff. Either way it is capped at the maximum delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - Index of the retry, starting at 0
+    /// * `retry_after` - Delay requested in the `Retry-After` header
+    pub fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let delay = retry_after.unwrap_or_else(|| {
+            self.base_delay
+                .checked_mul(2u32.saturating_pow(retry))
+                .unwrap_or(self.max_delay)
+        });
+
+        delay.min(self.max_delay)
+    }
+}
+
+/// Check whether a response status is worth retrying
+///
+/// # Arguments
+///
+/// * `status` - HTTP status code of the response
+pub fn is_retryable(status: &StatusCode) -> bool {
+    *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Obtain the delay requested in the `Retry-After` header of a response
+///
+/// Only delays in seconds are supported, as sent by the API.
+///
+/// # Arguments
+///
+/// * `response` - Response from the API
+pub fn retry_after(response: &Response) -> Option<Duration> {
+    response.headers().get("Retry-After")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use reqwest::StatusCode;
+    use retry::*;
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy::new(
+            5,
+            Duration::from_millis(100),
+            Duration::from_secs(1)
+        );
+
+        assert_eq!(policy.delay(0, None), Duration::from_millis(100));
+        assert_eq!(policy.delay(2, None), Duration::from_millis(400));
+        assert_eq!(policy.delay(4, None), Duration::from_secs(1));
+        assert_eq!(policy.delay(40, None), Duration::from_secs(1));
+        assert_eq!(
+            policy.delay(0, Some(Duration::from_millis(300))),
+            Duration::from_millis(300)
+        );
+    }
+
+    #[test]
+    fn retryable() {
+        assert!(is_retryable(&StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable(&StatusCode::NOT_FOUND));
+    }
+}
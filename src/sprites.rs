@@ -0,0 +1,181 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Sprite sheets of skill and trait icons
+/// Requires the `sprites` feature
+
+use std::collections::HashMap;
+
+use image;
+use image::imageops::{self, FilterType};
+use image::RgbaImage;
+
+use client::APIClient;
+use common::APIError;
+use api_v2::types::{Skill, Trait};
+use render::get_render_file;
+
+
+/// Position of an icon in a sprite sheet, in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteRect {
+    /// Horizontal offset from the left of the sheet
+    pub x: u32,
+    /// Vertical offset from the top of the sheet
+    pub y: u32,
+    /// Width of the icon
+    pub width: u32,
+    /// Height of the icon
+    pub height: u32
+}
+
+/// Icons packed in a single image
+#[derive(Debug)]
+pub struct SpriteSheet {
+    /// Image containing all the icons
+    pub image: RgbaImage,
+    /// Position of the icon of each skill, by skill ID
+    pub skills: HashMap<i32, SpriteRect>,
+    /// Position of the icon of each trait, by trait ID
+    pub traits: HashMap<i32, SpriteRect>
+}
+
+/// Compute the layout of a sprite sheet with square icons
+///
+/// Icons are placed left to right and top to bottom in a grid that is as
+/// square as possible. Returns the width and height of the sheet and the
+/// position of each icon.
+///
+/// # Arguments
+///
+/// * `count` - Number of icons
+/// * `icon_size` - Width and height of each icon, in pixels
+pub fn sprite_layout(
+    count: usize,
+    icon_size: u32
+) -> (u32, u32, Vec<SpriteRect>) {
+    if count == 0 {
+        return (0, 0, Vec::new());
+    }
+
+    let columns = (count as f64).sqrt().ceil() as usize;
+    let rows = (count + columns - 1) / columns;
+
+    let rects = (0..count)
+        .map(|index| SpriteRect {
+            x: (index % columns) as u32 * icon_size,
+            y: (index / columns) as u32 * icon_size,
+            width: icon_size,
+            height: icon_size
+        })
+        .collect();
+
+    (columns as u32 * icon_size, rows as u32 * icon_size, rects)
+}
+
+/// Download the icons of the given skills and traits into a sprite sheet
+///
+/// Icons are downloaded from the render service and scaled to `icon_size`.
+/// Skills and traits sharing the same icon share the same position in the
+/// sheet.
+///
+/// # Arguments
+///
+/// * `client` - The client to use when performing requests
+/// * `skills` - Skills to include in the sheet
+/// * `traits` - Traits to include in the sheet
+/// * `icon_size` - Width and height of each icon, in pixels
+pub fn build_sprite_sheet(
+    client: &APIClient,
+    skills: &[Skill],
+    traits: &[Trait],
+    icon_size: u32
+) -> Result<SpriteSheet, APIError> {
+    let mut urls: Vec<&str> = skills.iter()
+        .map(|skill| skill.icon.as_str())
+        .chain(traits.iter().map(|t| t.icon.as_str()))
+        .filter(|url| !url.is_empty())
+        .collect();
+    urls.sort();
+    urls.dedup();
+
+    let (width, height, rects) = sprite_layout(urls.len(), icon_size);
+    let mut sheet = RgbaImage::new(width, height);
+    let mut positions = HashMap::new();
+
+    for (url, rect) in urls.into_iter().zip(rects) {
+        let data = get_render_file(client, url)?;
+        let icon = image::load_from_memory(&data).map_err(|e| {
+            APIError::new(format!("failed to decode {}: {}", url, e).as_str())
+        })?;
+
+        let scaled = icon
+            .resize_exact(icon_size, icon_size, FilterType::Lanczos3)
+            .to_rgba();
+        imageops::replace(&mut sheet, &scaled, rect.x, rect.y);
+
+        positions.insert(url, rect);
+    }
+
+    Ok(SpriteSheet {
+        skills: skills.iter()
+            .filter_map(|s| positions.get(s.icon.as_str()).map(|r| (s.id, *r)))
+            .collect(),
+        traits: traits.iter()
+            .filter_map(|t| positions.get(t.icon.as_str()).map(|r| (t.id, *r)))
+            .collect(),
+        image: sheet
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "static")]
+    use client::APIClient;
+    #[cfg(feature = "static")]
+    use api_v2::mechanics::get_skills;
+    use sprites::*;
+
+    #[test]
+    fn layout() {
+        let (width, height, rects) = sprite_layout(5, 64);
+
+        assert_eq!((width, height), (192, 128));
+        assert_eq!((rects[2].x, rects[2].y), (128, 0));
+        assert_eq!((rects[4].x, rects[4].y), (64, 64));
+        assert!(rects.iter().all(|r| r.width == 64 && r.height == 64));
+
+        assert_eq!(sprite_layout(0, 64), (0, 0, Vec::new()));
+    }
+
+    #[test]
+    #[cfg(feature = "static")]
+    fn sprite_sheet() {
+        let client = APIClient::new("en", None);
+        let skills = get_skills(&client, vec![5516, 5517]).unwrap();
+
+        match build_sprite_sheet(&client, &skills, &[], 32) {
+            Ok(sheet) => assert_eq!(sheet.skills.len(), 2),
+            Err(e) => panic!(e.description().to_string()),
+        };
+    }
+}
@@ -0,0 +1,373 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Persistence of data between runs
+/// Stateful helpers store their data through the `Storage` trait, so that
+/// it can be backed by any database
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+
+/// Key-value storage grouped by namespace
+///
+/// Each stateful subsystem uses its own namespace (e.g. `cache`), and keys
+/// are unique within a namespace.
+pub trait Storage: Send + Sync {
+    /// Obtain the value stored under a key, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - Namespace the key belongs to
+    /// * `key` - Key of the value
+    fn get(&self, namespace: &str, key: &str) -> io::Result<Option<Vec<u8>>>;
+
+    /// Store a value under a key, replacing the previous one
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - Namespace the key belongs to
+    /// * `key` - Key of the value
+    /// * `value` - Data to store
+    fn put(&self, namespace: &str, key: &str, value: &[u8]) -> io::Result<()>;
+
+    /// Obtain all the keys stored in a namespace, sorted
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - Namespace to list
+    fn list(&self, namespace: &str) -> io::Result<Vec<String>>;
+}
+
+/// Storage kept in memory, lost when dropped
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    /// Values stored, by namespace and key
+    data: Mutex<HashMap<String, BTreeMap<String, Vec<u8>>>>
+}
+
+impl MemoryStorage {
+    /// Create a new empty storage
+    pub fn new() -> MemoryStorage {
+        Default::default()
+    }
+}
+
+/// Obtain the error returned when a lock has been poisoned
+fn poisoned() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "storage lock poisoned")
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, namespace: &str, key: &str) -> io::Result<Option<Vec<u8>>> {
+        let data = self.data.lock().map_err(|_| poisoned())?;
+
+        Ok(data.get(namespace).and_then(|values| values.get(key)).cloned())
+    }
+
+    fn put(&self, namespace: &str, key: &str, value: &[u8]) -> io::Result<()> {
+        let mut data = self.data.lock().map_err(|_| poisoned())?;
+
+        data.entry(namespace.to_string())
+            .or_insert_with(BTreeMap::new)
+            .insert(key.to_string(), value.to_vec());
+
+        Ok(())
+    }
+
+    fn list(&self, namespace: &str) -> io::Result<Vec<String>> {
+        let data = self.data.lock().map_err(|_| poisoned())?;
+
+        Ok(data.get(namespace)
+            .map(|values| values.keys().cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Longest file name used for a key, in bytes
+///
+/// Longer escaped keys are truncated and suffixed with their hash, and the
+/// full key is stored in the first line of the file.
+const MAX_NAME_LEN: usize = 200;
+
+/// Counter used to give temporary files unique names within the process
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Storage backed by files in a directory
+///
+/// Each namespace is a subdirectory of the root, and each key a file inside
+/// it. Characters that are not safe in file names are escaped, and long keys
+/// are shortened with a hash.
+#[derive(Debug)]
+pub struct FileStorage {
+    /// Directory containing the namespaces
+    root: PathBuf
+}
+
+impl FileStorage {
+    /// Create a new storage in the given directory
+    ///
+    /// The directory is created when storing the first value.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - Directory to store the data in
+    pub fn new<P: Into<PathBuf>>(root: P) -> FileStorage {
+        FileStorage {
+            root: root.into()
+        }
+    }
+
+    /// Obtain the directory of a namespace
+    fn dir(&self, namespace: &str) -> PathBuf {
+        self.root.join(file_name(namespace))
+    }
+}
+
+/// Obtain the file name for a key
+///
+/// Names longer than `MAX_NAME_LEN` are shortened, and their files start
+/// with the full escaped key.
+fn file_name(key: &str) -> String {
+    let escaped = escape(key);
+
+    if escaped.len() <= MAX_NAME_LEN {
+        return escaped;
+    }
+
+    // Escaped names are ASCII, so any byte is a character boundary
+    format!("{}~{:016x}", &escaped[..MAX_NAME_LEN - 17], hash(key))
+}
+
+/// Check whether a file name was shortened by `file_name()`
+fn is_hashed(name: &str) -> bool {
+    name.contains('~')
+}
+
+/// Hash a key with 64-bit FNV-1a, which is stable between runs
+fn hash(key: &str) -> u64 {
+    key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Split the contents of a shortened file into its key and value
+fn split_key(contents: &[u8]) -> Option<(String, &[u8])> {
+    let end = contents.iter().position(|&byte| byte == b'\n')?;
+    let key = unescape(::std::str::from_utf8(&contents[..end]).ok()?)?;
+
+    Some((key, &contents[end + 1..]))
+}
+
+/// Escape a name so that it can be used as a file name
+fn escape(name: &str) -> String {
+    let mut result = String::new();
+
+    for byte in name.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => {
+                result.push(byte as char)
+            },
+            _ => result.push_str(&format!("%{:02X}", byte))
+        }
+    }
+
+    result
+}
+
+/// Restore a name escaped with `escape()`
+fn unescape(name: &str) -> Option<String> {
+    let mut bytes = Vec::new();
+    let mut chars = name.bytes();
+
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let high = (chars.next()? as char).to_digit(16)?;
+            let low = (chars.next()? as char).to_digit(16)?;
+            bytes.push((high * 16 + low) as u8);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+impl Storage for FileStorage {
+    fn get(&self, namespace: &str, key: &str) -> io::Result<Option<Vec<u8>>> {
+        let name = file_name(key);
+        let mut file = match fs::File::open(self.dir(namespace).join(&name)) {
+            Ok(file) => file,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(None)
+            },
+            Err(e) => return Err(e)
+        };
+
+        let mut value = Vec::new();
+        file.read_to_end(&mut value)?;
+
+        if !is_hashed(&name) {
+            return Ok(Some(value));
+        }
+
+        // Another key with the same shortened name may be stored instead
+        Ok(match split_key(&value) {
+            Some((stored, value)) if stored == key => Some(value.to_vec()),
+            _ => None
+        })
+    }
+
+    fn put(&self, namespace: &str, key: &str, value: &[u8]) -> io::Result<()> {
+        let dir = self.dir(namespace);
+        let name = file_name(key);
+        fs::create_dir_all(&dir)?;
+
+        // Write to a temporary file first so that readers never see
+        // partially written values. Names starting with a dot are never
+        // produced by `escape()`, so temporary files are not listed.
+        let temp = dir.join(format!(
+            ".{}.{}.tmp",
+            process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+
+        let written = fs::File::create(&temp).and_then(|mut file| {
+            if is_hashed(&name) {
+                file.write_all(escape(key).as_bytes())?;
+                file.write_all(b"\n")?;
+            }
+
+            file.write_all(value)
+        }).and_then(|_| fs::rename(&temp, dir.join(name)));
+
+        if written.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+
+        written
+    }
+
+    fn list(&self, namespace: &str) -> io::Result<Vec<String>> {
+        let dir = self.dir(namespace);
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(Vec::new())
+            },
+            Err(e) => return Err(e)
+        };
+
+        let mut keys = Vec::new();
+
+        for entry in entries {
+            let name = entry?.file_name();
+            let name = match name.to_str() {
+                Some(name) if !name.starts_with('.') => name,
+                _ => continue
+            };
+
+            if !is_hashed(name) {
+                keys.extend(unescape(name));
+                continue;
+            }
+
+            let mut contents = Vec::new();
+            fs::File::open(dir.join(name))?.read_to_end(&mut contents)?;
+
+            if let Some((key, _)) = split_key(&contents) {
+                keys.push(key);
+            }
+        }
+
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use storage::*;
+
+    fn check_storage(storage: &dyn Storage) {
+        assert_eq!(storage.get("prices", "19721").unwrap(), None);
+        assert!(storage.list("prices").unwrap().is_empty());
+
+        storage.put("prices", "19721", b"120").unwrap();
+        storage.put("prices", "19721", b"125").unwrap();
+        storage.put("prices", "a/b c", b"1").unwrap();
+        storage.put("other", "19721", b"0").unwrap();
+
+        assert_eq!(
+            storage.get("prices", "19721").unwrap(),
+            Some(b"125".to_vec())
+        );
+        assert_eq!(storage.list("prices").unwrap(), vec!["19721", "a/b c"]);
+    }
+
+    #[test]
+    fn memory_storage() {
+        check_storage(&MemoryStorage::new());
+    }
+
+    #[test]
+    fn file_storage() {
+        let root = env::temp_dir().join("tyria-storage-test");
+        let _ = fs::remove_dir_all(&root);
+
+        check_storage(&FileStorage::new(root.clone()));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn long_keys() {
+        let root = env::temp_dir().join("tyria-storage-long-test");
+        let _ = fs::remove_dir_all(&root);
+        let storage = FileStorage::new(root.clone());
+
+        let first = format!("en:/v2/items?ids={}", vec!["1"; 200].join(","));
+        let second = format!("{},2", first);
+
+        storage.put("cache", &first, b"first").unwrap();
+        storage.put("cache", &second, b"second").unwrap();
+        fs::File::create(root.join("cache").join(".1.0.tmp")).unwrap();
+
+        assert_eq!(
+            storage.get("cache", &first).unwrap(),
+            Some(b"first".to_vec())
+        );
+        assert_eq!(
+            storage.get("cache", &second).unwrap(),
+            Some(b"second".to_vec())
+        );
+        assert_eq!(storage.list("cache").unwrap(), vec![first, second]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,744 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Non-blocking requests
+/// Endpoint calls run in the background and are awaited as futures, so
+/// many of them can be issued concurrently from async code
+///
+/// # Threading model
+///
+/// Every `AsyncClient` owns a pool with a fixed number of worker threads
+/// (`DEFAULT_WORKERS` unless given to `with_workers()`), shared by its
+/// clones. Calls passed to `spawn()` are queued and performed by the first
+/// idle worker, so no more than that many requests run at the same time.
+/// The workers stop once every clone of the client is dropped and the
+/// queued calls are done.
+///
+/// Combined tasks, such as those returned by `join()` and
+/// `get_localized()`, do not take up a worker while waiting: they finish
+/// in the worker that completes their last task. For the same reason, a
+/// call should not wait for another task of the same client, as all the
+/// workers could end up waiting for calls that are still queued; combine
+/// the tasks with `join()` instead.
+
+use std::cmp;
+use std::future::Future;
+use std::io;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use std::collections::HashMap;
+use serde::de::DeserializeOwned;
+
+use api_v2::endpoint::BulkEndpoint;
+use bundle::panic_error;
+use client::{APIClient, Language};
+use common::APIError;
+
+
+/// Number of worker threads used by `AsyncClient::new()`
+pub const DEFAULT_WORKERS: usize = 8;
+
+/// Work queued in the pool
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Callback run once a task finishes
+pub type Listener = Box<dyn FnOnce() + Send>;
+
+/// Fixed set of threads performing the calls of an `AsyncClient`
+struct WorkerPool {
+    /// Queue of the workers, closed when the pool is dropped
+    sender: Mutex<Sender<Job>>,
+    /// Number of workers started
+    workers: usize
+}
+
+impl WorkerPool {
+    /// Start a pool with the given number of workers
+    ///
+    /// If a worker cannot be started, the pool keeps the workers started
+    /// so far. An error is only returned if no worker could be started.
+    fn new(workers: usize) -> io::Result<WorkerPool> {
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let mut started = 0;
+
+        for index in 0..workers {
+            let receiver = receiver.clone();
+            let result = thread::Builder::new()
+                .name(format!("tyria-worker-{}", index))
+                .spawn(move || WorkerPool::work(&receiver));
+
+            match result {
+                Ok(_) => started += 1,
+                Err(e) if started == 0 => return Err(e),
+                Err(_) => break
+            }
+        }
+
+        Ok(WorkerPool {
+            sender: Mutex::new(sender),
+            workers: started
+        })
+    }
+
+    /// Perform queued jobs until the pool is dropped
+    fn work(receiver: &Mutex<Receiver<Job>>) {
+        loop {
+            let job = match receiver.lock() {
+                Ok(receiver) => receiver.recv(),
+                Err(_) => return
+            };
+
+            match job {
+                // Panics are already reported by the tasks, this only keeps
+                // the worker alive if a listener panics
+                Ok(job) => {
+                    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                },
+                Err(_) => return
+            }
+        }
+    }
+
+    /// Queue a job for the next idle worker
+    fn execute(&self, job: Job) -> Result<(), APIError> {
+        self.sender.lock()
+            .map_err(|_| APIError::new("worker pool is poisoned"))?
+            .send(job)
+            .map_err(|_| APIError::new("worker pool is stopped"))
+    }
+}
+
+/// State shared between a task and the worker performing it
+struct TaskState<T> {
+    /// Result of the call, once finished
+    result: Option<Result<T, APIError>>,
+    /// Waker of the last poll, to notify when the result is available
+    waker: Option<Waker>,
+    /// Callbacks to run when the result is available
+    listeners: Vec<Listener>
+}
+
+/// State of a task, with a condition to wait for it
+type Shared<T> = Arc<(Mutex<TaskState<T>>, Condvar)>;
+
+/// Store the result of a task and notify everyone waiting for it
+fn finish<T>(shared: &Shared<T>, result: Result<T, APIError>) {
+    let &(ref state, ref condvar) = &**shared;
+    let (waker, listeners) = match state.lock() {
+        Ok(mut state) => {
+            state.result = Some(result);
+            (state.waker.take(), mem::take(&mut state.listeners))
+        },
+        Err(_) => (None, vec![])
+    };
+
+    condvar.notify_all();
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+    for listener in listeners {
+        listener();
+    }
+}
+
+/// Endpoint call running in the background
+///
+/// The task is a `Future` that can be awaited from any executor, or waited
+/// for synchronously with `wait()`. Panics in the call are reported as an
+/// `APIError`.
+pub struct Task<T> {
+    /// State shared with the worker
+    shared: Shared<T>
+}
+
+impl<T: Send + 'static> Task<T> {
+    /// Create a task whose result is stored with `finish()`
+    fn pending() -> Task<T> {
+        Task {
+            shared: Arc::new((
+                Mutex::new(TaskState {
+                    result: None,
+                    waker: None,
+                    listeners: vec![]
+                }),
+                Condvar::new()
+            ))
+        }
+    }
+
+    /// Queue a call in the worker pool
+    fn spawn<F>(pool: &WorkerPool, client: APIClient, call: F) -> Task<T>
+        where F: FnOnce(&APIClient) -> Result<T, APIError> + Send + 'static {
+
+        let task = Task::pending();
+        let shared = task.shared.clone();
+
+        let queued = pool.execute(Box::new(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                call(&client)
+            })).unwrap_or_else(|payload| Err(panic_error(payload)));
+
+            finish(&shared, result);
+        }));
+
+        if let Err(e) = queued {
+            finish(&task.shared, Err(e));
+        }
+
+        task
+    }
+
+    /// Obtain a task finishing with `combine(tasks)` once all the tasks in
+    /// the set finish
+    fn combine<S, F>(tasks: S, combine: F) -> Task<T>
+        where S: TaskSet,
+              F: FnOnce(S) -> Result<T, APIError> + Send + 'static {
+
+        let task = Task::pending();
+        let shared = task.shared.clone();
+        let tasks_slot = Arc::new(Mutex::new(None));
+        let slot = tasks_slot.clone();
+
+        // Starts at one so the tasks finishing during the registration
+        // cannot complete the countdown before the set is stored
+        let remaining = Arc::new(AtomicUsize::new(1));
+        let done: Listener = Box::new(move || {
+            let result = match slot.lock().ok().and_then(|mut s| s.take()) {
+                Some(tasks) => combine(tasks),
+                None => Err(APIError::new("task state is poisoned"))
+            };
+            finish(&shared, result);
+        });
+        let done = Arc::new(Mutex::new(Some(done)));
+
+        let countdown = {
+            let remaining = remaining.clone();
+            move || {
+                if remaining.fetch_sub(1, Ordering::SeqCst) == 1 {
+                    let done = done.lock().ok().and_then(|mut d| d.take());
+                    if let Some(done) = done {
+                        done();
+                    }
+                }
+            }
+        };
+        let countdown = Arc::new(countdown);
+
+        tasks.notify(&|| {
+            remaining.fetch_add(1, Ordering::SeqCst);
+            let countdown = countdown.clone();
+            Box::new(move || countdown())
+        });
+
+        if let Ok(mut slot) = tasks_slot.lock() {
+            *slot = Some(tasks);
+        }
+        countdown();
+
+        task
+    }
+}
+
+impl<T> Task<T> {
+    /// Block the current thread until the call finishes
+    pub fn wait(self) -> Result<T, APIError> {
+        let &(ref state, ref condvar) = &*self.shared;
+        let mut state = state.lock()
+            .map_err(|_| APIError::new("task state is poisoned"))?;
+
+        loop {
+            if let Some(result) = state.result.take() {
+                return result;
+            }
+
+            state = condvar.wait(state)
+                .map_err(|_| APIError::new("task state is poisoned"))?;
+        }
+    }
+
+    /// Run a callback once the call finishes
+    ///
+    /// The callback runs right away if the call already finished, and
+    /// otherwise in the worker that performs it.
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - Callback to run
+    pub fn on_finish(&self, listener: Listener) {
+        let listener = match self.shared.0.lock() {
+            Ok(ref mut state) if state.result.is_none() => {
+                state.listeners.push(listener);
+                None
+            },
+            _ => Some(listener)
+        };
+
+        if let Some(listener) = listener {
+            listener();
+        }
+    }
+}
+
+impl<T> Future for Task<T> {
+    type Output = Result<T, APIError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut state = match self.shared.0.lock() {
+            Ok(state) => state,
+            Err(_) => {
+                return Poll::Ready(Err(APIError::new(
+                    "task state is poisoned"
+                )));
+            }
+        };
+
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Set of tasks that can be awaited together
+///
+/// Implemented for tuples of up to 8 tasks, which may return different
+/// types, and for vectors of tasks of the same type.
+pub trait TaskSet: Send + 'static {
+    /// Tuple with the result of each task, in the same order
+    type Output: Send + 'static;
+
+    /// Block the current thread until all the tasks finish
+    ///
+    /// Fails with the first error found, in the order of the tasks.
+    fn wait_all(self) -> Result<Self::Output, APIError>;
+
+    /// Register a callback obtained from `listener` on every task
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - Function returning the callback of each task
+    fn notify(&self, listener: &dyn Fn() -> Listener);
+}
+
+macro_rules! impl_task_set {
+    ($($result:ident $var:ident),+) => {
+        impl<$($result),+> TaskSet for ($(Task<$result>,)+)
+            where $($result: Send + 'static),+ {
+            type Output = ($($result,)+);
+
+            fn wait_all(self) -> Result<Self::Output, APIError> {
+                let ($($var,)+) = self;
+                Ok(($($var.wait()?,)+))
+            }
+
+            fn notify(&self, listener: &dyn Fn() -> Listener) {
+                let &($(ref $var,)+) = self;
+                $($var.on_finish(listener());)+
+            }
+        }
+    }
+}
+
+impl_task_set!(A a);
+impl_task_set!(A a, B b);
+impl_task_set!(A a, B b, C c);
+impl_task_set!(A a, B b, C c, D d);
+impl_task_set!(A a, B b, C c, D d, E e);
+impl_task_set!(A a, B b, C c, D d, E e, F f);
+impl_task_set!(A a, B b, C c, D d, E e, F f, G g);
+impl_task_set!(A a, B b, C c, D d, E e, F f, G g, H h);
+
+impl<T: Send + 'static> TaskSet for Vec<Task<T>> {
+    type Output = Vec<T>;
+
+    fn wait_all(self) -> Result<Self::Output, APIError> {
+        self.into_iter().map(Task::wait).collect()
+    }
+
+    fn notify(&self, listener: &dyn Fn() -> Listener) {
+        for task in self {
+            task.on_finish(listener());
+        }
+    }
+}
+
+/// Perform several endpoint calls concurrently and combine their results
+///
+/// Every call is queued right away through `AsyncClient::spawn()`, and the
+/// resulting `Task` finishes with a tuple of the results once all of them
+/// succeed, or with the first error found. Up to 8 calls are supported.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate tyria;
+/// use tyria::client::APIClient;
+/// use tyria::tasks::AsyncClient;
+/// use tyria::api_v2::account::{
+///     get_account,
+///     get_account_bank,
+///     get_account_wallet
+/// };
+///
+/// # fn main() {
+/// let client = AsyncClient::new(APIClient::new("en", None)).unwrap();
+/// let task = try_join_endpoints!(
+///     client,
+///     get_account,
+///     get_account_wallet,
+///     get_account_bank
+/// );
+///
+/// // In async code: `task.await`
+/// let (account, wallet, bank) = task.wait().unwrap();
+/// # }
+/// ```
+#[macro_export]
+macro_rules! try_join_endpoints {
+    ($client:expr, $($call:expr),+ $(,)*) => {{
+        let client: &$crate::tasks::AsyncClient = &$client;
+        client.join(($(client.spawn($call),)+))
+    }}
+}
+
+/// Client performing endpoint calls without blocking the caller
+///
+/// Every function in `api_v2` (and the helpers) can be used through
+/// `spawn()`, which queues it in the worker pool of the client with a clone
+/// of the client and returns a `Task` to await. Clones of the
+/// `AsyncClient` share the pool.
+///
+/// # Example
+///
+/// ```no_run
+/// use tyria::client::APIClient;
+/// use tyria::tasks::AsyncClient;
+/// use tyria::api_v2::account::{get_account, get_account_wallet};
+///
+/// let client = AsyncClient::new(APIClient::new("en", None)).unwrap();
+/// let account = client.spawn(get_account);
+/// let wallet = client.spawn(get_account_wallet);
+///
+/// // In async code: `account.await`
+/// let account = account.wait();
+/// let wallet = wallet.wait();
+/// ```
+#[derive(Clone)]
+pub struct AsyncClient {
+    /// Client used to perform the requests
+    client: APIClient,
+    /// Workers performing the calls
+    pool: Arc<WorkerPool>
+}
+
+impl AsyncClient {
+    /// Create a new asynchronous client with `DEFAULT_WORKERS` workers
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    pub fn new(client: APIClient) -> io::Result<AsyncClient> {
+        AsyncClient::with_workers(client, DEFAULT_WORKERS)
+    }
+
+    /// Create a new asynchronous client with the given number of workers
+    ///
+    /// At most `workers` calls are performed at the same time; the rest
+    /// wait in a queue. At least one worker is always started. If the
+    /// system cannot start all the workers, the client uses those that
+    /// could be started (see `workers()`). An error is returned if none
+    /// could be started.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use when performing API requests
+    /// * `workers` - Number of worker threads
+    pub fn with_workers(
+        client: APIClient,
+        workers: usize
+    ) -> io::Result<AsyncClient> {
+        Ok(AsyncClient {
+            client: client,
+            pool: Arc::new(WorkerPool::new(cmp::max(workers, 1))?)
+        })
+    }
+
+    /// Obtain the number of worker threads performing the calls
+    pub fn workers(&self) -> usize {
+        self.pool.workers
+    }
+
+    /// Obtain the underlying client
+    pub fn client(&self) -> &APIClient {
+        &self.client
+    }
+
+    /// Perform an endpoint call in the background
+    ///
+    /// # Arguments
+    ///
+    /// * `call` - Endpoint function, or closure taking the client
+    pub fn spawn<F, T>(&self, call: F) -> Task<T>
+        where F: FnOnce(&APIClient) -> Result<T, APIError> + Send + 'static,
+              T: Send + 'static {
+
+        Task::spawn(&self.pool, self.client.clone(), call)
+    }
+
+    /// Combine several tasks into one finishing with all their results
+    ///
+    /// Fails with the first error found, in the order of the tasks. The
+    /// tasks keep running concurrently; see also `try_join_endpoints!`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - Tuple or vector of tasks to combine
+    pub fn join<S: TaskSet>(&self, tasks: S) -> Task<S::Output> {
+        Task::combine(tasks, S::wait_all)
+    }
+
+    /// Perform a request to an endpoint in the background
+    ///
+    /// Same as `APIClient::get()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Path of the endpoint, including the query string
+    /// * `authenticated` - Whether to send the API token
+    pub fn get<T>(&self, endpoint: &str, authenticated: bool) -> Task<T>
+        where T: DeserializeOwned + Send + 'static {
+
+        let endpoint = endpoint.to_string();
+        self.spawn(move |client| client.get(&endpoint, authenticated))
+    }
+
+    /// Obtain the entry of a bulk endpoint in every supported language
+    ///
+    /// Same as `APIClient::get_localized()`, with one concurrent request
+    /// per language.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID to fetch from the server
+    pub fn get_localized<T>(&self, id: T::Id) -> Task<HashMap<Language, T>>
+        where T: BulkEndpoint + Send + 'static,
+              T::Id: Clone + Send + 'static {
+
+        let tasks: Vec<Task<T>> = Language::ALL.iter()
+            .map(|&language| {
+                let id = id.clone();
+                let client = self.client.localized(language);
+                Task::spawn(&self.pool, client, move |client| {
+                    client.get_one::<T>(id)
+                })
+            })
+            .collect();
+
+        Task::combine(tasks, |tasks| {
+            let entries = tasks.wait_all()?;
+            Ok(Language::ALL.iter().cloned().zip(entries).collect())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "static")]
+    use std::collections::HashSet;
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+    use std::time::Duration;
+    #[cfg(feature = "static")]
+    use api_v2::types::Outfit;
+    use client::APIClient;
+    #[cfg(feature = "static")]
+    use client::Language;
+    use common::APIError;
+    use tasks::*;
+    #[cfg(feature = "static")]
+    use transport::{MockTransport, Response};
+
+    /// Waker unparking the thread that polls the future
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// Minimal executor running a single future to completion
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park()
+            }
+        }
+    }
+
+    #[test]
+    fn concurrent_tasks() {
+        let client = AsyncClient::new(APIClient::new("en", None)).unwrap();
+        let first = client.spawn(|_| Ok(1));
+        let second = client.spawn(|_| -> Result<i32, APIError> {
+            Err(APIError::new("endpoint is down"))
+        });
+
+        assert_eq!(block_on(first).unwrap(), 1);
+        assert_eq!(
+            second.wait().unwrap_err().description(),
+            "endpoint is down"
+        );
+    }
+
+    #[test]
+    fn joined_tasks() {
+        let client = AsyncClient::new(APIClient::new("en", None)).unwrap();
+        let joined = try_join_endpoints!(
+            client,
+            |_: &APIClient| Ok(1),
+            |_: &APIClient| Ok("wallet".to_string()),
+        );
+        assert_eq!(block_on(joined).unwrap(), (1, "wallet".to_string()));
+
+        let failed = client.join((
+            client.spawn(|_| Ok(1)),
+            client.spawn(|_| -> Result<i32, APIError> {
+                Err(APIError::new("bank is down"))
+            })
+        ));
+        assert_eq!(failed.wait().unwrap_err().description(), "bank is down");
+    }
+
+    #[test]
+    fn bounded_workers() {
+        let client = AsyncClient::with_workers(APIClient::new("en", None), 2)
+            .unwrap();
+        assert_eq!(client.workers(), 2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<Task<()>> = (0..6)
+            .map(|_| {
+                let running = running.clone();
+                let peak = peak.clone();
+                client.spawn(move |_| {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(())
+                })
+            })
+            .collect();
+
+        assert_eq!(client.join(tasks).wait().unwrap().len(), 6);
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn join_single_worker() {
+        let client = AsyncClient::with_workers(APIClient::new("en", None), 1)
+            .unwrap();
+        let joined = client.join((
+            client.spawn(|_| Ok(1)),
+            client.join((client.spawn(|_| Ok(2)), client.spawn(|_| Ok(3))))
+        ));
+
+        assert_eq!(block_on(joined).unwrap(), (1, (2, 3)));
+    }
+
+    #[test]
+    fn panicked_task() {
+        let client = AsyncClient::new(APIClient::new("en", None)).unwrap();
+        let task = client.spawn(|_| -> Result<i32, APIError> {
+            panic!("connection reset")
+        });
+
+        assert_eq!(
+            block_on(task).unwrap_err().description(),
+            "request failed: connection reset"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "static")]
+    fn localized_entries() {
+        let transport = Arc::new(MockTransport::new());
+        for language in Language::ALL.iter() {
+            transport.respond(
+                &format!("/v2/outfits?id=1&lang={}", language),
+                Response::json(r#"{
+                    "id": 1,
+                    "name": "Outfit",
+                    "icon": "",
+                    "unlock_items": []
+                }"#)
+            );
+        }
+
+        let mut client = APIClient::builder()
+            .rate_limiter(None)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+        client.set_lang_param(true);
+
+        let client = AsyncClient::with_workers(client, 1).unwrap();
+        let task = client.get_localized::<Outfit>(1);
+        let outfits = block_on(task).unwrap();
+        assert_eq!(outfits.len(), Language::ALL.len());
+
+        let unique: HashSet<Outfit> = outfits.values().cloned().collect();
+        assert_eq!(unique.len(), 1);
+        assert_eq!(transport.requests().len(), Language::ALL.len());
+    }
+
+    #[test]
+    fn missing_token() {
+        let client = AsyncClient::new(APIClient::new("en", None)).unwrap();
+        let task = client.get::<Vec<i32>>("/v2/account/minis", true);
+        assert_eq!(
+            task.wait().unwrap_err().description(),
+            "token is not configured"
+        );
+    }
+}
@@ -0,0 +1,498 @@
+// MIT License
+//
+// Copyright (c) 2017 Rafael Medina García <rafamedgar@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Transport of HTTP requests
+/// The client performs its requests through the `Transport` trait, so that
+/// the HTTP client can be replaced (e.g. with canned responses in tests)
+
+use std::collections::HashMap;
+use std::io::{self, Cursor, Read};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest;
+use reqwest::{Proxy, StatusCode};
+use reqwest::blocking::Client;
+use reqwest::header::HeaderMap;
+
+use common::{APIError, APIErrorKind};
+
+
+/// Response to an HTTP request, with its whole body already received
+#[derive(Debug, Clone)]
+pub struct Response {
+    /// HTTP status code
+    status: StatusCode,
+    /// Headers of the response
+    headers: HeaderMap,
+    /// Body of the response
+    body: Cursor<Vec<u8>>
+}
+
+impl Response {
+    /// Create a new response
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - HTTP status code
+    /// * `headers` - Headers of the response
+    /// * `body` - Body of the response
+    pub fn new(
+        status: StatusCode,
+        headers: HeaderMap,
+        body: Vec<u8>
+    ) -> Response {
+        Response {
+            status: status,
+            headers: headers,
+            body: Cursor::new(body)
+        }
+    }
+
+    /// Create a successful response with a JSON body
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - JSON body of the response
+    pub fn json(body: &str) -> Response {
+        Response::new(
+            StatusCode::OK,
+            HeaderMap::new(),
+            body.as_bytes().to_vec()
+        )
+    }
+
+    /// Obtain the HTTP status code
+    pub fn status(&self) -> &StatusCode {
+        &self.status
+    }
+
+    /// Obtain the headers of the response
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+}
+
+impl Read for Response {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.body.read(buf)
+    }
+}
+
+/// Means of performing GET requests
+pub trait Transport: Send + Sync {
+    /// Perform a GET request
+    ///
+    /// Errors are only returned when no response is received (e.g. the
+    /// connection failed); error status codes are regular responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Full URL of the request
+    /// * `headers` - Headers to send
+    fn get(&self, url: &str, headers: HeaderMap) -> Result<Response, APIError>;
+
+    /// Perform a GET request, giving up when the server stops responding
+    ///
+    /// Requests that time out fail with an error of kind
+    /// `APIErrorKind::Timeout`. By default the timeout is ignored and the
+    /// request is performed with `get()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Full URL of the request
+    /// * `headers` - Headers to send
+    /// * `timeout` - Maximum time to wait for each read and write
+    fn get_with_timeout(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+        timeout: Duration
+    ) -> Result<Response, APIError> {
+        let _ = timeout;
+        self.get(url, headers)
+    }
+}
+
+/// Transport performing actual HTTP requests
+///
+/// Requests with a timeout are performed by separate HTTP clients created
+/// by the transport, one per timeout, since the timeout of a
+/// `reqwest::blocking::Client` is shared by all its clones.
+#[derive(Debug)]
+pub struct HttpTransport {
+    /// HTTP client, created on the first request if not given
+    client: Mutex<Option<Client>>,
+    /// Whether the HTTP clients created by the transport accept gzip
+    gzip: bool,
+    /// Proxies of the HTTP clients created by the transport
+    proxies: Vec<Proxy>,
+    /// HTTP clients with a timeout, by timeout
+    timed: Mutex<HashMap<Duration, Client>>
+}
+
+impl HttpTransport {
+    /// Create a new transport using an existing HTTP client
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - HTTP client to perform the requests with
+    pub fn new(client: Client) -> HttpTransport {
+        HttpTransport {
+            client: Mutex::new(Some(client)),
+            gzip: true,
+            proxies: Vec::new(),
+            timed: Mutex::new(HashMap::new())
+        }
+    }
+
+    /// Create a new transport whose HTTP client is created on first use
+    ///
+    /// Failing to create the HTTP client (e.g. when the TLS backend cannot
+    /// be initialised) is reported as an error of the first request instead
+    /// of a panic.
+    pub fn lazy() -> HttpTransport {
+        HttpTransport {
+            client: Mutex::new(None),
+            gzip: true,
+            proxies: Vec::new(),
+            timed: Mutex::new(HashMap::new())
+        }
+    }
+
+    /// Enable or disable gzip in the HTTP clients created by the transport
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to accept gzip compressed responses
+    pub fn gzip(mut self, enabled: bool) -> HttpTransport {
+        self.gzip = enabled;
+        self
+    }
+
+    /// Route the requests of the HTTP clients created by the transport
+    /// through a proxy
+    ///
+    /// Proxies are tried in the order they are added. Without any, the
+    /// proxies of the environment (e.g. `HTTPS_PROXY`) are used instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `proxy` - Proxy to add
+    pub fn proxy(mut self, proxy: Proxy) -> HttpTransport {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Create a new HTTP client with the settings of the transport
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Timeout of each read and write, if any
+    fn create_client(
+        &self,
+        timeout: Option<Duration>
+    ) -> Result<Client, APIError> {
+        let mut builder = Client::builder().gzip(self.gzip);
+        for proxy in &self.proxies {
+            builder = builder.proxy(proxy.clone());
+        }
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        builder.build().map_err(|e| {
+            APIError::new(
+                format!("failed to create HTTP client: {}", e).as_str()
+            )
+        })
+    }
+
+    /// Obtain the HTTP client, creating it if needed
+    fn client(&self) -> Result<Client, APIError> {
+        let mut client = self.client.lock()
+            .map_err(|_| APIError::new("HTTP client lock is poisoned"))?;
+
+        match *client {
+            Some(ref client) => Ok(client.clone()),
+            None => {
+                let created = self.create_client(None)?;
+                *client = Some(created.clone());
+                Ok(created)
+            }
+        }
+    }
+
+    /// Obtain the HTTP client with the given timeout, creating it if needed
+    fn timed_client(
+        &self,
+        timeout: Duration
+    ) -> Result<Client, APIError> {
+        let mut clients = self.timed.lock()
+            .map_err(|_| APIError::new("HTTP client lock is poisoned"))?;
+
+        if let Some(client) = clients.get(&timeout) {
+            return Ok(client.clone());
+        }
+
+        let created = self.create_client(Some(timeout))?;
+        clients.insert(timeout, created.clone());
+        Ok(created)
+    }
+
+    /// Perform a GET request with an HTTP client
+    fn send(
+        client: &Client,
+        url: &str,
+        headers: HeaderMap
+    ) -> Result<Response, APIError> {
+        let mut response = client.get(url)
+            .headers(headers)
+            .send()
+            .map_err(|e| {
+                let timed_out = e.is_timeout();
+                transport_error("request failed", &e.to_string(), timed_out)
+            })?;
+
+        let mut body = Vec::new();
+        response.read_to_end(&mut body).map_err(|e| {
+            transport_error(
+                "failed to read response",
+                &e.to_string(),
+                is_timeout(&e)
+            )
+        })?;
+
+        Ok(Response::new(response.status(), response.headers().clone(), body))
+    }
+}
+
+impl Transport for HttpTransport {
+    fn get(&self, url: &str, headers: HeaderMap) -> Result<Response, APIError> {
+        HttpTransport::send(&self.client()?, url, headers)
+    }
+
+    fn get_with_timeout(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+        timeout: Duration
+    ) -> Result<Response, APIError> {
+        HttpTransport::send(&self.timed_client(timeout)?, url, headers)
+    }
+}
+
+/// Obtain the error to return when a request fails
+///
+/// Errors caused by the timeout of the HTTP client are of kind
+/// `APIErrorKind::Timeout`.
+///
+/// # Arguments
+///
+/// * `context` - What failed
+/// * `message` - Message of the original error
+/// * `timed_out` - Whether the original error is a timeout
+fn transport_error(context: &str, message: &str, timed_out: bool) -> APIError {
+    if timed_out {
+        APIError::with_kind(
+            APIErrorKind::Timeout,
+            format!("request timed out: {}", message).as_str()
+        )
+    } else {
+        APIError::new(format!("{}: {}", context, message).as_str())
+    }
+}
+
+/// Check whether an I/O error is caused by the timeout of the HTTP client
+///
+/// Timeouts while reading the body are reported by `reqwest` as I/O errors
+/// wrapping its own error.
+fn is_timeout(error: &io::Error) -> bool {
+    match error.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => true,
+        _ => error.get_ref()
+            .and_then(|inner| inner.downcast_ref::<reqwest::Error>())
+            .map_or(false, |inner| inner.is_timeout())
+    }
+}
+
+/// Transport serving canned responses, meant for tests
+///
+/// Responses are registered by endpoint, i.e. the path of the URL along with
+/// its query string (e.g. `/v2/items?id=12345`). Requests to endpoints
+/// without a response obtain a `404 Not Found`.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use tyria::client::APIClient;
+/// use tyria::transport::{MockTransport, Response};
+///
+/// let transport = Arc::new(MockTransport::new());
+/// transport.respond("/v2/build", Response::json(r#"{"id": 115267}"#));
+///
+/// let mut client = APIClient::new("en", None);
+/// client.set_transport(transport.clone());
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    /// Responses to serve, by endpoint
+    responses: Mutex<HashMap<String, Response>>,
+    /// Endpoints requested, in order
+    requests: Mutex<Vec<String>>
+}
+
+impl MockTransport {
+    /// Create a new transport without responses
+    pub fn new() -> MockTransport {
+        Default::default()
+    }
+
+    /// Register the response to serve for an endpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Path of the endpoint, including the query string
+    /// * `response` - Response to serve
+    pub fn respond(&self, endpoint: &str, response: Response) {
+        if let Ok(mut responses) = self.responses.lock() {
+            responses.insert(endpoint.to_string(), response);
+        }
+    }
+
+    /// Obtain the endpoints requested so far, in order
+    pub fn requests(&self) -> Vec<String> {
+        match self.requests.lock() {
+            Ok(requests) => requests.clone(),
+            Err(_) => Vec::new()
+        }
+    }
+}
+
+impl Transport for MockTransport {
+    fn get(
+        &self,
+        url: &str,
+        _headers: HeaderMap
+    ) -> Result<Response, APIError> {
+        let endpoint = endpoint_of(url);
+
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(endpoint.to_string());
+        }
+
+        let response = self.responses.lock()
+            .map_err(|_| APIError::new("transport lock poisoned"))?
+            .get(endpoint)
+            .cloned();
+
+        Ok(response.unwrap_or_else(|| {
+            Response::new(
+                StatusCode::NOT_FOUND,
+                HeaderMap::new(),
+                br#"{"text": "no such endpoint"}"#.to_vec()
+            )
+        }))
+    }
+}
+
+/// Obtain the path and query string of a URL
+///
+/// # Arguments
+///
+/// * `url` - Full URL
+pub(crate) fn endpoint_of(url: &str) -> &str {
+    match url.find("://") {
+        Some(scheme) => {
+            let rest = &url[scheme + 3..];
+            match rest.find('/') {
+                Some(path) => &rest[path..],
+                None => "/"
+            }
+        },
+        None => url
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::time::Duration;
+    use reqwest::StatusCode;
+    use reqwest::header::HeaderMap;
+    use transport::*;
+
+    #[test]
+    fn lazy_client() {
+        let transport = HttpTransport::lazy();
+        assert!(transport.client.lock().unwrap().is_none());
+
+        assert!(transport.client().is_ok());
+        assert!(transport.client.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn timed_clients() {
+        let transport = HttpTransport::lazy().gzip(false);
+        assert!(!transport.gzip);
+
+        let timeout = Duration::from_secs(5);
+        assert!(transport.timed_client(timeout).is_ok());
+        assert!(transport.timed_client(timeout).is_ok());
+        assert!(transport.timed_client(Duration::from_secs(1)).is_ok());
+        assert_eq!(transport.timed.lock().unwrap().len(), 2);
+        assert!(transport.client.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn endpoints() {
+        assert_eq!(
+            endpoint_of("https://api.guildwars2.com/v2/items?id=1"),
+            "/v2/items?id=1"
+        );
+        assert_eq!(endpoint_of("http://localhost:8080"), "/");
+        assert_eq!(endpoint_of("/v2/build"), "/v2/build");
+    }
+
+    #[test]
+    fn mock() {
+        let transport = MockTransport::new();
+        transport.respond("/v2/build", Response::json(r#"{"id": 1}"#));
+
+        let mut response = transport
+            .get("https://api.guildwars2.com/v2/build", HeaderMap::new())
+            .unwrap();
+        let mut body = String::new();
+        response.read_to_string(&mut body).unwrap();
+        assert_eq!(*response.status(), StatusCode::OK);
+        assert_eq!(body, r#"{"id": 1}"#);
+
+        let missing = transport
+            .get("https://api.guildwars2.com/v2/items", HeaderMap::new())
+            .unwrap();
+        assert_eq!(*missing.status(), StatusCode::NOT_FOUND);
+
+        assert_eq!(transport.requests(), vec!["/v2/build", "/v2/items"]);
+    }
+}